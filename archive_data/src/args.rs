@@ -0,0 +1,128 @@
+use super::errors::Error;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage: archive_data <export|import> <archive> [--images] [--files] [--data-dir <dir>] [--db <file>]
+    archive     .json, .yaml or .zip (csv files) archive to write or read
+    --images    include images when exporting, copy them to the plants directory when importing
+    --files     use the file database in the data directory instead of the sqlite database
+    --data-dir  data directory containing plants and images (default: data)
+    --db        sqlite database file (default: plants.db)";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Mode {
+    Export,
+    Import,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub mode: Mode,
+    pub archive: PathBuf,
+    pub images: bool,
+    pub files: bool,
+    pub data_dir: PathBuf,
+    pub db_file: PathBuf,
+}
+
+impl TryFrom<Vec<String>> for Args {
+    type Error = Error;
+    fn try_from(args: Vec<String>) -> Result<Args, Error> {
+        let mut mode = None;
+        let mut archive = None;
+        let mut images = false;
+        let mut files = false;
+        let mut data_dir = PathBuf::from("data");
+        let mut db_file = PathBuf::from("plants.db");
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .map(PathBuf::from)
+                    .ok_or_else(|| Error::Args(format!("Missing value for {name}")))
+            };
+            match arg.as_str() {
+                "export" if mode.is_none() => mode = Some(Mode::Export),
+                "import" if mode.is_none() => mode = Some(Mode::Import),
+                "--images" => images = true,
+                "--files" => files = true,
+                "--data-dir" => data_dir = value("--data-dir")?,
+                "--db" => db_file = value("--db")?,
+                _ if arg.starts_with("--") => {
+                    return Err(Error::Args(format!("Unknown argument {arg}")))
+                }
+                _ if archive.is_none() => archive = Some(PathBuf::from(arg)),
+                _ => return Err(Error::Args(format!("Unexpected argument {arg}"))),
+            }
+        }
+
+        Ok(Args {
+            mode: mode.ok_or(Error::Args("Missing export or import".to_owned()))?,
+            archive: archive.ok_or(Error::Args("Missing archive".to_owned()))?,
+            images,
+            files,
+            data_dir,
+            db_file,
+        })
+    }
+}
+
+#[cfg(test)]
+mod args_tests {
+    use super::{Args, Mode};
+    use std::path::PathBuf;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let result = Args::try_from(to_args(&["export", "backup.zip"])).unwrap();
+        let expected = Args {
+            mode: Mode::Export,
+            archive: PathBuf::from("backup.zip"),
+            images: false,
+            files: false,
+            data_dir: PathBuf::from("data"),
+            db_file: PathBuf::from("plants.db"),
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_all() {
+        let result = Args::try_from(to_args(&[
+            "import",
+            "--images",
+            "backup.json",
+            "--files",
+            "--data-dir",
+            "other_data",
+            "--db",
+            "other.db",
+        ]))
+        .unwrap();
+        let expected = Args {
+            mode: Mode::Import,
+            archive: PathBuf::from("backup.json"),
+            images: true,
+            files: true,
+            data_dir: PathBuf::from("other_data"),
+            db_file: PathBuf::from("other.db"),
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_missing_archive() {
+        let result = Args::try_from(to_args(&["export", "--images"]));
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn parse_unknown() {
+        let result = Args::try_from(to_args(&["export", "backup.zip", "--verbose"]));
+        assert!(result.is_err())
+    }
+}
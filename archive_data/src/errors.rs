@@ -0,0 +1,46 @@
+use super::args::USAGE;
+use database::archive::errors::Error as ArchiveError;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Args(String),
+    DB(Box<dyn std::error::Error>),
+    Archive(ArchiveError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Args(msg) => write!(f, "Bad arguments: {msg}\n{USAGE}"),
+            Error::DB(err) => write!(f, "{err}"),
+            Error::Archive(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Box<dyn std::error::Error>> for Error {
+    fn from(err: Box<dyn std::error::Error>) -> Error {
+        Error::DB(err)
+    }
+}
+
+impl From<ArchiveError> for Error {
+    fn from(err: ArchiveError) -> Error {
+        Error::Archive(err)
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::{Error, USAGE};
+
+    #[test]
+    fn display_args() {
+        let result = format!("{}", Error::Args("missing archive".to_owned()));
+        let expected = format!("Bad arguments: missing archive\n{USAGE}");
+        assert_eq!(result, expected)
+    }
+}
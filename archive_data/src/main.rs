@@ -0,0 +1,57 @@
+mod args;
+mod errors;
+
+use args::{Args, Mode};
+use database::{
+    archive::{export_archive, import_archive},
+    database_manager::DatabaseManager,
+    file_backend::FileDB,
+    sqlite_backend::SQLiteDB,
+};
+use errors::Error;
+
+fn run_archive<T: DatabaseManager>(db: &mut T, args: &Args) -> Result<(), Error> {
+    match args.mode {
+        Mode::Export => {
+            export_archive(db, &args.archive, args.images)?;
+            println!("Exported to {:?}", args.archive);
+        }
+        Mode::Import => {
+            let images_dir = args.data_dir.join("Plants");
+            let images_dir = if args.images {
+                Some(images_dir.as_path())
+            } else {
+                None
+            };
+            import_archive(db, &args.archive, images_dir)?;
+            println!("Imported {:?}", args.archive);
+        }
+    }
+    Ok(())
+}
+
+fn run() -> Result<(), Error> {
+    let args = Args::try_from(std::env::args().skip(1).collect::<Vec<String>>())?;
+    if args.files {
+        let mut file_db = FileDB {
+            plants_dir: args.data_dir.join("Plants"),
+            species_dir: args.data_dir.join("Species"),
+            location_file: args.data_dir.join("Locations.csv"),
+            logs_dir: args.data_dir.join("Logs"),
+            ..Default::default()
+        };
+        run_archive(&mut file_db, &args)
+    } else {
+        let mut sqlite_db =
+            SQLiteDB::new(args.db_file.clone()).map_err(|err| Error::DB(Box::new(err)))?;
+        sqlite_db.plants_dir = args.data_dir.join("Plants");
+        run_archive(&mut sqlite_db, &args)
+    }
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
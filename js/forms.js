@@ -0,0 +1,21 @@
+function submit_form(form_id, action) {
+  form = document.getElementById(form_id);
+  status_div = document.getElementById(form_id + '-status');
+  // inputs have the id form_id-field_name
+  data = new URLSearchParams();
+  inputs = form.querySelectorAll('input, select');
+  for(var i=0; i<inputs.length; i++){
+    data.append(inputs[i].id.substring(form_id.length + 1), inputs[i].value);
+  }
+
+  status_div.textContent = 'Saving...';
+  fetch(action, {method: 'POST', body: data, credentials: 'same-origin'})
+    .then(response => response.text().then(text => {
+      status_div.textContent = text;
+      status_div.style.color = response.ok ? '' : 'red';
+    }))
+    .catch(err => {
+      status_div.textContent = 'Could not reach server: ' + err;
+      status_div.style.color = 'red';
+    });
+}
@@ -1,12 +1,7 @@
 function setup_img_events() {
   img_elements = document.getElementsByTagName('img')
   for(var i=0;i<img_elements.length;i++){
-    img_src = img_elements[i].src
-    if(img_src.includes('small')){ 
-      img_elements[i].style.cursor = 'default'; 
-    } else {
-      img_elements[i].addEventListener('click',open_image_viewer);
-    }
+    img_elements[i].addEventListener('click',open_image_viewer);
   }
 
   left_arrows = document.getElementsByClassName('left_arrow');
@@ -22,7 +17,8 @@ function setup_img_events() {
 }
 
 function open_image_viewer(evt){
-  img_src = evt.currentTarget.src
+  // pages show thumbnails, the viewer shows the full image
+  img_src = evt.currentTarget.src.replace('/thumbs/','/')
   image_viewer_img = document.getElementById('image_viewer_image')
   image_viewer_img.src = img_src
 
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+/// Delays between retries after errors, doubled after every error until max is reached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    current: Option<Duration>,
+}
+
+impl Default for Backoff {
+    fn default() -> Backoff {
+        Backoff::new(Duration::from_secs(1), Duration::from_secs(300))
+    }
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration) -> Backoff {
+        Backoff {
+            initial,
+            max,
+            current: None,
+        }
+    }
+
+    /// Delay before the next retry
+    pub fn next_delay(&mut self) -> Duration {
+        let next = match self.current {
+            None => self.initial,
+            Some(current) => (current * 2).min(self.max),
+        };
+        self.current = Some(next);
+        next
+    }
+
+    /// Called after a success, the next error is retried after the initial delay again
+    pub fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+#[cfg(test)]
+mod backoff_tests {
+    use super::Backoff;
+    use std::time::Duration;
+
+    fn example_backoff() -> Backoff {
+        Backoff::new(Duration::from_secs(1), Duration::from_secs(5))
+    }
+
+    #[test]
+    fn delays() {
+        let mut backoff = example_backoff();
+        let result: Vec<u64> = (0..5).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(result, vec![1, 2, 4, 5, 5])
+    }
+
+    #[test]
+    fn reset() {
+        let mut backoff = example_backoff();
+        backoff.next_delay();
+        backoff.next_delay();
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_secs(1))
+    }
+}
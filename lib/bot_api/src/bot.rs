@@ -1,15 +1,30 @@
 use super::{
-    bot_methods::{BotMethod, DownloadImage, GetUpdates, SendMessage},
+    backoff::Backoff,
+    bot_methods::{
+        AnswerCallbackQuery, BotMethod, DeleteWebhook, DownloadImage, EditMessageText, GetUpdates,
+        InputMediaPhoto, SendMediaGroup, SendMessage, SendPhoto, SetWebhook, DEFAULT_API_URL,
+    },
     commands::Command,
     errors::Error,
+    formatting::ParseMode,
     handlers::Handler,
-    update::Update,
-    update::Updates,
+    input_file::InputFile,
+    keyboard::InlineKeyboardMarkup,
+    update::{Update, UpdateContent, Updates},
+    webhook::{WebhookConfig, WebhookServer},
 };
 use bytes::Bytes;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// Seconds telegram keeps a poll for updates open while there are none
+const POLL_TIMEOUT: i32 = 30;
+/// Interval `Handler::on_poll` is called in by the webhook, besides after every request
+const WEBHOOK_TICK: Duration = Duration::from_secs(60);
 
 pub struct Bot {
     pub api_key: String,
+    pub api_url: String,
     pub last_update: i64,
 }
 
@@ -17,6 +32,7 @@ impl Bot {
     pub fn new(api_key: String) -> Bot {
         Bot {
             api_key,
+            api_url: DEFAULT_API_URL.to_owned(),
             last_update: 0,
         }
     }
@@ -33,7 +49,7 @@ impl Bot {
             timeout,
             allowed_updates,
         };
-        let mut updates = update.perform(&self.api_key).await?;
+        let mut updates = update.perform(&self.api_url, &self.api_key).await?;
         updates
             .updates
             .retain(|upd| upd.update_id > self.last_update);
@@ -42,16 +58,140 @@ impl Bot {
     }
 
     pub async fn get_all_updates(&mut self) -> Result<Updates, Error> {
-        self.get_updates(None, Some(1), None).await
+        self.get_updates(None, Some(POLL_TIMEOUT), None).await
     }
 
     pub async fn send_message(&self, chat_id: String, text: String) -> Result<(), Error> {
-        SendMessage { chat_id, text }.perform(&self.api_key).await?;
+        SendMessage {
+            chat_id,
+            text,
+            reply_markup: None,
+            parse_mode: None,
+        }
+        .perform(&self.api_url, &self.api_key)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn send_formatted(
+        &self,
+        chat_id: String,
+        text: String,
+        parse_mode: ParseMode,
+    ) -> Result<(), Error> {
+        SendMessage {
+            chat_id,
+            text,
+            reply_markup: None,
+            parse_mode: Some(parse_mode),
+        }
+        .perform(&self.api_url, &self.api_key)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn send_keyboard(
+        &self,
+        chat_id: String,
+        text: String,
+        keyboard: InlineKeyboardMarkup,
+    ) -> Result<(), Error> {
+        SendMessage {
+            chat_id,
+            text,
+            reply_markup: Some(keyboard),
+            parse_mode: None,
+        }
+        .perform(&self.api_url, &self.api_key)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn send_photo(
+        &self,
+        chat_id: String,
+        photo: InputFile,
+        caption: Option<String>,
+        parse_mode: Option<ParseMode>,
+    ) -> Result<(), Error> {
+        SendPhoto {
+            chat_id,
+            photo,
+            caption,
+            parse_mode,
+        }
+        .perform(&self.api_url, &self.api_key)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn send_media_group(
+        &self,
+        chat_id: String,
+        media: Vec<InputMediaPhoto>,
+    ) -> Result<(), Error> {
+        SendMediaGroup { chat_id, media }
+            .perform(&self.api_url, &self.api_key)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn answer_callback_query(
+        &self,
+        callback_query_id: String,
+        text: Option<String>,
+    ) -> Result<(), Error> {
+        AnswerCallbackQuery {
+            callback_query_id,
+            text,
+        }
+        .perform(&self.api_url, &self.api_key)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn edit_message_text(
+        &self,
+        chat_id: String,
+        message_id: i64,
+        text: String,
+        keyboard: Option<InlineKeyboardMarkup>,
+    ) -> Result<(), Error> {
+        EditMessageText {
+            chat_id,
+            message_id,
+            text,
+            reply_markup: keyboard,
+        }
+        .perform(&self.api_url, &self.api_key)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_webhook(
+        &self,
+        url: String,
+        secret_token: Option<String>,
+    ) -> Result<(), Error> {
+        SetWebhook { url, secret_token }
+            .perform(&self.api_url, &self.api_key)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_webhook(&self) -> Result<(), Error> {
+        DeleteWebhook {
+            drop_pending_updates: false,
+        }
+        .perform(&self.api_url, &self.api_key)
+        .await?;
         Ok(())
     }
 
     pub async fn download_image(&self, file_id: String) -> Result<Bytes, Error> {
-        DownloadImage { file_id }.perform(&self.api_key).await
+        DownloadImage { file_id }
+            .perform(&self.api_url, &self.api_key)
+            .await
     }
 
     pub async fn handle_update<U: Handler<T>, T: Command>(
@@ -60,6 +200,10 @@ impl Bot {
         handler: &mut U,
     ) -> Result<(), Error> {
         self.last_update = update.update_id;
+        if let Some(UpdateContent::CallbackQuery(query)) = update.content {
+            handler.handle_callback(self, query).await;
+            return Ok(());
+        }
         let msg = update.get_message()?;
         if msg.is_command() {
             let cmd: T = msg.get_command().map_err(Error::Other)?;
@@ -72,25 +216,84 @@ impl Bot {
         Ok(())
     }
 
+    /// Handles all new updates, only getting the updates can fail
+    /// an update that can not be handled (e.g. an unknown command) is logged and skipped
     pub async fn handle_updates<U: Handler<T>, T: Command>(
         &mut self,
         handler: &mut U,
     ) -> Result<(), Error> {
         let updates = self.get_all_updates().await?;
         for update in updates.updates {
-            self.handle_update(update, handler).await?;
+            let update_id = update.update_id;
+            if let Err(err) = self.handle_update(update, handler).await {
+                log::error!("Could not handle update {update_id}: {err}");
+            }
         }
         Ok(())
     }
 
+    /// Handles all new updates once, `run` polls repeatedly
+    pub async fn poll<U: Handler<T>, T: Command>(&mut self, handler: &mut U) -> Result<(), Error> {
+        handler.on_poll(self).await;
+        self.handle_updates(handler).await
+    }
+
+    /// Polls for updates forever, after errors getting the updates polling is paused with exponential backoff
     pub async fn run<U: Handler<T>, T: Command>(&mut self, handler: &mut U) {
+        // updates can only be polled while no webhook is set
+        if let Err(err) = self.delete_webhook().await {
+            log::warn!("Could not delete webhook: {err}");
+        }
+        let mut backoff = Backoff::default();
         loop {
-            match self.handle_updates(handler).await {
-                Ok(_) => (),
+            match self.poll(handler).await {
+                Ok(_) => backoff.reset(),
                 Err(err) => {
-                    log::error!("Bot encountered an error: {err}");
+                    let delay = backoff.next_delay();
+                    log::error!(
+                        "Bot encountered an error: {err}, retrying in {} seconds",
+                        delay.as_secs()
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// Sets the webhook and handles the updates telegram sends to it forever
+    pub async fn run_webhook<U: Handler<T>, T: Command>(
+        &mut self,
+        handler: &mut U,
+        config: &WebhookConfig,
+    ) -> Result<(), Error> {
+        let server = WebhookServer::bind(&config.address, &config.secret_token).await?;
+        self.set_webhook(config.url.clone(), Some(config.secret_token.clone()))
+            .await?;
+        log::info!("Receiving updates on {}", config.address);
+        let mut tick = tokio::time::interval(WEBHOOK_TICK);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            // only waiting for a connection is raced against the tick,
+            // so a request that is being read or handled is never dropped
+            tokio::select! {
+                res = server.accept() => {
+                    let res = match res {
+                        Ok((stream, addr)) => server.answer(stream, addr).await,
+                        Err(err) => Err(err),
+                    };
+                    match res {
+                        Ok(Some(update)) => {
+                            if let Err(err) = self.handle_update(update, handler).await {
+                                log::error!("Bot encountered an error: {err}");
+                            }
+                        }
+                        Ok(None) => (),
+                        Err(err) => log::error!("Could not receive update: {err}"),
+                    }
                 }
+                _ = tick.tick() => (),
             }
+            handler.on_poll(self).await;
         }
     }
 }
@@ -98,56 +301,146 @@ impl Bot {
 #[cfg(test)]
 mod bot_tests {
     use super::Bot;
-    use crate::test_common::load_config;
+    use crate::{
+        bot_methods::DEFAULT_API_URL,
+        formatting::ParseMode,
+        input_file::InputFile,
+        mock_server::{MockServer, SentMessage},
+        test_common::ExampleHandler,
+    };
+
+    const API_KEY: &str = "test_key";
+
+    fn example_bot(server: &MockServer) -> Bot {
+        let mut bot = Bot::new(API_KEY.to_owned());
+        bot.api_url = server.url.clone();
+        bot
+    }
+
+    #[test]
+    fn new_bot() {
+        let result = Bot::new(API_KEY.to_owned());
+        assert_eq!(result.api_url, DEFAULT_API_URL);
+        assert_eq!(result.last_update, 0)
+    }
 
     #[tokio::test]
     async fn bot_updates() {
-        let data = load_config();
-        Bot::new(data.api_key)
+        let server = MockServer::start(API_KEY).unwrap();
+        server.push_message(1, 1, "a message");
+        let result = example_bot(&server)
             .get_updates(None, None, None)
             .await
             .unwrap();
+        assert_eq!(result.updates.len(), 1)
     }
 
     #[tokio::test]
     async fn update_fail() {
-        let result = Bot::new("".to_owned()).get_updates(None, None, None).await;
+        let server = MockServer::start(API_KEY).unwrap();
+        let mut bot = example_bot(&server);
+        bot.api_key = "".to_owned();
+        let result = bot.get_updates(None, None, None).await;
         assert!(result.is_err())
     }
 
     #[tokio::test]
     async fn all_updates() {
-        let data = load_config();
-        let mut bot = Bot::new(data.api_key);
-        bot.last_update = 4;
-        let result = bot.get_all_updates().await;
-        assert!(result.is_ok())
-    }
-
-    #[tokio::test]
-    async fn all_updates_fail() {
-        let result = Bot::new("".to_owned()).get_all_updates().await;
-        assert!(result.is_err())
+        let server = MockServer::start(API_KEY).unwrap();
+        server.push_message(1, 1, "a message");
+        server.push_message(1, 1, "another message");
+        let mut bot = example_bot(&server);
+        bot.last_update = 1;
+        let result = bot.get_all_updates().await.unwrap();
+        assert_eq!(result.updates.len(), 1);
+        assert_eq!(result.updates[0].update_id, 2)
     }
 
     #[tokio::test]
     async fn send_message() {
-        let data = load_config();
-        let bot = Bot::new(data.api_key);
-        let chat_id = data.white_list.get(0).unwrap();
+        let server = MockServer::start(API_KEY).unwrap();
+        let bot = example_bot(&server);
         let res = bot
-            .send_message(chat_id.to_string(), "Running Tests".to_owned())
+            .send_message("1".to_owned(), "Running Tests".to_owned())
             .await;
-        assert!(res.is_ok())
+        assert!(res.is_ok());
+        let expected = vec![SentMessage {
+            message_id: 1,
+            chat_id: "1".to_owned(),
+            text: "Running Tests".to_owned(),
+            reply_markup: None,
+            parse_mode: None,
+            photo: None,
+        }];
+        assert_eq!(server.get_sent(), expected)
+    }
+
+    #[tokio::test]
+    async fn send_formatted() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let bot = example_bot(&server);
+        bot.send_formatted("1".to_owned(), "<b>Plant</b>".to_owned(), ParseMode::Html)
+            .await
+            .unwrap();
+        assert_eq!(server.get_sent()[0].parse_mode, Some("HTML".to_owned()))
+    }
+
+    #[tokio::test]
+    async fn send_photo() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let bot = example_bot(&server);
+        let photo = InputFile::Bytes {
+            file_name: "plant.jpg".to_owned(),
+            data: vec![1, 2, 3],
+        };
+        bot.send_photo("1".to_owned(), photo, Some("Plant".to_owned()), None)
+            .await
+            .unwrap();
+        let sent = &server.get_sent()[0];
+        assert_eq!(sent.text, "Plant");
+        assert_eq!(sent.photo, Some(vec![1, 2, 3]))
     }
 
     #[tokio::test]
     async fn message_fail() {
-        let data = load_config();
-        let bot = Bot::new(data.api_key);
+        let server = MockServer::start(API_KEY).unwrap();
+        let bot = example_bot(&server);
         let res = bot
             .send_message("not a real chat".to_owned(), "Running Tests".to_owned())
             .await;
         assert!(res.is_err())
     }
+
+    #[tokio::test]
+    async fn poll() {
+        let server = MockServer::start(API_KEY).unwrap();
+        server.push_message(1, 1, "/succ");
+        server.push_message(1, 1, "a message");
+        let mut bot = example_bot(&server);
+        bot.poll(&mut ExampleHandler).await.unwrap();
+        assert_eq!(bot.last_update, 2)
+    }
+
+    #[tokio::test]
+    async fn poll_unknown_command() {
+        let server = MockServer::start(API_KEY).unwrap();
+        server.push_message(1, 1, "/unknown");
+        server.push_message(1, 1, "a message");
+        let mut bot = example_bot(&server);
+        let result = bot.poll(&mut ExampleHandler).await;
+        assert!(result.is_ok());
+        assert_eq!(bot.last_update, 2)
+    }
+
+    #[tokio::test]
+    async fn poll_webhook_set() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let mut bot = example_bot(&server);
+        bot.set_webhook("https://example.com".to_owned(), None)
+            .await
+            .unwrap();
+        assert!(bot.poll(&mut ExampleHandler).await.is_err());
+        bot.delete_webhook().await.unwrap();
+        assert!(bot.poll(&mut ExampleHandler).await.is_ok())
+    }
 }
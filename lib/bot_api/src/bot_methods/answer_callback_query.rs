@@ -0,0 +1,85 @@
+use super::BotMethod;
+use crate::{
+    errors::{Error, WrongType},
+    parse_json::check_ok,
+};
+use reqwest::Client;
+use serde_json::Value;
+
+/// Confirms a callback query, telegram clients show a loading indicator until it is answered
+pub struct AnswerCallbackQuery {
+    pub callback_query_id: String,
+    /// Notification shown to the user
+    pub text: Option<String>,
+}
+
+impl AnswerCallbackQuery {
+    fn to_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("callback_query_id", self.callback_query_id.clone())];
+        if let Some(text) = &self.text {
+            params.push(("text", text.clone()));
+        }
+        params
+    }
+}
+
+impl BotMethod for AnswerCallbackQuery {
+    type Res = bool;
+
+    fn get_endpoint(&self) -> String {
+        "answerCallbackQuery".to_owned()
+    }
+
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Self::Res, Error> {
+        let client = Client::new();
+        let url = self.get_url(api_url, api_key);
+        let params = self.to_params();
+        let resp = client.post(url).form(&params).send().await?;
+        self.check_status(&resp)?;
+        let resp_json: Value = resp.json().await?;
+        match check_ok(resp_json)? {
+            Value::Bool(b) => Ok(b),
+            _ => Err(WrongType {
+                field_name: "result".to_owned(),
+                field_type: "bool".to_owned(),
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod answer_callback_query_tests {
+    use super::{AnswerCallbackQuery, BotMethod};
+
+    fn example_answer() -> AnswerCallbackQuery {
+        AnswerCallbackQuery {
+            callback_query_id: "1".to_owned(),
+            text: Some("Selected".to_owned()),
+        }
+    }
+
+    #[test]
+    fn answer_endpoint() {
+        assert_eq!(example_answer().get_endpoint(), "answerCallbackQuery")
+    }
+
+    #[test]
+    fn answer_params() {
+        let result = example_answer().to_params();
+        let expected = vec![
+            ("callback_query_id", "1".to_owned()),
+            ("text", "Selected".to_owned()),
+        ];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn answer_params_no_text() {
+        let mut answer = example_answer();
+        answer.text = None;
+        let result = answer.to_params();
+        let expected = vec![("callback_query_id", "1".to_owned())];
+        assert_eq!(result, expected)
+    }
+}
@@ -0,0 +1,60 @@
+use super::BotMethod;
+use crate::{
+    errors::{Error, WrongType},
+    parse_json::check_ok,
+};
+use reqwest::Client;
+use serde_json::Value;
+
+/// Removes the webhook, so updates can be received with getUpdates again
+pub struct DeleteWebhook {
+    pub drop_pending_updates: bool,
+}
+
+impl BotMethod for DeleteWebhook {
+    type Res = bool;
+
+    fn get_endpoint(&self) -> String {
+        "deleteWebhook".to_owned()
+    }
+
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Self::Res, Error> {
+        let client = Client::new();
+        let url = self.get_url(api_url, api_key);
+        let params = [(
+            "drop_pending_updates",
+            self.drop_pending_updates.to_string(),
+        )];
+        let resp = client.post(url).form(&params).send().await?;
+        self.check_status(&resp)?;
+        let resp_json: Value = resp.json().await?;
+        match check_ok(resp_json)? {
+            Value::Bool(b) => Ok(b),
+            _ => Err(WrongType {
+                field_name: "result".to_owned(),
+                field_type: "bool".to_owned(),
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod delete_webhook_tests {
+    use super::{BotMethod, DeleteWebhook};
+    use crate::mock_server::MockServer;
+
+    const API_KEY: &str = "test_key";
+
+    #[tokio::test]
+    async fn perform_delete() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let result = DeleteWebhook {
+            drop_pending_updates: false,
+        }
+        .perform(&server.url, API_KEY)
+        .await;
+        assert!(result.unwrap());
+        assert_eq!(server.get_webhook(), None)
+    }
+}
@@ -14,18 +14,18 @@ impl BotMethod for DownloadImage {
         "getFile".to_owned()
     }
 
-    async fn perform(&self, api_key: &str) -> Result<Self::Res, Error> {
-        let api_url = self.get_url(api_key);
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Self::Res, Error> {
+        let method_url = self.get_url(api_url, api_key);
         let request_params = vec![("file_id", self.file_id.clone())];
 
-        let request_url = Url::parse_with_params(&api_url, &request_params).unwrap();
+        let request_url = Url::parse_with_params(&method_url, &request_params)?;
         let resp = reqwest::get(request_url).await?;
 
         self.check_status(&resp)?;
 
         let photo_val: serde_json::Value = resp.json().await?;
         let path = get_filename(photo_val)?;
-        let download_url = format!("https://api.telegram.org/file/bot{}/{}", api_key, path);
+        let download_url = format!("{api_url}/file/bot{api_key}/{path}");
 
         let dl_resp = reqwest::get(download_url).await?;
         self.check_status(&dl_resp)?;
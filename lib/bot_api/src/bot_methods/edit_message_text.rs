@@ -0,0 +1,77 @@
+use super::BotMethod;
+use crate::{
+    errors::Error, keyboard::InlineKeyboardMarkup, message::Message, parse_json::check_ok,
+};
+use reqwest::Client;
+use serde_json::Value;
+
+/// Replaces the text of a sent message, the keyboard is removed if `reply_markup` is None
+pub struct EditMessageText {
+    pub chat_id: String,
+    pub message_id: i64,
+    pub text: String,
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+impl EditMessageText {
+    fn to_params(&self) -> Result<Vec<(&'static str, String)>, Error> {
+        let mut params = vec![
+            ("chat_id", self.chat_id.clone()),
+            ("message_id", self.message_id.to_string()),
+            ("text", self.text.clone()),
+        ];
+        if let Some(markup) = &self.reply_markup {
+            params.push(("reply_markup", serde_json::to_string(markup)?));
+        }
+        Ok(params)
+    }
+}
+
+impl BotMethod for EditMessageText {
+    type Res = Message;
+
+    fn get_endpoint(&self) -> String {
+        "editMessageText".to_owned()
+    }
+
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Self::Res, Error> {
+        let client = Client::new();
+        let url = self.get_url(api_url, api_key);
+        let params = self.to_params()?;
+        let resp = client.post(url).form(&params).send().await?;
+        self.check_status(&resp)?;
+        let resp_json: Value = resp.json().await?;
+        let resp_ok = check_ok(resp_json)?;
+        resp_ok.try_into()
+    }
+}
+
+#[cfg(test)]
+mod edit_message_text_tests {
+    use super::{BotMethod, EditMessageText};
+
+    fn example_edit() -> EditMessageText {
+        EditMessageText {
+            chat_id: "1".to_owned(),
+            message_id: 2,
+            text: "Edited".to_owned(),
+            reply_markup: None,
+        }
+    }
+
+    #[test]
+    fn edit_endpoint() {
+        assert_eq!(example_edit().get_endpoint(), "editMessageText")
+    }
+
+    #[test]
+    fn edit_params() {
+        let result = example_edit().to_params().unwrap();
+        let expected = vec![
+            ("chat_id", "1".to_owned()),
+            ("message_id", "2".to_owned()),
+            ("text", "Edited".to_owned()),
+        ];
+        assert_eq!(result, expected)
+    }
+}
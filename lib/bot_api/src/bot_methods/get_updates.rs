@@ -41,11 +41,11 @@ impl BotMethod for GetUpdates {
         "getUpdates".to_owned()
     }
 
-    async fn perform(&self, api_key: &str) -> Result<Updates, Error> {
-        let api_url = self.get_url(api_key);
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Updates, Error> {
+        let api_url = self.get_url(api_url, api_key);
         let request_params = self.to_get_params();
 
-        let request_url = Url::parse_with_params(&api_url, &request_params).unwrap();
+        let request_url = Url::parse_with_params(&api_url, &request_params)?;
         let resp = reqwest::get(request_url).await?;
 
         self.check_status(&resp)?;
@@ -59,7 +59,9 @@ impl BotMethod for GetUpdates {
 #[cfg(test)]
 mod get_updates_tests {
     use super::{BotMethod, GetUpdates};
-    use crate::test_common::load_config;
+    use crate::{bot_methods::DEFAULT_API_URL, mock_server::MockServer};
+
+    const API_KEY: &str = "test_key";
 
     fn example_get_updates1() -> GetUpdates {
         GetUpdates {
@@ -99,24 +101,32 @@ mod get_updates_tests {
 
     #[test]
     fn get_url() {
-        let data = load_config();
-        let result = example_get_updates1().get_url(&data.api_key);
+        let result = example_get_updates1().get_url(DEFAULT_API_URL, API_KEY);
         assert_eq!(
             result,
-            format!("https://api.telegram.org/bot{}/getUpdates", data.api_key)
+            format!("https://api.telegram.org/bot{API_KEY}/getUpdates")
         );
     }
 
     #[tokio::test]
     async fn perform_updates1() {
-        let data = load_config();
-        let res = example_get_updates1().perform(&data.api_key).await;
+        let server = MockServer::start(API_KEY).unwrap();
+        let res = example_get_updates1().perform(&server.url, API_KEY).await;
         assert!(res.is_ok())
     }
 
     #[tokio::test]
     async fn perform_updates_wrong_key() {
-        let res = example_get_updates1().perform("not a valid key").await;
+        let server = MockServer::start(API_KEY).unwrap();
+        let res = example_get_updates1()
+            .perform(&server.url, "not a valid key")
+            .await;
+        assert!(res.is_err())
+    }
+
+    #[tokio::test]
+    async fn perform_updates_bad_url() {
+        let res = example_get_updates1().perform("not a url", API_KEY).await;
         assert!(res.is_err())
     }
 }
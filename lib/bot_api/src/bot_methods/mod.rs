@@ -1,27 +1,42 @@
 use crate::errors::{Error, RequestError};
 use reqwest::{Response, StatusCode};
 
+mod answer_callback_query;
+mod delete_webhook;
 mod download_image;
+mod edit_message_text;
 mod get_updates;
+mod multipart;
+mod send_media_group;
 mod send_message;
+mod send_photo;
+mod set_webhook;
 
+pub use answer_callback_query::AnswerCallbackQuery;
+pub use delete_webhook::DeleteWebhook;
 pub use download_image::DownloadImage;
+pub use edit_message_text::EditMessageText;
 pub use get_updates::GetUpdates;
+pub use send_media_group::{InputMediaPhoto, SendMediaGroup};
 pub use send_message::SendMessage;
+pub use send_photo::SendPhoto;
+pub use set_webhook::SetWebhook;
 
-const API_URL: &str = "https://api.telegram.org/bot";
+/// Url of the telegram bot api, other urls can be used to run against a local server
+pub const DEFAULT_API_URL: &str = "https://api.telegram.org";
 
 pub trait BotMethod {
     type Res;
 
     fn get_endpoint(&self) -> String;
 
-    fn get_url(&self, api_key: &str) -> String {
-        API_URL.to_owned() + api_key + "/" + &self.get_endpoint().to_string()
+    fn get_url(&self, api_url: &str, api_key: &str) -> String {
+        format!("{api_url}/bot{api_key}/{}", self.get_endpoint())
     }
 
     fn perform(
         &self,
+        api_url: &str,
         api_key: &str,
     ) -> impl std::future::Future<Output = Result<Self::Res, Error>> + Send;
 
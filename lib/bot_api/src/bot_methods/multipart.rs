@@ -0,0 +1,73 @@
+/// Body of a multipart/form-data request, used to upload files
+pub struct MultipartForm {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl MultipartForm {
+    pub fn new() -> MultipartForm {
+        MultipartForm {
+            boundary: "plant-bot-form-boundary".to_owned(),
+            body: vec![],
+        }
+    }
+
+    pub fn text(&mut self, name: &str, value: &str) {
+        self.body.extend(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n",
+                self.boundary
+            )
+            .as_bytes(),
+        );
+    }
+
+    pub fn file(&mut self, name: &str, file_name: &str, data: &[u8]) {
+        self.body.extend(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{name}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                self.boundary,
+                file_name.replace('"', "")
+            )
+            .as_bytes(),
+        );
+        self.body.extend(data);
+        self.body.extend(b"\r\n");
+    }
+
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.body
+            .extend(format!("--{}--\r\n", self.boundary).as_bytes());
+        self.body
+    }
+}
+
+#[cfg(test)]
+mod multipart_tests {
+    use super::MultipartForm;
+
+    #[test]
+    fn form_body() {
+        let mut form = MultipartForm::new();
+        form.text("chat_id", "1");
+        form.file("photo", "a.jpg", b"img");
+        let result = String::from_utf8(form.finish()).unwrap();
+        let expected = "--plant-bot-form-boundary\r\nContent-Disposition: form-data; name=\"chat_id\"\r\n\r\n1\r\n\
+--plant-bot-form-boundary\r\nContent-Disposition: form-data; name=\"photo\"; filename=\"a.jpg\"\r\nContent-Type: application/octet-stream\r\n\r\nimg\r\n\
+--plant-bot-form-boundary--\r\n";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn content_type() {
+        let result = MultipartForm::new().content_type();
+        assert_eq!(
+            result,
+            "multipart/form-data; boundary=plant-bot-form-boundary"
+        )
+    }
+}
@@ -0,0 +1,137 @@
+use super::{multipart::MultipartForm, BotMethod};
+use crate::{
+    errors::{Error, WrongType},
+    formatting::ParseMode,
+    input_file::InputFile,
+    message::Message,
+    parse_json::check_ok,
+};
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde_json::{json, Value};
+
+/// Photo in a media group
+pub struct InputMediaPhoto {
+    pub photo: InputFile,
+    pub caption: Option<String>,
+    pub parse_mode: Option<ParseMode>,
+}
+
+/// Sends 2 to 10 photos as an album
+pub struct SendMediaGroup {
+    pub chat_id: String,
+    pub media: Vec<InputMediaPhoto>,
+}
+
+impl SendMediaGroup {
+    fn to_form(&self) -> Result<MultipartForm, Error> {
+        if !(2..=10).contains(&self.media.len()) {
+            return Err(Error::MediaGroupSize(self.media.len()));
+        }
+
+        let mut form = MultipartForm::new();
+        form.text("chat_id", &self.chat_id);
+        let mut media_vals = vec![];
+        let mut files = vec![];
+        for (ind, item) in self.media.iter().enumerate() {
+            let attach_name = format!("photo{ind}");
+            let mut media_val =
+                json!({"type": "photo", "media": format!("attach://{attach_name}")});
+            if let Some(caption) = &item.caption {
+                media_val["caption"] = json!(caption);
+            }
+            if let Some(mode) = &item.parse_mode {
+                media_val["parse_mode"] = json!(mode.to_string());
+            }
+            media_vals.push(media_val);
+            files.push((attach_name, item.photo.load()?));
+        }
+        form.text("media", &serde_json::to_string(&media_vals)?);
+        for (attach_name, (file_name, data)) in files {
+            form.file(&attach_name, &file_name, &data);
+        }
+        Ok(form)
+    }
+}
+
+impl BotMethod for SendMediaGroup {
+    type Res = Vec<Message>;
+
+    fn get_endpoint(&self) -> String {
+        "sendMediaGroup".to_owned()
+    }
+
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Self::Res, Error> {
+        let client = Client::new();
+        let url = self.get_url(api_url, api_key);
+        let form = self.to_form()?;
+        let resp = client
+            .post(url)
+            .header(CONTENT_TYPE, form.content_type())
+            .body(form.finish())
+            .send()
+            .await?;
+        self.check_status(&resp)?;
+        let resp_json: Value = resp.json().await?;
+        match check_ok(resp_json)? {
+            Value::Array(vals) => vals.into_iter().map(|val| val.try_into()).collect(),
+            _ => Err(WrongType {
+                field_name: "result".to_owned(),
+                field_type: "Array".to_owned(),
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod send_media_group_tests {
+    use super::{BotMethod, InputMediaPhoto, SendMediaGroup};
+    use crate::{input_file::InputFile, mock_server::MockServer};
+
+    const API_KEY: &str = "test_key";
+
+    fn example_media(num: u8) -> InputMediaPhoto {
+        InputMediaPhoto {
+            photo: InputFile::Bytes {
+                file_name: format!("plant{num}.jpg"),
+                data: vec![num],
+            },
+            caption: Some(format!("Plant{num}")),
+            parse_mode: None,
+        }
+    }
+
+    fn example_group() -> SendMediaGroup {
+        SendMediaGroup {
+            chat_id: "1".to_owned(),
+            media: vec![example_media(1), example_media(2)],
+        }
+    }
+
+    #[test]
+    fn group_form() {
+        let body = String::from_utf8(example_group().to_form().unwrap().finish()).unwrap();
+        assert!(body.contains(r#""media":"attach://photo0""#));
+        assert!(body.contains(r#""media":"attach://photo1""#));
+        assert!(body.contains("name=\"photo1\"; filename=\"plant2.jpg\""))
+    }
+
+    #[test]
+    fn group_too_small() {
+        let group = SendMediaGroup {
+            chat_id: "1".to_owned(),
+            media: vec![example_media(1)],
+        };
+        assert!(group.to_form().is_err())
+    }
+
+    #[tokio::test]
+    async fn perform_send_group() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let result = example_group().perform(&server.url, API_KEY).await.unwrap();
+        assert_eq!(result.len(), 2);
+        let sent = server.get_sent();
+        assert_eq!(sent[0].text, "Plant1");
+        assert_eq!(sent[1].photo, Some(vec![2]))
+    }
+}
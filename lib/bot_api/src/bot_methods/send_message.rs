@@ -1,11 +1,32 @@
 use super::BotMethod;
-use crate::{errors::Error, message::Message, parse_json::check_ok};
+use crate::{
+    errors::Error, formatting::ParseMode, keyboard::InlineKeyboardMarkup, message::Message,
+    parse_json::check_ok,
+};
 use reqwest::Client;
 use serde_json::Value;
 
 pub struct SendMessage {
     pub chat_id: String,
     pub text: String,
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+    pub parse_mode: Option<ParseMode>,
+}
+
+impl SendMessage {
+    fn to_params(&self) -> Result<Vec<(&'static str, String)>, Error> {
+        let mut params = vec![
+            ("chat_id", self.chat_id.clone()),
+            ("text", self.text.clone()),
+        ];
+        if let Some(markup) = &self.reply_markup {
+            params.push(("reply_markup", serde_json::to_string(markup)?));
+        }
+        if let Some(mode) = &self.parse_mode {
+            params.push(("parse_mode", mode.to_string()));
+        }
+        Ok(params)
+    }
 }
 
 impl BotMethod for SendMessage {
@@ -15,10 +36,10 @@ impl BotMethod for SendMessage {
         "sendMessage".to_owned()
     }
 
-    async fn perform(&self, api_key: &str) -> Result<Self::Res, Error> {
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Self::Res, Error> {
         let client = Client::new();
-        let url = self.get_url(api_key);
-        let params = [("chat_id", &self.chat_id), ("text", &self.text)];
+        let url = self.get_url(api_url, api_key);
+        let params = self.to_params()?;
         let resp = client.post(url).form(&params).send().await?;
         self.check_status(&resp)?;
         let resp_json: Value = resp.json().await?;
@@ -30,49 +51,95 @@ impl BotMethod for SendMessage {
 #[cfg(test)]
 mod send_message_tests {
     use super::{BotMethod, SendMessage};
-    use crate::test_common::{load_config, JSONData};
+    use crate::{
+        bot_methods::DEFAULT_API_URL, formatting::ParseMode, keyboard::InlineKeyboardMarkup,
+        mock_server::MockServer,
+    };
+
+    const API_KEY: &str = "test_key";
 
-    fn example_message(data: &JSONData) -> SendMessage {
+    fn example_message() -> SendMessage {
         SendMessage {
-            chat_id: data.white_list.get(0).unwrap().to_string(),
+            chat_id: "1".to_owned(),
             text: "Testing Message".to_owned(),
+            reply_markup: None,
+            parse_mode: None,
         }
     }
 
     #[test]
     fn send_url() {
-        let data = load_config();
-        let message = example_message(&data);
+        let message = example_message();
         assert_eq!(
-            message.get_url(&data.api_key),
-            format!("https://api.telegram.org/bot{}/sendMessage", data.api_key)
+            message.get_url(DEFAULT_API_URL, API_KEY),
+            format!("https://api.telegram.org/bot{API_KEY}/sendMessage")
         );
     }
 
+    #[test]
+    fn send_params() {
+        let result = example_message().to_params().unwrap();
+        let expected = vec![
+            ("chat_id", "1".to_owned()),
+            ("text", "Testing Message".to_owned()),
+        ];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn send_params_keyboard() {
+        let message = SendMessage {
+            chat_id: "1".to_owned(),
+            text: "Testing Message".to_owned(),
+            reply_markup: Some(InlineKeyboardMarkup::from_options(&["a".to_owned()], 1)),
+            parse_mode: None,
+        };
+        let result = message.to_params().unwrap();
+        assert_eq!(
+            result[2],
+            (
+                "reply_markup",
+                r#"{"inline_keyboard":[[{"text":"a","callback_data":"a"}]]}"#.to_owned()
+            )
+        )
+    }
+
+    #[test]
+    fn send_params_parse_mode() {
+        let message = SendMessage {
+            parse_mode: Some(ParseMode::MarkdownV2),
+            ..example_message()
+        };
+        let result = message.to_params().unwrap();
+        assert_eq!(result[2], ("parse_mode", "MarkdownV2".to_owned()))
+    }
+
     #[tokio::test]
     async fn perform_send() {
-        let data = load_config();
-        let message = example_message(&data);
-        let res = message.perform(&data.api_key).await;
+        let server = MockServer::start(API_KEY).unwrap();
+        let res = example_message().perform(&server.url, API_KEY).await;
         assert!(res.is_ok())
     }
 
     #[tokio::test]
     async fn perform_send_wrong_key() {
-        let data = load_config();
-        let message = example_message(&data);
-        let res = message.perform("not a valid key").await;
+        let server = MockServer::start(API_KEY).unwrap();
+        let res = example_message()
+            .perform(&server.url, "not a valid key")
+            .await;
         assert!(res.is_err())
     }
 
     #[tokio::test]
     async fn perform_send_wrong_id() {
-        let data = load_config();
+        let server = MockServer::start(API_KEY).unwrap();
         let message = SendMessage {
             chat_id: "not a valid chat".to_owned(),
             text: "".to_owned(),
+            reply_markup: None,
+            parse_mode: None,
         };
-        let res = message.perform(&data.api_key).await;
+        let res = message.perform(&server.url, API_KEY).await;
         assert!(res.is_err())
     }
 }
@@ -0,0 +1,114 @@
+use super::{multipart::MultipartForm, BotMethod};
+use crate::{
+    errors::Error, formatting::ParseMode, input_file::InputFile, message::Message,
+    parse_json::check_ok,
+};
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde_json::Value;
+
+/// Uploads a photo, with an optional caption
+pub struct SendPhoto {
+    pub chat_id: String,
+    pub photo: InputFile,
+    pub caption: Option<String>,
+    pub parse_mode: Option<ParseMode>,
+}
+
+impl SendPhoto {
+    fn to_form(&self) -> Result<MultipartForm, Error> {
+        let (file_name, data) = self.photo.load()?;
+        let mut form = MultipartForm::new();
+        form.text("chat_id", &self.chat_id);
+        if let Some(caption) = &self.caption {
+            form.text("caption", caption);
+        }
+        if let Some(mode) = &self.parse_mode {
+            form.text("parse_mode", &mode.to_string());
+        }
+        form.file("photo", &file_name, &data);
+        Ok(form)
+    }
+}
+
+impl BotMethod for SendPhoto {
+    type Res = Message;
+
+    fn get_endpoint(&self) -> String {
+        "sendPhoto".to_owned()
+    }
+
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Self::Res, Error> {
+        let client = Client::new();
+        let url = self.get_url(api_url, api_key);
+        let form = self.to_form()?;
+        let resp = client
+            .post(url)
+            .header(CONTENT_TYPE, form.content_type())
+            .body(form.finish())
+            .send()
+            .await?;
+        self.check_status(&resp)?;
+        let resp_json: Value = resp.json().await?;
+        let resp_ok = check_ok(resp_json)?;
+        resp_ok.try_into()
+    }
+}
+
+#[cfg(test)]
+mod send_photo_tests {
+    use super::{BotMethod, SendPhoto};
+    use crate::{formatting::ParseMode, input_file::InputFile, mock_server::MockServer};
+    use std::path::PathBuf;
+
+    const API_KEY: &str = "test_key";
+
+    fn example_photo() -> SendPhoto {
+        SendPhoto {
+            chat_id: "1".to_owned(),
+            photo: InputFile::Bytes {
+                file_name: "plant.jpg".to_owned(),
+                data: vec![1, 2, 3],
+            },
+            caption: Some("<b>Plant1</b>".to_owned()),
+            parse_mode: Some(ParseMode::Html),
+        }
+    }
+
+    #[test]
+    fn photo_form() {
+        let body = String::from_utf8(example_photo().to_form().unwrap().finish()).unwrap();
+        assert!(body.contains("name=\"caption\"\r\n\r\n<b>Plant1</b>\r\n"));
+        assert!(body.contains("name=\"parse_mode\"\r\n\r\nHTML\r\n"));
+        assert!(body.contains("name=\"photo\"; filename=\"plant.jpg\""))
+    }
+
+    #[test]
+    fn photo_form_missing_file() {
+        let photo = SendPhoto {
+            photo: InputFile::Path(PathBuf::from("not_a_file.jpg")),
+            ..example_photo()
+        };
+        assert!(photo.to_form().is_err())
+    }
+
+    #[tokio::test]
+    async fn perform_send_photo() {
+        let server = MockServer::start(API_KEY).unwrap();
+        example_photo().perform(&server.url, API_KEY).await.unwrap();
+        let sent = server.get_sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].text, "<b>Plant1</b>");
+        assert_eq!(sent[0].photo, Some(vec![1, 2, 3]))
+    }
+
+    #[tokio::test]
+    async fn perform_send_photo_wrong_id() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let photo = SendPhoto {
+            chat_id: "not a valid chat".to_owned(),
+            ..example_photo()
+        };
+        let res = photo.perform(&server.url, API_KEY).await;
+        assert!(res.is_err())
+    }
+}
@@ -0,0 +1,84 @@
+use super::BotMethod;
+use crate::{
+    errors::{Error, WrongType},
+    parse_json::check_ok,
+};
+use reqwest::Client;
+use serde_json::Value;
+
+/// Makes telegram send updates to url instead of keeping them for getUpdates
+pub struct SetWebhook {
+    pub url: String,
+    /// Sent by telegram in the X-Telegram-Bot-Api-Secret-Token header of every request
+    pub secret_token: Option<String>,
+}
+
+impl SetWebhook {
+    fn to_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("url", self.url.clone())];
+        if let Some(token) = &self.secret_token {
+            params.push(("secret_token", token.clone()));
+        }
+        params
+    }
+}
+
+impl BotMethod for SetWebhook {
+    type Res = bool;
+
+    fn get_endpoint(&self) -> String {
+        "setWebhook".to_owned()
+    }
+
+    async fn perform(&self, api_url: &str, api_key: &str) -> Result<Self::Res, Error> {
+        let client = Client::new();
+        let url = self.get_url(api_url, api_key);
+        let resp = client.post(url).form(&self.to_params()).send().await?;
+        self.check_status(&resp)?;
+        let resp_json: Value = resp.json().await?;
+        match check_ok(resp_json)? {
+            Value::Bool(b) => Ok(b),
+            _ => Err(WrongType {
+                field_name: "result".to_owned(),
+                field_type: "bool".to_owned(),
+            }
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod set_webhook_tests {
+    use super::{BotMethod, SetWebhook};
+    use crate::mock_server::MockServer;
+
+    const API_KEY: &str = "test_key";
+
+    fn example_webhook() -> SetWebhook {
+        SetWebhook {
+            url: "https://example.com/bot".to_owned(),
+            secret_token: Some("secret".to_owned()),
+        }
+    }
+
+    #[test]
+    fn webhook_params() {
+        let result = example_webhook().to_params();
+        let expected = vec![
+            ("url", "https://example.com/bot".to_owned()),
+            ("secret_token", "secret".to_owned()),
+        ];
+        assert_eq!(result, expected)
+    }
+
+    #[tokio::test]
+    async fn perform_set() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let result = example_webhook().perform(&server.url, API_KEY).await;
+        assert!(result.unwrap());
+        assert_eq!(
+            server.get_webhook(),
+            Some("https://example.com/bot".to_owned())
+        )
+    }
+}
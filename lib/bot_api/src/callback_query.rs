@@ -0,0 +1,122 @@
+use super::{
+    errors::Error,
+    message::Message,
+    parse_json::{get_map, get_option_str, get_str, get_val},
+    user::User,
+};
+use serde_json::Value;
+
+/// Sent when a button of an inline keyboard is pressed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallbackQuery {
+    pub id: String,
+    pub from: User,
+    /// Message the keyboard is attached to
+    pub message: Option<Message>,
+    pub data: Option<String>,
+}
+
+impl TryFrom<Value> for CallbackQuery {
+    type Error = Error;
+    fn try_from(val: Value) -> Result<CallbackQuery, Self::Error> {
+        let mut val_map = get_map(val)?;
+        let id = get_str(&mut val_map, "id")?;
+        let from = get_val(&mut val_map, "from")?.try_into()?;
+
+        let mut message = None;
+        let message_val = val_map.remove("message");
+        if let Some(msg_val) = message_val {
+            let msg = msg_val.try_into()?;
+            message = Some(msg);
+        }
+
+        let data = get_option_str(&mut val_map, "data")?;
+        Ok(CallbackQuery {
+            id,
+            from,
+            message,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod callback_query_tests {
+    use super::CallbackQuery;
+    use crate::user::User;
+    use serde_json::{Map, Number, Value};
+
+    fn example_query_val() -> Map<String, Value> {
+        let mut user_map = Map::new();
+        user_map.insert("id".to_owned(), Value::Number(Number::from(1)));
+        user_map.insert("is_bot".to_owned(), Value::Bool(false));
+        user_map.insert(
+            "first_name".to_owned(),
+            Value::String("firstname".to_owned()),
+        );
+
+        let mut val_map = Map::new();
+        val_map.insert("id".to_owned(), Value::String("1".to_owned()));
+        val_map.insert("from".to_owned(), Value::Object(user_map));
+        val_map.insert("data".to_owned(), Value::String("Plant1".to_owned()));
+        val_map
+    }
+
+    fn example_query() -> CallbackQuery {
+        CallbackQuery {
+            id: "1".to_owned(),
+            from: User {
+                id: 1,
+                is_bot: false,
+                first_name: "firstname".to_owned(),
+                last_name: None,
+                username: None,
+                language_code: None,
+            },
+            message: None,
+            data: Some("Plant1".to_owned()),
+        }
+    }
+
+    #[test]
+    fn query_from_val() {
+        let result =
+            <Value as TryInto<CallbackQuery>>::try_into(Value::Object(example_query_val()))
+                .unwrap();
+        let expected = example_query();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn query_no_data() {
+        let mut val_map = example_query_val();
+        val_map.remove("data");
+        let result = <Value as TryInto<CallbackQuery>>::try_into(Value::Object(val_map)).unwrap();
+        let mut expected = example_query();
+        expected.data = None;
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn query_no_from() {
+        let mut val_map = example_query_val();
+        val_map.remove("from");
+        let result = <Value as TryInto<CallbackQuery>>::try_into(Value::Object(val_map));
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn query_bad_message() {
+        let mut val_map = example_query_val();
+        val_map.insert("message".to_owned(), Value::String("bad value".to_owned()));
+        let result = <Value as TryInto<CallbackQuery>>::try_into(Value::Object(val_map));
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn query_no_map() {
+        let result =
+            <Value as TryInto<CallbackQuery>>::try_into(Value::String("bad value".to_owned()));
+        assert!(result.is_err())
+    }
+}
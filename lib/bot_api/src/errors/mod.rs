@@ -23,6 +23,7 @@ pub enum Error {
     NoMessage(Box<Update>),
     MissingImage,
     MissingHandler(String),
+    MediaGroupSize(usize),
     Other(Box<dyn std::error::Error>),
 }
 
@@ -43,6 +44,9 @@ impl fmt::Display for Error {
             }
             Error::MissingImage => write!(frmt, "No image sizes were provided"),
             Error::MissingHandler(msg) => write!(frmt, "Missing handler {msg}"),
+            Error::MediaGroupSize(size) => {
+                write!(frmt, "Media groups need 2 to 10 items, got {size}")
+            }
             Error::Other(err) => err.fmt(frmt),
         }
     }
@@ -207,4 +211,11 @@ mod error_tests {
         let expected = "No image sizes were provided";
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn display_media_group_size() {
+        let result = format!("{}", Error::MediaGroupSize(1));
+        let expected = "Media groups need 2 to 10 items, got 1";
+        assert_eq!(result, expected)
+    }
 }
@@ -0,0 +1,65 @@
+use std::fmt;
+
+/// How telegram formats the text of a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    MarkdownV2,
+    Html,
+}
+
+impl fmt::Display for ParseMode {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMode::MarkdownV2 => frmt.write_str("MarkdownV2"),
+            ParseMode::Html => frmt.write_str("HTML"),
+        }
+    }
+}
+
+/// Escapes text so it is shown as is with `ParseMode::Html`
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escapes text so it is shown as is with `ParseMode::MarkdownV2`
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "_*[]()~`>#+-=|{}.!\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod formatting_tests {
+    use super::{escape_html, escape_markdown, ParseMode};
+
+    #[test]
+    fn display_markdown() {
+        assert_eq!(ParseMode::MarkdownV2.to_string(), "MarkdownV2")
+    }
+
+    #[test]
+    fn display_html() {
+        assert_eq!(ParseMode::Html.to_string(), "HTML")
+    }
+
+    #[test]
+    fn html() {
+        let result = escape_html("<b>Plant & Co</b>");
+        let expected = "&lt;b&gt;Plant &amp; Co&lt;/b&gt;";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn markdown() {
+        let result = escape_markdown("A-Plant (1.5cm)!");
+        let expected = "A\\-Plant \\(1\\.5cm\\)\\!";
+        assert_eq!(result, expected)
+    }
+}
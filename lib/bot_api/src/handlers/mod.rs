@@ -1,8 +1,18 @@
-use super::{bot::Bot, commands::Command, message::Message, photo_size::Photo};
+use super::{
+    bot::Bot, callback_query::CallbackQuery, commands::Command, message::Message, photo_size::Photo,
+};
 use std::future::Future;
 
 pub trait Handler<T: Command> {
     fn handle_msg(&mut self, b: &mut Bot, msg: Message) -> impl Future<Output = ()>;
     fn handle_cmd(&mut self, b: &mut Bot, cmd: T, msg: Message) -> impl Future<Output = ()>;
     fn handle_img(&mut self, b: &mut Bot, photo: Photo, msg: Message) -> impl Future<Output = ()>;
+    /// Called when a button of an inline keyboard is pressed
+    fn handle_callback(&mut self, _b: &mut Bot, _query: CallbackQuery) -> impl Future<Output = ()> {
+        async {}
+    }
+    /// Called after every poll for updates, used for scheduled messages
+    fn on_poll(&mut self, _b: &mut Bot) -> impl Future<Output = ()> {
+        async {}
+    }
 }
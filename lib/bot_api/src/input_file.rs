@@ -0,0 +1,48 @@
+use super::errors::Error;
+use std::{fs, path::PathBuf};
+
+/// File that is uploaded with a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputFile {
+    Path(PathBuf),
+    Bytes { file_name: String, data: Vec<u8> },
+}
+
+impl InputFile {
+    /// File name and contents of the file
+    pub fn load(&self) -> Result<(String, Vec<u8>), Error> {
+        match self {
+            InputFile::Path(path) => {
+                let data = fs::read(path).map_err(|err| Error::Other(Box::new(err)))?;
+                let file_name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                Ok((file_name, data))
+            }
+            InputFile::Bytes { file_name, data } => Ok((file_name.clone(), data.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod input_file_tests {
+    use super::InputFile;
+    use std::path::PathBuf;
+
+    #[test]
+    fn load_bytes() {
+        let file = InputFile::Bytes {
+            file_name: "chart.png".to_owned(),
+            data: vec![1, 2, 3],
+        };
+        let result = file.load().unwrap();
+        assert_eq!(result, ("chart.png".to_owned(), vec![1, 2, 3]))
+    }
+
+    #[test]
+    fn load_path_fail() {
+        let result = InputFile::Path(PathBuf::from("not_a_file.jpg")).load();
+        assert!(result.is_err())
+    }
+}
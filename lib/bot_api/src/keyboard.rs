@@ -0,0 +1,84 @@
+use serde::Serialize;
+
+/// Telegram only accepts callback data of up to 64 bytes
+pub const MAX_CALLBACK_DATA: usize = 64;
+
+/// Button shown below a message, pressing it sends `callback_data` as a callback query
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct InlineKeyboardButton {
+    pub text: String,
+    pub callback_data: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct InlineKeyboardMarkup {
+    /// Rows of buttons
+    pub inline_keyboard: Vec<Vec<InlineKeyboardButton>>,
+}
+
+impl InlineKeyboardMarkup {
+    /// Keyboard with one button per option, the option is used as text and callback data
+    /// options that are too long for callback data are left out
+    pub fn from_options(options: &[String], row_len: usize) -> InlineKeyboardMarkup {
+        let buttons: Vec<InlineKeyboardButton> = options
+            .iter()
+            .filter(|option| !option.is_empty() && option.len() <= MAX_CALLBACK_DATA)
+            .map(|option| InlineKeyboardButton {
+                text: option.clone(),
+                callback_data: option.clone(),
+            })
+            .collect();
+        InlineKeyboardMarkup {
+            inline_keyboard: buttons
+                .chunks(row_len.max(1))
+                .map(|row| row.to_vec())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inline_keyboard.iter().all(|row| row.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod keyboard_tests {
+    use super::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+    fn example_button(text: &str) -> InlineKeyboardButton {
+        InlineKeyboardButton {
+            text: text.to_owned(),
+            callback_data: text.to_owned(),
+        }
+    }
+
+    #[test]
+    fn from_options() {
+        let options = vec!["a".to_owned(), "b".to_owned(), "c".to_owned()];
+        let result = InlineKeyboardMarkup::from_options(&options, 2);
+        let expected = InlineKeyboardMarkup {
+            inline_keyboard: vec![
+                vec![example_button("a"), example_button("b")],
+                vec![example_button("c")],
+            ],
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn from_options_too_long() {
+        let options = vec!["a".repeat(65), "".to_owned()];
+        let result = InlineKeyboardMarkup::from_options(&options, 2);
+        assert!(result.is_empty())
+    }
+
+    #[test]
+    fn serialize() {
+        let keyboard = InlineKeyboardMarkup {
+            inline_keyboard: vec![vec![example_button("a")]],
+        };
+        let result = serde_json::to_string(&keyboard).unwrap();
+        let expected = r#"{"inline_keyboard":[[{"text":"a","callback_data":"a"}]]}"#;
+        assert_eq!(result, expected)
+    }
+}
@@ -1,39 +1,26 @@
+pub mod backoff;
 pub mod bot;
 pub mod bot_methods;
+pub mod callback_query;
 pub mod chat;
 pub mod commands;
 pub mod errors;
+pub mod formatting;
 pub mod handlers;
+pub mod input_file;
+pub mod keyboard;
 pub mod message;
+pub mod mock_server;
 pub mod parse_json;
 pub mod photo_size;
 pub mod update;
 pub mod user;
+pub mod webhook;
 
 #[cfg(test)]
 pub mod test_common {
     use crate::{bot::Bot, commands::Command, handlers::Handler, message::Message};
-    use serde::Deserialize;
-    use serde_json::from_str;
-    use std::{fmt, fs, path::PathBuf};
-
-    #[derive(Deserialize)]
-    pub struct JSONData {
-        pub api_key: String,
-        pub white_list: Vec<i64>,
-    }
-
-    pub fn load_config() -> JSONData {
-        let config_path = PathBuf::from("../../testing/bot_conf.json");
-        let file_contents = fs::read_to_string(config_path).unwrap();
-        let res: JSONData = from_str(&file_contents).unwrap();
-        res
-    }
-
-    #[test]
-    fn load_config_test() {
-        load_config();
-    }
+    use std::fmt;
 
     pub struct ExampleHandler;
     #[derive(Debug, PartialEq, Eq)]
@@ -0,0 +1,581 @@
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread,
+};
+use url::form_urlencoded;
+
+/// Message the bot sent to the mock server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentMessage {
+    pub message_id: i64,
+    pub chat_id: String,
+    pub text: String,
+    /// Serialized keyboard, if the message has one
+    pub reply_markup: Option<String>,
+    pub parse_mode: Option<String>,
+    /// Contents of the uploaded photo, the text is its caption
+    pub photo: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    /// Updates not yet confirmed by the bot
+    updates: Vec<Value>,
+    sent: Vec<SentMessage>,
+    /// File contents by file id
+    files: HashMap<String, Vec<u8>>,
+    last_update: i64,
+    last_message: i64,
+    webhook: Option<String>,
+}
+
+/// Local stand in for the telegram bot api, so bots can be tested without network access
+/// supports getUpdates, sendMessage, sendPhoto, sendMediaGroup, editMessageText,
+/// answerCallbackQuery, getFile, file downloads and setting webhooks
+pub struct MockServer {
+    pub api_key: String,
+    /// Url to use as api url of the bot
+    pub url: String,
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl MockServer {
+    /// Starts the server on a free local port, requests are handled until it is dropped
+    pub fn start(api_key: &str) -> Result<MockServer, std::io::Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let server_key = api_key.to_owned();
+        let server_state = state.clone();
+        let server_stopped = stopped.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if server_stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                if let Err(err) = handle_connection(stream, &server_key, &server_state) {
+                    log::warn!("Mock server could not handle request: {err}");
+                }
+            }
+        });
+
+        Ok(MockServer {
+            api_key: api_key.to_owned(),
+            url: format!("http://{addr}"),
+            addr,
+            state,
+            stopped,
+        })
+    }
+
+    fn get_state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Adds a text message from user in chat, messages starting with / are sent as commands
+    pub fn push_message(&self, chat_id: i64, user_id: i64, text: &str) {
+        let mut state = self.get_state();
+        state.last_message += 1;
+        let mut message = message_json(state.last_message, chat_id, text);
+        message["from"] = user_json(user_id);
+        if text.starts_with('/') {
+            let cmd_len = text.split(' ').next().unwrap_or_default().len();
+            message["entities"] = json!([{"type": "bot_command", "offset": 0, "length": cmd_len}]);
+        }
+        state.push_update("message", message);
+    }
+
+    /// Adds a photo with caption from user in chat, the photo can be downloaded with file_id
+    pub fn push_photo(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        caption: &str,
+        file_id: &str,
+        img: Vec<u8>,
+    ) {
+        let mut state = self.get_state();
+        state.files.insert(file_id.to_owned(), img);
+        state.last_message += 1;
+        let message = json!({
+            "message_id": state.last_message,
+            "date": 0,
+            "chat": {"id": chat_id, "type": "private"},
+            "from": user_json(user_id),
+            "caption": caption,
+            "photo": [{
+                "file_id": file_id,
+                "file_unique_id": file_id,
+                "width": 1,
+                "height": 1,
+            }],
+        });
+        state.push_update("message", message);
+    }
+
+    /// Adds a press of a button with data attached to the sent message with message_id
+    pub fn push_callback(&self, user_id: i64, message_id: i64, data: &str) {
+        let mut state = self.get_state();
+        let message = state
+            .sent
+            .iter()
+            .find(|sent| sent.message_id == message_id)
+            .map(|sent| {
+                message_json(
+                    sent.message_id,
+                    sent.chat_id.parse().unwrap_or_default(),
+                    &sent.text,
+                )
+            });
+        let query = json!({
+            "id": (state.last_update + 1).to_string(),
+            "from": user_json(user_id),
+            "message": message,
+            "data": data,
+        });
+        state.push_update("callback_query", query);
+    }
+
+    /// All messages sent by the bot, with edits applied
+    pub fn get_sent(&self) -> Vec<SentMessage> {
+        self.get_state().sent.clone()
+    }
+
+    /// Url of the webhook set by the bot
+    pub fn get_webhook(&self) -> Option<String> {
+        self.get_state().webhook.clone()
+    }
+
+    /// Number of updates the bot has not confirmed yet
+    pub fn num_pending(&self) -> usize {
+        self.get_state().updates.len()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // wakes up the server thread, so it sees it has been stopped
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+impl MockState {
+    fn push_update(&mut self, kind: &str, content: Value) {
+        self.last_update += 1;
+        let mut update = json!({ "update_id": self.last_update });
+        update[kind] = content;
+        self.updates.push(update);
+    }
+
+    fn push_sent(&mut self, chat_id: i64, text: String, params: &HashMap<String, String>) -> Value {
+        self.last_message += 1;
+        self.sent.push(SentMessage {
+            message_id: self.last_message,
+            chat_id: chat_id.to_string(),
+            text: text.clone(),
+            reply_markup: params.get("reply_markup").cloned(),
+            parse_mode: params.get("parse_mode").cloned(),
+            photo: None,
+        });
+        message_json(self.last_message, chat_id, &text)
+    }
+
+    fn push_photo(
+        &mut self,
+        chat_id: i64,
+        caption: String,
+        photo: Vec<u8>,
+        parse_mode: Option<String>,
+    ) -> Value {
+        self.last_message += 1;
+        self.sent.push(SentMessage {
+            message_id: self.last_message,
+            chat_id: chat_id.to_string(),
+            text: caption.clone(),
+            reply_markup: None,
+            parse_mode,
+            photo: Some(photo),
+        });
+        let mut message = message_json(self.last_message, chat_id, "");
+        message["caption"] = json!(caption);
+        message
+    }
+
+    /// Returns the status and body of the response
+    fn respond(
+        &mut self,
+        method: &str,
+        params: &HashMap<String, String>,
+        uploads: &HashMap<String, Vec<u8>>,
+    ) -> (u16, Vec<u8>) {
+        let param = |name: &str| params.get(name).cloned().unwrap_or_default();
+        match method {
+            "getUpdates" if self.webhook.is_some() => error_response(
+                409,
+                "Conflict: can't use getUpdates method while webhook is active",
+            ),
+            "getUpdates" => {
+                // like the real api, updates before the offset are confirmed and removed
+                let offset: i64 = param("offset").parse().unwrap_or_default();
+                self.updates
+                    .retain(|update| update["update_id"].as_i64().unwrap_or_default() >= offset);
+                ok_response(json!(self.updates))
+            }
+            "sendMessage" => {
+                let Ok(chat_id) = param("chat_id").parse::<i64>() else {
+                    return error_response(400, "Bad Request: chat not found");
+                };
+                ok_response(self.push_sent(chat_id, param("text"), params))
+            }
+            "sendPhoto" => {
+                let Ok(chat_id) = param("chat_id").parse::<i64>() else {
+                    return error_response(400, "Bad Request: chat not found");
+                };
+                let Some(photo) = uploads.get("photo") else {
+                    return error_response(400, "Bad Request: there is no photo in the request");
+                };
+                let parse_mode = params.get("parse_mode").cloned();
+                ok_response(self.push_photo(chat_id, param("caption"), photo.clone(), parse_mode))
+            }
+            "sendMediaGroup" => {
+                let Ok(chat_id) = param("chat_id").parse::<i64>() else {
+                    return error_response(400, "Bad Request: chat not found");
+                };
+                let Ok(Value::Array(media)) = serde_json::from_str::<Value>(&param("media")) else {
+                    return error_response(400, "Bad Request: can't parse media");
+                };
+                if !(2..=10).contains(&media.len()) {
+                    return error_response(400, "Bad Request: wrong number of media");
+                }
+                let mut messages = vec![];
+                for item in media {
+                    let attach_name = item["media"]
+                        .as_str()
+                        .and_then(|media| media.strip_prefix("attach://"))
+                        .unwrap_or_default();
+                    let Some(photo) = uploads.get(attach_name) else {
+                        return error_response(400, "Bad Request: wrong file in media");
+                    };
+                    let caption = item["caption"].as_str().unwrap_or_default().to_owned();
+                    let parse_mode = item["parse_mode"].as_str().map(|mode| mode.to_owned());
+                    messages.push(self.push_photo(chat_id, caption, photo.clone(), parse_mode));
+                }
+                ok_response(json!(messages))
+            }
+            "editMessageText" => {
+                let message_id: i64 = param("message_id").parse().unwrap_or_default();
+                let Some(sent) = self
+                    .sent
+                    .iter_mut()
+                    .find(|sent| sent.message_id == message_id && sent.chat_id == param("chat_id"))
+                else {
+                    return error_response(400, "Bad Request: message to edit not found");
+                };
+                sent.text = param("text");
+                sent.reply_markup = params.get("reply_markup").cloned();
+                sent.parse_mode = params.get("parse_mode").cloned();
+                ok_response(message_json(
+                    message_id,
+                    sent.chat_id.parse().unwrap_or_default(),
+                    &sent.text,
+                ))
+            }
+            "answerCallbackQuery" => ok_response(json!(true)),
+            "setWebhook" => {
+                self.webhook = Some(param("url"));
+                ok_response(json!(true))
+            }
+            "deleteWebhook" => {
+                self.webhook = None;
+                ok_response(json!(true))
+            }
+            "getFile" => {
+                let file_id = param("file_id");
+                if self.files.contains_key(&file_id) {
+                    ok_response(json!({
+                        "file_id": file_id,
+                        "file_unique_id": file_id,
+                        "file_path": file_id,
+                    }))
+                } else {
+                    error_response(400, "Bad Request: invalid file_id")
+                }
+            }
+            _ => error_response(404, "Not Found"),
+        }
+    }
+}
+
+fn user_json(user_id: i64) -> Value {
+    json!({
+        "id": user_id,
+        "is_bot": false,
+        "first_name": "Tester",
+        "username": "tester",
+    })
+}
+
+fn message_json(message_id: i64, chat_id: i64, text: &str) -> Value {
+    json!({
+        "message_id": message_id,
+        "date": 0,
+        "chat": {"id": chat_id, "type": "private"},
+        "text": text,
+    })
+}
+
+fn ok_response(result: Value) -> (u16, Vec<u8>) {
+    (
+        200,
+        json!({"ok": true, "result": result})
+            .to_string()
+            .into_bytes(),
+    )
+}
+
+fn error_response(status: u16, description: &str) -> (u16, Vec<u8>) {
+    let body = json!({"ok": false, "error_code": status, "description": description});
+    (status, body.to_string().into_bytes())
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Name, file name (for uploaded files) and content of each part of a multipart/form-data body
+fn parse_multipart(body: &[u8], boundary: &str) -> Vec<(String, Option<String>, Vec<u8>)> {
+    let delimiter = format!("--{boundary}");
+    let mut parts = vec![];
+    let mut rest = body;
+    while let Some(start) = find_bytes(rest, delimiter.as_bytes()) {
+        rest = &rest[start + delimiter.len()..];
+        if rest.starts_with(b"--") {
+            break;
+        }
+        let Some(headers_end) = find_bytes(rest, b"\r\n\r\n") else {
+            break;
+        };
+        let headers = String::from_utf8_lossy(&rest[..headers_end]).to_string();
+        let content = &rest[headers_end + 4..];
+        let content_end =
+            find_bytes(content, format!("\r\n{delimiter}").as_bytes()).unwrap_or(content.len());
+
+        let header_val = |key: &str| {
+            headers
+                .split(';')
+                .filter_map(|field| field.trim().strip_prefix(&format!("{key}=\"")))
+                .map(|val| val.trim_end_matches('"').to_owned())
+                .next()
+        };
+        if let Some(name) = header_val("name") {
+            parts.push((
+                name,
+                header_val("filename"),
+                content[..content_end].to_vec(),
+            ));
+        }
+        rest = &content[content_end..];
+    }
+    parts
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    api_key: &str,
+    state: &Mutex<MockState>,
+) -> Result<(), std::io::Error> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let target = request_line
+        .split(' ')
+        .nth(1)
+        .unwrap_or_default()
+        .to_owned();
+
+    let mut content_length = 0;
+    let mut boundary = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or_default();
+            } else if name.eq_ignore_ascii_case("content-type") {
+                boundary = value
+                    .split_once("boundary=")
+                    .map(|(_, boundary)| boundary.trim().trim_matches('"').to_owned());
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let mut params: HashMap<String, String> = form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+    let mut uploads = HashMap::new();
+    match boundary {
+        Some(boundary) => {
+            for (name, file_name, content) in parse_multipart(&body, &boundary) {
+                match file_name {
+                    Some(_) => {
+                        uploads.insert(name, content);
+                    }
+                    None => {
+                        params.insert(name, String::from_utf8_lossy(&content).to_string());
+                    }
+                }
+            }
+        }
+        None => params.extend(form_urlencoded::parse(&body).into_owned()),
+    }
+
+    let mut state = state.lock().unwrap_or_else(|err| err.into_inner());
+    let (status, content) =
+        if let Some(file_id) = path.strip_prefix(&format!("/file/bot{api_key}/")) {
+            match state.files.get(file_id) {
+                Some(file) => (200, file.clone()),
+                None => error_response(404, "Not Found"),
+            }
+        } else if let Some(method) = path.strip_prefix(&format!("/bot{api_key}/")) {
+            state.respond(method, &params, &uploads)
+        } else {
+            error_response(401, "Unauthorized")
+        };
+    drop(state);
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        if status == 200 { "OK" } else { "Error" },
+        content.len()
+    )?;
+    stream.write_all(&content)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod mock_server_tests {
+    use super::{parse_multipart, MockServer, SentMessage};
+    use crate::bot::Bot;
+
+    const API_KEY: &str = "test_key";
+
+    fn example_bot(server: &MockServer) -> Bot {
+        let mut bot = Bot::new(API_KEY.to_owned());
+        bot.api_url = server.url.clone();
+        bot
+    }
+
+    #[tokio::test]
+    async fn updates() {
+        let server = MockServer::start(API_KEY).unwrap();
+        server.push_message(1, 2, "/water");
+        server.push_message(1, 2, "Plant1");
+        let mut bot = example_bot(&server);
+        let result = bot.get_all_updates().await.unwrap();
+        assert_eq!(result.updates.len(), 2);
+        let msg = result.updates[0].get_message().unwrap();
+        assert!(msg.is_command());
+        assert_eq!(msg.from.unwrap().id, 2);
+        let msg = result.updates[1].get_message().unwrap();
+        assert!(!msg.is_command());
+        assert_eq!(msg.text, Some("Plant1".to_owned()))
+    }
+
+    #[tokio::test]
+    async fn updates_confirmed() {
+        let server = MockServer::start(API_KEY).unwrap();
+        server.push_message(1, 2, "a message");
+        let mut bot = example_bot(&server);
+        bot.last_update = 1;
+        let result = bot.get_all_updates().await.unwrap();
+        assert!(result.updates.is_empty());
+        assert_eq!(server.num_pending(), 0)
+    }
+
+    #[tokio::test]
+    async fn send_edit() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let bot = example_bot(&server);
+        bot.send_message("1".to_owned(), "a message".to_owned())
+            .await
+            .unwrap();
+        bot.edit_message_text("1".to_owned(), 1, "edited".to_owned(), None)
+            .await
+            .unwrap();
+        let expected = vec![SentMessage {
+            message_id: 1,
+            chat_id: "1".to_owned(),
+            text: "edited".to_owned(),
+            reply_markup: None,
+            parse_mode: None,
+            photo: None,
+        }];
+        assert_eq!(server.get_sent(), expected)
+    }
+
+    #[test]
+    fn multipart() {
+        let body = b"--b\r\nContent-Disposition: form-data; name=\"chat_id\"\r\n\r\n1\r\n\
+--b\r\nContent-Disposition: form-data; name=\"photo\"; filename=\"a.jpg\"\r\n\r\nimg\r\n--b--\r\n";
+        let result = parse_multipart(body, "b");
+        let expected = vec![
+            ("chat_id".to_owned(), None, b"1".to_vec()),
+            (
+                "photo".to_owned(),
+                Some("a.jpg".to_owned()),
+                b"img".to_vec(),
+            ),
+        ];
+        assert_eq!(result, expected)
+    }
+
+    #[tokio::test]
+    async fn download() {
+        let server = MockServer::start(API_KEY).unwrap();
+        server.push_photo(1, 2, "Plant1", "photo1", vec![1, 2, 3]);
+        let bot = example_bot(&server);
+        let result = bot.download_image("photo1".to_owned()).await.unwrap();
+        assert_eq!(result.to_vec(), vec![1, 2, 3])
+    }
+
+    #[tokio::test]
+    async fn download_fail() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let bot = example_bot(&server);
+        let result = bot.download_image("photo1".to_owned()).await;
+        assert!(result.is_err())
+    }
+
+    #[tokio::test]
+    async fn wrong_key() {
+        let server = MockServer::start(API_KEY).unwrap();
+        let mut bot = example_bot(&server);
+        bot.api_key = "not a valid key".to_owned();
+        let result = bot.get_all_updates().await;
+        assert!(result.is_err())
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    callback_query::CallbackQuery,
     errors::{Error, WrongType},
     message::Message,
     parse_json::{check_ok, get_i64, get_map},
@@ -30,6 +31,7 @@ impl Update {
 pub enum UpdateContent {
     Message(Message),
     EditedMessage(Message),
+    CallbackQuery(CallbackQuery),
 }
 
 impl TryFrom<Value> for Update {
@@ -53,6 +55,12 @@ impl TryFrom<Value> for Update {
             content = Some(UpdateContent::EditedMessage(msg));
         }
 
+        let content_callback = val_map.remove("callback_query");
+        if let Some(query_val) = content_callback {
+            let query = query_val.try_into()?;
+            content = Some(UpdateContent::CallbackQuery(query));
+        }
+
         Ok(Update { update_id, content })
     }
 }
@@ -84,7 +92,7 @@ impl TryFrom<Value> for Updates {
 #[cfg(test)]
 mod updates_test {
     use super::{Update, UpdateContent, Updates};
-    use crate::{chat::Chat, message::Message};
+    use crate::{callback_query::CallbackQuery, chat::Chat, message::Message, user::User};
     use serde_json::{Map, Number, Value};
 
     fn example_update_val() -> Map<String, Value> {
@@ -154,7 +162,9 @@ mod updates_test {
         let mut expected = example_update();
         let msg = match expected.content.unwrap() {
             UpdateContent::Message(msg) => msg,
-            UpdateContent::EditedMessage(_) => panic!("should never happen"),
+            UpdateContent::EditedMessage(_) | UpdateContent::CallbackQuery(_) => {
+                panic!("should never happen")
+            }
         };
         expected.content = Some(UpdateContent::EditedMessage(msg));
         assert_eq!(result, expected)
@@ -194,6 +204,54 @@ mod updates_test {
         assert!(result.is_err())
     }
 
+    #[test]
+    fn update_callback() {
+        let mut user_map = Map::new();
+        user_map.insert("id".to_owned(), Value::Number(Number::from(1)));
+        user_map.insert("is_bot".to_owned(), Value::Bool(false));
+        user_map.insert(
+            "first_name".to_owned(),
+            Value::String("firstname".to_owned()),
+        );
+        let mut query_map = Map::new();
+        query_map.insert("id".to_owned(), Value::String("1".to_owned()));
+        query_map.insert("from".to_owned(), Value::Object(user_map));
+
+        let mut val_map = example_update_val();
+        val_map.remove("message");
+        val_map.insert("callback_query".to_owned(), Value::Object(query_map));
+        let result = <Value as TryInto<Update>>::try_into(Value::Object(val_map)).unwrap();
+        let expected = Update {
+            update_id: 1,
+            content: Some(UpdateContent::CallbackQuery(CallbackQuery {
+                id: "1".to_owned(),
+                from: User {
+                    id: 1,
+                    is_bot: false,
+                    first_name: "firstname".to_owned(),
+                    last_name: None,
+                    username: None,
+                    language_code: None,
+                },
+                message: None,
+                data: None,
+            })),
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn update_bad_callback() {
+        let mut val_map = example_update_val();
+        val_map.remove("message");
+        val_map.insert(
+            "callback_query".to_owned(),
+            Value::String("bad value".to_owned()),
+        );
+        let result = <Value as TryInto<Update>>::try_into(Value::Object(val_map));
+        assert!(result.is_err())
+    }
+
     fn example_updates_val() -> Map<String, Value> {
         let mut val_map = Map::new();
         val_map.insert("ok".to_owned(), Value::Bool(true));
@@ -0,0 +1,232 @@
+use super::{errors::Error, update::Update};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::HashMap, net::SocketAddr, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+/// Header telegram sends the secret token in
+pub const SECRET_HEADER: &str = "x-telegram-bot-api-secret-token";
+/// Largest request body that is read
+const MAX_BODY: usize = 1024 * 1024;
+/// Time a client has to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    /// Local address updates are received on, e.g. 127.0.0.1:8443
+    pub address: String,
+    /// Public url telegram sends updates to, usually a reverse proxy forwarding to `address`
+    pub url: String,
+    /// Requests without this token are rejected
+    pub secret_token: String,
+}
+
+/// Receives updates telegram sends to the webhook
+pub struct WebhookServer {
+    listener: TcpListener,
+    secret_token: String,
+}
+
+struct Request {
+    method: String,
+    /// Header names are lowercase
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+fn io_error(err: std::io::Error) -> Error {
+    Error::Other(Box::new(err))
+}
+
+impl WebhookServer {
+    pub async fn bind(address: &str, secret_token: &str) -> Result<WebhookServer, Error> {
+        let listener = TcpListener::bind(address).await.map_err(io_error)?;
+        Ok(WebhookServer {
+            listener,
+            secret_token: secret_token.to_owned(),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        self.listener.local_addr().map_err(io_error)
+    }
+
+    /// Waits for the next request and answers it
+    /// returns the update if the request had the secret token and contained a valid update
+    pub async fn next_update(&self) -> Result<Option<Update>, Error> {
+        let (stream, addr) = self.accept().await?;
+        self.answer(stream, addr).await
+    }
+
+    /// Waits for the next connection, cancelling this does not lose any request
+    pub async fn accept(&self) -> Result<(TcpStream, SocketAddr), Error> {
+        self.listener.accept().await.map_err(io_error)
+    }
+
+    /// Reads the request of an accepted connection and answers it, same as `next_update`
+    pub async fn answer(
+        &self,
+        mut stream: TcpStream,
+        addr: SocketAddr,
+    ) -> Result<Option<Update>, Error> {
+        let request = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await {
+            Ok(Ok(request)) => request,
+            Ok(Err(err)) => {
+                log::warn!("Could not read webhook request from {addr}: {err}");
+                return Ok(None);
+            }
+            Err(_) => {
+                log::warn!("Webhook request from {addr} timed out");
+                return Ok(None);
+            }
+        };
+
+        let (status, update) = if request.method != "POST" {
+            (405, None)
+        } else if request.headers.get(SECRET_HEADER) != Some(&self.secret_token) {
+            log::warn!("Rejected webhook request from {addr} with wrong secret token");
+            (401, None)
+        } else {
+            match serde_json::from_slice::<Value>(&request.body)
+                .map_err(Error::from)
+                .and_then(Update::try_from)
+            {
+                Ok(update) => (200, Some(update)),
+                Err(err) => {
+                    log::warn!("Could not parse webhook update: {err}");
+                    (400, None)
+                }
+            }
+        };
+
+        let reason = match status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            _ => "Method Not Allowed",
+        };
+        stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 {status} {reason}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )
+                .as_bytes(),
+            )
+            .await
+            .map_err(io_error)?;
+        Ok(update)
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<Request, std::io::Error> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let method = request_line
+        .split(' ')
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+        }
+    }
+
+    let content_length: usize = headers
+        .get("content-length")
+        .and_then(|len| len.parse().ok())
+        .unwrap_or_default();
+    if content_length > MAX_BODY {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("request body of {content_length} bytes is too large"),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(Request {
+        method,
+        headers,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod webhook_tests {
+    use super::{WebhookServer, SECRET_HEADER};
+    use reqwest::{Client, StatusCode};
+
+    const SECRET: &str = "secret";
+    const UPDATE: &str = r#"{"update_id":1,"message":{"message_id":1,"date":1,"chat":{"id":1,"type":"private"},"text":"a message"}}"#;
+
+    async fn send_request(
+        server: &WebhookServer,
+        secret: &str,
+        body: &str,
+    ) -> (Option<i64>, tokio::task::JoinHandle<StatusCode>) {
+        let url = format!("http://{}", server.local_addr().unwrap());
+        let secret = secret.to_owned();
+        let body = body.to_owned();
+        let request = tokio::spawn(async move {
+            Client::new()
+                .post(url)
+                .header(SECRET_HEADER, secret)
+                .body(body)
+                .send()
+                .await
+                .unwrap()
+                .status()
+        });
+        let update = server.next_update().await.unwrap();
+        (update.map(|update| update.update_id), request)
+    }
+
+    #[tokio::test]
+    async fn receive_update() {
+        let server = WebhookServer::bind("127.0.0.1:0", SECRET).await.unwrap();
+        let (result, request) = send_request(&server, SECRET, UPDATE).await;
+        assert_eq!(result, Some(1));
+        assert_eq!(request.await.unwrap(), StatusCode::OK)
+    }
+
+    #[tokio::test]
+    async fn wrong_secret() {
+        let server = WebhookServer::bind("127.0.0.1:0", SECRET).await.unwrap();
+        let (result, request) = send_request(&server, "not the secret", UPDATE).await;
+        assert_eq!(result, None);
+        assert_eq!(request.await.unwrap(), StatusCode::UNAUTHORIZED)
+    }
+
+    #[tokio::test]
+    async fn bad_update() {
+        let server = WebhookServer::bind("127.0.0.1:0", SECRET).await.unwrap();
+        let (result, request) = send_request(&server, SECRET, "not an update").await;
+        assert_eq!(result, None);
+        assert_eq!(request.await.unwrap(), StatusCode::BAD_REQUEST)
+    }
+
+    #[tokio::test]
+    async fn wrong_method() {
+        let server = WebhookServer::bind("127.0.0.1:0", SECRET).await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        let request = tokio::spawn(async move { reqwest::get(url).await.unwrap().status() });
+        assert!(server.next_update().await.unwrap().is_none());
+        assert_eq!(request.await.unwrap(), StatusCode::METHOD_NOT_ALLOWED)
+    }
+
+    #[tokio::test]
+    async fn bind_fail() {
+        let result = WebhookServer::bind("not an address", SECRET).await;
+        assert!(result.is_err())
+    }
+}
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS plants (name string unique, species string, location string, origin string, obtained string, auto_water bool, notes string);
+CREATE TABLE IF NOT EXISTS activities (name string, date string, plant string not null, note string, CONSTRAINT unq UNIQUE (name,date,plant));
+CREATE TABLE IF NOT EXISTS graveyard (name string, species string, planted string, died string, reason string);
+CREATE TABLE IF NOT EXISTS growth (plant string, date string, height_cm real ,width_cm real, note string, health int, CONSTRAINT unq UNIQUE (plant,date));
+CREATE TABLE IF NOT EXISTS locations (name string unique, outside bool);
+CREATE TABLE IF NOT EXISTS species (name string unique, scientific_name string, genus string, family string, sunlight string, temp_min real, temp_max real, temp_min_opt real, temp_max_opt real, planting_distance real, ph_min real, ph_max real, watering_notes string, fertilizing_notes string, avg_watering_days int, avg_fertilizing_days int, pruning_notes string,companions string, additional_notes string);
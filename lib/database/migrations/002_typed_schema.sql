@@ -0,0 +1,281 @@
+-- Typed and normalized schema
+-- dates are stored as ISO 8601 (YYYY-MM-DD), booleans as 0/1
+-- lists of notes are stored in child tables instead of ", "-joined strings
+-- species and locations not contained in their tables are kept in other_species/other_location
+
+ALTER TABLE plants RENAME TO plants_v1;
+ALTER TABLE species RENAME TO species_v1;
+ALTER TABLE locations RENAME TO locations_v1;
+ALTER TABLE activities RENAME TO activities_v1;
+ALTER TABLE growth RENAME TO growth_v1;
+ALTER TABLE graveyard RENAME TO graveyard_v1;
+
+CREATE TABLE locations (
+    name text primary key not null,
+    outside integer not null default 0 check (outside in (0, 1))
+);
+
+CREATE TABLE species (
+    name text primary key not null,
+    scientific_name text not null,
+    genus text not null,
+    family text not null,
+    sunlight text not null check (sunlight in ('Direct', 'Indirect', 'Shade')),
+    temp_min real not null,
+    temp_max real not null,
+    temp_min_opt real not null,
+    temp_max_opt real not null,
+    planting_distance real,
+    ph_min real not null,
+    ph_max real not null,
+    avg_watering_days integer,
+    avg_fertilizing_days integer
+);
+
+CREATE TABLE species_notes (
+    species text not null references species(name) on update cascade on delete cascade,
+    kind text not null check (kind in ('watering', 'fertilizing', 'pruning', 'additional')),
+    position integer not null,
+    note text not null,
+    primary key (species, kind, position)
+);
+
+CREATE TABLE species_companions (
+    species text not null references species(name) on update cascade on delete cascade,
+    position integer not null,
+    companion text not null,
+    primary key (species, position)
+);
+
+CREATE TABLE plants (
+    name text primary key not null,
+    species text references species(name) on update cascade on delete restrict,
+    other_species text,
+    location text references locations(name) on update cascade on delete restrict,
+    other_location text,
+    origin text not null,
+    obtained text not null check (date(obtained) = obtained),
+    auto_water integer not null default 0 check (auto_water in (0, 1)),
+    check ((species is null) != (other_species is null)),
+    check ((location is null) != (other_location is null))
+);
+CREATE INDEX plants_species ON plants (species);
+CREATE INDEX plants_location ON plants (location);
+
+CREATE TABLE plant_notes (
+    plant text not null references plants(name) on update cascade on delete cascade,
+    position integer not null,
+    note text not null,
+    primary key (plant, position)
+);
+
+CREATE TABLE activities (
+    name text not null,
+    date text not null check (date(date) = date),
+    plant text not null references plants(name) on update cascade on delete cascade,
+    note text,
+    unique (name, date, plant)
+);
+CREATE INDEX activities_plant_date ON activities (plant, date);
+CREATE INDEX activities_date ON activities (date);
+
+CREATE TABLE growth (
+    plant text not null references plants(name) on update cascade on delete cascade,
+    date text not null check (date(date) = date),
+    height_cm real not null,
+    width_cm real not null,
+    note text,
+    health integer not null check (health between 0 and 5),
+    primary key (plant, date)
+);
+CREATE INDEX growth_date ON growth (date);
+
+CREATE TABLE graveyard (
+    name text not null,
+    species text not null,
+    planted text not null check (date(planted) = planted),
+    died text not null check (date(died) = died),
+    reason text not null
+);
+CREATE INDEX graveyard_died ON graveyard (died);
+
+-- rows of the old tables that cannot be migrated
+-- (logs of plants that are no longer in the plants table or rows with invalid values,
+-- e.g. dates that are not dd.mm.YYYY, so one legacy row does not abort the migration)
+CREATE TABLE archived_plants (
+    name text,
+    species text,
+    location text,
+    origin text,
+    obtained text,
+    auto_water text,
+    notes text
+);
+CREATE TABLE archived_graveyard (
+    name text,
+    species text,
+    planted text,
+    died text,
+    reason text
+);
+CREATE TABLE archived_activities (name text, date text, plant text, note text);
+CREATE TABLE archived_growth (
+    plant text,
+    date text,
+    height_cm text,
+    width_cm text,
+    note text,
+    health text
+);
+
+INSERT INTO locations (name, outside)
+SELECT name, CASE WHEN outside IN ('true', '1', 1) THEN 1 ELSE 0 END
+FROM locations_v1
+WHERE name IS NOT NULL;
+
+INSERT INTO species (
+    name, scientific_name, genus, family, sunlight,
+    temp_min, temp_max, temp_min_opt, temp_max_opt,
+    planting_distance, ph_min, ph_max,
+    avg_watering_days, avg_fertilizing_days
+)
+SELECT
+    name, trim(scientific_name), trim(genus), trim(family), sunlight,
+    temp_min, temp_max, temp_min_opt, temp_max_opt,
+    planting_distance, ph_min, ph_max,
+    avg_watering_days, avg_fertilizing_days
+FROM species_v1
+WHERE name IS NOT NULL;
+
+INSERT INTO species_notes (species, kind, position, note)
+WITH RECURSIVE split(species, kind, position, note, rest) AS (
+    SELECT name, 'watering', -1, '', watering_notes || ', '
+    FROM species_v1 WHERE watering_notes IS NOT NULL AND watering_notes != ''
+    UNION ALL
+    SELECT name, 'fertilizing', -1, '', fertilizing_notes || ', '
+    FROM species_v1 WHERE fertilizing_notes IS NOT NULL AND fertilizing_notes != ''
+    UNION ALL
+    SELECT name, 'pruning', -1, '', pruning_notes || ', '
+    FROM species_v1 WHERE pruning_notes IS NOT NULL AND pruning_notes != ''
+    UNION ALL
+    SELECT name, 'additional', -1, '', additional_notes || ', '
+    FROM species_v1 WHERE additional_notes IS NOT NULL AND additional_notes != ''
+    UNION ALL
+    SELECT
+        species, kind, position + 1,
+        substr(rest, 1, instr(rest, ', ') - 1),
+        substr(rest, instr(rest, ', ') + 2)
+    FROM split
+    WHERE rest != ''
+)
+SELECT species, kind, position, note FROM split WHERE position >= 0;
+
+INSERT INTO species_companions (species, position, companion)
+WITH RECURSIVE split(species, position, companion, rest) AS (
+    SELECT name, -1, '', companions || ', '
+    FROM species_v1 WHERE companions IS NOT NULL AND companions != ''
+    UNION ALL
+    SELECT
+        species, position + 1,
+        substr(rest, 1, instr(rest, ', ') - 1),
+        substr(rest, instr(rest, ', ') + 2)
+    FROM split
+    WHERE rest != ''
+)
+SELECT species, position, companion FROM split WHERE position >= 0;
+
+INSERT INTO plants (
+    name, species, other_species, location, other_location,
+    origin, obtained, auto_water
+)
+SELECT
+    plants_v1.name,
+    species.name,
+    CASE WHEN species.name IS NULL THEN plants_v1.species END,
+    locations.name,
+    CASE WHEN locations.name IS NULL THEN plants_v1.location END,
+    plants_v1.origin,
+    substr(obtained, 7, 4) || '-' || substr(obtained, 4, 2) || '-' || substr(obtained, 1, 2),
+    CASE WHEN auto_water IN ('true', '1', 1) THEN 1 ELSE 0 END
+FROM plants_v1
+LEFT JOIN species ON species.name = plants_v1.species
+LEFT JOIN locations ON locations.name = plants_v1.location
+WHERE obtained GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]';
+
+INSERT INTO archived_plants (name, species, location, origin, obtained, auto_water, notes)
+SELECT name, species, location, origin, obtained, auto_water, notes
+FROM plants_v1
+WHERE obtained IS NULL OR obtained NOT GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]';
+
+INSERT INTO plant_notes (plant, position, note)
+WITH RECURSIVE split(plant, position, note, rest) AS (
+    SELECT name, -1, '', notes || ', '
+    FROM plants_v1 WHERE notes IS NOT NULL AND notes != ''
+    AND name IN (SELECT name FROM plants)
+    UNION ALL
+    SELECT
+        plant, position + 1,
+        substr(rest, 1, instr(rest, ', ') - 1),
+        substr(rest, instr(rest, ', ') + 2)
+    FROM split
+    WHERE rest != ''
+)
+SELECT plant, position, note FROM split WHERE position >= 0;
+
+INSERT INTO activities (name, date, plant, note)
+SELECT name, substr(date, 7, 4) || '-' || substr(date, 4, 2) || '-' || substr(date, 1, 2), plant, note
+FROM activities_v1
+WHERE name IS NOT NULL
+AND plant IN (SELECT name FROM plants)
+AND date GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]';
+
+INSERT INTO archived_activities (name, date, plant, note)
+SELECT name, date, plant, note
+FROM activities_v1
+WHERE plant NOT IN (SELECT name FROM plants)
+OR date NOT GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]'
+OR name IS NULL OR plant IS NULL OR date IS NULL;
+
+INSERT INTO growth (plant, date, height_cm, width_cm, note, health)
+SELECT plant, substr(date, 7, 4) || '-' || substr(date, 4, 2) || '-' || substr(date, 1, 2),
+    height_cm, width_cm, note, health
+FROM growth_v1
+WHERE plant IN (SELECT name FROM plants)
+AND date GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]'
+AND height_cm IS NOT NULL AND width_cm IS NOT NULL
+AND typeof(health) = 'integer' AND health BETWEEN 0 AND 5;
+
+INSERT INTO archived_growth (plant, date, height_cm, width_cm, note, health)
+SELECT plant, date, height_cm, width_cm, note, health
+FROM growth_v1
+WHERE NOT (
+    plant IN (SELECT name FROM plants)
+    AND date GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]'
+    AND height_cm IS NOT NULL AND width_cm IS NOT NULL
+    AND typeof(health) = 'integer' AND health BETWEEN 0 AND 5
+) OR plant IS NULL OR date IS NULL;
+
+INSERT INTO graveyard (name, species, planted, died, reason)
+SELECT
+    name, species,
+    substr(planted, 7, 4) || '-' || substr(planted, 4, 2) || '-' || substr(planted, 1, 2),
+    substr(died, 7, 4) || '-' || substr(died, 4, 2) || '-' || substr(died, 1, 2),
+    reason
+FROM graveyard_v1
+WHERE planted GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]'
+AND died GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]';
+
+INSERT INTO archived_graveyard (name, species, planted, died, reason)
+SELECT name, species, planted, died, reason
+FROM graveyard_v1
+WHERE NOT (
+    planted GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]'
+    AND died GLOB '[0-9][0-9].[0-9][0-9].[0-9][0-9][0-9][0-9]'
+) OR planted IS NULL OR died IS NULL;
+
+DROP TABLE plants_v1;
+DROP TABLE species_v1;
+DROP TABLE locations_v1;
+DROP TABLE activities_v1;
+DROP TABLE growth_v1;
+DROP TABLE graveyard_v1;
@@ -0,0 +1,16 @@
+-- Schedules of care activities besides watering and fertilizing
+-- schedules of a plant replace the schedule of its species for the same activity
+
+CREATE TABLE species_schedules (
+    species text not null references species(name) on update cascade on delete cascade,
+    activity text not null,
+    interval_days integer not null check (interval_days > 0),
+    primary key (species, activity)
+);
+
+CREATE TABLE plant_schedules (
+    plant text not null references plants(name) on update cascade on delete cascade,
+    activity text not null,
+    interval_days integer not null check (interval_days > 0),
+    primary key (plant, activity)
+);
@@ -0,0 +1,31 @@
+-- Schedules that only apply in some months
+-- months is a comma separated list of month numbers (1-12), '' for the whole year
+-- interval_days is null if the activity should not happen in these months
+
+ALTER TABLE species_schedules RENAME TO species_schedules_v3;
+ALTER TABLE plant_schedules RENAME TO plant_schedules_v3;
+
+CREATE TABLE species_schedules (
+    species text not null references species(name) on update cascade on delete cascade,
+    activity text not null,
+    months text not null default '',
+    interval_days integer check (interval_days > 0),
+    primary key (species, activity, months)
+);
+
+CREATE TABLE plant_schedules (
+    plant text not null references plants(name) on update cascade on delete cascade,
+    activity text not null,
+    months text not null default '',
+    interval_days integer check (interval_days > 0),
+    primary key (plant, activity, months)
+);
+
+INSERT INTO species_schedules (species, activity, interval_days)
+SELECT species, activity, interval_days FROM species_schedules_v3;
+
+INSERT INTO plant_schedules (plant, activity, interval_days)
+SELECT plant, activity, interval_days FROM plant_schedules_v3;
+
+DROP TABLE species_schedules_v3;
+DROP TABLE plant_schedules_v3;
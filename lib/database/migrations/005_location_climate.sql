@@ -0,0 +1,11 @@
+-- Expected temperatures of locations
+-- months is a comma separated list of month numbers (1-12), '' for the whole year
+
+CREATE TABLE location_climate (
+    location text not null references locations(name) on update cascade on delete cascade,
+    months text not null default '',
+    temp_min real not null,
+    temp_max real not null,
+    check (temp_min <= temp_max),
+    primary key (location, months)
+);
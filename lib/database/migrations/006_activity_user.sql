@@ -0,0 +1,3 @@
+-- User that entered an activity, null for activities entered before users were recorded
+
+ALTER TABLE activities ADD COLUMN user text;
@@ -0,0 +1,9 @@
+-- Captions and cover flags of plant images, images without either have no row
+
+CREATE TABLE image_metadata (
+    plant text not null references plants(name) on update cascade on delete cascade,
+    file_name text not null,
+    caption text,
+    cover integer not null default 0 check (cover in (0, 1)),
+    primary key (plant, file_name)
+);
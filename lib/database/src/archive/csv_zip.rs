@@ -0,0 +1,383 @@
+use super::{errors::Error, Archive, ArchiveImage};
+use chrono::NaiveDate;
+use csv::{ReaderBuilder, WriterBuilder};
+use plants::{
+    plant::{PlantInfo, PlantLocation, PlantSpecies},
+    schedule::ActivitySchedule,
+    serialize::date_serializer,
+    species::{Species, SunlightRequirement},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::{create_dir_all, File},
+    io::{copy, Read, Seek, Write},
+    path::{Path, PathBuf},
+};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+const VERSION_FILE: &str = "version.txt";
+const PLANTS_FILE: &str = "Plants.csv";
+const PLANT_NOTES_FILE: &str = "PlantNotes.csv";
+const SPECIES_FILE: &str = "Species.csv";
+const SPECIES_NOTES_FILE: &str = "SpeciesNotes.csv";
+const SCHEDULES_FILE: &str = "Schedules.csv";
+const LOCATIONS_FILE: &str = "Locations.csv";
+const ACTIVITIES_FILE: &str = "Activities.csv";
+const GROWTH_FILE: &str = "Growth.csv";
+const GRAVEYARD_FILE: &str = "Graveyard.csv";
+const IMAGES_FILE: &str = "Images.csv";
+const IMAGES_DIR: &str = "images";
+
+/// A plant without its notes, which are stored in a separate csv
+#[derive(Serialize, Deserialize)]
+struct PlantRecord {
+    name: String,
+    species: String,
+    location: String,
+    origin: String,
+    #[serde(with = "date_serializer")]
+    obtained: NaiveDate,
+    auto_water: bool,
+}
+
+/// A species without any of its notes, which are stored in a separate csv
+#[derive(Serialize, Deserialize)]
+struct SpeciesRecord {
+    name: String,
+    scientific_name: String,
+    genus: String,
+    family: String,
+    sunlight: SunlightRequirement,
+    temp_min: f32,
+    temp_max: f32,
+    opt_temp_min: f32,
+    opt_temp_max: f32,
+    planting_distance: Option<f32>,
+    ph_min: f32,
+    ph_max: f32,
+    avg_watering_days: Option<i32>,
+    avg_fertilizing_days: Option<i32>,
+}
+
+/// A single note of a plant or species
+/// `field` is the name of the list the note belongs to
+#[derive(Serialize, Deserialize)]
+struct NoteRecord {
+    name: String,
+    field: String,
+    note: String,
+}
+
+/// A single schedule of a plant or species
+/// `kind` is either "plant" or "species", `months` are space separated month numbers
+#[derive(Serialize, Deserialize)]
+struct ScheduleRecord {
+    name: String,
+    kind: String,
+    activity: String,
+    interval_days: Option<i32>,
+    #[serde(default)]
+    months: String,
+}
+
+fn to_schedules(name: &str, kind: &str, schedules: &[ActivitySchedule]) -> Vec<ScheduleRecord> {
+    schedules
+        .iter()
+        .map(|schedule| ScheduleRecord {
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            activity: schedule.activity.clone(),
+            interval_days: schedule.interval_days,
+            months: schedule
+                .months
+                .iter()
+                .map(|month| month.to_string())
+                .collect::<Vec<String>>()
+                .join(" "),
+        })
+        .collect()
+}
+
+fn from_schedules(
+    name: &str,
+    kind: &str,
+    schedules: &[ScheduleRecord],
+) -> Result<Vec<ActivitySchedule>, Error> {
+    schedules
+        .iter()
+        .filter(|schedule| schedule.name == name && schedule.kind == kind)
+        .map(|schedule| {
+            let months = schedule
+                .months
+                .split_whitespace()
+                .map(|month| month.parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()
+                .map_err(|err| Error::Serialize {
+                    path: PathBuf::from(SCHEDULES_FILE),
+                    err_msg: err.to_string(),
+                })?;
+            Ok(ActivitySchedule {
+                activity: schedule.activity.clone(),
+                interval_days: schedule.interval_days,
+                months,
+            })
+        })
+        .collect()
+}
+
+fn to_notes(name: &str, field: &str, notes: &[String]) -> Vec<NoteRecord> {
+    notes
+        .iter()
+        .map(|note| NoteRecord {
+            name: name.to_owned(),
+            field: field.to_owned(),
+            note: note.clone(),
+        })
+        .collect()
+}
+
+fn from_notes(name: &str, field: &str, notes: &[NoteRecord]) -> Vec<String> {
+    notes
+        .iter()
+        .filter(|note| note.name == name && note.field == field)
+        .map(|note| note.note.clone())
+        .collect()
+}
+
+fn image_entry(image: &ArchiveImage) -> String {
+    let path = image.archive_path();
+    let path = path.to_string_lossy();
+    format!("{IMAGES_DIR}/{path}")
+}
+
+fn csv_err(name: &str, err: csv::Error) -> Error {
+    Error::Serialize {
+        path: PathBuf::from(name),
+        err_msg: err.to_string(),
+    }
+}
+
+fn write_records<T: Serialize, W: Write + Seek>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    items: &[T],
+) -> Result<(), Error> {
+    zip.start_file(name, SimpleFileOptions::default())?;
+    let mut writer = WriterBuilder::new().delimiter(b';').from_writer(zip);
+    for item in items.iter() {
+        writer.serialize(item).map_err(|err| csv_err(name, err))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_records<T: DeserializeOwned, R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<Vec<T>, Error> {
+    let file = zip.by_name(name)?;
+    let mut reader = ReaderBuilder::new().delimiter(b';').from_reader(file);
+    reader
+        .deserialize()
+        .map(|record| record.map_err(|err| csv_err(name, err)))
+        .collect()
+}
+
+pub fn write_zip(archive: &Archive, path: &Path) -> Result<(), Error> {
+    log::info!("Writing archive {:?}", path);
+    let mut zip = ZipWriter::new(File::create(path)?);
+    zip.start_file(VERSION_FILE, SimpleFileOptions::default())?;
+    zip.write_all(archive.version.to_string().as_bytes())?;
+
+    let plants: Vec<PlantRecord> = archive
+        .plants
+        .iter()
+        .map(|plant| PlantRecord {
+            name: plant.name.clone(),
+            species: plant.species.to_string(),
+            location: plant.location.to_string(),
+            origin: plant.origin.clone(),
+            obtained: plant.obtained,
+            auto_water: plant.auto_water,
+        })
+        .collect();
+    let plant_notes: Vec<NoteRecord> = archive
+        .plants
+        .iter()
+        .flat_map(|plant| to_notes(&plant.name, "notes", &plant.notes))
+        .collect();
+    write_records(&mut zip, PLANTS_FILE, &plants)?;
+    write_records(&mut zip, PLANT_NOTES_FILE, &plant_notes)?;
+
+    let species: Vec<SpeciesRecord> = archive
+        .species
+        .iter()
+        .map(|species| SpeciesRecord {
+            name: species.name.clone(),
+            scientific_name: species.scientific_name.clone(),
+            genus: species.genus.clone(),
+            family: species.family.clone(),
+            sunlight: species.sunlight.clone(),
+            temp_min: species.temp_min,
+            temp_max: species.temp_max,
+            opt_temp_min: species.opt_temp_min,
+            opt_temp_max: species.opt_temp_max,
+            planting_distance: species.planting_distance,
+            ph_min: species.ph_min,
+            ph_max: species.ph_max,
+            avg_watering_days: species.avg_watering_days,
+            avg_fertilizing_days: species.avg_fertilizing_days,
+        })
+        .collect();
+    let species_notes: Vec<NoteRecord> = archive
+        .species
+        .iter()
+        .flat_map(|species| {
+            let name = &species.name;
+            let mut notes = to_notes(name, "watering_notes", &species.watering_notes);
+            notes.extend(to_notes(
+                name,
+                "fertilizing_notes",
+                &species.fertilizing_notes,
+            ));
+            notes.extend(to_notes(name, "pruning_notes", &species.pruning_notes));
+            notes.extend(to_notes(name, "companions", &species.companions));
+            notes.extend(to_notes(
+                name,
+                "additional_notes",
+                &species.additional_notes,
+            ));
+            notes
+        })
+        .collect();
+    write_records(&mut zip, SPECIES_FILE, &species)?;
+    write_records(&mut zip, SPECIES_NOTES_FILE, &species_notes)?;
+
+    let mut schedules: Vec<ScheduleRecord> = archive
+        .plants
+        .iter()
+        .flat_map(|plant| to_schedules(&plant.name, "plant", &plant.schedules))
+        .collect();
+    schedules.extend(
+        archive
+            .species
+            .iter()
+            .flat_map(|species| to_schedules(&species.name, "species", &species.schedules)),
+    );
+    write_records(&mut zip, SCHEDULES_FILE, &schedules)?;
+
+    write_records(&mut zip, LOCATIONS_FILE, &archive.locations)?;
+    write_records(&mut zip, ACTIVITIES_FILE, &archive.logs)?;
+    write_records(&mut zip, GROWTH_FILE, &archive.growth)?;
+    write_records(&mut zip, GRAVEYARD_FILE, &archive.graveyard)?;
+    write_records(&mut zip, IMAGES_FILE, &archive.images)?;
+
+    for image in archive.images.iter() {
+        zip.start_file(image_entry(image), SimpleFileOptions::default())?;
+        let mut image_file = File::open(&image.path)?;
+        copy(&mut image_file, &mut zip)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+pub fn read_zip(path: &Path) -> Result<Archive, Error> {
+    log::info!("Reading archive {:?}", path);
+    let mut zip = ZipArchive::new(File::open(path)?)?;
+    let mut version_str = String::new();
+    zip.by_name(VERSION_FILE)?
+        .read_to_string(&mut version_str)?;
+    let version = version_str
+        .trim()
+        .parse::<u32>()
+        .map_err(|err| Error::Serialize {
+            path: PathBuf::from(VERSION_FILE),
+            err_msg: err.to_string(),
+        })?;
+    if version != super::ARCHIVE_VERSION {
+        return Err(Error::Version(version));
+    }
+
+    let plant_notes: Vec<NoteRecord> = read_records(&mut zip, PLANT_NOTES_FILE)?;
+    // schedules are optional, archives without them are still valid
+    let schedules: Vec<ScheduleRecord> = if zip.file_names().any(|name| name == SCHEDULES_FILE) {
+        read_records(&mut zip, SCHEDULES_FILE)?
+    } else {
+        vec![]
+    };
+    let plants = read_records::<PlantRecord, _>(&mut zip, PLANTS_FILE)?
+        .into_iter()
+        .map(|plant| {
+            Ok(PlantInfo {
+                notes: from_notes(&plant.name, "notes", &plant_notes),
+                schedules: from_schedules(&plant.name, "plant", &schedules)?,
+                name: plant.name,
+                species: PlantSpecies::Other(plant.species),
+                location: PlantLocation::Other(plant.location),
+                origin: plant.origin,
+                obtained: plant.obtained,
+                auto_water: plant.auto_water,
+            })
+        })
+        .collect::<Result<Vec<PlantInfo>, Error>>()?;
+
+    let species_notes: Vec<NoteRecord> = read_records(&mut zip, SPECIES_NOTES_FILE)?;
+    let species = read_records::<SpeciesRecord, _>(&mut zip, SPECIES_FILE)?
+        .into_iter()
+        .map(|species| {
+            let notes = |field: &str| from_notes(&species.name, field, &species_notes);
+            Ok(Species {
+                watering_notes: notes("watering_notes"),
+                fertilizing_notes: notes("fertilizing_notes"),
+                pruning_notes: notes("pruning_notes"),
+                companions: notes("companions"),
+                additional_notes: notes("additional_notes"),
+                schedules: from_schedules(&species.name, "species", &schedules)?,
+                name: species.name.clone(),
+                scientific_name: species.scientific_name,
+                genus: species.genus,
+                family: species.family,
+                sunlight: species.sunlight,
+                temp_min: species.temp_min,
+                temp_max: species.temp_max,
+                opt_temp_min: species.opt_temp_min,
+                opt_temp_max: species.opt_temp_max,
+                planting_distance: species.planting_distance,
+                ph_min: species.ph_min,
+                ph_max: species.ph_max,
+                avg_watering_days: species.avg_watering_days,
+                avg_fertilizing_days: species.avg_fertilizing_days,
+            })
+        })
+        .collect::<Result<Vec<Species>, Error>>()?;
+
+    let mut images: Vec<ArchiveImage> = read_records(&mut zip, IMAGES_FILE)?;
+    for image in images.iter_mut() {
+        image.path = PathBuf::from(image_entry(image));
+    }
+
+    Ok(Archive {
+        version,
+        plants,
+        species,
+        locations: read_records(&mut zip, LOCATIONS_FILE)?,
+        logs: read_records(&mut zip, ACTIVITIES_FILE)?,
+        growth: read_records(&mut zip, GROWTH_FILE)?,
+        graveyard: read_records(&mut zip, GRAVEYARD_FILE)?,
+        images,
+    })
+}
+
+pub fn extract_images(path: &Path, images: &[ArchiveImage], out_dir: &Path) -> Result<(), Error> {
+    let mut zip = ZipArchive::new(File::open(path)?)?;
+    for image in images.iter() {
+        let mut image_file = zip.by_name(&image_entry(image))?;
+        let out_path = out_dir.join(image.archive_path());
+        if let Some(parent) = out_path.parent() {
+            create_dir_all(parent)?;
+        }
+        let mut out_file = File::create(out_path)?;
+        copy(&mut image_file, &mut out_file)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use super::{errors::Error, Archive, ArchiveFormat, ArchiveImage};
+use serde::Deserialize;
+use std::{
+    fs::{copy, create_dir_all, read_to_string, remove_dir_all, write},
+    path::{Path, PathBuf},
+};
+
+/// Only the version of an archive, read before the rest so old archives give a useful error
+#[derive(Deserialize)]
+struct ArchiveVersion {
+    version: u32,
+}
+
+/// Images of a json or yaml archive are stored in a directory next to the archive file
+pub fn images_dir(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!("{stem}_images"))
+}
+
+fn serialize_err(path: &Path, err_msg: String) -> Error {
+    Error::Serialize {
+        path: path.to_path_buf(),
+        err_msg,
+    }
+}
+
+fn parse<'a, T: Deserialize<'a>>(
+    contents: &'a str,
+    path: &Path,
+    format: ArchiveFormat,
+) -> Result<T, Error> {
+    match format {
+        ArchiveFormat::Yaml => {
+            serde_yaml::from_str(contents).map_err(|err| serialize_err(path, err.to_string()))
+        }
+        _ => serde_json::from_str(contents).map_err(|err| serialize_err(path, err.to_string())),
+    }
+}
+
+pub fn write_document(archive: &Archive, path: &Path, format: ArchiveFormat) -> Result<(), Error> {
+    log::info!("Writing archive {:?}", path);
+    let contents = match format {
+        ArchiveFormat::Yaml => {
+            serde_yaml::to_string(archive).map_err(|err| serialize_err(path, err.to_string()))?
+        }
+        _ => serde_json::to_string_pretty(archive)
+            .map_err(|err| serialize_err(path, err.to_string()))?,
+    };
+    write(path, contents)?;
+
+    let dir = images_dir(path);
+    if dir.exists() {
+        remove_dir_all(&dir)?;
+    }
+    for image in archive.images.iter() {
+        copy_image(&image.path, &dir.join(image.archive_path()))?;
+    }
+    Ok(())
+}
+
+pub fn read_document(path: &Path, format: ArchiveFormat) -> Result<Archive, Error> {
+    log::info!("Reading archive {:?}", path);
+    let contents = read_to_string(path)?;
+    let version: ArchiveVersion = parse(&contents, path, format)?;
+    if version.version != super::ARCHIVE_VERSION {
+        return Err(Error::Version(version.version));
+    }
+
+    let mut archive: Archive = parse(&contents, path, format)?;
+    let dir = images_dir(path);
+    for image in archive.images.iter_mut() {
+        image.path = dir.join(image.archive_path());
+    }
+    Ok(archive)
+}
+
+pub fn extract_images(images: &[ArchiveImage], out_dir: &Path) -> Result<(), Error> {
+    for image in images.iter() {
+        copy_image(&image.path, &out_dir.join(image.archive_path()))?;
+    }
+    Ok(())
+}
+
+fn copy_image(from: &Path, to: &Path) -> Result<(), Error> {
+    if let Some(parent) = to.parent() {
+        create_dir_all(parent)?;
+    }
+    copy(from, to)?;
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::{fmt, io::Error as IOError, path::PathBuf};
+use zip::result::ZipError;
+
+#[derive(Debug)]
+pub enum Error {
+    IO(String),
+    Zip(String),
+    Serialize { path: PathBuf, err_msg: String },
+    UnknownFormat(PathBuf),
+    Version(u32),
+    Invalid(Vec<String>),
+    PlantsExist(Vec<String>),
+    DB(Box<dyn std::error::Error>),
+}
+
+impl std::error::Error for Error {}
+
+impl From<IOError> for Error {
+    fn from(err: IOError) -> Error {
+        Error::IO(err.to_string())
+    }
+}
+
+impl From<ZipError> for Error {
+    fn from(err: ZipError) -> Error {
+        Error::Zip(err.to_string())
+    }
+}
+
+impl From<Box<dyn std::error::Error>> for Error {
+    fn from(err: Box<dyn std::error::Error>) -> Error {
+        Error::DB(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::IO(msg) => write!(frmt, "IO Error: {msg}"),
+            Error::Zip(msg) => write!(frmt, "Could not read or write zip archive: {msg}"),
+            Error::Serialize { path, err_msg } => {
+                write!(frmt, "Could not (de)serialize {path:?}, message: {err_msg}")
+            }
+            Error::UnknownFormat(path) => write!(
+                frmt,
+                "Unknown archive format for {path:?}, use .json, .yaml or .zip"
+            ),
+            Error::Version(version) => write!(
+                frmt,
+                "Archive version {version} is not supported, expected {}",
+                super::ARCHIVE_VERSION
+            ),
+            Error::Invalid(problems) => {
+                write!(frmt, "Archive is invalid:\n{}", problems.join("\n"))
+            }
+            Error::PlantsExist(names) => {
+                write!(frmt, "Plants already exist: {}", names.join(", "))
+            }
+            Error::DB(err) => err.fmt(frmt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::Error;
+
+    #[test]
+    fn display_version() {
+        let result = format!("{}", Error::Version(0));
+        let expected = "Archive version 0 is not supported, expected 1";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn display_invalid() {
+        let result = format!(
+            "{}",
+            Error::Invalid(vec!["first".to_owned(), "second".to_owned()])
+        );
+        let expected = "Archive is invalid:\nfirst\nsecond";
+        assert_eq!(result, expected)
+    }
+}
@@ -0,0 +1,515 @@
+pub mod csv_zip;
+pub mod documents;
+pub mod errors;
+
+use crate::database_manager::DatabaseManager;
+use errors::Error;
+use plants::{
+    graveyard::GraveyardPlant,
+    growth_item::GrowthItem,
+    location::Location,
+    log_item::LogItem,
+    plant::{PlantInfo, PlantLocation, PlantSpecies},
+    species::Species,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+/// Version of the archive format, increased whenever archives change incompatibly
+pub const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ArchiveFormat {
+    Json,
+    Yaml,
+    CsvZip,
+}
+
+impl TryFrom<&Path> for ArchiveFormat {
+    type Error = Error;
+    fn try_from(path: &Path) -> Result<ArchiveFormat, Error> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(ArchiveFormat::Json),
+            Some("yaml") | Some("yml") => Ok(ArchiveFormat::Yaml),
+            Some("zip") => Ok(ArchiveFormat::CsvZip),
+            _ => Err(Error::UnknownFormat(path.to_path_buf())),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ArchiveImage {
+    pub plant: String,
+    pub file_name: String,
+    /// Where the image file currently is, not part of the archive itself
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl ArchiveImage {
+    /// Path of the image inside the images folder of an archive
+    /// this is the same layout as the plants directory
+    pub fn archive_path(&self) -> PathBuf {
+        PathBuf::from(self.plant.replace(' ', "")).join(&self.file_name)
+    }
+}
+
+/// All contents of a database, independent of its backend
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Archive {
+    pub version: u32,
+    pub plants: Vec<PlantInfo>,
+    pub species: Vec<Species>,
+    pub locations: Vec<Location>,
+    pub logs: Vec<LogItem>,
+    pub growth: Vec<GrowthItem>,
+    pub graveyard: Vec<GraveyardPlant>,
+    pub images: Vec<ArchiveImage>,
+}
+
+fn duplicates<'a>(names: impl Iterator<Item = &'a String>) -> Vec<&'a String> {
+    let mut seen = HashSet::new();
+    let mut duplicates: Vec<&String> = names.filter(|name| !seen.insert(*name)).collect();
+    duplicates.sort();
+    duplicates.dedup();
+    duplicates
+}
+
+impl Archive {
+    /// Loads all contents of `db`
+    /// items are sorted, so the same data always gives the same archive
+    pub fn load<T: DatabaseManager>(db: &mut T, include_images: bool) -> Result<Archive, Error> {
+        let mut plants = vec![];
+        let mut images = vec![];
+        for plant in db.get_all_plants()?.into_iter() {
+            if include_images {
+                images.extend(plant.images.into_iter().map(|image| ArchiveImage {
+                    plant: plant.info.name.clone(),
+                    path: image.file_path.join(&image.file_name),
+                    file_name: image.file_name,
+                }));
+            }
+            // archives only contain names of species and locations
+            plants.push(PlantInfo {
+                species: PlantSpecies::Other(plant.info.species.to_string()),
+                location: PlantLocation::Other(plant.info.location.to_string()),
+                ..plant.info
+            });
+        }
+        plants.sort_by(|plant1, plant2| plant1.name.cmp(&plant2.name));
+        images.sort_by(|img1, img2| {
+            (&img1.plant, &img1.file_name).cmp(&(&img2.plant, &img2.file_name))
+        });
+        images.dedup_by(|img1, img2| img1.plant == img2.plant && img1.file_name == img2.file_name);
+
+        let mut species = db.get_all_species()?;
+        species.sort_by(|species1, species2| species1.name.cmp(&species2.name));
+        let mut locations = db.get_locations()?;
+        locations.sort_by(|loc1, loc2| loc1.name.cmp(&loc2.name));
+        let mut logs = db.get_logs()?;
+        logs.sort_by(|log1, log2| {
+            (log1.date, &log1.plant, &log1.activity).cmp(&(log2.date, &log2.plant, &log2.activity))
+        });
+        let mut growth = db.get_growth()?;
+        growth.sort_by(|item1, item2| (item1.date, &item1.plant).cmp(&(item2.date, &item2.plant)));
+        let mut graveyard = db.get_graveyard()?;
+        graveyard.sort_by(|dead1, dead2| (dead1.died, &dead1.name).cmp(&(dead2.died, &dead2.name)));
+
+        Ok(Archive {
+            version: ARCHIVE_VERSION,
+            plants,
+            species,
+            locations,
+            logs,
+            growth,
+            graveyard,
+            images,
+        })
+    }
+
+    /// Checks the archive is consistent, returning all problems at once
+    /// plants with unknown species or locations are allowed, as the backends allow them as well
+    /// logs and growth of plants in the graveyard are allowed, they are skipped on import
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut problems = vec![];
+        if self.version != ARCHIVE_VERSION {
+            return Err(Error::Version(self.version));
+        }
+
+        for name in duplicates(self.plants.iter().map(|plant| &plant.name)) {
+            problems.push(format!("Plant {name} appears more than once"));
+        }
+        for name in duplicates(self.species.iter().map(|species| &species.name)) {
+            problems.push(format!("Species {name} appears more than once"));
+        }
+        for name in duplicates(self.locations.iter().map(|location| &location.name)) {
+            problems.push(format!("Location {name} appears more than once"));
+        }
+
+        let plant_names: HashSet<&String> = self.plants.iter().map(|plant| &plant.name).collect();
+        let known_names: HashSet<&String> = plant_names
+            .iter()
+            .copied()
+            .chain(self.graveyard.iter().map(|dead| &dead.name))
+            .collect();
+        for log in self.logs.iter() {
+            if !known_names.contains(&log.plant) {
+                problems.push(format!(
+                    "Activity {} on {} is for unknown plant {}",
+                    log.activity, log.date, log.plant
+                ));
+            }
+        }
+        for item in self.growth.iter() {
+            if !known_names.contains(&item.plant) {
+                problems.push(format!(
+                    "Growth on {} is for unknown plant {}",
+                    item.date, item.plant
+                ));
+            }
+        }
+        for image in self.images.iter() {
+            if !plant_names.contains(&image.plant) {
+                problems.push(format!(
+                    "Image {} is for unknown plant {}",
+                    image.file_name, image.plant
+                ));
+            }
+        }
+
+        let species_names: HashSet<String> = self
+            .species
+            .iter()
+            .map(|species| species.name.clone())
+            .collect();
+        let location_names: HashSet<String> = self
+            .locations
+            .iter()
+            .map(|location| location.name.clone())
+            .collect();
+        for plant in self.plants.iter() {
+            if !species_names.contains(&plant.species.to_string()) {
+                log::warn!("Plant {} has unknown species {}", plant.name, plant.species);
+            }
+            if !location_names.contains(&plant.location.to_string()) {
+                log::warn!(
+                    "Plant {} has unknown location {}",
+                    plant.name,
+                    plant.location
+                );
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Invalid(problems))
+        }
+    }
+
+    /// Adds all contents to `db`, in one transaction
+    /// fails if any of the plants already exist, species that already exist are kept
+    /// logs and growth of plants only in the graveyard are skipped, as the backends only keep them for living plants
+    pub fn import<T: DatabaseManager>(self, db: &mut T) -> Result<(), Error> {
+        let mut existing = vec![];
+        for plant in self.plants.iter() {
+            if db.plant_exists(&plant.name)? {
+                existing.push(plant.name.clone());
+            }
+        }
+        if !existing.is_empty() {
+            return Err(Error::PlantsExist(existing));
+        }
+
+        let Archive {
+            plants,
+            species,
+            locations,
+            logs,
+            growth,
+            graveyard,
+            ..
+        } = self;
+        let plant_names: HashSet<&String> = plants.iter().map(|plant| &plant.name).collect();
+        let (logs, dead_logs): (Vec<LogItem>, Vec<LogItem>) = logs
+            .into_iter()
+            .partition(|log| plant_names.contains(&log.plant));
+        let (growth, dead_growth): (Vec<GrowthItem>, Vec<GrowthItem>) = growth
+            .into_iter()
+            .partition(|item| plant_names.contains(&item.plant));
+        if !dead_logs.is_empty() || !dead_growth.is_empty() {
+            log::warn!(
+                "Skipping {} logs and {} growth items of plants in the graveyard",
+                dead_logs.len(),
+                dead_growth.len()
+            );
+        }
+        db.transaction(|db| {
+            for location in locations.into_iter() {
+                db.write_location(location)?;
+            }
+            for species in species.into_iter() {
+                if db.species_exists(&species.name)? {
+                    log::info!("Keeping existing species {}", species.name);
+                } else {
+                    db.write_species(species)?;
+                }
+            }
+            db.write_plants(plants)?;
+            db.write_logs(logs)?;
+            db.write_growths(growth)?;
+
+            let dead = db.get_graveyard()?;
+            let new_dead = graveyard
+                .into_iter()
+                .filter(|plant| !dead.contains(plant))
+                .collect();
+            db.write_graveyard(new_dead)?;
+            Ok::<(), Error>(())
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        match ArchiveFormat::try_from(path)? {
+            ArchiveFormat::CsvZip => csv_zip::write_zip(self, path),
+            format => documents::write_document(self, path, format),
+        }
+    }
+
+    /// Reads and validates an archive
+    pub fn read(path: &Path) -> Result<Archive, Error> {
+        let archive = match ArchiveFormat::try_from(path)? {
+            ArchiveFormat::CsvZip => csv_zip::read_zip(path)?,
+            format => documents::read_document(path, format)?,
+        };
+        archive.validate()?;
+        Ok(archive)
+    }
+}
+
+/// Copies images of the archive at `path` to `out_dir`, using the layout of the plants directory
+pub fn extract_images(path: &Path, images: &[ArchiveImage], out_dir: &Path) -> Result<(), Error> {
+    match ArchiveFormat::try_from(path)? {
+        ArchiveFormat::CsvZip => csv_zip::extract_images(path, images, out_dir),
+        _ => documents::extract_images(images, out_dir),
+    }
+}
+
+/// Writes all contents of `db` to an archive, the format is determined by the file extension
+pub fn export_archive<T: DatabaseManager>(
+    db: &mut T,
+    path: &Path,
+    include_images: bool,
+) -> Result<(), Error> {
+    let archive = Archive::load(db, include_images)?;
+    archive.validate()?;
+    archive.write(path)
+}
+
+/// Adds the contents of an archive to `db`
+/// images are copied to `images_dir` once the import succeeded
+pub fn import_archive<T: DatabaseManager>(
+    db: &mut T,
+    path: &Path,
+    images_dir: Option<&Path>,
+) -> Result<(), Error> {
+    let archive = Archive::read(path)?;
+    let images = archive.images.clone();
+    archive.import(db)?;
+    if let Some(dir) = images_dir {
+        extract_images(path, &images, dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod archive_tests {
+    use super::{errors::Error, export_archive, import_archive, Archive, ArchiveFormat};
+    use crate::{
+        database_manager::DatabaseManager,
+        file_backend::{
+            test_common::{DUMMY_LOGS_PATH, DUMMY_PLANT_PATH, DUMMY_SPECIES_PATH, LOCATIONS_DUMMY},
+            FileDB,
+        },
+        sqlite_backend::SQLiteDB,
+    };
+    use chrono::NaiveDate;
+    use plants::{graveyard::GraveyardPlant, log_item::LogItem};
+    use std::{
+        fs::{copy, create_dir_all, remove_dir_all, remove_file, write},
+        path::{Path, PathBuf},
+    };
+
+    const ARCHIVE_BASE: &str = "../../testing/archive_test";
+    const ARCHIVE_SQLITE_DB: &str = "../../testing/archive_import_test.db";
+
+    fn dummy_db() -> FileDB {
+        FileDB {
+            plants_dir: PathBuf::from(DUMMY_PLANT_PATH),
+            species_dir: PathBuf::from(DUMMY_SPECIES_PATH),
+            location_file: PathBuf::from(LOCATIONS_DUMMY),
+            logs_dir: PathBuf::from(DUMMY_LOGS_PATH),
+            ..Default::default()
+        }
+    }
+
+    fn empty_db(base: &Path) -> FileDB {
+        let db = FileDB {
+            plants_dir: base.join("Plants"),
+            species_dir: base.join("Species"),
+            location_file: base.join("Locations.csv"),
+            logs_dir: base.join("Logs"),
+            ..Default::default()
+        };
+        create_dir_all(&db.plants_dir).unwrap();
+        create_dir_all(&db.species_dir).unwrap();
+        create_dir_all(&db.logs_dir).unwrap();
+        write(&db.location_file, "name;outside\n").unwrap();
+        copy(
+            PathBuf::from(DUMMY_LOGS_PATH).join("Graveyard.csv"),
+            db.get_graveyard_filepath(),
+        )
+        .unwrap();
+        write(db.get_activities_filepath(), "activity;date;plant;note\n").unwrap();
+        write(
+            db.get_growth_filepath(),
+            "plant;date;height_cm;width_cm;note;health\n",
+        )
+        .unwrap();
+        db
+    }
+
+    fn without_images(archive: Archive) -> Archive {
+        Archive {
+            images: vec![],
+            ..archive
+        }
+    }
+
+    fn round_trip(file_name: &str) {
+        let base = PathBuf::from(ARCHIVE_BASE).join(file_name);
+        create_dir_all(&base).unwrap();
+        let path = base.join(file_name);
+        let mut expected = Archive::load(&mut dummy_db(), true).unwrap();
+        expected.plants[0].schedules = vec!["Repotting: 365".parse().unwrap()];
+        expected.species[0].schedules = vec![
+            "Misting: 3".parse().unwrap(),
+            "Fertilizing: never @ Nov-Feb".parse().unwrap(),
+        ];
+        expected.write(&path).unwrap();
+        let result = Archive::read(&path);
+        remove_dir_all(&base).unwrap();
+
+        let result = result.unwrap();
+        let image_names: Vec<String> = result
+            .images
+            .iter()
+            .map(|image| image.file_name.clone())
+            .collect();
+        assert_eq!(image_names, vec!["01011970.jpg".to_owned()]);
+        assert_eq!(without_images(result), without_images(expected))
+    }
+
+    #[test]
+    fn archive_format() {
+        let result = ArchiveFormat::try_from(Path::new("backup.yml")).unwrap();
+        assert_eq!(result, ArchiveFormat::Yaml);
+        let result = ArchiveFormat::try_from(Path::new("backup.zip")).unwrap();
+        assert_eq!(result, ArchiveFormat::CsvZip);
+        assert!(ArchiveFormat::try_from(Path::new("backup.tar")).is_err())
+    }
+
+    #[test]
+    fn round_trip_json() {
+        round_trip("archive.json")
+    }
+
+    #[test]
+    fn round_trip_yaml() {
+        round_trip("archive.yaml")
+    }
+
+    #[test]
+    fn round_trip_zip() {
+        round_trip("archive.zip")
+    }
+
+    #[test]
+    fn validate_unknown_plant() {
+        let mut archive = Archive::load(&mut dummy_db(), false).unwrap();
+        archive.logs[0].plant = "unknown".to_owned();
+        archive.plants.push(archive.plants[0].clone());
+        let result = archive.validate();
+        match result {
+            Err(Error::Invalid(problems)) => assert_eq!(problems.len(), 2),
+            _ => panic!("Archive should be invalid"),
+        }
+    }
+
+    #[test]
+    fn read_wrong_version() {
+        let path = PathBuf::from(ARCHIVE_BASE).with_extension("json");
+        let archive = Archive {
+            version: 0,
+            ..Archive::load(&mut dummy_db(), false).unwrap()
+        };
+        archive.write(&path).unwrap();
+        let result = Archive::read(&path);
+        remove_file(&path).unwrap();
+        assert!(matches!(result, Err(Error::Version(0))))
+    }
+
+    #[test]
+    fn import_zip() {
+        let base = PathBuf::from(ARCHIVE_BASE).join("import");
+        let path = base.join("archive.zip");
+        let mut target = empty_db(&base.join("data"));
+        let images_dir = target.plants_dir.clone();
+        export_archive(&mut dummy_db(), &path, true).unwrap();
+        import_archive(&mut target, &path, Some(&images_dir)).unwrap();
+        let result = Archive::load(&mut target, false).unwrap();
+        let image_exists = images_dir.join("Dummy1").join("01011970.jpg").exists();
+        let second_import = import_archive(&mut target, &path, None);
+        remove_dir_all(&base).unwrap();
+
+        let expected = Archive::load(&mut dummy_db(), false).unwrap();
+        assert_eq!(result, expected);
+        assert!(image_exists);
+        assert!(matches!(second_import, Err(Error::PlantsExist(_))))
+    }
+
+    #[test]
+    fn import_sqlite_skips_graveyard_logs() {
+        let mut archive = Archive::load(&mut dummy_db(), false).unwrap();
+        let num_logs = archive.logs.len();
+        let died = NaiveDate::from_ymd_opt(1970, 1, 2).unwrap();
+        archive.graveyard.push(GraveyardPlant {
+            name: "Dead plant".to_owned(),
+            species: "test species".to_owned(),
+            planted: died,
+            died,
+            reason: "testing".to_owned(),
+        });
+        archive.logs.push(LogItem {
+            activity: "Watering".to_owned(),
+            date: died,
+            plant: "Dead plant".to_owned(),
+            note: None,
+            user: None,
+        });
+        archive.validate().unwrap();
+
+        let mut db = SQLiteDB::new(PathBuf::from(ARCHIVE_SQLITE_DB)).unwrap();
+        let result = archive.import(&mut db);
+        let logs = db.get_logs().unwrap();
+        let graveyard = db.get_graveyard().unwrap();
+        remove_file(ARCHIVE_SQLITE_DB).unwrap();
+
+        result.unwrap();
+        assert_eq!(logs.len(), num_logs);
+        assert!(graveyard.iter().any(|dead| dead.name == "Dead plant"))
+    }
+}
@@ -3,7 +3,7 @@ use plants::{
     growth_item::GrowthItem,
     location::Location,
     log_item::LogItem,
-    plant::{Plant, PlantInfo},
+    plant::{ImageMeta, Plant, PlantInfo},
     species::Species,
 };
 use std::error::Error;
@@ -20,21 +20,29 @@ pub trait DatabaseManager {
     }
     fn write_plants(&mut self, plants: Vec<PlantInfo>) -> Result<(), Box<dyn Error>>;
     fn find_plant_name(&mut self, plant_name: String) -> Result<String, Box<dyn Error>>;
+    /// Renames a plant, including its activities, growth and images
+    fn rename_plant(&mut self, plant_name: &str, new_name: &str) -> Result<(), Box<dyn Error>>;
 
     // Species Methods
     fn get_all_species(&mut self) -> Result<Vec<Species>, Box<dyn Error>>;
     fn get_species(&mut self, species_name: &str) -> Result<Species, Box<dyn Error>>;
     fn write_species(&mut self, species: Species) -> Result<(), Box<dyn Error>>;
     fn find_species_name(&mut self, plant_name: String) -> Result<String, Box<dyn Error>>;
+    /// Deletes a species, fails if there are still plants of this species
+    fn delete_species(&mut self, species_name: &str) -> Result<(), Box<dyn Error>>;
 
     // Graveyard Methods
     fn get_graveyard(&mut self) -> Result<Vec<GraveyardPlant>, Box<dyn Error>>;
     fn kill_plant(&mut self, plant: GraveyardPlant) -> Result<(), Box<dyn Error>>;
+    /// Adds entries to the graveyard without removing any plants
+    fn write_graveyard(&mut self, graveyard: Vec<GraveyardPlant>) -> Result<(), Box<dyn Error>>;
 
     // Location Methods
     fn get_locations(&mut self) -> Result<Vec<Location>, Box<dyn Error>>;
     fn get_location(&mut self, location_name: &str) -> Result<Location, Box<dyn Error>>;
     fn write_location(&mut self, location: Location) -> Result<(), Box<dyn Error>>;
+    /// Deletes a location, fails if there are still plants at this location
+    fn delete_location(&mut self, location_name: &str) -> Result<(), Box<dyn Error>>;
 
     // Log Methods
     fn get_logs(&mut self) -> Result<Vec<LogItem>, Box<dyn Error>>;
@@ -43,6 +51,8 @@ pub trait DatabaseManager {
     fn write_log(&mut self, log: LogItem) -> Result<(), Box<dyn Error>> {
         self.write_logs(vec![log])
     }
+    /// Deletes the log with the same activity, date and plant as `log`
+    fn delete_log(&mut self, log: LogItem) -> Result<(), Box<dyn Error>>;
 
     // Growth Methods
     fn get_growth(&mut self) -> Result<Vec<GrowthItem>, Box<dyn Error>>;
@@ -51,8 +61,42 @@ pub trait DatabaseManager {
     fn write_growth(&mut self, growth: GrowthItem) -> Result<(), Box<dyn Error>> {
         self.write_growths(vec![growth])
     }
+    /// Deletes the growth item with the same plant and date as `growth`
+    fn delete_growth(&mut self, growth: GrowthItem) -> Result<(), Box<dyn Error>>;
+
+    // Image Methods
+    /// Sets caption and cover of an image, other images of the plant stop being the cover
+    fn write_image_meta(&mut self, meta: ImageMeta) -> Result<(), Box<dyn Error>>;
 
     // Existence Methods
     fn plant_exists(&mut self, plant_name: &str) -> Result<bool, Box<dyn Error>>;
     fn species_exists(&mut self, species_name: &str) -> Result<bool, Box<dyn Error>>;
+
+    // Transaction Methods
+    fn begin_transaction(&mut self) -> Result<(), Box<dyn Error>>;
+    fn commit_transaction(&mut self) -> Result<(), Box<dyn Error>>;
+    fn rollback_transaction(&mut self) -> Result<(), Box<dyn Error>>;
+
+    /// Runs `action` in a transaction
+    /// changes are committed when it succeeds and rolled back when it fails
+    fn transaction<T, E, F>(&mut self, action: F) -> Result<T, E>
+    where
+        Self: Sized,
+        F: FnOnce(&mut Self) -> Result<T, E>,
+        E: From<Box<dyn Error>>,
+    {
+        self.begin_transaction()?;
+        match action(self) {
+            Ok(res) => {
+                self.commit_transaction()?;
+                Ok(res)
+            }
+            Err(err) => {
+                if let Err(rollback_err) = self.rollback_transaction() {
+                    log::error!("Could not roll back transaction: {rollback_err}");
+                }
+                Err(err)
+            }
+        }
+    }
 }
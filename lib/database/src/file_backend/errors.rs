@@ -19,6 +19,11 @@ pub enum Error {
     SpeciesNotFound(String),
     LocationNotFound(String),
     PlantNotFound(String),
+    LogNotFound(String),
+    GrowthNotFound(String),
+    PlantExists(String),
+    LocationInUse(String),
+    SpeciesInUse(String),
 }
 impl std::error::Error for Error {}
 
@@ -97,6 +102,15 @@ impl fmt::Display for Error {
             Error::LocationNotFound(name) => {
                 frmt.write_str(&format!("Could not find location {name}"))
             }
+            Error::LogNotFound(desc) => frmt.write_str(&format!("Log {desc} not found")),
+            Error::GrowthNotFound(desc) => frmt.write_str(&format!("Growth {desc} not found")),
+            Error::PlantExists(name) => frmt.write_str(&format!("Plant {name} already exists")),
+            Error::LocationInUse(name) => {
+                frmt.write_str(&format!("Location {name} still has plants"))
+            }
+            Error::SpeciesInUse(name) => {
+                frmt.write_str(&format!("Species {name} still has plants"))
+            }
         }
     }
 }
@@ -263,4 +277,18 @@ mod error_tests {
         let expected = "Plant not a plant not found";
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn display_plant_exists() {
+        let result = format!("{}", Error::PlantExists("a plant".to_owned()));
+        let expected = "Plant a plant already exists";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn display_location_in_use() {
+        let result = format!("{}", Error::LocationInUse("a location".to_owned()));
+        let expected = "Location a location still has plants";
+        assert_eq!(result, expected)
+    }
 }
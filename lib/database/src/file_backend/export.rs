@@ -0,0 +1,152 @@
+use super::{
+    errors::Error,
+    write_csv::{write_activities, write_csv, write_graveyard, write_growth},
+    write_json::item_path,
+    FileDB,
+};
+use crate::database_manager::DatabaseManager;
+use std::{
+    error::Error as StdErr,
+    fs::{create_dir_all, read_dir},
+    io::Error as IOError,
+    path::Path,
+};
+
+/// Replaces the contents of `out` with all contents of `db`
+/// image files are not touched (only their captions), and all items are sorted, so exporting the same data always gives the same files
+pub fn export<T: DatabaseManager>(db: &mut T, out: &mut FileDB) -> Result<(), Box<dyn StdErr>> {
+    let all_plants = db.get_all_plants()?;
+    let mut image_meta: Vec<_> = all_plants
+        .iter()
+        .flat_map(|plant| {
+            plant
+                .images
+                .iter()
+                .filter_map(|image| image.get_meta(&plant.info.name))
+        })
+        .collect();
+    image_meta.sort_by(|meta1, meta2| {
+        (&meta1.plant, &meta1.file_name).cmp(&(&meta2.plant, &meta2.file_name))
+    });
+    let plants: Vec<_> = all_plants.into_iter().map(|plant| plant.info).collect();
+    let species = db.get_all_species()?;
+    let mut locations = db.get_locations()?;
+    locations.sort_by(|loc1, loc2| loc1.name.cmp(&loc2.name));
+    let mut logs = db.get_logs()?;
+    logs.sort_by(|log1, log2| {
+        (log1.date, &log1.plant, &log1.activity).cmp(&(log2.date, &log2.plant, &log2.activity))
+    });
+    let mut growth = db.get_growth()?;
+    growth.sort_by(|item1, item2| (item1.date, &item1.plant).cmp(&(item2.date, &item2.plant)));
+    let mut graveyard = db.get_graveyard()?;
+    graveyard.sort_by(|dead1, dead2| (dead1.died, &dead1.name).cmp(&(dead2.died, &dead2.name)));
+
+    log::info!("Exporting {} plants to {:?}", plants.len(), out.plants_dir);
+    out.transaction(|out_db| {
+        create_dir_all(&out_db.plants_dir).map_err(<IOError as Into<Error>>::into)?;
+        create_dir_all(&out_db.species_dir).map_err(<IOError as Into<Error>>::into)?;
+        create_dir_all(&out_db.logs_dir).map_err(<IOError as Into<Error>>::into)?;
+
+        let plants_dir = out_db.plants_dir.clone();
+        let plant_names: Vec<&str> = plants.iter().map(|plant| plant.name.as_str()).collect();
+        remove_stale_items(out_db, &plants_dir, &plant_names)?;
+        out_db.write_items(plants, &plants_dir)?;
+        let species_dir = out_db.species_dir.clone();
+        let species_names: Vec<&str> = species.iter().map(|sp| sp.name.as_str()).collect();
+        remove_stale_items(out_db, &species_dir, &species_names)?;
+        out_db.write_items(species, &species_dir)?;
+
+        let location_file = out_db.write_path(out_db.location_file.clone(), false)?;
+        write_csv(locations, &location_file, false)?;
+        let activities_file = out_db.write_path(out_db.get_activities_filepath(), false)?;
+        write_activities(logs, &activities_file, false)?;
+        let growth_file = out_db.write_path(out_db.get_growth_filepath(), false)?;
+        write_growth(growth, &growth_file, false)?;
+        let graveyard_file = out_db.write_path(out_db.get_graveyard_filepath(), false)?;
+        write_graveyard(graveyard, &graveyard_file, false)?;
+        let images_file = out_db.write_path(out_db.get_images_filepath(), false)?;
+        write_csv(image_meta, &images_file, false)?;
+        Ok(())
+    })
+}
+
+/// Removes the json files in `dir` of items not in `names`
+/// only json files are removed, so image files are kept
+fn remove_stale_items(out_db: &mut FileDB, dir: &Path, names: &[&str]) -> Result<(), Error> {
+    let keep: Vec<String> = names.iter().map(|name| name.replace(' ', "")).collect();
+    for entry in read_dir(dir)? {
+        let item_name = entry?.file_name().to_string_lossy().to_string();
+        let json_path = item_path(dir, &item_name);
+        if !keep.contains(&item_name) && json_path.is_file() {
+            out_db.remove_data_file(json_path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::export;
+    use crate::{
+        database_manager::DatabaseManager,
+        file_backend::{
+            test_common::{DUMMY_LOGS_PATH, DUMMY_PLANT_PATH, DUMMY_SPECIES_PATH, LOCATIONS_DUMMY},
+            FileDB,
+        },
+    };
+    use std::{
+        fs::{read_to_string, remove_dir_all},
+        path::PathBuf,
+    };
+
+    const EXPORT_BASE: &str = "../../testing/export_test";
+
+    fn out_db() -> FileDB {
+        let base = PathBuf::from(EXPORT_BASE);
+        FileDB {
+            plants_dir: base.join("Plants"),
+            species_dir: base.join("Species"),
+            location_file: base.join("Locations.csv"),
+            logs_dir: base.join("Logs"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn export_file_db() {
+        let mut source = FileDB {
+            plants_dir: PathBuf::from(DUMMY_PLANT_PATH),
+            species_dir: PathBuf::from(DUMMY_SPECIES_PATH),
+            location_file: PathBuf::from(LOCATIONS_DUMMY),
+            logs_dir: PathBuf::from(DUMMY_LOGS_PATH),
+            ..Default::default()
+        };
+        let mut out = out_db();
+        export(&mut source, &mut out).unwrap();
+        let activities = read_to_string(out.get_activities_filepath()).unwrap();
+        export(&mut source, &mut out).unwrap();
+        let activities_again = read_to_string(out.get_activities_filepath()).unwrap();
+
+        let mut reloaded = out_db();
+        let result: Vec<_> = reloaded
+            .get_all_plants()
+            .unwrap()
+            .into_iter()
+            .map(|plant| (plant.info, plant.activities, plant.growth))
+            .collect();
+        let expected: Vec<_> = source
+            .get_all_plants()
+            .unwrap()
+            .into_iter()
+            .map(|plant| (plant.info, plant.activities, plant.growth))
+            .collect();
+        let species = reloaded.get_all_species().unwrap();
+        let locations = reloaded.get_locations().unwrap();
+        remove_dir_all(EXPORT_BASE).unwrap();
+
+        assert_eq!(activities, activities_again);
+        assert_eq!(result, expected);
+        assert_eq!(species, source.get_all_species().unwrap());
+        assert_eq!(locations.len(), source.get_locations().unwrap().len())
+    }
+}
@@ -3,12 +3,16 @@ use super::{
     load_csv::{load_activities, load_growth, load_locations},
     load_json::{load_plant_infos, load_species},
 };
-use chrono::NaiveDate;
 use plants::{
     growth_item::GrowthItem,
+    location::Location,
     log_item::LogItem,
     named::Named,
-    plant::{Plant, PlantImage, PlantLocation, PlantSpecies},
+    plant::{
+        plant_image::{exif_date, image_name_date},
+        Plant, PlantImage, PlantInfo, PlantLocation, PlantSpecies,
+    },
+    species::Species,
 };
 use std::{ffi::OsString, fs, path::PathBuf};
 
@@ -20,11 +24,28 @@ pub fn load_plants(
     locations_file: &PathBuf,
 ) -> Result<Vec<Plant>, Error> {
     log::info!("Loading plants");
-    let mut plant_infos = load_plant_infos(plants_dir)?;
+    let plant_infos = load_plant_infos(plants_dir)?;
     let species = load_species(species_dir)?;
     let locations = load_locations(locations_file)?;
     let logs = load_activities(activity_file)?;
     let growth = load_growth(growth_file)?;
+    build_plants(plant_infos, species, locations, logs, growth, |name| {
+        plants_dir.join(name.replace(' ', ""))
+    })
+}
+
+/// Combines the loaded items to plants, the images of a plant are loaded from `image_dir`
+pub fn build_plants<F>(
+    mut plant_infos: Vec<PlantInfo>,
+    species: Vec<Species>,
+    locations: Vec<Location>,
+    logs: Vec<LogItem>,
+    growth: Vec<GrowthItem>,
+    image_dir: F,
+) -> Result<Vec<Plant>, Error>
+where
+    F: Fn(&str) -> PathBuf,
+{
     let mut plants = vec![];
     for plant_info in plant_infos.iter_mut() {
         log::info!("Loading plant {}", plant_info.name);
@@ -90,8 +111,7 @@ pub fn load_plants(
             .collect();
         log::info!("Loaded growth for plant {}", plant_info.name);
 
-        let img_dir = plants_dir.join(plant_info.name.replace(' ', ""));
-        let images = load_images(&img_dir)?;
+        let images = load_images(&image_dir(&plant_info.name))?;
         log::info!("Loaded images for plant {}", plant_info.name);
         if images.is_empty() {
             log::warn!("No images for plant {}", plant_info.name);
@@ -120,24 +140,31 @@ pub fn load_images(image_dir: &PathBuf) -> Result<Vec<PlantImage>, Error> {
         if path.extension() != Some(&OsString::from("jpg")) {
             continue;
         }
-        let stem = path.file_stem().ok_or(IOErr {
-            kind: "Get File Stem".to_owned(),
-        })?;
-        let stem_str = stem.to_str().ok_or(IOErr {
-            kind: "Convert from OS String".to_owned(),
-        })?;
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or(IOErr {
+                kind: "Convert from OS String".to_owned(),
+            })?
+            .to_owned();
 
-        let created = NaiveDate::parse_from_str(stem_str, "%d%m%Y").map_err(|_| ParseError {
-            ty: "Date".to_owned(),
-            input: stem_str.to_owned().to_owned(),
-        })?;
+        // images not named by date (e.g. copied from a camera) use the date they were taken
+        let created = match image_name_date(&file_name) {
+            Some(date) => date,
+            None => exif_date(&fs::read(&path)?).ok_or(ParseError {
+                ty: "Date".to_owned(),
+                input: file_name.clone(),
+            })?,
+        };
         let file_path = path.parent().ok_or(IOErr {
             kind: "Get Path Parent".to_owned(),
         })?;
         let image = PlantImage {
             created,
-            file_name: stem_str.to_owned() + ".jpg",
+            file_name,
             file_path: file_path.to_path_buf(),
+            caption: None,
+            cover: false,
         };
         plant_images.push(image)
     }
@@ -149,10 +176,13 @@ pub fn load_images(image_dir: &PathBuf) -> Result<Vec<PlantImage>, Error> {
 mod json_to_plant_tests {
     use super::{load_images, load_plants};
     use crate::file_backend::test_common::{
-        dummy_plant1, dummy_plant2, ACTIVITIES_DUMMY, DUMMY_PLANT_PATH, DUMMY_SPECIES_PATH,
-        FILE_DOES_NOT_EXIST, GROWTH_DUMMY, LOCATIONS_DUMMY, TESTING_BASE,
+        dummy_date, dummy_plant1, dummy_plant2, ACTIVITIES_DUMMY, DUMMY_PLANT_PATH,
+        DUMMY_SPECIES_PATH, FILE_DOES_NOT_EXIST, GROWTH_DUMMY, LOCATIONS_DUMMY, TESTING_BASE,
+    };
+    use std::{
+        fs::{create_dir_all, remove_dir_all, write},
+        path::PathBuf,
     };
-    use std::path::PathBuf;
 
     #[test]
     fn load_dummy_plants() {
@@ -206,6 +236,19 @@ mod json_to_plant_tests {
         assert!(result.is_err())
     }
 
+    #[test]
+    fn load_images_suffix() {
+        let image_dir = PathBuf::from(TESTING_BASE).join("images_suffix_test");
+        create_dir_all(&image_dir).unwrap();
+        write(image_dir.join("01011970_2.jpg"), []).unwrap();
+        let result = load_images(&image_dir);
+        remove_dir_all(&image_dir).unwrap();
+        let images = result.unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].file_name, "01011970_2.jpg");
+        assert_eq!(images[0].created, dummy_date())
+    }
+
     #[test]
     fn load_images_no_date() {
         let result = load_images(&PathBuf::from(&TESTING_BASE));
@@ -130,6 +130,7 @@ mod load_csv_tests {
             date: dummy_date(),
             plant: "Dummy1".to_owned(),
             note: None,
+            user: None,
         }];
         assert_eq!(result, expected)
     }
@@ -13,9 +13,9 @@ pub fn load_json<T: DeserializeOwned>(file_name: &PathBuf) -> Result<T, Error> {
     Ok(res)
 }
 
-pub fn load_dir<T: DeserializeOwned>(dir_path: &PathBuf) -> Result<Vec<T>, Error> {
-    log::info!("Loading JSON from dir {:?}", dir_path);
-    let mut struct_list = vec![];
+/// The json files in the subdirectories of `dir_path`, one directory per item
+pub fn item_files(dir_path: &PathBuf) -> Result<Vec<PathBuf>, Error> {
+    let mut files = vec![];
     for dir_entry in fs::read_dir(dir_path)? {
         let entry = dir_entry?;
         let entry_contents = fs::read_dir(entry.path())?;
@@ -24,12 +24,19 @@ pub fn load_dir<T: DeserializeOwned>(dir_path: &PathBuf) -> Result<Vec<T>, Error
             .collect::<Result<Vec<DirEntry>, Error>>()?;
         let json_files = content_paths
             .into_iter()
-            .filter(|entry| entry.path().extension() == Some(&OsString::from("json")));
+            .map(|entry| entry.path())
+            .filter(|path| path.extension() == Some(&OsString::from("json")));
+        files.extend(json_files);
+    }
+    Ok(files)
+}
 
-        for json_file in json_files {
-            let json_contents = load_json(&json_file.path())?;
-            struct_list.push(json_contents);
-        }
+pub fn load_dir<T: DeserializeOwned>(dir_path: &PathBuf) -> Result<Vec<T>, Error> {
+    log::info!("Loading JSON from dir {:?}", dir_path);
+    let mut struct_list = vec![];
+    for json_file in item_files(dir_path)? {
+        let json_contents = load_json(&json_file)?;
+        struct_list.push(json_contents);
     }
     Ok(struct_list)
 }
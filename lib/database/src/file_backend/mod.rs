@@ -1,16 +1,18 @@
 pub mod errors;
+pub mod export;
 pub mod json_to_plant;
 pub mod load_csv;
 pub mod load_json;
+pub mod transaction;
 pub mod write_csv;
 pub mod write_json;
 
 use errors::Error;
-use json_to_plant::load_plants;
-use load_csv::{load_activities, load_graveyard, load_growth, load_locations};
-use load_json::load_species;
-use write_csv::{add_location, write_activities, write_graveyard, write_growth};
-use write_json::{write_plants, write_species};
+use json_to_plant::build_plants;
+use load_csv::{load_activities, load_csv, load_graveyard, load_growth, load_locations};
+use transaction::FileTransaction;
+use write_csv::{write_activities, write_csv, write_graveyard, write_growth};
+use write_json::item_path;
 
 use crate::database_manager::DatabaseManager;
 use plants::{
@@ -19,13 +21,13 @@ use plants::{
     location::Location,
     log_item::LogItem,
     named::Named,
-    plant::{Plant, PlantInfo, PlantSpecies},
+    plant::{plant_image::apply_image_meta, ImageMeta, Plant, PlantInfo, PlantSpecies},
     species::Species,
 };
 use std::{
-    fs::{create_dir_all, remove_file, rename},
-    io::Error as IOError,
-    path::PathBuf,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
 };
 
 #[derive(Debug, PartialEq)]
@@ -37,6 +39,7 @@ pub struct FileDB {
     pub graveyard_csv: String,
     pub growth_csv: String,
     pub activities_csv: String,
+    pub images_csv: String,
     pub date_format: String,
 
     pub plants_cache: Vec<Plant>,
@@ -45,6 +48,8 @@ pub struct FileDB {
     pub location_cache: Vec<Location>,
     pub logs_cache: Vec<LogItem>,
     pub growth_cache: Vec<GrowthItem>,
+
+    pub transaction: Option<FileTransaction>,
 }
 
 impl Default for FileDB {
@@ -58,6 +63,7 @@ impl Default for FileDB {
             graveyard_csv: "Graveyard.csv".to_owned(),
             growth_csv: "Growth.csv".to_owned(),
             activities_csv: "Activities.csv".to_owned(),
+            images_csv: "Images.csv".to_owned(),
             date_format: "%d.%m.%Y".to_owned(),
             plants_cache: vec![],
             graveyard_cache: vec![],
@@ -65,6 +71,7 @@ impl Default for FileDB {
             location_cache: vec![],
             logs_cache: vec![],
             growth_cache: vec![],
+            transaction: None,
         }
     }
 }
@@ -82,24 +89,68 @@ impl FileDB {
         self.logs_dir.join(self.growth_csv.clone())
     }
 
+    pub fn get_images_filepath(&self) -> PathBuf {
+        self.logs_dir.join(self.images_csv.clone())
+    }
+
+    /// Loads a csv file with the changes of the running transaction
+    fn read_csv<T>(
+        &self,
+        path: &Path,
+        load: fn(&PathBuf) -> Result<Vec<T>, Error>,
+    ) -> Result<Vec<T>, Error> {
+        match self.read_path(path) {
+            Some(path) => load(&path),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Whether the header of the activities file has the user column, older files were written without it
+    fn activities_have_user(&self) -> Result<bool, Error> {
+        let activities_file = match self.read_path(&self.get_activities_filepath()) {
+            Some(path) => path,
+            None => return Ok(false),
+        };
+        let mut header = String::new();
+        BufReader::new(File::open(activities_file)?).read_line(&mut header)?;
+        Ok(header.trim().split(';').any(|column| column == "user"))
+    }
+
+    /// Captions and cover flags of images, the file is only created once an image has any
+    fn load_image_meta(&self) -> Result<Vec<ImageMeta>, Error> {
+        let images_file = self.get_images_filepath();
+        if self.file_exists(&images_file) {
+            self.read_csv(&images_file, load_csv)
+        } else {
+            Ok(vec![])
+        }
+    }
+
     fn load_plants(&mut self) -> Result<(), Error> {
         log::info!("Loading plants from json and csv");
-        let activity_file = self.get_activities_filepath();
-        let growth_file = self.get_growth_filepath();
-        let plants = load_plants(
-            &self.plants_dir,
-            &self.species_dir,
-            &activity_file,
-            &growth_file,
-            &self.location_file,
-        )?;
+        let plant_infos = self.load_items(&self.plants_dir)?;
+        let species = self.load_items(&self.species_dir)?;
+        let locations = self.read_csv(&self.location_file, load_locations)?;
+        let logs = self.read_csv(&self.get_activities_filepath(), load_activities)?;
+        let growth = self.read_csv(&self.get_growth_filepath(), load_growth)?;
+        let plants = build_plants(plant_infos, species, locations, logs, growth, |name| {
+            self.current_dir(&self.plants_dir.join(name.replace(' ', "")))
+        })?;
+        let image_meta = self.load_image_meta()?;
+        let plants = plants
+            .into_iter()
+            .map(|mut plant| {
+                apply_image_meta(&mut plant.images, &plant.info.name, &image_meta);
+                plant
+            })
+            .collect();
         self.plants_cache = plants;
         Ok(())
     }
 
     fn load_species(&mut self) -> Result<(), Error> {
         log::info!("Loading species from json");
-        let species = load_species(&self.species_dir)?;
+        let species = self.load_items(&self.species_dir)?;
         self.species_cache = species;
         Ok(())
     }
@@ -107,28 +158,28 @@ impl FileDB {
     fn load_graveyard(&mut self) -> Result<(), Error> {
         log::info!("Loading graveyard from csv");
         let graveyard_file = self.get_graveyard_filepath();
-        let graveyard = load_graveyard(&graveyard_file)?;
+        let graveyard = self.read_csv(&graveyard_file, load_graveyard)?;
         self.graveyard_cache = graveyard;
         Ok(())
     }
 
     fn load_locations(&mut self) -> Result<(), Error> {
         log::info!("Loading Locations from csv");
-        let locations = load_locations(&self.location_file)?;
+        let locations = self.read_csv(&self.location_file, load_locations)?;
         self.location_cache = locations;
         Ok(())
     }
 
     fn load_logs(&mut self) -> Result<(), Error> {
         log::info!("Loading activities from csv");
-        let logs = load_activities(&self.get_activities_filepath())?;
+        let logs = self.read_csv(&self.get_activities_filepath(), load_activities)?;
         self.logs_cache = logs;
         Ok(())
     }
 
     fn load_growth(&mut self) -> Result<(), Error> {
         log::info!("Loading growth from csv");
-        let growth = load_growth(&self.get_growth_filepath())?;
+        let growth = self.read_csv(&self.get_growth_filepath(), load_growth)?;
         self.growth_cache = growth;
         Ok(())
     }
@@ -182,6 +233,54 @@ impl DatabaseManager for FileDB {
         Err(Box::new(Error::PlantNotFound(plant_name)))
     }
 
+    fn rename_plant(
+        &mut self,
+        plant_name: &str,
+        new_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let plant = self.get_plant(plant_name)?;
+        if self.plant_exists(new_name)? {
+            return Err(Error::PlantExists(new_name.to_owned()).into());
+        }
+
+        let rename = |name: &mut String| {
+            if *name == plant_name {
+                *name = new_name.to_owned();
+            }
+        };
+        let mut logs = self.get_logs()?;
+        logs.iter_mut().for_each(|log| rename(&mut log.plant));
+        let mut growth = self.get_growth()?;
+        growth.iter_mut().for_each(|item| rename(&mut item.plant));
+        let mut image_meta = self.load_image_meta()?;
+        image_meta
+            .iter_mut()
+            .for_each(|meta| rename(&mut meta.plant));
+
+        self.transaction(|db| {
+            let mut info = plant.info;
+            info.name = new_name.to_owned();
+            let old_dir = plant_name.replace(' ', "");
+            let new_dir = new_name.replace(' ', "");
+            if old_dir != new_dir {
+                // images are moved with the directory, the old json is replaced
+                db.move_dir(db.plants_dir.join(&old_dir), db.plants_dir.join(&new_dir))?;
+                db.remove_data_file(db.plants_dir.join(new_dir).join(old_dir + ".json"))?;
+            }
+            let plants_dir = db.plants_dir.clone();
+            db.write_items(vec![info], &plants_dir)?;
+            let activities_file = db.write_path(db.get_activities_filepath(), false)?;
+            write_activities(logs, &activities_file, false)?;
+            let growth_file = db.write_path(db.get_growth_filepath(), false)?;
+            write_growth(growth, &growth_file, false)?;
+            if !image_meta.is_empty() {
+                let images_file = db.write_path(db.get_images_filepath(), false)?;
+                write_csv(image_meta, &images_file, false)?;
+            }
+            Ok(())
+        })
+    }
+
     fn get_all_species(&mut self) -> Result<Vec<Species>, Box<dyn std::error::Error>> {
         if self.species_cache.is_empty() {
             self.load_species()?;
@@ -222,6 +321,19 @@ impl DatabaseManager for FileDB {
         Err(Box::new(Error::SpeciesNotFound(species_name)))
     }
 
+    fn delete_species(&mut self, species_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.species_exists(species_name)? {
+            return Err(Error::SpeciesNotFound(species_name.to_owned()).into());
+        }
+        if !self.get_plants_species(species_name)?.is_empty() {
+            return Err(Error::SpeciesInUse(species_name.to_owned()).into());
+        }
+        self.transaction(|db| {
+            db.remove_dir(db.species_dir.join(species_name.replace(' ', "")))?;
+            Ok(())
+        })
+    }
+
     fn get_graveyard(&mut self) -> Result<Vec<GraveyardPlant>, Box<dyn std::error::Error>> {
         if self.graveyard_cache.is_empty() {
             self.load_graveyard()?;
@@ -274,7 +386,62 @@ impl DatabaseManager for FileDB {
     }
 
     fn write_location(&mut self, loc: Location) -> Result<(), Box<dyn std::error::Error>> {
-        add_location(loc, &self.location_file)?;
+        let mut locations = if self.file_exists(&self.location_file) {
+            self.get_locations()?
+        } else {
+            vec![]
+        };
+        match locations.iter_mut().find(|old| old.name == loc.name) {
+            // existing locations are updated in place
+            Some(old) => *old = loc,
+            None => locations.push(loc),
+        }
+        // the file is rewritten, as it might have been written without the climate column
+        let location_file = self.write_path(self.location_file.clone(), false)?;
+        write_csv(locations.clone(), &location_file, false)?;
+        self.location_cache = locations;
+        Ok(())
+    }
+
+    fn delete_location(&mut self, location_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let locations = self.get_locations()?;
+        if !locations.iter().any(|loc| loc.name == location_name) {
+            return Err(Error::LocationNotFound(location_name.to_owned()).into());
+        }
+        if !self.get_plants_by_location(location_name)?.is_empty() {
+            return Err(Error::LocationInUse(location_name.to_owned()).into());
+        }
+        let new_locations: Vec<Location> = locations
+            .into_iter()
+            .filter(|loc| loc.name != location_name)
+            .collect();
+        self.transaction(|db| {
+            let location_file = db.write_path(db.location_file.clone(), false)?;
+            write_csv(new_locations, &location_file, false)?;
+            Ok(())
+        })
+    }
+
+    fn write_image_meta(&mut self, meta: ImageMeta) -> Result<(), Box<dyn std::error::Error>> {
+        let mut image_meta = self.load_image_meta()?;
+        if meta.cover {
+            image_meta
+                .iter_mut()
+                .filter(|old| old.plant == meta.plant)
+                .for_each(|old| old.cover = false);
+        }
+        match image_meta
+            .iter_mut()
+            .find(|old| old.plant == meta.plant && old.file_name == meta.file_name)
+        {
+            Some(old) => *old = meta,
+            None => image_meta.push(meta),
+        }
+        // images without caption or cover don't need to be stored
+        image_meta.retain(|meta| meta.caption.is_some() || meta.cover);
+        let images_file = self.write_path(self.get_images_filepath(), false)?;
+        write_csv(image_meta, &images_file, false)?;
+        self.plants_cache = vec![];
         Ok(())
     }
 
@@ -315,10 +482,42 @@ impl DatabaseManager for FileDB {
     }
 
     fn write_logs(&mut self, logs: Vec<LogItem>) -> Result<(), Box<dyn std::error::Error>> {
-        write_activities(logs, &self.get_activities_filepath(), true)?;
+        let activities_path = self.get_activities_filepath();
+        if self.file_exists(&activities_path) && !self.activities_have_user()? {
+            // files written without the user column are migrated once by rewriting them
+            let mut all_logs = self.get_logs()?;
+            all_logs.extend(logs);
+            let activities_file = self.write_path(activities_path, false)?;
+            write_activities(all_logs, &activities_file, false)?;
+        } else {
+            let activities_file = self.write_path(activities_path, true)?;
+            write_activities(logs, &activities_file, true)?;
+        }
+        self.logs_cache = vec![];
+        self.plants_cache = vec![];
         Ok(())
     }
 
+    fn delete_log(&mut self, log: LogItem) -> Result<(), Box<dyn std::error::Error>> {
+        let logs = self.get_logs()?;
+        let num_logs = logs.len();
+        let new_logs: Vec<LogItem> = logs
+            .into_iter()
+            .filter(|item| {
+                item.activity != log.activity || item.date != log.date || item.plant != log.plant
+            })
+            .collect();
+        if new_logs.len() == num_logs {
+            let desc = format!("{} of {} on {}", log.activity, log.plant, log.date);
+            return Err(Error::LogNotFound(desc).into());
+        }
+        self.transaction(|db| {
+            let activities_file = db.write_path(db.get_activities_filepath(), false)?;
+            write_activities(new_logs, &activities_file, false)?;
+            Ok(())
+        })
+    }
+
     fn get_plants_by_location(
         &mut self,
         location: &str,
@@ -354,10 +553,31 @@ impl DatabaseManager for FileDB {
     }
 
     fn write_growths(&mut self, growth: Vec<GrowthItem>) -> Result<(), Box<dyn std::error::Error>> {
-        write_growth(growth, &self.get_growth_filepath(), true)?;
+        let growth_file = self.write_path(self.get_growth_filepath(), true)?;
+        write_growth(growth, &growth_file, true)?;
+        self.growth_cache = vec![];
+        self.plants_cache = vec![];
         Ok(())
     }
 
+    fn delete_growth(&mut self, growth: GrowthItem) -> Result<(), Box<dyn std::error::Error>> {
+        let items = self.get_growth()?;
+        let num_items = items.len();
+        let new_items: Vec<GrowthItem> = items
+            .into_iter()
+            .filter(|item| item.plant != growth.plant || item.date != growth.date)
+            .collect();
+        if new_items.len() == num_items {
+            let desc = format!("of {} on {}", growth.plant, growth.date);
+            return Err(Error::GrowthNotFound(desc).into());
+        }
+        self.transaction(|db| {
+            let growth_file = db.write_path(db.get_growth_filepath(), false)?;
+            write_growth(new_items, &growth_file, false)?;
+            Ok(())
+        })
+    }
+
     fn write_plant(&mut self, plant: PlantInfo) -> Result<(), Box<dyn std::error::Error>> {
         self.write_plants(vec![plant])?;
         self.load_plants()?;
@@ -365,76 +585,98 @@ impl DatabaseManager for FileDB {
     }
 
     fn write_plants(&mut self, plants: Vec<PlantInfo>) -> Result<(), Box<dyn std::error::Error>> {
-        write_plants(plants, &self.plants_dir)?;
+        let plants_dir = self.plants_dir.clone();
+        self.write_items(plants, &plants_dir)?;
         self.load_plants()?;
         Ok(())
     }
 
     fn write_species(&mut self, species: Species) -> Result<(), Box<dyn std::error::Error>> {
-        write_species(vec![species], &self.species_dir)?;
+        let species_dir = self.species_dir.clone();
+        self.write_items(vec![species], &species_dir)?;
         self.load_species()?;
         Ok(())
     }
 
     fn kill_plant(&mut self, plant: GraveyardPlant) -> Result<(), Box<dyn std::error::Error>> {
-        // Enter new graveyard plant
-        let name = plant.name.clone();
-        write_graveyard(vec![plant], &self.get_graveyard_filepath(), true)?;
-
-        // remove plant json
-        let plant_filename = name.replace(' ', "") + ".json";
-        let plant_path = PathBuf::from(&self.plants_dir).join(name.clone());
-        remove_file(plant_path.clone().join(plant_filename))
-            .map_err(<IOError as Into<Error>>::into)?;
+        self.transaction(|db| {
+            // Enter new graveyard plant
+            let name = plant.name.clone();
+            let graveyard_file = db.write_path(db.get_graveyard_filepath(), true)?;
+            write_graveyard(vec![plant], &graveyard_file, true)?;
+
+            // move images to dead dir and remove plant json
+            let dir_name = name.replace(' ', "");
+            let dead_dir = db.plants_dir.join("dead");
+            db.move_dir(db.plants_dir.join(&dir_name), dead_dir.join(&dir_name))?;
+            db.remove_data_file(item_path(&dead_dir, &name))?;
+            db.load_plants()?;
+
+            //remove plant activitites
+            if db.logs_cache.is_empty() {
+                db.load_logs()?;
+            }
+            let new_logs: Vec<LogItem> = db
+                .logs_cache
+                .iter()
+                .filter(|log| log.plant != name)
+                .cloned()
+                .collect();
+            let activities_file = db.write_path(db.get_activities_filepath(), false)?;
+            write_activities(new_logs.clone(), &activities_file, false)?;
+            db.logs_cache = new_logs;
+
+            //remove plant growth
+            if db.growth_cache.is_empty() {
+                db.load_growth()?;
+            }
+            let new_growth: Vec<GrowthItem> = db
+                .growth_cache
+                .iter()
+                .filter(|growth| growth.plant != name)
+                .cloned()
+                .collect();
+            let growth_file = db.write_path(db.get_growth_filepath(), false)?;
+            write_growth(new_growth.clone(), &growth_file, false)?;
+            db.growth_cache = new_growth;
+
+            //remove image captions, the images themselves are kept
+            let image_meta = db.load_image_meta()?;
+            if !image_meta.is_empty() {
+                let new_meta: Vec<ImageMeta> = image_meta
+                    .into_iter()
+                    .filter(|meta| meta.plant != name)
+                    .collect();
+                let images_file = db.write_path(db.get_images_filepath(), false)?;
+                write_csv(new_meta, &images_file, false)?;
+            }
 
-        // remove plant from cache
-        if self.plants_cache.is_empty() {
-            self.load_plants()?;
-        }
-        let new_plants: Vec<PlantInfo> = self
-            .plants_cache
-            .iter()
-            .filter(|pl| pl.info.name != name)
-            .cloned()
-            .map(|plant| plant.info)
-            .collect();
-        self.write_plants(new_plants)?;
-        self.load_plants()?;
+            Ok(())
+        })
+    }
 
-        //remove plant activitites
-        if self.logs_cache.is_empty() {
-            self.load_logs()?;
-        }
-        let new_logs: Vec<LogItem> = self
-            .logs_cache
-            .iter()
-            .filter(|log| log.plant != name)
-            .cloned()
-            .collect();
-        self.logs_cache.clone_from(&new_logs);
-        self.write_logs(new_logs)?;
+    fn write_graveyard(
+        &mut self,
+        graveyard: Vec<GraveyardPlant>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let graveyard_file = self.write_path(self.get_graveyard_filepath(), true)?;
+        write_graveyard(graveyard, &graveyard_file, true)?;
+        self.graveyard_cache = vec![];
+        Ok(())
+    }
 
-        //remove plant growth
-        if self.growth_cache.is_empty() {
-            self.load_growth()?;
-        }
-        let new_growth: Vec<GrowthItem> = self
-            .growth_cache
-            .iter()
-            .filter(|growth| growth.plant != name)
-            .cloned()
-            .collect();
-        self.growth_cache.clone_from(&new_growth);
-        self.write_growths(new_growth)?;
+    fn begin_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.begin()?;
+        Ok(())
+    }
 
-        // move images to dead dir
-        let dead_dir = self.plants_dir.join("dead");
-        if !dead_dir.exists() {
-            create_dir_all(dead_dir.clone())?;
-        }
-        let dead_path = dead_dir.join(name);
-        rename(plant_path, dead_path).map_err(<IOError as Into<Error>>::into)?;
+    fn commit_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.commit()?;
+        Ok(())
+    }
 
+    fn rollback_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.rollback()?;
         Ok(())
     }
 }
@@ -488,6 +730,8 @@ pub mod test_common {
     pub const GROWTHS_DUMMY_OUT: &str = "Growth_test2.csv";
     pub const ACTIVITIES_DUMMY_OUT: &str = "Activities_test.csv";
     pub const ACTIVITIES_DUMMY_OUT2: &str = "Activities_test2.csv";
+    pub const ACTIVITIES_USER_DUMMY_OUT: &str = "Activities_user_test.csv";
+    pub const ACTIVITIES_APPEND_DUMMY_OUT: &str = "Activities_append_test.csv";
     pub const SPECIES_DUMMY_OUT: &str = "../../testing/species_test";
     pub const PLANTS_DUMMY_OUT: &str = "../../testing/plants_test";
     pub const PLANTS_DUMMY_OUT2: &str = "../../testing/plants_test2";
@@ -495,6 +739,10 @@ pub mod test_common {
     pub const GRAVEYARD_DUMMY_OUT: &str = "Graveyard_test.csv";
     pub const GROWTH_DEATH_DUMMY_OUT: &str = "Growth_kill_test.csv";
     pub const ACTIVITIES_DEATH_DUMMY_OUT: &str = "Activities_kill_test.csv";
+    pub const RENAME_DUMMY_OUT: &str = "../../testing/rename_test";
+    pub const IMAGE_META_DUMMY_OUT: &str = "../../testing/image_meta_test";
+    pub const DELETE_DUMMY_OUT: &str = "../../testing/delete_test";
+    pub const LOCATIONS_CLIMATE_DUMMY_OUT: &str = "../../testing/Locations_climate_test.csv";
 
     pub const FILE_DOES_NOT_EXIST: &str = "../../testing/notaflie";
 
@@ -589,6 +837,7 @@ pub mod test_common {
             pruning_notes: vec!["".to_owned()],
             companions: vec!["".to_owned()],
             additional_notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -596,6 +845,7 @@ pub mod test_common {
         Location {
             name: "test outside".to_owned(),
             outside: true,
+            climate: vec![],
         }
     }
 
@@ -603,6 +853,7 @@ pub mod test_common {
         Location {
             name: "test inside".to_owned(),
             outside: false,
+            climate: vec![],
         }
     }
 
@@ -610,6 +861,7 @@ pub mod test_common {
         Location {
             name: "test location".to_owned(),
             outside: false,
+            climate: vec![],
         }
     }
 
@@ -641,6 +893,7 @@ pub mod test_common {
             date: NaiveDate::parse_from_str("01.01.1970", "%d.%m.%Y").unwrap(),
             plant: "Dummy1".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -674,6 +927,7 @@ pub mod test_common {
                 obtained: dummy_date(),
                 auto_water: true,
                 notes: vec![],
+                schedules: vec![],
             },
             growth: vec![GrowthItem {
                 plant: "Dummy1".to_owned(),
@@ -688,11 +942,14 @@ pub mod test_common {
                 date: dummy_date(),
                 plant: "Dummy1".to_owned(),
                 note: None,
+                user: None,
             }],
             images: vec![PlantImage {
                 created: dummy_date(),
                 file_name: "01011970.jpg".to_owned(),
                 file_path: PathBuf::from("../../testing/plants/Dummy1"),
+                caption: None,
+                cover: false,
             }],
         }
     }
@@ -707,6 +964,7 @@ pub mod test_common {
                 obtained: dummy_date(),
                 auto_water: true,
                 notes: vec![],
+                schedules: vec![],
             },
             growth: vec![],
             activities: vec![],
@@ -907,15 +1165,17 @@ mod file_backend_tests {
         test_common::{
             dummy_activity, dummy_graveyard1, dummy_graveyard2, dummy_growth1, dummy_growth2,
             dummy_location1, dummy_location2, dummy_location3, dummy_plant1, dummy_plant2,
-            dummy_species, ACTIVITIES_DUMMY, ACTIVITIES_DUMMY_OUT, ACTIVITIES_DUMMY_OUT2,
-            DUMMY_LOGS_PATH, DUMMY_PLANT_PATH, DUMMY_SPECIES_PATH, FILE_DOES_NOT_EXIST,
-            GRAVEYARD_DUMMY, GROWTHS_DUMMY_OUT, GROWTH_DUMMY, GROWTH_DUMMY_OUT, LOCATIONS_DUMMY,
-            PLANTS_DUMMY_OUT, PLANTS_DUMMY_OUT2, SPECIES_DUMMY_OUT,
+            dummy_species, ACTIVITIES_APPEND_DUMMY_OUT, ACTIVITIES_DUMMY, ACTIVITIES_DUMMY_OUT,
+            ACTIVITIES_DUMMY_OUT2, ACTIVITIES_USER_DUMMY_OUT, DELETE_DUMMY_OUT, DUMMY_LOGS_PATH,
+            DUMMY_PLANT_PATH, DUMMY_SPECIES_PATH, FILE_DOES_NOT_EXIST, GRAVEYARD_DUMMY,
+            GROWTHS_DUMMY_OUT, GROWTH_DUMMY, GROWTH_DUMMY_OUT, IMAGE_META_DUMMY_OUT,
+            LOCATIONS_CLIMATE_DUMMY_OUT, LOCATIONS_DUMMY, PLANTS_DUMMY_OUT, PLANTS_DUMMY_OUT2,
+            RENAME_DUMMY_OUT, SPECIES_DUMMY_OUT,
         },
         FileDB,
     };
     use crate::database_manager::DatabaseManager;
-    use plants::named::Named;
+    use plants::{climate::parse_climate, named::Named, plant::ImageMeta};
     use std::{fs, path::PathBuf};
 
     fn dummy_db() -> FileDB {
@@ -927,6 +1187,7 @@ mod file_backend_tests {
             graveyard_csv: "Graveyard.csv".to_owned(),
             growth_csv: "Growth.csv".to_owned(),
             activities_csv: "Activities.csv".to_owned(),
+            images_csv: "Images.csv".to_owned(),
             date_format: "%d.%m.%Y".to_owned(),
             plants_cache: vec![],
             species_cache: vec![],
@@ -934,6 +1195,33 @@ mod file_backend_tests {
             location_cache: vec![],
             logs_cache: vec![],
             growth_cache: vec![],
+            transaction: None,
+        }
+    }
+
+    /// Copies Dummy1 and its logs to `base`
+    fn copy_db(base: &str) -> FileDB {
+        let base = PathBuf::from(base);
+        let plants_dir = base.join("Plants");
+        let logs_dir = base.join("Logs");
+        let plant_dir = plants_dir.join("Dummy1");
+        fs::create_dir_all(&plant_dir).unwrap();
+        fs::create_dir_all(&logs_dir).unwrap();
+        for file in ["Dummy1.json", "01011970.jpg"] {
+            let dummy_file = PathBuf::from(DUMMY_PLANT_PATH).join("Dummy1").join(file);
+            fs::copy(dummy_file, plant_dir.join(file)).unwrap();
+        }
+        for file in ["Activities.csv", "Growth.csv"] {
+            fs::copy(
+                PathBuf::from(DUMMY_LOGS_PATH).join(file),
+                logs_dir.join(file),
+            )
+            .unwrap();
+        }
+        FileDB {
+            plants_dir,
+            logs_dir,
+            ..dummy_db()
         }
     }
 
@@ -950,6 +1238,7 @@ mod file_backend_tests {
             graveyard_csv: "Graveyard.csv".to_owned(),
             growth_csv: "Growth.csv".to_owned(),
             activities_csv: "Activities.csv".to_owned(),
+            images_csv: "Images.csv".to_owned(),
             date_format: "%d.%m.%Y".to_owned(),
             plants_cache: vec![],
             graveyard_cache: vec![],
@@ -957,6 +1246,7 @@ mod file_backend_tests {
             location_cache: vec![],
             logs_cache: vec![],
             growth_cache: vec![],
+            transaction: None,
         };
 
         assert_eq!(result, expected)
@@ -997,6 +1287,13 @@ mod file_backend_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn images_file_path() {
+        let result = FileDB::default().get_images_filepath();
+        let expected = PathBuf::from("data/Logs/Images.csv");
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn growth_path_dummy() {
         let result = dummy_db().get_growth_filepath();
@@ -1310,6 +1607,49 @@ mod file_backend_tests {
         assert!(!new_file.exists())
     }
 
+    #[test]
+    fn db_man_write_logs_user() {
+        let mut db = dummy_db();
+        fs::copy(
+            db.get_activities_filepath(),
+            db.logs_dir.join(ACTIVITIES_USER_DUMMY_OUT),
+        )
+        .unwrap();
+        db.activities_csv = ACTIVITIES_USER_DUMMY_OUT.to_owned();
+        let mut log = dummy_activity();
+        log.user = Some("gardener".to_owned());
+        db.write_logs(vec![log.clone()]).unwrap();
+        let result = db.get_logs().unwrap();
+        std::fs::remove_file(db.get_activities_filepath()).unwrap();
+        let expected = vec![dummy_activity(), log];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn db_man_write_logs_append() {
+        let mut db = dummy_db();
+        fs::copy(
+            db.get_activities_filepath(),
+            db.logs_dir.join(ACTIVITIES_APPEND_DUMMY_OUT),
+        )
+        .unwrap();
+        db.activities_csv = ACTIVITIES_APPEND_DUMMY_OUT.to_owned();
+        let mut log = dummy_activity();
+        log.user = Some("gardener".to_owned());
+        db.write_logs(vec![log.clone()]).unwrap();
+        db.write_logs(vec![log.clone()]).unwrap();
+        let contents = fs::read_to_string(db.get_activities_filepath()).unwrap();
+        let result = db.get_logs().unwrap();
+        std::fs::remove_file(db.get_activities_filepath()).unwrap();
+        assert_eq!(
+            contents.lines().next(),
+            Some("activity;date;plant;note;user")
+        );
+        assert_eq!(contents.lines().count(), 4);
+        let expected = vec![dummy_activity(), log.clone(), log];
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn db_man_get_plants_by_location() {
         let mut db = dummy_db();
@@ -1434,4 +1774,105 @@ mod file_backend_tests {
         fs::remove_dir_all(PLANTS_DUMMY_OUT2).unwrap();
         assert!(!PathBuf::from(PLANTS_DUMMY_OUT2).exists())
     }
+
+    #[test]
+    fn db_man_rename_plant() {
+        let mut db = copy_db(RENAME_DUMMY_OUT);
+        db.rename_plant("Dummy1", "Dummy Renamed").unwrap();
+        let old_exists = db.plant_exists("Dummy1").unwrap();
+        let result = db.get_plant("Dummy Renamed").unwrap();
+        let plants_dir = db.plants_dir.clone();
+        let new_image = plants_dir.join("DummyRenamed").join("01011970.jpg");
+        let new_json = plants_dir.join("DummyRenamed").join("DummyRenamed.json");
+        let old_dir = plants_dir.join("Dummy1");
+        let new_image_exists = new_image.exists();
+        let new_json_exists = new_json.exists();
+        let old_json_exists = plants_dir.join("DummyRenamed").join("Dummy1.json").exists();
+        let old_dir_exists = old_dir.exists();
+        fs::remove_dir_all(RENAME_DUMMY_OUT).unwrap();
+
+        assert!(!old_exists);
+        assert_eq!(result.activities.len(), 1);
+        assert_eq!(result.activities[0].plant, "Dummy Renamed");
+        assert_eq!(result.growth.len(), 1);
+        assert_eq!(result.images.len(), 1);
+        assert!(new_image_exists);
+        assert!(new_json_exists);
+        assert!(!old_json_exists);
+        assert!(!old_dir_exists)
+    }
+
+    #[test]
+    fn db_man_write_image_meta() {
+        let mut db = copy_db(IMAGE_META_DUMMY_OUT);
+        let meta = ImageMeta {
+            plant: "Dummy1".to_owned(),
+            file_name: "01011970.jpg".to_owned(),
+            caption: Some("first leaves".to_owned()),
+            cover: true,
+        };
+        db.write_image_meta(meta.clone()).unwrap();
+        let image = db.get_plant("Dummy1").unwrap().images[0].clone();
+        db.rename_plant("Dummy1", "Dummy Renamed").unwrap();
+        let renamed_image = db.get_plant("Dummy Renamed").unwrap().images[0].clone();
+        db.write_image_meta(ImageMeta {
+            plant: "Dummy Renamed".to_owned(),
+            caption: None,
+            cover: false,
+            ..meta
+        })
+        .unwrap();
+        let meta_file = fs::read_to_string(db.get_images_filepath()).unwrap();
+        fs::remove_dir_all(IMAGE_META_DUMMY_OUT).unwrap();
+
+        assert_eq!(image.caption, Some("first leaves".to_owned()));
+        assert!(image.cover);
+        assert_eq!(renamed_image.caption, Some("first leaves".to_owned()));
+        assert_eq!(meta_file, "")
+    }
+
+    #[test]
+    fn db_man_delete_log() {
+        let mut db = copy_db(DELETE_DUMMY_OUT);
+        db.delete_log(dummy_activity()).unwrap();
+        let logs = db.get_logs().unwrap();
+        let deleted_twice = db.delete_log(dummy_activity());
+        db.delete_growth(dummy_growth1()).unwrap();
+        let growth = db.get_growth().unwrap();
+        fs::remove_dir_all(DELETE_DUMMY_OUT).unwrap();
+
+        assert!(logs.is_empty());
+        assert!(deleted_twice.is_err());
+        assert!(growth.is_empty())
+    }
+
+    #[test]
+    fn db_man_write_location_climate() {
+        fs::copy(LOCATIONS_DUMMY, LOCATIONS_CLIMATE_DUMMY_OUT).unwrap();
+        let mut db = FileDB {
+            location_file: PathBuf::from(LOCATIONS_CLIMATE_DUMMY_OUT),
+            ..dummy_db()
+        };
+        let mut location = dummy_location1();
+        location.name = "Balcony".to_owned();
+        location.climate = parse_climate("-5..10 @ Nov-Feb, 5..30").unwrap();
+        db.write_location(location.clone()).unwrap();
+        db.location_cache = vec![];
+        let result = db.get_locations().unwrap();
+        fs::remove_file(LOCATIONS_CLIMATE_DUMMY_OUT).unwrap();
+        let expected = vec![
+            dummy_location1(),
+            dummy_location2(),
+            dummy_location3(),
+            location,
+        ];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn db_man_delete_location_in_use() {
+        let mut db = dummy_db();
+        let result = db.delete_location("test location");
+        assert!(result.is_err())
+    }
 }
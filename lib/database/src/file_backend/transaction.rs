@@ -0,0 +1,543 @@
+use super::{
+    errors::{Error, IOErr},
+    load_json::{item_files, load_dir, load_json},
+    write_json::{item_path, write_json},
+    FileDB,
+};
+use plants::named::Named;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{copy, create_dir_all, remove_dir_all, remove_file, rename},
+    io::{Error as IOError, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// Name of the file in `staging_dir` listing the changes of a transaction while it is committed
+const COMMIT_FILE: &str = "commit.json";
+
+/// Changes made in a transaction or one of its nested transactions
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+pub struct Scope {
+    /// Changed data files and the temp file with their new contents, `None` if the file is removed
+    pub files: BTreeMap<PathBuf, Option<PathBuf>>,
+    /// Directories moved on commit, in order
+    pub moves: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Scope {
+    fn merge(&mut self, inner: Scope) {
+        self.files.extend(inner.files);
+        self.moves.extend(inner.moves);
+    }
+}
+
+/// A running transaction of a FileDB
+/// Writes go to temp files in `staging_dir` and the data files are only changed on commit,
+/// by saving the changes to `staging_dir` first and then renaming the temp files to the data files
+/// If the process dies while renaming, the commit is finished once the next transaction begins
+#[derive(Debug, PartialEq, Clone)]
+pub struct FileTransaction {
+    pub staging_dir: PathBuf,
+    pub scope: Scope,
+    /// Scopes of nested transactions, the innermost last
+    pub nested: Vec<Scope>,
+    pub num_temp: usize,
+}
+
+impl FileTransaction {
+    fn top(&mut self) -> &mut Scope {
+        self.nested.last_mut().unwrap_or(&mut self.scope)
+    }
+
+    fn scopes(&self) -> impl DoubleEndedIterator<Item = &Scope> {
+        std::iter::once(&self.scope).chain(self.nested.iter())
+    }
+
+    fn moves(&self) -> impl DoubleEndedIterator<Item = &(PathBuf, PathBuf)> {
+        self.scopes().flat_map(|scope| scope.moves.iter())
+    }
+
+    fn new_temp(&mut self) -> PathBuf {
+        self.num_temp += 1;
+        self.staging_dir.join(self.num_temp.to_string())
+    }
+
+    /// Where `path` is before the directories are moved
+    fn current_path(&self, path: &Path) -> PathBuf {
+        let mut current = path.to_path_buf();
+        for (from, to) in self.moves().rev() {
+            if let Ok(rest) = current.strip_prefix(to) {
+                current = from.join(rest);
+            }
+        }
+        current
+    }
+
+    /// Where `path` is once the directories are moved
+    fn final_path(&self, path: &Path) -> PathBuf {
+        let mut moved = path.to_path_buf();
+        for (from, to) in self.moves() {
+            if let Ok(rest) = moved.strip_prefix(from) {
+                moved = to.join(rest);
+            }
+        }
+        moved
+    }
+
+    /// The file to read the contents of `path` from, `None` if it was removed
+    fn read_path(&self, path: &Path) -> Option<PathBuf> {
+        match self.scopes().rev().find_map(|scope| scope.files.get(path)) {
+            Some(staged) => staged.clone(),
+            None => Some(self.current_path(path)),
+        }
+    }
+}
+
+fn ignore_missing(result: std::io::Result<()>) -> Result<(), Error> {
+    match result {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Moves the directories and renames the temp files, changes that were already made are skipped
+fn apply(scope: &Scope) -> Result<(), Error> {
+    for (from, to) in scope.moves.iter() {
+        if from.exists() && !to.exists() {
+            if let Some(parent) = to.parent() {
+                create_dir_all(parent)?;
+            }
+            rename(from, to)?;
+        }
+    }
+    for (path, staged) in scope.files.iter() {
+        match staged {
+            Some(temp) if temp.exists() => {
+                if let Some(parent) = path.parent() {
+                    create_dir_all(parent)?;
+                }
+                rename(temp, path)?;
+            }
+            Some(_) => (),
+            None => ignore_missing(remove_file(path))?,
+        }
+    }
+    Ok(())
+}
+
+impl FileDB {
+    fn staging_dir(&self) -> PathBuf {
+        let dir_name = self
+            .plants_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.plants_dir
+            .parent()
+            .map(|parent| parent.to_path_buf())
+            .unwrap_or_default()
+            .join(format!(".{dir_name}_transaction"))
+    }
+
+    /// The file to read the contents of a data file from, `None` if it was removed in the running transaction
+    pub fn read_path(&self, path: &Path) -> Option<PathBuf> {
+        match self.transaction.as_ref() {
+            Some(tr) => tr.read_path(path),
+            None => Some(path.to_path_buf()),
+        }
+    }
+
+    pub fn file_exists(&self, path: &Path) -> bool {
+        self.read_path(path).is_some_and(|path| path.is_file())
+    }
+
+    /// Where a directory is until the running transaction moves it
+    pub fn current_dir(&self, path: &Path) -> PathBuf {
+        match self.transaction.as_ref() {
+            Some(tr) => tr.current_path(path),
+            None => path.to_path_buf(),
+        }
+    }
+
+    /// The file to write the new contents of a data file to
+    /// in a transaction this is a new temp file, which starts with the current contents if `keep_contents` is set
+    pub fn write_path(&mut self, path: PathBuf, keep_contents: bool) -> Result<PathBuf, Error> {
+        let transaction = match self.transaction.as_mut() {
+            Some(tr) => tr,
+            None => {
+                if let Some(parent) = path.parent() {
+                    create_dir_all(parent)?;
+                }
+                return Ok(path);
+            }
+        };
+        let temp = transaction.new_temp();
+        if keep_contents {
+            if let Some(current) = transaction.read_path(&path).filter(|path| path.is_file()) {
+                copy(current, &temp)?;
+            }
+        }
+        transaction.top().files.insert(path, Some(temp.clone()));
+        Ok(temp)
+    }
+
+    /// Loads the json files of all items in `dir`, including the changes of the running transaction
+    pub fn load_items<T: DeserializeOwned>(&self, dir: &PathBuf) -> Result<Vec<T>, Error> {
+        let transaction = match self.transaction.as_ref() {
+            Some(tr) => tr,
+            None => return load_dir(dir),
+        };
+        let mut files: BTreeSet<PathBuf> = item_files(&transaction.current_path(dir))?
+            .iter()
+            .map(|file| transaction.final_path(file))
+            .collect();
+        files.extend(
+            transaction
+                .scopes()
+                .flat_map(|scope| scope.files.keys().cloned()),
+        );
+        let mut items = vec![];
+        for file in files {
+            if file.parent().and_then(Path::parent) != Some(dir.as_path()) {
+                continue;
+            }
+            if let Some(path) = transaction.read_path(&file) {
+                items.push(load_json(&path)?);
+            }
+        }
+        Ok(items)
+    }
+
+    /// Writes the json files of items to `dir`
+    pub fn write_items<T: Serialize + Named>(
+        &mut self,
+        items: Vec<T>,
+        dir: &Path,
+    ) -> Result<(), Error> {
+        for item in items {
+            let path = self.write_path(item_path(dir, &item.get_name()), false)?;
+            write_json(item, &path)?;
+        }
+        Ok(())
+    }
+
+    /// Removes a data file, in a transaction it is removed on commit
+    pub fn remove_data_file(&mut self, path: PathBuf) -> Result<(), Error> {
+        let transaction = match self.transaction.as_mut() {
+            Some(tr) => tr,
+            None => {
+                remove_file(path)?;
+                return Ok(());
+            }
+        };
+        if !transaction
+            .read_path(&path)
+            .is_some_and(|path| path.is_file())
+        {
+            return Err(IOError::from(ErrorKind::NotFound).into());
+        }
+        transaction.top().files.insert(path, None);
+        Ok(())
+    }
+
+    /// Moves a directory, in a transaction it is moved on commit
+    /// files in the directory can only be changed once it is moved
+    pub fn move_dir(&mut self, from: PathBuf, to: PathBuf) -> Result<(), Error> {
+        let transaction = match self.transaction.as_mut() {
+            Some(tr) => tr,
+            None => {
+                if let Some(parent) = to.parent() {
+                    create_dir_all(parent)?;
+                }
+                rename(from, to)?;
+                return Ok(());
+            }
+        };
+        if !transaction.current_path(&from).is_dir() {
+            return Err(IOError::from(ErrorKind::NotFound).into());
+        }
+        let changed_inside = transaction
+            .scopes()
+            .flat_map(|scope| scope.files.keys())
+            .any(|path| path.starts_with(&from));
+        if changed_inside {
+            return Err(IOErr {
+                kind: format!("Move of {from:?} after its files were changed"),
+            }
+            .into());
+        }
+        transaction.top().moves.push((from, to));
+        Ok(())
+    }
+
+    /// Removes a directory, in a transaction it is moved to `staging_dir` on commit
+    pub fn remove_dir(&mut self, path: PathBuf) -> Result<(), Error> {
+        match self.transaction.as_mut() {
+            Some(tr) => {
+                let removed = tr.new_temp();
+                self.move_dir(path, removed)
+            }
+            None => {
+                remove_dir_all(path)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn clear_caches(&mut self) {
+        self.plants_cache = vec![];
+        self.graveyard_cache = vec![];
+        self.species_cache = vec![];
+        self.location_cache = vec![];
+        self.logs_cache = vec![];
+        self.growth_cache = vec![];
+    }
+
+    /// Starts a transaction, or a nested transaction if one is running
+    /// a commit that was interrupted is finished first, changes of a transaction that was neither
+    /// committed nor rolled back are dropped
+    pub fn begin(&mut self) -> Result<(), Error> {
+        if let Some(tr) = self.transaction.as_mut() {
+            tr.nested.push(Scope::default());
+            return Ok(());
+        }
+
+        let staging_dir = self.staging_dir();
+        let commit_file = staging_dir.join(COMMIT_FILE);
+        if commit_file.exists() {
+            log::warn!("Finishing interrupted commit from {:?}", staging_dir);
+            apply(&load_json(&commit_file)?)?;
+            self.clear_caches();
+        }
+        if staging_dir.exists() {
+            remove_dir_all(&staging_dir)?;
+        }
+        create_dir_all(&staging_dir)?;
+        self.transaction = Some(FileTransaction {
+            staging_dir,
+            scope: Scope::default(),
+            nested: vec![],
+            num_temp: 0,
+        });
+        Ok(())
+    }
+
+    /// Commits a transaction, the changes of a nested transaction become part of the enclosing one
+    pub fn commit(&mut self) -> Result<(), Error> {
+        match self.transaction.as_mut() {
+            None => return Ok(()),
+            Some(tr) => {
+                if let Some(inner) = tr.nested.pop() {
+                    tr.top().merge(inner);
+                    return Ok(());
+                }
+            }
+        }
+        let transaction = match self.transaction.take() {
+            None => return Ok(()),
+            Some(tr) => tr,
+        };
+        self.clear_caches();
+        write_json(
+            &transaction.scope,
+            &transaction.staging_dir.join(COMMIT_FILE),
+        )?;
+        apply(&transaction.scope)?;
+        remove_dir_all(&transaction.staging_dir)?;
+        Ok(())
+    }
+
+    /// Drops the changes of a transaction, for a nested transaction only its own changes are dropped
+    pub fn rollback(&mut self) -> Result<(), Error> {
+        self.clear_caches();
+        match self.transaction.as_mut() {
+            None => return Ok(()),
+            Some(tr) => {
+                if let Some(inner) = tr.nested.pop() {
+                    for temp in inner.files.values().flatten() {
+                        ignore_missing(remove_file(temp))?;
+                    }
+                    return Ok(());
+                }
+            }
+        }
+        if let Some(transaction) = self.transaction.take() {
+            remove_dir_all(&transaction.staging_dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod transaction_tests {
+    use super::FileDB;
+    use super::{
+        super::test_common::{
+            dummy_activity, DUMMY_LOGS_PATH, DUMMY_SPECIES_PATH, LOCATIONS_DUMMY,
+        },
+        COMMIT_FILE,
+    };
+    use crate::{database_manager::DatabaseManager, file_backend::write_json::write_json};
+    use chrono::NaiveDate;
+    use plants::growth_item::GrowthItem;
+    use std::{
+        fs::{create_dir_all, read_to_string, remove_dir_all, write},
+        path::PathBuf,
+    };
+
+    const TRANSACTION_BASE: &str = "../../testing/transaction_test";
+    const TRANSACTION_ROLLBACK_BASE: &str = "../../testing/transaction_rollback_test";
+    const TRANSACTION_STAGED_BASE: &str = "../../testing/transaction_staged_test";
+    const TRANSACTION_NESTED_BASE: &str = "../../testing/transaction_nested_test";
+    const TRANSACTION_RECOVER_BASE: &str = "../../testing/transaction_recover_test";
+    const TRANSACTION_FINISH_BASE: &str = "../../testing/transaction_finish_test";
+
+    fn transaction_db(base: &str) -> FileDB {
+        let plants_dir = PathBuf::from(base).join("Plants");
+        let logs_dir = PathBuf::from(base).join("Logs");
+        create_dir_all(&plants_dir).unwrap();
+        create_dir_all(&logs_dir).unwrap();
+        for file in ["Activities.csv", "Growth.csv"] {
+            write(
+                logs_dir.join(file),
+                std::fs::read_to_string(PathBuf::from(DUMMY_LOGS_PATH).join(file)).unwrap(),
+            )
+            .unwrap();
+        }
+        base_db(base)
+    }
+
+    fn base_db(base: &str) -> FileDB {
+        FileDB {
+            plants_dir: PathBuf::from(base).join("Plants"),
+            species_dir: PathBuf::from(DUMMY_SPECIES_PATH),
+            location_file: PathBuf::from(LOCATIONS_DUMMY),
+            logs_dir: PathBuf::from(base).join("Logs"),
+            ..Default::default()
+        }
+    }
+
+    fn dummy_growth() -> GrowthItem {
+        GrowthItem {
+            plant: "Dummy1".to_owned(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            height_cm: 10.0,
+            width_cm: 10.0,
+            note: None,
+            health: 3,
+        }
+    }
+
+    #[test]
+    fn transaction_commit() {
+        let mut db = transaction_db(TRANSACTION_BASE);
+        let before = db.get_logs().unwrap().len();
+        db.transaction(|db| db.write_log(dummy_activity())).unwrap();
+        let staging_exists = db.staging_dir().exists();
+        let mut reloaded = base_db(TRANSACTION_BASE);
+        let after = reloaded.get_logs().unwrap().len();
+        remove_dir_all(TRANSACTION_BASE).unwrap();
+
+        assert!(!staging_exists);
+        assert!(db.transaction.is_none());
+        assert_eq!(after, before + 1)
+    }
+
+    #[test]
+    fn transaction_rollback() {
+        let mut db = transaction_db(TRANSACTION_ROLLBACK_BASE);
+        let before = db.get_logs().unwrap();
+        let result: Result<(), Box<dyn std::error::Error>> = db.transaction(|db| {
+            db.write_log(dummy_activity())?;
+            Err("failed after writing".into())
+        });
+        let after = db.get_logs().unwrap();
+        let staging_exists = db.staging_dir().exists();
+        remove_dir_all(TRANSACTION_ROLLBACK_BASE).unwrap();
+
+        assert!(result.is_err());
+        assert!(!staging_exists);
+        assert_eq!(after, before)
+    }
+
+    #[test]
+    fn transaction_stages_writes() {
+        let mut db = transaction_db(TRANSACTION_STAGED_BASE);
+        let activities = read_to_string(db.get_activities_filepath()).unwrap();
+        let before = db.get_logs().unwrap().len();
+        db.begin().unwrap();
+        db.write_log(dummy_activity()).unwrap();
+        let staged = db.get_logs().unwrap().len();
+        let activities_staged = read_to_string(db.get_activities_filepath()).unwrap();
+        db.rollback().unwrap();
+        remove_dir_all(TRANSACTION_STAGED_BASE).unwrap();
+
+        assert_eq!(staged, before + 1);
+        assert_eq!(activities_staged, activities)
+    }
+
+    #[test]
+    fn transaction_nested_rollback() {
+        let mut db = transaction_db(TRANSACTION_NESTED_BASE);
+        let logs_before = db.get_logs().unwrap().len();
+        let growth_before = db.get_growth().unwrap();
+        db.begin().unwrap();
+        db.write_log(dummy_activity()).unwrap();
+        let inner: Result<(), Box<dyn std::error::Error>> = db.transaction(|db| {
+            db.write_growths(vec![dummy_growth()])?;
+            Err("failed in nested transaction".into())
+        });
+        db.commit().unwrap();
+        let mut reloaded = base_db(TRANSACTION_NESTED_BASE);
+        let logs_after = reloaded.get_logs().unwrap().len();
+        let growth_after = reloaded.get_growth().unwrap();
+        remove_dir_all(TRANSACTION_NESTED_BASE).unwrap();
+
+        assert!(inner.is_err());
+        assert_eq!(logs_after, logs_before + 1);
+        assert_eq!(growth_after, growth_before)
+    }
+
+    #[test]
+    fn transaction_recover() {
+        let mut db = transaction_db(TRANSACTION_RECOVER_BASE);
+        let before = db.get_logs().unwrap();
+        db.begin().unwrap();
+        db.write_log(dummy_activity()).unwrap();
+        // the process dies before committing
+        drop(db);
+
+        let mut db = base_db(TRANSACTION_RECOVER_BASE);
+        db.begin().unwrap();
+        db.commit().unwrap();
+        let after = db.get_logs().unwrap();
+        remove_dir_all(TRANSACTION_RECOVER_BASE).unwrap();
+
+        assert_eq!(after, before)
+    }
+
+    #[test]
+    fn transaction_finish_commit() {
+        let mut db = transaction_db(TRANSACTION_FINISH_BASE);
+        let before = db.get_logs().unwrap().len();
+        db.begin().unwrap();
+        db.write_log(dummy_activity()).unwrap();
+        // the process dies after saving the changes, before renaming the files
+        let transaction = db.transaction.take().unwrap();
+        write_json(
+            &transaction.scope,
+            &transaction.staging_dir.join(COMMIT_FILE),
+        )
+        .unwrap();
+        drop(db);
+
+        let mut db = base_db(TRANSACTION_FINISH_BASE);
+        db.begin().unwrap();
+        db.commit().unwrap();
+        let after = db.get_logs().unwrap().len();
+        remove_dir_all(TRANSACTION_FINISH_BASE).unwrap();
+
+        assert_eq!(after, before + 1)
+    }
+}
@@ -1,8 +1,6 @@
 use super::errors::{Error, SerializeError};
 use csv::WriterBuilder;
-use plants::{
-    graveyard::GraveyardPlant, growth_item::GrowthItem, location::Location, log_item::LogItem,
-};
+use plants::{graveyard::GraveyardPlant, growth_item::GrowthItem, log_item::LogItem};
 use serde::Serialize;
 use std::{
     fs::{File, OpenOptions},
@@ -23,6 +21,7 @@ pub fn write_csv<T: Serialize>(
     let file = OpenOptions::new()
         .write(true)
         .append(append)
+        .truncate(!append)
         .open(file_path)?;
     let mut writer = WriterBuilder::new()
         .delimiter(b';')
@@ -61,10 +60,6 @@ pub fn write_graveyard(
     write_csv(graveyard, graveyard_out, append)
 }
 
-pub fn add_location(location: Location, location_out: &PathBuf) -> Result<(), Error> {
-    write_csv(vec![location], location_out, true)
-}
-
 #[cfg(test)]
 mod write_csv_tests {
     use super::{write_activities, write_csv, write_graveyard, write_growth};
@@ -19,23 +19,29 @@ pub fn write_json<T: Serialize>(item: T, out_filepath: &PathBuf) -> Result<(), E
     Ok(())
 }
 
-pub fn write_vec<T: Serialize + Named>(items: Vec<T>, out_path: &PathBuf) -> Result<(), Error> {
+/// Path of the json file `write_vec` writes for an item
+pub fn item_path(out_path: &Path, name: &str) -> PathBuf {
+    let item_name = name.replace(' ', "");
+    let file_name = format!("{item_name}.json");
+    out_path.join(item_name).join(file_name)
+}
+
+pub fn write_vec<T: Serialize + Named>(items: Vec<T>, out_path: &Path) -> Result<(), Error> {
     for item in items.iter() {
-        let item_name = &item.get_name().replace(' ', "");
-        let file_name = format!("{item_name}.json");
-        let out_dir = Path::new(out_path).join(item_name);
-        create_dir_all(out_dir.clone())?;
-        let out_path = out_dir.join(file_name);
+        let out_path = item_path(out_path, &item.get_name());
+        if let Some(out_dir) = out_path.parent() {
+            create_dir_all(out_dir)?;
+        }
         write_json(item, &out_path)?;
     }
     Ok(())
 }
 
-pub fn write_plants(plants: Vec<PlantInfo>, plant_dir: &PathBuf) -> Result<(), Error> {
+pub fn write_plants(plants: Vec<PlantInfo>, plant_dir: &Path) -> Result<(), Error> {
     write_vec(plants, plant_dir)
 }
 
-pub fn write_species(species: Vec<Species>, species_dir: &PathBuf) -> Result<(), Error> {
+pub fn write_species(species: Vec<Species>, species_dir: &Path) -> Result<(), Error> {
     write_vec(species, species_dir)
 }
 
@@ -1,3 +1,5 @@
+pub mod archive;
 pub mod database_manager;
 pub mod file_backend;
 pub mod sqlite_backend;
+pub mod weather;
@@ -1,148 +1,49 @@
-use super::{errors::Error, SQLiteDB};
-use crate::{database_manager::DatabaseManager, file_backend::json_to_plant::load_images};
+use super::{errors::Error, load::SPECIES_COLUMNS, SQLiteDB};
+use crate::database_manager::DatabaseManager;
 use plants::{
     graveyard::GraveyardPlant,
     growth_item::GrowthItem,
     location::Location,
     log_item::LogItem,
     named::Named,
-    plant::{Plant, PlantInfo, PlantLocation, PlantSpecies},
+    plant::{ImageMeta, Plant, PlantInfo},
     species::Species,
 };
 
-use std::{error::Error as StdErr, path::PathBuf};
+use sqlite::Value;
+use std::{error::Error as StdErr, fs::rename, path::PathBuf};
 
 impl DatabaseManager for SQLiteDB {
     // Plant Methods
     fn get_all_plants(&mut self) -> Result<Vec<Plant>, Box<dyn StdErr>> {
-        let info_query = "SELECT * FROM plants";
-        let info_maps = self.read_rows(
-            info_query,
-            vec![
-                "name",
-                "species",
-                "location",
-                "origin",
-                "obtained",
-                "auto_water",
-                "notes",
-            ],
-        )?;
-        let mut infos = vec![];
-        for mut map in info_maps.into_iter() {
-            map.insert("date_format".to_owned(), self.date_format.clone());
-            let info: PlantInfo = map.try_into()?;
-            infos.push(info);
-        }
-
-        let mut plants = vec![];
-        for plant in infos.iter_mut() {
-            match self.get_species(&plant.species.get_name()) {
-                Ok(sp) => plant.species = PlantSpecies::Species(Box::new(sp)),
-                Err(_) => (),
-            };
-            match self.get_location(&plant.location.get_name()) {
-                Ok(loc) => plant.location = PlantLocation::Location(Box::new(loc)),
-                Err(_) => (),
-            };
-            let growths = self.get_growth_plant(&plant.name)?;
-            let logs = self.get_logs_plant(&plant.name)?;
-
-            let img_dir = self.plants_dir.join(plant.name.replace(' ', ""));
-            let images = load_images(&img_dir)?;
-            plants.push(Plant {
-                info: plant.clone(),
-                growth: growths,
-                activities: logs,
-                images,
-            });
-        }
-        Ok(plants)
+        self.load_plants("1", vec![])
     }
 
     fn get_plants_by_location(&mut self, location: &str) -> Result<Vec<Plant>, Box<dyn StdErr>> {
-        let location_query =
-            format!("SELECT DISTINCT name FROM plants WHERE location LIKE '%{location}%';");
-        let name_maps = self.read_rows(&location_query, vec!["name"])?;
-        let names = name_maps
-            .into_iter()
-            .map(|map| {
-                map.get("name").cloned().ok_or(Error::MissingValue {
-                    key: "name".to_owned(),
-                })
-            })
-            .collect::<Result<Vec<String>, Error>>()?;
-
-        let mut plants = vec![];
-        for name in names {
-            plants.push(self.get_plant(&name)?);
-        }
-        Ok(plants)
+        self.load_plants(
+            "COALESCE(location,other_location) LIKE ?",
+            vec![format!("%{location}%").into()],
+        )
     }
 
     fn get_plant(&mut self, plant_name: &str) -> Result<Plant, Box<dyn StdErr>> {
-        let info_query = format!("SELECT * FROM plants WHERE name='{plant_name}'");
-        let info_maps = self.read_rows(
-            &info_query,
-            vec![
-                "name",
-                "species",
-                "location",
-                "origin",
-                "obtained",
-                "auto_water",
-                "notes",
-            ],
-        )?;
-        let mut plant_map = info_maps.first().cloned().ok_or(Error::PlantNotFound {
+        let plants = self.load_plants("name=?", vec![plant_name.into()])?;
+        let plant = plants.into_iter().next().ok_or(Error::PlantNotFound {
             name: plant_name.to_owned(),
         })?;
-        plant_map.insert("date_format".to_owned(), self.date_format.clone());
-        let mut info: PlantInfo = plant_map.try_into()?;
-        match self.get_species(&info.species.get_name()) {
-            Ok(sp) => info.species = PlantSpecies::Species(Box::new(sp)),
-            Err(_) => (),
-        };
-        match self.get_location(&info.location.get_name()) {
-            Ok(loc) => info.location = PlantLocation::Location(Box::new(loc)),
-            Err(_) => (),
-        };
-        let growths = self.get_growth_plant(&plant_name)?;
-        let logs = self.get_logs_plant(&plant_name)?;
-        let img_dir = self.plants_dir.join(plant_name.replace(' ', ""));
-        let images = load_images(&img_dir)?;
-        Ok(Plant {
-            info,
-            growth: growths,
-            activities: logs,
-            images,
-        })
+        Ok(plant)
     }
 
     fn get_plants_species(&mut self, species_name: &str) -> Result<Vec<Plant>, Box<dyn StdErr>> {
-        let species_query = format!("SELECT name FROM plants WHERE species={species_name}");
-        let species_maps = self.read_rows(&species_query, vec!["name"])?;
-        let names: Vec<String> = species_maps
-            .into_iter()
-            .map(|map| {
-                map.get("name")
-                    .ok_or(Error::MissingValue {
-                        key: "name".to_owned(),
-                    })
-                    .cloned()
-            })
-            .collect::<Result<Vec<String>, Error>>()?;
-
-        let mut plants = vec![];
-        for name in names {
-            plants.push(self.get_plant(&name)?);
-        }
-        Ok(plants)
+        self.load_plants(
+            "COALESCE(species,other_species)=?",
+            vec![species_name.into()],
+        )
     }
 
     fn get_num_plants(&mut self) -> Result<i32, Box<dyn StdErr>> {
         let num_query = "SELECT COUNT(*) AS num FROM plants";
-        let num_map = self.read_rows(num_query, vec!["num"])?;
+        let num_map = self.read_rows(num_query, vec![], vec!["num"])?;
         let fst = num_map.first().ok_or(Error::MissingValue {
             key: "num".to_owned(),
         })?;
@@ -155,7 +56,7 @@ impl DatabaseManager for SQLiteDB {
 
     fn find_plant_name(&mut self, plant_name: String) -> Result<String, Box<dyn StdErr>> {
         let names_query = "SELECT name FROM plants";
-        let rows = self.read_rows(names_query, vec!["name"])?;
+        let rows = self.read_rows(names_query, vec![], vec!["name"])?;
         for row in rows.into_iter() {
             let name = row.get("name").ok_or(Box::new(Error::MissingValue {
                 key: "name".to_owned(),
@@ -167,231 +68,141 @@ impl DatabaseManager for SQLiteDB {
         Err(Box::new(Error::PlantNotFound { name: plant_name }))
     }
 
-    fn write_plant(&mut self, plant: PlantInfo) -> Result<(), Box<dyn StdErr>> {
-        let fmt_plant = |info: &PlantInfo, include_name: bool| {
-            let notes_str = if info.notes.is_empty() {
-                "null".to_owned()
-            } else {
-                format!("'{}'", self.sanitize(&info.notes.join(", ")))
-            };
-
-            let name_str = if include_name {
-                format!("'{}',", self.sanitize(&info.name))
-            } else {
-                "".to_owned()
-            };
-
-            format!(
-                "({} '{}', '{}','{}','{}','{}',{})",
-                name_str,
-                self.sanitize(&info.species),
-                self.sanitize(&info.location),
-                self.sanitize(&info.origin),
-                info.obtained.format(&self.date_format),
-                self.sanitize(&info.auto_water),
-                notes_str
-            )
-        };
-        let mut plant_query =
-            "INSERT INTO plants (name,species,location,origin,obtained,auto_water,notes) VALUES "
-                .to_owned();
-
-        plant_query += &fmt_plant(&plant, true);
-        plant_query += " ON CONFLICT(name) DO UPDATE SET (species,location,origin,obtained,auto_water,notes) = ";
-        plant_query += &fmt_plant(&plant, false);
-        plant_query += ";";
-        self.connection.execute(plant_query)?;
+    fn rename_plant(&mut self, plant_name: &str, new_name: &str) -> Result<(), Box<dyn StdErr>> {
+        if !self.plant_exists(plant_name)? {
+            return Err(Error::PlantNotFound {
+                name: plant_name.to_owned(),
+            }
+            .into());
+        }
+        if self.plant_exists(new_name)? {
+            return Err(Error::PlantExists {
+                name: new_name.to_owned(),
+            }
+            .into());
+        }
+        // activities, growth, notes and image metadata are renamed by the foreign key constraints
+        self.transaction(|db| {
+            db.execute(
+                "UPDATE plants SET name=? WHERE name=?;",
+                vec![new_name.into(), plant_name.into()],
+            )?;
+            Ok::<(), Box<dyn StdErr>>(())
+        })?;
+        // the image directory is only renamed once the new name is committed
+        // if that fails, the plant gets its old name back
+        let old_dir = self.plants_dir.join(plant_name.replace(' ', ""));
+        let new_dir = self.plants_dir.join(new_name.replace(' ', ""));
+        if old_dir.exists() && old_dir != new_dir {
+            if let Err(err) = rename(old_dir, new_dir) {
+                self.execute(
+                    "UPDATE plants SET name=? WHERE name=?;",
+                    vec![plant_name.into(), new_name.into()],
+                )?;
+                return Err(err.into());
+            }
+        }
         Ok(())
     }
 
+    fn write_plant(&mut self, plant: PlantInfo) -> Result<(), Box<dyn StdErr>> {
+        let plant_name = plant.name.clone();
+        let params = vec![
+            plant.name.into(),
+            plant.species.get_name().into(),
+            plant.location.get_name().into(),
+            plant.origin.into(),
+            plant.obtained.format(&self.date_format).to_string().into(),
+            (plant.auto_water as i64).into(),
+        ];
+        // species and locations not contained in their tables are saved as other_species/other_location
+        self.transaction(|db| {
+            db.execute(
+                "INSERT INTO plants (name,species,other_species,location,other_location,origin,obtained,auto_water)
+                VALUES (
+                    ?1,
+                    (SELECT name FROM species WHERE name=?2),
+                    CASE WHEN EXISTS (SELECT 1 FROM species WHERE name=?2) THEN NULL ELSE ?2 END,
+                    (SELECT name FROM locations WHERE name=?3),
+                    CASE WHEN EXISTS (SELECT 1 FROM locations WHERE name=?3) THEN NULL ELSE ?3 END,
+                    ?4,?5,?6
+                )
+                ON CONFLICT(name) DO UPDATE SET
+                (species,other_species,location,other_location,origin,obtained,auto_water) =
+                (excluded.species,excluded.other_species,excluded.location,excluded.other_location,
+                excluded.origin,excluded.obtained,excluded.auto_water);",
+                params,
+            )?;
+            db.write_plant_notes(&plant_name, plant.notes)?;
+            db.write_schedules("plant", &plant_name, &plant.schedules)?;
+            Ok::<(), Box<dyn StdErr>>(())
+        })
+    }
+
     fn write_plants(&mut self, plants: Vec<PlantInfo>) -> Result<(), Box<dyn StdErr>> {
-        for plant in plants.into_iter() {
-            self.write_plant(plant)?;
-        }
-        Ok(())
+        self.transaction(|db| {
+            for plant in plants.into_iter() {
+                db.write_plant(plant)?;
+            }
+            Ok(())
+        })
     }
 
     // Species Methods
     fn get_all_species(&mut self) -> Result<Vec<Species>, Box<dyn StdErr>> {
-        let species_query = "SELECT * FROM species;";
-        let species_maps = self.read_rows(
-            species_query,
-            vec![
-                "name",
-                "scientific_name",
-                "genus",
-                "family",
-                "sunlight",
-                "temp_min",
-                "temp_max",
-                "temp_min_opt",
-                "temp_max_opt",
-                "planting_distance",
-                "ph_min",
-                "ph_max",
-                "watering_notes",
-                "fertilizing_notes",
-                "avg_watering_days",
-                "avg_fertilizing_days",
-                "pruning_notes",
-                "companions",
-                "additional_notes",
-            ],
-        )?;
-        let mut species = vec![];
-        for mut species_map in species_maps.into_iter() {
-            species_map.insert("date_format".to_owned(), self.date_format.clone());
-            species.push(species_map.try_into()?);
-        }
-
-        Ok(species)
+        self.load_species("1", vec![])
     }
 
     fn get_species(&mut self, species_name: &str) -> Result<Species, Box<dyn StdErr>> {
-        let species_query = format!("SELECT * FROM species WHERE name='{species_name}';");
-        let species_map = self.read_rows(
-            &species_query,
-            vec![
-                "name",
-                "scientific_name",
-                "genus",
-                "family",
-                "sunlight",
-                "temp_min",
-                "temp_max",
-                "temp_min_opt",
-                "temp_max_opt",
-                "planting_distance",
-                "ph_min",
-                "ph_max",
-                "watering_notes",
-                "fertilizing_notes",
-                "avg_watering_days",
-                "avg_fertilizing_days",
-                "pruning_notes",
-                "companions",
-                "additional_notes",
-            ],
-        )?;
-        let map_fst = species_map.first().cloned().ok_or(Error::SpeciesNotFound {
+        let species = self.load_species("name=?", vec![species_name.into()])?;
+        let species = species.into_iter().next().ok_or(Error::SpeciesNotFound {
             name: species_name.to_owned(),
         })?;
-        let species: Species = map_fst.try_into()?;
-
         Ok(species)
     }
 
     fn write_species(&mut self, species: Species) -> Result<(), Box<dyn StdErr>> {
-        let fmt_species = |species: &Species, include_name: bool| {
-            let name_str = if include_name {
-                format!("'{}',", self.sanitize(&species.name))
-            } else {
-                "".to_owned()
-            };
-
-            let plant_distance_str = species
-                .planting_distance
-                .map(|f| format!("{}", f))
-                .unwrap_or("null".to_owned());
-            let avg_watering_days_str = species
-                .avg_watering_days
-                .map(|f| format!("{}", f))
-                .unwrap_or("null".to_owned());
-            let avg_fertilizing_str = species
-                .avg_fertilizing_days
-                .map(|f| format!("{}", f))
-                .unwrap_or("null".to_owned());
-
-            let watering_notes_str = if species.watering_notes.is_empty() {
-                "null".to_owned()
-            } else {
-                format!("'{}'", self.sanitize(&species.watering_notes.join(", ")))
-            };
-            let fertilizing_notes_str = if species.fertilizing_notes.is_empty() {
-                "null".to_owned()
-            } else {
-                format!("'{}'", self.sanitize(&species.fertilizing_notes.join(", ")))
-            };
-            let pruning_str = if species.pruning_notes.is_empty() {
-                "null".to_owned()
-            } else {
-                format!("'{}'", self.sanitize(&species.pruning_notes.join(", ")))
-            };
-            let companions_str = if species.companions.is_empty() {
-                "null".to_owned()
-            } else {
-                format!("'{}'", self.sanitize(&species.companions.join(", ")))
-            };
-            let notes_str = if species.additional_notes.is_empty() {
-                "null".to_owned()
-            } else {
-                format!("'{}'", self.sanitize(&species.additional_notes.join(", ")))
-            };
-            format!(
-                "({}'{}','{}','{}','{}',{},{},{},{},{},{},{},{},{},{},{},{},{},{})",
-                name_str,
-                self.sanitize(&species.scientific_name),
-                self.sanitize(&species.genus),
-                self.sanitize(&species.family),
-                self.sanitize(&species.sunlight),
-                species.temp_min,
-                species.temp_max,
-                species.opt_temp_min,
-                species.opt_temp_max,
-                plant_distance_str,
-                species.ph_min,
-                species.ph_max,
-                watering_notes_str,
-                fertilizing_notes_str,
-                avg_watering_days_str,
-                avg_fertilizing_str,
-                pruning_str,
-                companions_str,
-                notes_str
-            )
-        };
-
-        let fields = |include_name: bool| {
-            let name = if include_name { "name," } else { "" };
-            format!(
-                "({name}
-            scientific_name, 
-            genus, 
-            family, 
-            sunlight, 
-            temp_min, 
-            temp_max, 
-            temp_min_opt, 
-            temp_max_opt,
-            planting_distance,
-            ph_min,
-            ph_max,
-            watering_notes,
-            fertilizing_notes,
-            avg_watering_days,
-            avg_fertilizing_days,
-            pruning_notes,
-            companions,
-            additional_notes)"
-            )
-        };
-        let mut species_query = "INSERT INTO species ".to_owned();
-        species_query += &fields(true);
-        species_query += " VALUES ";
-        species_query += &fmt_species(&species, true);
-        species_query += " ON CONFLICT(name) DO UPDATE SET ";
-        species_query += &fields(false);
-        species_query += " = ";
-        species_query += &fmt_species(&species, false);
-        species_query += ";";
-        self.connection.execute(species_query)?;
+        let params = vec![
+            species.name.clone().into(),
+            species.scientific_name.clone().into(),
+            species.genus.clone().into(),
+            species.family.clone().into(),
+            species.sunlight.to_string().into(),
+            (species.temp_min as f64).into(),
+            (species.temp_max as f64).into(),
+            (species.opt_temp_min as f64).into(),
+            (species.opt_temp_max as f64).into(),
+            SQLiteDB::optional_value(species.planting_distance.map(|dist| dist as f64)),
+            (species.ph_min as f64).into(),
+            (species.ph_max as f64).into(),
+            SQLiteDB::optional_value(species.avg_watering_days.map(|days| days as i64)),
+            SQLiteDB::optional_value(species.avg_fertilizing_days.map(|days| days as i64)),
+        ];
+
+        let fields = SPECIES_COLUMNS[1..].join(",");
+        let excluded_fields = SPECIES_COLUMNS[1..]
+            .iter()
+            .map(|field| format!("excluded.{field}"))
+            .collect::<Vec<String>>()
+            .join(",");
+        let species_query = format!(
+            "INSERT INTO species (name,{fields})
+            VALUES (?,?,?,?,?,?,?,?,?,?,?,?,?,?)
+            ON CONFLICT(name) DO UPDATE SET ({fields}) = ({excluded_fields});"
+        );
+        self.transaction(|db| {
+            db.execute(&species_query, params)?;
+            db.write_species_lists(&species)?;
+            db.write_schedules("species", &species.name, &species.schedules)?;
+            Ok::<(), Box<dyn StdErr>>(())
+        })?;
 
         Ok(())
     }
 
     fn find_species_name(&mut self, species_name: String) -> Result<String, Box<dyn StdErr>> {
         let names_query = "SELECT name FROM species";
-        let rows = self.read_rows(names_query, vec!["name"])?;
+        let rows = self.read_rows(names_query, vec![], vec!["name"])?;
         for row in rows.into_iter() {
             let name = row.get("name").ok_or(Box::new(Error::MissingValue {
                 key: "name".to_owned(),
@@ -403,11 +214,39 @@ impl DatabaseManager for SQLiteDB {
         Err(Box::new(Error::PlantNotFound { name: species_name }))
     }
 
+    fn delete_species(&mut self, species_name: &str) -> Result<(), Box<dyn StdErr>> {
+        if !self.species_exists(species_name)? {
+            return Err(Error::SpeciesNotFound {
+                name: species_name.to_owned(),
+            }
+            .into());
+        }
+        let num_plants = self.count_rows(
+            "SELECT COUNT(*) AS num FROM plants WHERE species=?",
+            vec![species_name.into()],
+        )?;
+        if num_plants > 0 {
+            return Err(Error::SpeciesInUse {
+                name: species_name.to_owned(),
+            }
+            .into());
+        }
+        // notes and companions are removed by the foreign key constraints
+        self.execute(
+            "DELETE FROM species WHERE name=?;",
+            vec![species_name.into()],
+        )?;
+        Ok(())
+    }
+
     // Graveyard Methods
     fn get_graveyard(&mut self) -> Result<Vec<GraveyardPlant>, Box<dyn StdErr>> {
         let query = "SELECT * FROM graveyard";
-        let graveyard_maps =
-            self.read_rows(&query, vec!["name", "species", "planted", "died", "reason"])?;
+        let graveyard_maps = self.read_rows(
+            query,
+            vec![],
+            vec!["name", "species", "planted", "died", "reason"],
+        )?;
         let mut graveyard = vec![];
         for mut map in graveyard_maps.into_iter() {
             map.insert("date_format".to_owned(), self.date_format.clone());
@@ -419,56 +258,49 @@ impl DatabaseManager for SQLiteDB {
     }
 
     fn kill_plant(&mut self, plant: GraveyardPlant) -> Result<(), Box<dyn StdErr>> {
-        let info_query = format!(
-            "DELETE FROM plants WHERE name='{}';",
-            self.sanitize(&plant.name)
-        );
-        self.connection.execute(info_query)?;
-
-        let logs_query = format!(
-            "DELETE FROM activities WHERE plant='{}';",
-            self.sanitize(&plant.name)
-        );
-        self.connection.execute(logs_query)?;
-
-        let growth_query = format!(
-            "DELETE FROM growth WHERE plant='{}';",
-            self.sanitize(&plant.name)
-        );
-        self.connection.execute(growth_query)?;
-
+        // activities, growth, notes and image metadata are removed by the foreign key constraints
         let plant_name = plant.name.clone();
-        self.add_to_graveyard(plant)?;
+        self.transaction(|db| {
+            db.execute(
+                "DELETE FROM plants WHERE name=?;",
+                vec![plant.name.clone().into()],
+            )?;
+            db.add_to_graveyard(plant)
+        })?;
 
         // move images to dead dir
         let dead_dir = self.plants_dir.join("dead");
         if !dead_dir.exists() {
             std::fs::create_dir_all(dead_dir.clone())?;
         }
-        let dead_path = dead_dir.join(plant_name.clone());
-        let plant_path = PathBuf::from(&self.plants_dir).join(plant_name);
+        let dir_name = plant_name.replace(' ', "");
+        let dead_path = dead_dir.join(&dir_name);
+        let plant_path = PathBuf::from(&self.plants_dir).join(dir_name);
         let _ = std::fs::rename(plant_path, dead_path);
 
         Ok(())
     }
 
+    fn write_graveyard(&mut self, graveyard: Vec<GraveyardPlant>) -> Result<(), Box<dyn StdErr>> {
+        self.transaction(|db| {
+            for plant in graveyard.into_iter() {
+                db.add_to_graveyard(plant)?;
+            }
+            Ok(())
+        })
+    }
+
     // Location Methods
     fn get_locations(&mut self) -> Result<Vec<Location>, Box<dyn StdErr>> {
-        let query = "SELECT * FROM locations";
-        let location_maps = self.read_rows(query, vec!["name", "outside"])?;
-        let mut locations = vec![];
-        for mut map in location_maps.into_iter() {
-            map.insert("date_format".to_owned(), self.date_format.clone());
-            let loc: Location = map.try_into()?;
-            locations.push(loc);
-        }
-
-        Ok(locations)
+        self.load_locations()
     }
 
     fn get_location(&mut self, location_name: &str) -> Result<Location, Box<dyn StdErr>> {
-        let query = format!("SELECT * FROM locations WHERE name LIKE '%{location_name}%'");
-        let location_maps = self.read_rows(&query, vec!["name", "outside"])?;
+        let location_maps = self.read_rows(
+            "SELECT * FROM locations WHERE name LIKE ? ORDER BY name=? DESC",
+            vec![format!("%{location_name}%").into(), location_name.into()],
+            vec!["name", "outside"],
+        )?;
         let map_fst = location_maps.first().ok_or(Error::LocationNotFound {
             name: location_name.to_owned(),
         })?;
@@ -478,146 +310,207 @@ impl DatabaseManager for SQLiteDB {
                 key: key.to_owned(),
             })
         };
+        let name = lookup_fun("name")?;
+        let mut climate = self.get_climate("location=?", vec![name.as_str().into()])?;
         let location = Location {
-            name: lookup_fun("name")?,
-            outside: lookup_fun("outside")? == "1",
+            climate: climate.remove(&name).unwrap_or_default(),
+            outside: matches!(lookup_fun("outside")?.as_str(), "1" | "true"),
+            name,
         };
         Ok(location)
     }
 
     fn write_location(&mut self, location: Location) -> Result<(), Box<dyn StdErr>> {
-        let fmt_location =
-            |loc: &Location| format!("('{}','{}')", self.sanitize(&loc.name), loc.outside);
-        let mut query = "INSERT INTO locations ".to_owned();
-        query += "(name,outside)";
-        query += " VALUES ";
-        query += &fmt_location(&location);
-        query += " ON CONFLICT(name) DO UPDATE SET outside= ";
-        query += &format!("'{}'", location.outside);
-        query += ";";
-        self.connection.execute(query)?;
+        self.transaction(|db| {
+            db.execute(
+                "INSERT INTO locations (name,outside) VALUES (?,?)
+                ON CONFLICT(name) DO UPDATE SET outside=excluded.outside;",
+                vec![
+                    location.name.as_str().into(),
+                    (location.outside as i64).into(),
+                ],
+            )?;
+            db.write_climate(&location.name, &location.climate)?;
+            Ok::<(), Box<dyn StdErr>>(())
+        })?;
+        Ok(())
+    }
+
+    fn delete_location(&mut self, location_name: &str) -> Result<(), Box<dyn StdErr>> {
+        let num_locations = self.count_rows(
+            "SELECT COUNT(*) AS num FROM locations WHERE name=?",
+            vec![location_name.into()],
+        )?;
+        if num_locations == 0 {
+            return Err(Error::LocationNotFound {
+                name: location_name.to_owned(),
+            }
+            .into());
+        }
+        let num_plants = self.count_rows(
+            "SELECT COUNT(*) AS num FROM plants WHERE location=?",
+            vec![location_name.into()],
+        )?;
+        if num_plants > 0 {
+            return Err(Error::LocationInUse {
+                name: location_name.to_owned(),
+            }
+            .into());
+        }
+        self.execute(
+            "DELETE FROM locations WHERE name=?;",
+            vec![location_name.into()],
+        )?;
         Ok(())
     }
 
     // Log Methods
     fn get_logs(&mut self) -> Result<Vec<LogItem>, Box<dyn StdErr>> {
-        let query = "SELECT * FROM activities";
-        let logs_maps = self.read_rows(query, vec!["name", "date", "plant", "note"])?;
-        let mut logs = vec![];
-        for mut map in logs_maps.into_iter() {
-            map.insert("date_format".to_owned(), self.date_format.clone());
-            let log: LogItem = map.try_into()?;
-            logs.push(log);
-        }
-        Ok(logs)
+        self.load_logs("1", vec![])
     }
 
     fn get_logs_plant(&mut self, plant_name: &str) -> Result<Vec<LogItem>, Box<dyn StdErr>> {
-        self.get_logs_plant(plant_name)
+        self.load_logs("plant=?", vec![plant_name.into()])
     }
 
     fn write_log(&mut self, log: LogItem) -> Result<(), Box<dyn StdErr>> {
-        let fmt_log = |log: &LogItem| {
-            let note_str = match &log.note {
-                Some(note) => format!("'{}'", &note),
-                None => "null".to_owned(),
-            };
-
-            format!(
-                "('{}','{}','{}',{})",
-                self.sanitize(&log.activity),
-                &log.date.format(&self.date_format),
-                self.sanitize(&log.plant),
-                self.sanitize(&note_str),
-            )
-        };
-
-        let mut query = "INSERT INTO activities ".to_owned();
-        query += "(name,date,plant,note)";
-        query += " VALUES ";
-        query += &fmt_log(&log);
-        query += " ON CONFLICT DO NOTHING";
-        query += ";";
-        self.connection.execute(query)?;
+        let params = vec![
+            log.activity.into(),
+            log.date.format(&self.date_format).to_string().into(),
+            log.plant.into(),
+            SQLiteDB::optional_value(log.note),
+            SQLiteDB::optional_value(log.user),
+        ];
+        self.execute(
+            "INSERT INTO activities (name,date,plant,note,user) VALUES (?,?,?,?,?) ON CONFLICT DO NOTHING;",
+            params,
+        )?;
         Ok(())
     }
 
     fn write_logs(&mut self, logs: Vec<LogItem>) -> Result<(), Box<dyn StdErr>> {
-        for log in logs {
-            self.write_log(log)?;
+        self.transaction(|db| {
+            for log in logs {
+                db.write_log(log)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn delete_log(&mut self, log: LogItem) -> Result<(), Box<dyn StdErr>> {
+        let params: Vec<Value> = vec![
+            log.activity.clone().into(),
+            log.date.format(&self.date_format).to_string().into(),
+            log.plant.clone().into(),
+        ];
+        let num_logs = self.count_rows(
+            "SELECT COUNT(*) AS num FROM activities WHERE name=? AND date=? AND plant=?",
+            params.clone(),
+        )?;
+        if num_logs == 0 {
+            return Err(Error::LogNotFound {
+                plant: log.plant,
+                activity: log.activity,
+            }
+            .into());
         }
+        self.execute(
+            "DELETE FROM activities WHERE name=? AND date=? AND plant=?;",
+            params,
+        )?;
         Ok(())
     }
 
     // Growth Methods
     fn get_growth(&mut self) -> Result<Vec<GrowthItem>, Box<dyn StdErr>> {
-        let query = "SELECT * FROM growth";
-        let growth_maps = self.read_rows(
-            query,
-            vec!["plant", "date", "height_cm", "width_cm", "note", "health"],
-        )?;
-        let mut growth = vec![];
-        for mut map in growth_maps.into_iter() {
-            map.insert("date_format".to_owned(), self.date_format.clone());
-            let item: GrowthItem = map.try_into()?;
-            growth.push(item);
-        }
-        Ok(growth)
+        self.load_growth("1", vec![])
     }
 
     fn get_growth_plant(&mut self, plant_name: &str) -> Result<Vec<GrowthItem>, Box<dyn StdErr>> {
-        self.get_growth_plant(plant_name)
+        self.load_growth("plant=?", vec![plant_name.into()])
     }
 
     fn write_growth(&mut self, growth: GrowthItem) -> Result<(), Box<dyn StdErr>> {
-        let fmt_growth = |item: &GrowthItem, include_key: bool| {
-            let prefix = if include_key {
-                format!(
-                    "'{}','{}',",
-                    self.sanitize(&item.plant),
-                    &item.date.format(&self.date_format)
-                )
-            } else {
-                "".to_owned()
-            };
-            let note_str = match &item.note {
-                None => "null".to_owned(),
-                Some(note) => format!("'{}'", self.sanitize(note)),
-            };
-
-            format!(
-                "({} {},{},{},{})",
-                prefix,
-                self.sanitize(&item.height_cm),
-                self.sanitize(&item.width_cm),
-                &note_str,
-                self.sanitize(&item.health)
-            )
-        };
-        let mut query = "INSERT INTO growth".to_owned();
-        query += "(plant,date,height_cm,width_cm,note,health)";
-        query += " VALUES ";
-        query += &fmt_growth(&growth, true);
-        query += " ON CONFLICT DO UPDATE SET ";
-        query += "(height_cm,width_cm,note,health) = ";
-        query += &fmt_growth(&growth, false);
-        query += ";";
-
-        self.connection.execute(query)?;
+        let params = vec![
+            growth.plant.into(),
+            growth.date.format(&self.date_format).to_string().into(),
+            (growth.height_cm as f64).into(),
+            (growth.width_cm as f64).into(),
+            SQLiteDB::optional_value(growth.note),
+            (growth.health as i64).into(),
+        ];
+        self.execute(
+            "INSERT INTO growth (plant,date,height_cm,width_cm,note,health) VALUES (?,?,?,?,?,?)
+            ON CONFLICT DO UPDATE SET (height_cm,width_cm,note,health) =
+            (excluded.height_cm,excluded.width_cm,excluded.note,excluded.health);",
+            params,
+        )?;
         Ok(())
     }
 
     fn write_growths(&mut self, growth: Vec<GrowthItem>) -> Result<(), Box<dyn StdErr>> {
-        for growth in growth.into_iter() {
-            self.write_growth(growth)?;
+        self.transaction(|db| {
+            for growth in growth.into_iter() {
+                db.write_growth(growth)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn delete_growth(&mut self, growth: GrowthItem) -> Result<(), Box<dyn StdErr>> {
+        let params: Vec<Value> = vec![
+            growth.plant.clone().into(),
+            growth.date.format(&self.date_format).to_string().into(),
+        ];
+        let num_items = self.count_rows(
+            "SELECT COUNT(*) AS num FROM growth WHERE plant=? AND date=?",
+            params.clone(),
+        )?;
+        if num_items == 0 {
+            return Err(Error::GrowthNotFound {
+                plant: growth.plant,
+            }
+            .into());
         }
+        self.execute("DELETE FROM growth WHERE plant=? AND date=?;", params)?;
         Ok(())
     }
 
+    // Image Methods
+    fn write_image_meta(&mut self, meta: ImageMeta) -> Result<(), Box<dyn StdErr>> {
+        self.transaction(|db| {
+            if meta.cover {
+                db.execute(
+                    "UPDATE image_metadata SET cover=0 WHERE plant=?;",
+                    vec![meta.plant.clone().into()],
+                )?;
+            }
+            db.execute(
+                "INSERT INTO image_metadata (plant,file_name,caption,cover) VALUES (?,?,?,?)
+                ON CONFLICT DO UPDATE SET (caption,cover) = (excluded.caption,excluded.cover);",
+                vec![
+                    meta.plant.into(),
+                    meta.file_name.into(),
+                    SQLiteDB::optional_value(meta.caption),
+                    (meta.cover as i64).into(),
+                ],
+            )?;
+            // images without caption or cover don't need to be stored
+            db.execute(
+                "DELETE FROM image_metadata WHERE caption IS NULL AND cover=0;",
+                vec![],
+            )?;
+            Ok(())
+        })
+    }
+
     // Existence Methods
     fn plant_exists(&mut self, plant_name: &str) -> Result<bool, Box<dyn StdErr>> {
-        let query = format!("SELECT COUNT(*) AS num FROM plants WHERE name='{plant_name}'");
-        let plant_maps = self.read_rows(&query, vec!["num"])?;
+        let plant_maps = self.read_rows(
+            "SELECT COUNT(*) AS num FROM plants WHERE name=?",
+            vec![plant_name.into()],
+            vec!["num"],
+        )?;
         let plant_map = plant_maps.first().ok_or(Error::MissingValue {
             key: "num".to_owned(),
         })?;
@@ -629,8 +522,11 @@ impl DatabaseManager for SQLiteDB {
     }
 
     fn species_exists(&mut self, species_name: &str) -> Result<bool, Box<dyn StdErr>> {
-        let query = format!("SELECT COUNT(*) AS num FROM species WHERE name='{species_name}'");
-        let species_maps = self.read_rows(&query, vec!["num"])?;
+        let species_maps = self.read_rows(
+            "SELECT COUNT(*) AS num FROM species WHERE name=?",
+            vec![species_name.into()],
+            vec!["num"],
+        )?;
         let species_map = species_maps.first().ok_or(Error::MissingValue {
             key: "num".to_owned(),
         })?;
@@ -640,4 +536,385 @@ impl DatabaseManager for SQLiteDB {
         let exists = num.parse::<usize>()? > 0;
         Ok(exists)
     }
+
+    // Transaction Methods
+    // savepoints are used so transactions can be nested
+    fn begin_transaction(&mut self) -> Result<(), Box<dyn StdErr>> {
+        self.connection.execute("SAVEPOINT db_transaction;")?;
+        Ok(())
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), Box<dyn StdErr>> {
+        self.connection.execute("RELEASE db_transaction;")?;
+        Ok(())
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), Box<dyn StdErr>> {
+        self.connection
+            .execute("ROLLBACK TO db_transaction; RELEASE db_transaction;")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod db_man_tests {
+    use super::SQLiteDB;
+    use crate::database_manager::DatabaseManager;
+    use chrono::NaiveDate;
+    use plants::{
+        climate::parse_climate,
+        graveyard::GraveyardPlant,
+        location::Location,
+        log_item::LogItem,
+        plant::{ImageMeta, PlantInfo, PlantLocation, PlantSpecies},
+        schedule::ActivitySchedule,
+        species::{Species, SunlightRequirement},
+    };
+    use std::{
+        fs::{create_dir_all, remove_dir_all, remove_file, write},
+        path::PathBuf,
+    };
+
+    const QUOTES_DB: &str = "../../testing/sqlite_quotes_test.db";
+    const QUOTES_PLANTS_DIR: &str = "../../testing/sqlite_quotes_plants";
+    const LOCATION_DB: &str = "../../testing/sqlite_location_test.db";
+    const NOTES_DB: &str = "../../testing/sqlite_notes_test.db";
+    const NOTES_PLANTS_DIR: &str = "../../testing/sqlite_notes_plants";
+    const SCHEDULES_DB: &str = "../../testing/sqlite_schedules_test.db";
+    const SCHEDULES_PLANTS_DIR: &str = "../../testing/sqlite_schedules_plants";
+    const KILL_DB: &str = "../../testing/sqlite_kill_test.db";
+    const KILL_PLANTS_DIR: &str = "../../testing/sqlite_kill_plants";
+    const LINKED_DB: &str = "../../testing/sqlite_linked_test.db";
+    const LINKED_PLANTS_DIR: &str = "../../testing/sqlite_linked_plants";
+    const RENAME_DB: &str = "../../testing/sqlite_rename_test.db";
+    const RENAME_PLANTS_DIR: &str = "../../testing/sqlite_rename_plants";
+    const RENAME_FAIL_DB: &str = "../../testing/sqlite_rename_fail_test.db";
+    const RENAME_FAIL_PLANTS_DIR: &str = "../../testing/sqlite_rename_fail_plants";
+    const DELETE_DB: &str = "../../testing/sqlite_delete_test.db";
+    const DELETE_PLANTS_DIR: &str = "../../testing/sqlite_delete_plants";
+    const IMAGE_META_DB: &str = "../../testing/sqlite_image_meta_test.db";
+    const IMAGE_META_PLANTS_DIR: &str = "../../testing/sqlite_image_meta_plants";
+
+    fn linked_species() -> Species {
+        Species {
+            name: "Fern".to_owned(),
+            scientific_name: "Nephrolepis".to_owned(),
+            genus: "Nephrolepis".to_owned(),
+            family: "Lomariopsidaceae".to_owned(),
+            sunlight: SunlightRequirement::Shade,
+            temp_min: 10.0,
+            temp_max: 30.0,
+            opt_temp_min: 18.0,
+            opt_temp_max: 24.0,
+            planting_distance: None,
+            ph_min: 5.0,
+            ph_max: 6.5,
+            watering_notes: vec!["keep moist".to_owned(), "no standing water".to_owned()],
+            avg_watering_days: Some(3),
+            fertilizing_notes: vec![],
+            avg_fertilizing_days: None,
+            pruning_notes: vec![],
+            companions: vec!["Moss".to_owned()],
+            additional_notes: vec![],
+            schedules: vec![
+                ActivitySchedule {
+                    activity: "Fertilizing".to_owned(),
+                    interval_days: None,
+                    months: vec![1, 2, 11, 12],
+                },
+                ActivitySchedule {
+                    activity: "Misting".to_owned(),
+                    interval_days: Some(2),
+                    months: vec![],
+                },
+            ],
+        }
+    }
+
+    fn quoted_info() -> PlantInfo {
+        PlantInfo {
+            name: "O'Brien".to_owned(),
+            species: PlantSpecies::Other("Bird's Nest Fern".to_owned()),
+            location: PlantLocation::Other("Kid's Room".to_owned()),
+            origin: "'; DROP TABLE plants; --".to_owned(),
+            obtained: NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+            auto_water: false,
+            notes: vec![],
+            schedules: vec![],
+        }
+    }
+
+    fn quoted_log() -> LogItem {
+        LogItem {
+            activity: "Watering".to_owned(),
+            date: NaiveDate::from_ymd_opt(1970, 1, 2).unwrap(),
+            plant: "O'Brien".to_owned(),
+            note: Some("it's wet".to_owned()),
+            user: Some("gardener".to_owned()),
+        }
+    }
+
+    #[test]
+    fn write_get_quoted_plant() {
+        let db_path = PathBuf::from(QUOTES_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(QUOTES_PLANTS_DIR);
+        db.write_plant(quoted_info()).unwrap();
+        db.write_log(quoted_log()).unwrap();
+        let exists = db.plant_exists("O'Brien").unwrap();
+        let result = db.get_plant("O'Brien").unwrap();
+        let num_plants = db.get_num_plants().unwrap();
+        drop(db);
+        remove_file(db_path).unwrap();
+        let _ = remove_dir_all(QUOTES_PLANTS_DIR);
+
+        assert!(exists);
+        assert_eq!(num_plants, 1);
+        assert_eq!(result.info.name, "O'Brien");
+        assert_eq!(result.info.origin, quoted_info().origin);
+        assert_eq!(result.activities, vec![quoted_log()])
+    }
+
+    #[test]
+    fn write_get_location() {
+        let db_path = PathBuf::from(LOCATION_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        let location = Location {
+            name: "Kid's Room".to_owned(),
+            outside: false,
+            climate: parse_climate("5.5..30, -5..10 @ Nov-Feb").unwrap(),
+        };
+        db.write_location(location.clone()).unwrap();
+        let result = db.get_locations().unwrap();
+        let single = db.get_location("Kid").unwrap();
+        drop(db);
+        remove_file(db_path).unwrap();
+        assert_eq!(result, vec![location.clone()]);
+        assert_eq!(single, location)
+    }
+
+    #[test]
+    fn write_get_notes() {
+        let db_path = PathBuf::from(NOTES_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(NOTES_PLANTS_DIR);
+        let mut info = quoted_info();
+        info.notes = vec!["first, with comma".to_owned(), "second".to_owned()];
+        db.write_plant(info.clone()).unwrap();
+        info.notes = vec!["replaced".to_owned()];
+        db.write_plant(info.clone()).unwrap();
+        let result = db.get_plant("O'Brien").unwrap();
+        drop(db);
+        remove_file(db_path).unwrap();
+        let _ = remove_dir_all(NOTES_PLANTS_DIR);
+        assert_eq!(result.info.notes, info.notes)
+    }
+
+    #[test]
+    fn write_get_schedules() {
+        let db_path = PathBuf::from(SCHEDULES_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(SCHEDULES_PLANTS_DIR);
+        let mut info = quoted_info();
+        info.schedules = vec!["Repotting: 365".parse().unwrap()];
+        db.write_plant(info.clone()).unwrap();
+        info.schedules = vec![
+            "Misting: 3".parse().unwrap(),
+            "Pruning: 90".parse().unwrap(),
+        ];
+        db.write_plant(info.clone()).unwrap();
+        let result = db.get_plant("O'Brien").unwrap();
+        drop(db);
+        remove_file(db_path).unwrap();
+        let _ = remove_dir_all(SCHEDULES_PLANTS_DIR);
+        assert_eq!(result.info.schedules, info.schedules)
+    }
+
+    #[test]
+    fn kill_plant_cascade() {
+        let db_path = PathBuf::from(KILL_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(KILL_PLANTS_DIR);
+        db.write_plant(quoted_info()).unwrap();
+        db.write_log(quoted_log()).unwrap();
+        db.kill_plant(GraveyardPlant {
+            name: "O'Brien".to_owned(),
+            species: "Bird's Nest Fern".to_owned(),
+            planted: NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+            died: NaiveDate::from_ymd_opt(1970, 1, 3).unwrap(),
+            reason: "test".to_owned(),
+        })
+        .unwrap();
+        let logs = db.get_logs().unwrap();
+        let graveyard = db.get_graveyard().unwrap();
+        let log_result = db.write_log(quoted_log());
+        drop(db);
+        remove_file(db_path).unwrap();
+        let _ = remove_dir_all(KILL_PLANTS_DIR);
+        assert!(logs.is_empty());
+        assert_eq!(graveyard.len(), 1);
+        assert!(log_result.is_err())
+    }
+
+    #[test]
+    fn write_image_meta_cover() {
+        let db_path = PathBuf::from(IMAGE_META_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(IMAGE_META_PLANTS_DIR);
+        let image_dir = db.plants_dir.join("O'Brien");
+        create_dir_all(&image_dir).unwrap();
+        write(image_dir.join("01011970.jpg"), []).unwrap();
+        write(image_dir.join("01011970_2.jpg"), []).unwrap();
+        db.write_plant(quoted_info()).unwrap();
+        let meta = |file_name: &str, caption: Option<&str>| ImageMeta {
+            plant: "O'Brien".to_owned(),
+            file_name: file_name.to_owned(),
+            caption: caption.map(|caption| caption.to_owned()),
+            cover: true,
+        };
+        db.write_image_meta(meta("01011970.jpg", Some("first")))
+            .unwrap();
+        db.write_image_meta(meta("01011970_2.jpg", None)).unwrap();
+        let mut images = db.get_plant("O'Brien").unwrap().images;
+        images.sort_by(|img1, img2| img1.file_name.cmp(&img2.file_name));
+        drop(db);
+        remove_file(db_path).unwrap();
+        remove_dir_all(IMAGE_META_PLANTS_DIR).unwrap();
+        assert_eq!(images[0].caption, Some("first".to_owned()));
+        assert!(!images[0].cover);
+        assert_eq!(images[1].caption, None);
+        assert!(images[1].cover)
+    }
+
+    #[test]
+    fn get_all_plants_linked() {
+        let db_path = PathBuf::from(LINKED_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(LINKED_PLANTS_DIR);
+        let location = Location {
+            name: "Bathroom".to_owned(),
+            outside: false,
+            climate: vec![],
+        };
+        db.write_species(linked_species()).unwrap();
+        db.write_location(location.clone()).unwrap();
+        let mut linked = quoted_info();
+        linked.species = PlantSpecies::Species(Box::new(linked_species()));
+        linked.location = PlantLocation::Location(Box::new(location));
+        db.write_plant(linked.clone()).unwrap();
+        let mut other = quoted_info();
+        other.name = "Other".to_owned();
+        db.write_plant(other.clone()).unwrap();
+        db.write_log(quoted_log()).unwrap();
+
+        let result = db.get_all_plants().unwrap();
+        let species_plants = db.get_plants_species("Fern").unwrap();
+        drop(db);
+        remove_file(db_path).unwrap();
+        let _ = remove_dir_all(LINKED_PLANTS_DIR);
+
+        let infos: Vec<PlantInfo> = result.iter().map(|plant| plant.info.clone()).collect();
+        assert_eq!(infos, vec![linked, other]);
+        assert_eq!(result[0].activities, vec![quoted_log()]);
+        assert!(result[1].activities.is_empty());
+        assert_eq!(species_plants.len(), 1)
+    }
+
+    #[test]
+    fn rename_plant_cascade() {
+        let db_path = PathBuf::from(RENAME_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(RENAME_PLANTS_DIR);
+        let mut info = quoted_info();
+        info.notes = vec!["a note".to_owned()];
+        db.write_plant(info).unwrap();
+        db.write_log(quoted_log()).unwrap();
+        create_dir_all(db.plants_dir.join("O'Brien")).unwrap();
+        db.rename_plant("O'Brien", "New Name").unwrap();
+        let old_exists = db.plant_exists("O'Brien").unwrap();
+        let result = db.get_plant("New Name").unwrap();
+        let new_dir_exists = db.plants_dir.join("NewName").exists();
+        let old_dir_exists = db.plants_dir.join("O'Brien").exists();
+        drop(db);
+        remove_file(db_path).unwrap();
+        let _ = remove_dir_all(RENAME_PLANTS_DIR);
+
+        let mut log = quoted_log();
+        log.plant = "New Name".to_owned();
+        assert!(!old_exists);
+        assert_eq!(result.info.notes, vec!["a note".to_owned()]);
+        assert_eq!(result.activities, vec![log]);
+        assert!(new_dir_exists);
+        assert!(!old_dir_exists)
+    }
+
+    #[test]
+    fn rename_plant_dir_fails() {
+        let db_path = PathBuf::from(RENAME_FAIL_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(RENAME_FAIL_PLANTS_DIR);
+        db.write_plant(quoted_info()).unwrap();
+        create_dir_all(db.plants_dir.join("O'Brien")).unwrap();
+        // the directory can not be renamed to a directory with files
+        create_dir_all(db.plants_dir.join("NewName")).unwrap();
+        write(db.plants_dir.join("NewName").join("image.jpg"), "").unwrap();
+        let result = db.rename_plant("O'Brien", "New Name");
+        let old_exists = db.plant_exists("O'Brien").unwrap();
+        let new_exists = db.plant_exists("New Name").unwrap();
+        drop(db);
+        remove_file(db_path).unwrap();
+        let _ = remove_dir_all(RENAME_FAIL_PLANTS_DIR);
+
+        assert!(result.is_err());
+        assert!(old_exists);
+        assert!(!new_exists)
+    }
+
+    #[test]
+    fn delete_items() {
+        let db_path = PathBuf::from(DELETE_DB);
+        let mut db = SQLiteDB::new(db_path.clone()).unwrap();
+        db.plants_dir = PathBuf::from(DELETE_PLANTS_DIR);
+        let location = Location {
+            name: "Bathroom".to_owned(),
+            outside: false,
+            climate: vec![],
+        };
+        db.write_species(linked_species()).unwrap();
+        db.write_location(location.clone()).unwrap();
+        let mut info = quoted_info();
+        info.species = PlantSpecies::Species(Box::new(linked_species()));
+        info.location = PlantLocation::Location(Box::new(location));
+        db.write_plant(info).unwrap();
+        db.write_log(quoted_log()).unwrap();
+
+        let location_in_use = db.delete_location("Bathroom");
+        let species_in_use = db.delete_species("Fern");
+        db.delete_log(quoted_log()).unwrap();
+        let log_deleted_twice = db.delete_log(quoted_log());
+        let logs = db.get_logs().unwrap();
+        db.rename_plant("O'Brien", "Other").unwrap();
+        db.kill_plant(GraveyardPlant {
+            name: "Other".to_owned(),
+            species: "Fern".to_owned(),
+            planted: NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+            died: NaiveDate::from_ymd_opt(1970, 1, 3).unwrap(),
+            reason: "test".to_owned(),
+        })
+        .unwrap();
+        db.delete_location("Bathroom").unwrap();
+        db.delete_species("Fern").unwrap();
+        let locations = db.get_locations().unwrap();
+        let species = db.get_all_species().unwrap();
+        drop(db);
+        remove_file(db_path).unwrap();
+        let _ = remove_dir_all(DELETE_PLANTS_DIR);
+
+        assert!(location_in_use.is_err());
+        assert!(species_in_use.is_err());
+        assert!(log_deleted_twice.is_err());
+        assert!(logs.is_empty());
+        assert!(locations.is_empty());
+        assert!(species.is_empty())
+    }
 }
@@ -9,6 +9,13 @@ pub enum Error {
     PlantNotFound { name: String },
     SpeciesNotFound { name: String },
     BadValue { key: String, value: String },
+    MigrationFailed { version: i64, msg: String },
+    ForeignKeyViolation { table: String },
+    LogNotFound { plant: String, activity: String },
+    GrowthNotFound { plant: String },
+    PlantExists { name: String },
+    LocationInUse { name: String },
+    SpeciesInUse { name: String },
 }
 
 impl fmt::Display for Error {
@@ -23,6 +30,19 @@ impl fmt::Display for Error {
                 write!(f, "Could not parse value {value} in column {key}")
             }
             Error::SpeciesNotFound { name } => write!(f, "Could not find species {name}"),
+            Error::MigrationFailed { version, msg } => {
+                write!(f, "Could not apply migration {version}, message: {msg}")
+            }
+            Error::ForeignKeyViolation { table } => {
+                write!(f, "Foreign key constraint violated in table {table}")
+            }
+            Error::LogNotFound { plant, activity } => {
+                write!(f, "Could not find activity {activity} for plant {plant}")
+            }
+            Error::GrowthNotFound { plant } => write!(f, "Could not find growth for plant {plant}"),
+            Error::PlantExists { name } => write!(f, "Plant {name} already exists"),
+            Error::LocationInUse { name } => write!(f, "Location {name} still has plants"),
+            Error::SpeciesInUse { name } => write!(f, "Species {name} still has plants"),
         }
     }
 }
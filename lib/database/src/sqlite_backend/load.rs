@@ -0,0 +1,455 @@
+use super::{errors::Error, SQLiteDB};
+use crate::file_backend::json_to_plant::load_images;
+use plants::{
+    climate::LocationClimate,
+    growth_item::GrowthItem,
+    location::Location,
+    log_item::LogItem,
+    named::Named,
+    plant::{
+        plant_image::apply_image_meta, ImageMeta, Plant, PlantImage, PlantInfo, PlantLocation,
+        PlantSpecies,
+    },
+    schedule::ActivitySchedule,
+    species::Species,
+};
+use sqlite::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error as StdErr,
+    fs::read_dir,
+};
+
+pub const PLANT_SELECT: &str = "SELECT name,
+    COALESCE(species,other_species) AS species,
+    COALESCE(location,other_location) AS location,
+    origin,
+    obtained,
+    auto_water";
+pub const PLANT_COLUMNS: [&str; 6] = [
+    "name",
+    "species",
+    "location",
+    "origin",
+    "obtained",
+    "auto_water",
+];
+pub const SPECIES_COLUMNS: [&str; 14] = [
+    "name",
+    "scientific_name",
+    "genus",
+    "family",
+    "sunlight",
+    "temp_min",
+    "temp_max",
+    "temp_min_opt",
+    "temp_max_opt",
+    "planting_distance",
+    "ph_min",
+    "ph_max",
+    "avg_watering_days",
+    "avg_fertilizing_days",
+];
+
+fn group_by_plant<T>(items: Vec<T>, plant: fn(&T) -> &str) -> HashMap<String, Vec<T>> {
+    let mut groups: HashMap<String, Vec<T>> = HashMap::new();
+    for item in items.into_iter() {
+        groups
+            .entry(plant(&item).to_owned())
+            .or_default()
+            .push(item);
+    }
+    groups
+}
+
+impl SQLiteDB {
+    /// Loads all plants matching `filter`, a condition on the plants table with `?` parameters
+    /// The number of queries does not depend on the number of plants
+    pub fn load_plants(
+        &mut self,
+        filter: &str,
+        params: Vec<Value>,
+    ) -> Result<Vec<Plant>, Box<dyn StdErr>> {
+        let info_maps = self.read_rows(
+            &format!("{PLANT_SELECT} FROM plants WHERE {filter}"),
+            params.clone(),
+            PLANT_COLUMNS.to_vec(),
+        )?;
+        let mut infos = vec![];
+        for mut map in info_maps.into_iter() {
+            map.insert("date_format".to_owned(), self.date_format.clone());
+            let info: PlantInfo = map.try_into()?;
+            infos.push(info);
+        }
+        if infos.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let plant_filter = format!("plant IN (SELECT name FROM plants WHERE {filter})");
+        let mut notes = self.get_plant_notes(&plant_filter, params.clone())?;
+        let mut schedules = self.get_schedules("plant", &plant_filter, params.clone())?;
+        let logs = self.load_logs(&plant_filter, params.clone())?;
+        let mut logs = group_by_plant(logs, |log| log.plant.as_str());
+        let growth = self.load_growth(&plant_filter, params.clone())?;
+        let mut growth = group_by_plant(growth, |item| item.plant.as_str());
+        let species: HashMap<String, Species> = self
+            .load_species("1", vec![])?
+            .into_iter()
+            .map(|sp| (sp.name.clone(), sp))
+            .collect();
+        let locations: HashMap<String, Location> = self
+            .load_locations()?
+            .into_iter()
+            .map(|loc| (loc.name.clone(), loc))
+            .collect();
+        let names: Vec<String> = infos.iter().map(|info| info.name.clone()).collect();
+        let mut images = self.load_plant_images(&names)?;
+        let image_meta = self.load_image_meta(&plant_filter, params)?;
+
+        let mut plants = vec![];
+        for mut info in infos.into_iter() {
+            if let Some(sp) = species.get(&info.species.get_name()) {
+                info.species = PlantSpecies::Species(Box::new(sp.clone()));
+            }
+            if let Some(loc) = locations.get(&info.location.get_name()) {
+                info.location = PlantLocation::Location(Box::new(loc.clone()));
+            }
+            info.notes = notes.remove(&info.name).unwrap_or_default();
+            info.schedules = schedules.remove(&info.name).unwrap_or_default();
+            let activities = logs.remove(&info.name).unwrap_or_default();
+            let growth = growth.remove(&info.name).unwrap_or_default();
+            let mut images = images.remove(&info.name).unwrap_or_default();
+            apply_image_meta(&mut images, &info.name, &image_meta);
+            plants.push(Plant {
+                info,
+                growth,
+                activities,
+                images,
+            });
+        }
+        Ok(plants)
+    }
+
+    /// Loads all species matching `filter` including their notes and companions
+    pub fn load_species(
+        &mut self,
+        filter: &str,
+        params: Vec<Value>,
+    ) -> Result<Vec<Species>, Box<dyn StdErr>> {
+        let species_maps = self.read_rows(
+            &format!("SELECT * FROM species WHERE {filter}"),
+            params.clone(),
+            SPECIES_COLUMNS.to_vec(),
+        )?;
+        let mut species = vec![];
+        for species_map in species_maps.into_iter() {
+            let sp: Species = species_map.try_into()?;
+            species.push(sp);
+        }
+        if species.is_empty() {
+            return Ok(species);
+        }
+        let indices: HashMap<String, usize> = species
+            .iter()
+            .enumerate()
+            .map(|(ind, sp)| (sp.name.clone(), ind))
+            .collect();
+        let species_filter = format!("species IN (SELECT name FROM species WHERE {filter})");
+
+        let note_maps = self.read_rows(
+            &format!(
+                "SELECT species, kind, note FROM species_notes
+                WHERE {species_filter} ORDER BY species, kind, position"
+            ),
+            params.clone(),
+            vec!["species", "kind", "note"],
+        )?;
+        for map in note_maps.into_iter() {
+            let lookup_fun = |key: &str| {
+                map.get(key).cloned().ok_or(Error::MissingValue {
+                    key: key.to_owned(),
+                })
+            };
+            let ind = match indices.get(&lookup_fun("species")?) {
+                Some(ind) => *ind,
+                None => continue,
+            };
+            let kind = lookup_fun("kind")?;
+            let note = lookup_fun("note")?;
+            let sp = &mut species[ind];
+            match kind.as_str() {
+                "watering" => sp.watering_notes.push(note),
+                "fertilizing" => sp.fertilizing_notes.push(note),
+                "pruning" => sp.pruning_notes.push(note),
+                "additional" => sp.additional_notes.push(note),
+                _ => {
+                    return Err(Box::new(Error::BadValue {
+                        key: "kind".to_owned(),
+                        value: kind,
+                    }))
+                }
+            }
+        }
+
+        let companion_maps = self.read_rows(
+            &format!(
+                "SELECT species, companion FROM species_companions
+                WHERE {species_filter} ORDER BY species, position"
+            ),
+            params.clone(),
+            vec!["species", "companion"],
+        )?;
+        for map in companion_maps.into_iter() {
+            let lookup_fun = |key: &str| {
+                map.get(key).cloned().ok_or(Error::MissingValue {
+                    key: key.to_owned(),
+                })
+            };
+            if let Some(ind) = indices.get(&lookup_fun("species")?) {
+                species[*ind].companions.push(lookup_fun("companion")?);
+            }
+        }
+
+        let mut schedules = self.get_schedules("species", &species_filter, params)?;
+        for sp in species.iter_mut() {
+            sp.schedules = schedules.remove(&sp.name).unwrap_or_default();
+        }
+        Ok(species)
+    }
+
+    pub fn load_locations(&mut self) -> Result<Vec<Location>, Box<dyn StdErr>> {
+        let location_maps =
+            self.read_rows("SELECT * FROM locations", vec![], vec!["name", "outside"])?;
+        let mut climate = self.get_climate("1", vec![])?;
+        let mut locations = vec![];
+        for map in location_maps.into_iter() {
+            let mut loc: Location = map.try_into()?;
+            loc.climate = climate.remove(&loc.name).unwrap_or_default();
+            locations.push(loc);
+        }
+        Ok(locations)
+    }
+
+    /// Loads the climate of locations matching `filter`, a condition on the location_climate table
+    pub fn get_climate(
+        &mut self,
+        filter: &str,
+        params: Vec<Value>,
+    ) -> Result<HashMap<String, Vec<LocationClimate>>, Error> {
+        let climate_maps = self.read_rows(
+            &format!(
+                "SELECT location, months, temp_min, temp_max FROM location_climate
+                WHERE {filter} ORDER BY location, months"
+            ),
+            params,
+            vec!["location", "months", "temp_min", "temp_max"],
+        )?;
+        let mut climate: HashMap<String, Vec<LocationClimate>> = HashMap::new();
+        for mut map in climate_maps.into_iter() {
+            let mut lookup_fun = |key: &str| {
+                map.remove(key).ok_or(Error::MissingValue {
+                    key: key.to_owned(),
+                })
+            };
+            let name = lookup_fun("location")?;
+            let months_str = lookup_fun("months")?;
+            let min_str = lookup_fun("temp_min")?;
+            let max_str = lookup_fun("temp_max")?;
+            let bad_value = |key: &str, value: &str| Error::BadValue {
+                key: key.to_owned(),
+                value: value.to_owned(),
+            };
+            let months = months_str
+                .split(',')
+                .filter(|month| !month.is_empty())
+                .map(|month| month.parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()
+                .map_err(|_| bad_value("months", &months_str))?;
+            climate.entry(name).or_default().push(LocationClimate {
+                temp_min: min_str
+                    .parse::<f32>()
+                    .map_err(|_| bad_value("temp_min", &min_str))?,
+                temp_max: max_str
+                    .parse::<f32>()
+                    .map_err(|_| bad_value("temp_max", &max_str))?,
+                months,
+            });
+        }
+        Ok(climate)
+    }
+
+    /// Loads all logs matching `filter`, a condition on the activities table
+    pub fn load_logs(
+        &mut self,
+        filter: &str,
+        params: Vec<Value>,
+    ) -> Result<Vec<LogItem>, Box<dyn StdErr>> {
+        let log_maps = self.read_rows(
+            &format!("SELECT * FROM activities WHERE {filter}"),
+            params,
+            vec!["name", "date", "plant", "note", "user"],
+        )?;
+        let mut logs = vec![];
+        for mut map in log_maps.into_iter() {
+            map.insert("date_format".to_owned(), self.date_format.clone());
+            let item: LogItem = map.try_into()?;
+            logs.push(item);
+        }
+        Ok(logs)
+    }
+
+    /// Loads all growth items matching `filter`, a condition on the growth table
+    pub fn load_growth(
+        &mut self,
+        filter: &str,
+        params: Vec<Value>,
+    ) -> Result<Vec<GrowthItem>, Box<dyn StdErr>> {
+        let growth_maps = self.read_rows(
+            &format!("SELECT * FROM growth WHERE {filter}"),
+            params,
+            vec!["plant", "date", "height_cm", "width_cm", "health", "note"],
+        )?;
+        let mut growth = vec![];
+        for mut map in growth_maps.into_iter() {
+            map.insert("date_format".to_owned(), self.date_format.clone());
+            let item: GrowthItem = map.try_into()?;
+            growth.push(item)
+        }
+        Ok(growth)
+    }
+
+    /// Loads notes of all plants matching `filter`, a condition on the plant_notes table
+    pub fn get_plant_notes(
+        &mut self,
+        filter: &str,
+        params: Vec<Value>,
+    ) -> Result<HashMap<String, Vec<String>>, Error> {
+        let note_maps = self.read_rows(
+            &format!("SELECT plant, note FROM plant_notes WHERE {filter} ORDER BY plant, position"),
+            params,
+            vec!["plant", "note"],
+        )?;
+        let mut notes: HashMap<String, Vec<String>> = HashMap::new();
+        for mut map in note_maps.into_iter() {
+            let plant = map.remove("plant").ok_or(Error::MissingValue {
+                key: "plant".to_owned(),
+            })?;
+            let note = map.remove("note").ok_or(Error::MissingValue {
+                key: "note".to_owned(),
+            })?;
+            notes.entry(plant).or_default().push(note);
+        }
+        Ok(notes)
+    }
+
+    /// Loads schedules of plants or species (`owner` is "plant" or "species")
+    /// matching `filter`, a condition on the `{owner}_schedules` table
+    pub fn get_schedules(
+        &mut self,
+        owner: &str,
+        filter: &str,
+        params: Vec<Value>,
+    ) -> Result<HashMap<String, Vec<ActivitySchedule>>, Error> {
+        let schedule_maps = self.read_rows(
+            &format!(
+                "SELECT {owner}, activity, months, interval_days FROM {owner}_schedules
+                WHERE {filter} ORDER BY {owner}, activity, months"
+            ),
+            params,
+            vec![owner, "activity", "months", "interval_days"],
+        )?;
+        let mut schedules: HashMap<String, Vec<ActivitySchedule>> = HashMap::new();
+        for mut map in schedule_maps.into_iter() {
+            let mut lookup_fun = |key: &str| {
+                map.remove(key).ok_or(Error::MissingValue {
+                    key: key.to_owned(),
+                })
+            };
+            let name = lookup_fun(owner)?;
+            let activity = lookup_fun("activity")?;
+            let months_str = lookup_fun("months")?;
+            let days = lookup_fun("interval_days")?;
+            let bad_value = |key: &str, value: &str| Error::BadValue {
+                key: key.to_owned(),
+                value: value.to_owned(),
+            };
+            // null intervals are read as "", meaning the activity is not scheduled in these months
+            let interval_days = if days.is_empty() {
+                None
+            } else {
+                Some(
+                    days.parse::<i32>()
+                        .map_err(|_| bad_value("interval_days", &days))?,
+                )
+            };
+            let months = months_str
+                .split(',')
+                .filter(|month| !month.is_empty())
+                .map(|month| month.parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()
+                .map_err(|_| bad_value("months", &months_str))?;
+            schedules.entry(name).or_default().push(ActivitySchedule {
+                activity,
+                interval_days,
+                months,
+            });
+        }
+        Ok(schedules)
+    }
+
+    /// Loads images of the given plants, scanning only directories that exist
+    pub fn load_plant_images(
+        &self,
+        names: &[String],
+    ) -> Result<HashMap<String, Vec<PlantImage>>, Box<dyn StdErr>> {
+        let mut images = HashMap::new();
+        if !self.plants_dir.exists() {
+            return Ok(images);
+        }
+        let dirs: HashSet<String> = read_dir(&self.plants_dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        for name in names.iter() {
+            let dir_name = name.replace(' ', "");
+            if dirs.contains(&dir_name) {
+                let plant_images = load_images(&self.plants_dir.join(dir_name))?;
+                images.insert(name.clone(), plant_images);
+            }
+        }
+        Ok(images)
+    }
+
+    /// Loads captions and cover flags of images of plants matching `filter`
+    pub fn load_image_meta(
+        &mut self,
+        filter: &str,
+        params: Vec<Value>,
+    ) -> Result<Vec<ImageMeta>, Error> {
+        let meta_maps = self.read_rows(
+            &format!("SELECT plant, file_name, caption, cover FROM image_metadata WHERE {filter}"),
+            params,
+            vec!["plant", "file_name", "caption", "cover"],
+        )?;
+        let mut metas = vec![];
+        for mut map in meta_maps.into_iter() {
+            let mut take = |key: &str| {
+                map.remove(key).ok_or(Error::MissingValue {
+                    key: key.to_owned(),
+                })
+            };
+            let plant = take("plant")?;
+            let file_name = take("file_name")?;
+            let caption = take("caption")?;
+            let cover = take("cover")?;
+            metas.push(ImageMeta {
+                plant,
+                file_name,
+                caption: Some(caption).filter(|caption| !caption.is_empty()),
+                cover: cover == "1",
+            });
+        }
+        Ok(metas)
+    }
+}
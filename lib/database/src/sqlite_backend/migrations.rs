@@ -0,0 +1,324 @@
+use super::errors::Error;
+use chrono::Local;
+use sqlite::{Connection, State, Value};
+
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// All schema migrations, ordered by version
+/// New migrations have to be appended with a higher version than the last one
+pub const MIGRATIONS: [Migration; 7] = [
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        sql: include_str!("../../migrations/001_initial_schema.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "typed_schema",
+        sql: include_str!("../../migrations/002_typed_schema.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "activity_schedules",
+        sql: include_str!("../../migrations/003_activity_schedules.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "seasonal_schedules",
+        sql: include_str!("../../migrations/004_seasonal_schedules.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "location_climate",
+        sql: include_str!("../../migrations/005_location_climate.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "activity_user",
+        sql: include_str!("../../migrations/006_activity_user.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "image_metadata",
+        sql: include_str!("../../migrations/007_image_metadata.sql"),
+    },
+];
+
+const CREATE_VERSION_TABLE: &str = "CREATE TABLE IF NOT EXISTS schema_version (
+    version integer primary key,
+    name text not null,
+    applied text not null
+);";
+
+pub fn latest_version() -> i64 {
+    MIGRATIONS.iter().map(|mig| mig.version).max().unwrap_or(0)
+}
+
+pub fn current_version(con: &Connection) -> Result<i64, Error> {
+    con.execute(CREATE_VERSION_TABLE)?;
+    let mut statement = con.prepare("SELECT MAX(version) AS version FROM schema_version")?;
+    let mut version = 0;
+    while statement.next()? == State::Row {
+        version = statement.read::<Option<i64>, _>("version")?.unwrap_or(0);
+    }
+    Ok(version)
+}
+
+/// Makes sure no migration left rows referencing missing rows
+/// foreign keys are not enforced while migrating, so this has to be checked manually
+fn check_foreign_keys(con: &Connection) -> Result<(), Error> {
+    let mut statement = con.prepare("PRAGMA foreign_key_check;")?;
+    if statement.next()? == State::Row {
+        let table = statement.read::<String, _>(0)?;
+        return Err(Error::ForeignKeyViolation { table });
+    }
+    Ok(())
+}
+
+fn apply_migration(con: &Connection, migration: &Migration) -> Result<(), Error> {
+    con.execute(migration.sql)?;
+    check_foreign_keys(con)?;
+    let mut statement =
+        con.prepare("INSERT INTO schema_version (version,name,applied) VALUES (?,?,?)")?;
+    statement.bind_iter([
+        (1, Value::Integer(migration.version)),
+        (2, Value::String(migration.name.to_owned())),
+        (3, Value::String(Local::now().naive_local().to_string())),
+    ])?;
+    while statement.next()? != State::Done {}
+    Ok(())
+}
+
+pub fn run_migrations(con: &Connection) -> Result<(), Error> {
+    let version = current_version(con)?;
+    for migration in MIGRATIONS.iter().filter(|mig| mig.version > version) {
+        log::info!(
+            "Applying database migration {} ({})",
+            migration.version,
+            migration.name
+        );
+        con.execute("BEGIN TRANSACTION;")?;
+        match apply_migration(con, migration) {
+            Ok(()) => con.execute("COMMIT;")?,
+            Err(err) => {
+                con.execute("ROLLBACK;")?;
+                return Err(Error::MigrationFailed {
+                    version: migration.version,
+                    msg: err.to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod migration_tests {
+    use super::{apply_migration, current_version, latest_version, run_migrations, MIGRATIONS};
+    use sqlite::State;
+    use std::{fs::remove_file, path::PathBuf};
+
+    const MIGRATIONS_DB: &str = "../../testing/migrations_test.db";
+    const MIGRATIONS_TWICE_DB: &str = "../../testing/migrations_twice_test.db";
+    const MIGRATIONS_TYPED_DB: &str = "../../testing/migrations_typed_test.db";
+    const MIGRATIONS_DATES_DB: &str = "../../testing/migrations_dates_test.db";
+    const MIGRATIONS_NULL_DB: &str = "../../testing/migrations_null_test.db";
+
+    #[test]
+    fn versions_ordered() {
+        let versions: Vec<i64> = MIGRATIONS.iter().map(|mig| mig.version).collect();
+        let mut sorted = versions.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(versions, sorted)
+    }
+
+    #[test]
+    fn migrate_empty() {
+        let db_path = PathBuf::from(MIGRATIONS_DB);
+        let con = sqlite::open(db_path.clone()).unwrap();
+        assert_eq!(current_version(&con).unwrap(), 0);
+        run_migrations(&con).unwrap();
+        let result = current_version(&con).unwrap();
+        drop(con);
+        remove_file(db_path).unwrap();
+        assert_eq!(result, latest_version())
+    }
+
+    #[test]
+    fn migrate_twice() {
+        let db_path = PathBuf::from(MIGRATIONS_TWICE_DB);
+        let con = sqlite::open(db_path.clone()).unwrap();
+        run_migrations(&con).unwrap();
+        let result = run_migrations(&con);
+        let version = current_version(&con).unwrap();
+        drop(con);
+        remove_file(db_path).unwrap();
+        assert!(result.is_ok());
+        assert_eq!(version, latest_version())
+    }
+
+    #[test]
+    fn migrate_typed_schema() {
+        let db_path = PathBuf::from(MIGRATIONS_TYPED_DB);
+        let con = sqlite::open(db_path.clone()).unwrap();
+        current_version(&con).unwrap();
+        apply_migration(&con, &MIGRATIONS[0]).unwrap();
+        con.execute(
+            "INSERT INTO locations VALUES ('Kitchen','false');
+            INSERT INTO plants VALUES ('Plant1','Unknown','Kitchen','Shop','01.02.2024','true','note1, note2');
+            INSERT INTO plants VALUES ('Plant2','Unknown','Garden','Shop','03.04.2024','false',NULL);
+            INSERT INTO activities VALUES ('Watering','05.06.2024','Plant1',NULL);
+            INSERT INTO activities VALUES ('Watering','05.06.2024','Dead Plant',NULL);",
+        )
+        .unwrap();
+        run_migrations(&con).unwrap();
+
+        let mut rows = vec![];
+        let mut statement = con
+            .prepare(
+                "SELECT obtained, location, other_location, auto_water,
+                (SELECT COUNT(*) FROM plant_notes WHERE plant=plants.name) AS notes
+                FROM plants ORDER BY name",
+            )
+            .unwrap();
+        while statement.next().unwrap() == State::Row {
+            rows.push((
+                statement.read::<String, _>("obtained").unwrap(),
+                statement.read::<Option<String>, _>("location").unwrap(),
+                statement
+                    .read::<Option<String>, _>("other_location")
+                    .unwrap(),
+                statement.read::<i64, _>("auto_water").unwrap(),
+                statement.read::<i64, _>("notes").unwrap(),
+            ));
+        }
+        drop(statement);
+
+        let mut statement = con
+            .prepare(
+                "SELECT (SELECT COUNT(*) FROM activities) AS num,
+                (SELECT COUNT(*) FROM archived_activities) AS archived",
+            )
+            .unwrap();
+        statement.next().unwrap();
+        let num_activities = statement.read::<i64, _>("num").unwrap();
+        let num_archived = statement.read::<i64, _>("archived").unwrap();
+        drop(statement);
+        drop(con);
+        remove_file(db_path).unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    "2024-02-01".to_owned(),
+                    Some("Kitchen".to_owned()),
+                    None,
+                    1,
+                    2
+                ),
+                (
+                    "2024-04-03".to_owned(),
+                    None,
+                    Some("Garden".to_owned()),
+                    0,
+                    0
+                )
+            ]
+        );
+        assert_eq!(num_activities, 1);
+        assert_eq!(num_archived, 1)
+    }
+
+    #[test]
+    fn migrate_bad_dates() {
+        let db_path = PathBuf::from(MIGRATIONS_DATES_DB);
+        let con = sqlite::open(db_path.clone()).unwrap();
+        current_version(&con).unwrap();
+        apply_migration(&con, &MIGRATIONS[0]).unwrap();
+        con.execute(
+            "INSERT INTO plants VALUES ('Plant1','Unknown','Kitchen','Shop','01.02.2024','true','note1');
+            INSERT INTO plants VALUES ('Plant2','Unknown','Kitchen','Shop','2024-04-03','false','note2');
+            INSERT INTO activities VALUES ('Watering','05.06.2024','Plant2',NULL);
+            INSERT INTO graveyard VALUES ('Plant3','Unknown','01.01.2020','02.01.2021','frost');
+            INSERT INTO graveyard VALUES ('Plant4','Unknown','1.1.2020','02.01.2021','frost');",
+        )
+        .unwrap();
+        let result = run_migrations(&con);
+
+        let mut statement = con
+            .prepare(
+                "SELECT (SELECT COUNT(*) FROM plants) AS plants,
+                (SELECT COUNT(*) FROM archived_plants) AS archived_plants,
+                (SELECT COUNT(*) FROM archived_activities) AS archived_activities,
+                (SELECT COUNT(*) FROM graveyard) AS graveyard,
+                (SELECT COUNT(*) FROM archived_graveyard) AS archived_graveyard",
+            )
+            .unwrap();
+        statement.next().unwrap();
+        let counts: Vec<i64> = [
+            "plants",
+            "archived_plants",
+            "archived_activities",
+            "graveyard",
+            "archived_graveyard",
+        ]
+        .iter()
+        .map(|column| statement.read::<i64, _>(*column).unwrap())
+        .collect();
+        drop(statement);
+        drop(con);
+        remove_file(db_path).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(counts, vec![1, 1, 1, 1, 1])
+    }
+
+    #[test]
+    fn migrate_null_logs() {
+        let db_path = PathBuf::from(MIGRATIONS_NULL_DB);
+        let con = sqlite::open(db_path.clone()).unwrap();
+        current_version(&con).unwrap();
+        apply_migration(&con, &MIGRATIONS[0]).unwrap();
+        con.execute(
+            "INSERT INTO plants VALUES ('Plant1','Unknown','Kitchen','Shop','01.02.2024','true','note1');
+            INSERT INTO activities VALUES ('Watering','05.06.2024','Plant1',NULL);
+            INSERT INTO activities VALUES ('Watering',NULL,'Plant1',NULL);
+            INSERT INTO growth VALUES ('Plant1','05.06.2024',10.0,10.0,NULL,3);
+            INSERT INTO growth VALUES (NULL,'05.06.2024',10.0,10.0,NULL,3);
+            INSERT INTO growth VALUES ('Plant1',NULL,10.0,10.0,NULL,3);",
+        )
+        .unwrap();
+        let result = run_migrations(&con);
+
+        let mut statement = con
+            .prepare(
+                "SELECT (SELECT COUNT(*) FROM activities) AS activities,
+                (SELECT COUNT(*) FROM archived_activities) AS archived_activities,
+                (SELECT COUNT(*) FROM growth) AS growth,
+                (SELECT COUNT(*) FROM archived_growth) AS archived_growth",
+            )
+            .unwrap();
+        statement.next().unwrap();
+        let counts: Vec<i64> = [
+            "activities",
+            "archived_activities",
+            "growth",
+            "archived_growth",
+        ]
+        .iter()
+        .map(|column| statement.read::<i64, _>(*column).unwrap())
+        .collect();
+        drop(statement);
+        drop(con);
+        remove_file(db_path).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(counts, vec![1, 1, 1, 2])
+    }
+}
@@ -1,10 +1,16 @@
-use plants::{graveyard::GraveyardPlant, growth_item::GrowthItem, log_item::LogItem};
-use sqlite::Connection;
+use plants::{
+    climate::LocationClimate, graveyard::GraveyardPlant, schedule::ActivitySchedule,
+    species::Species,
+};
+use sqlite::{Connection, State, Value};
 use std::{collections::HashMap, path::PathBuf};
 
 pub mod db_man;
 pub mod errors;
+pub mod load;
+pub mod migrations;
 use errors::Error;
+use migrations::run_migrations;
 
 pub struct SQLiteDB {
     pub db_path: PathBuf,
@@ -16,94 +22,214 @@ pub struct SQLiteDB {
 impl SQLiteDB {
     pub fn new(path: PathBuf) -> Result<SQLiteDB, Error> {
         let con = sqlite::open(path.clone())?;
+        run_migrations(&con)?;
+        con.execute("PRAGMA foreign_keys = ON;")?;
         Ok(SQLiteDB {
             db_path: path,
             connection: con,
-            date_format: "%d.%m.%Y".to_owned(),
+            date_format: "%Y-%m-%d".to_owned(),
             plants_dir: PathBuf::from("data").join("Plants"),
         })
     }
 
+    /// Runs a query with bound parameters (`?` in the query)
+    /// and returns all rows as maps from column name to value
+    /// only columns contained in `column_keys` are included, null values are read as ""
     pub fn read_rows(
         &mut self,
         query: &str,
+        params: Vec<Value>,
         column_keys: Vec<&str>,
     ) -> Result<Vec<HashMap<String, String>>, Error> {
+        let mut statement = self.connection.prepare(query)?;
+        statement.bind_iter(
+            params
+                .into_iter()
+                .enumerate()
+                .map(|(ind, val)| (ind + 1, val)),
+        )?;
+        let column_names = statement.column_names().to_vec();
+
         let mut maps = vec![];
-        let callback = |cols: &[(&str, Option<&str>)]| {
+        while statement.next()? == State::Row {
             let mut map = HashMap::new();
-            for (key, val) in cols.into_iter() {
-                let value = if let Some(val) = val { val } else { "" };
-                if column_keys.contains(key) {
-                    map.insert(format!("{}", key), format!("{}", value));
+            for (ind, key) in column_names.iter().enumerate() {
+                if !column_keys.contains(&key.as_str()) {
+                    continue;
                 }
+                let value = statement.read::<Option<String>, _>(ind)?;
+                map.insert(key.clone(), value.unwrap_or_default());
             }
             maps.push(map);
-            true
-        };
-        self.connection.iterate(query, callback)?;
+        }
         Ok(maps)
     }
 
-    pub fn get_growth_plant(
-        &mut self,
-        plant_name: &str,
-    ) -> Result<Vec<GrowthItem>, Box<dyn std::error::Error>> {
-        let growth_query = format!("SELECT * FROM growth WHERE plant='{}'", plant_name);
-        let growth_maps = self.read_rows(
-            &growth_query,
-            vec!["plant", "date", "height_cm", "width_cm", "health", "note"],
+    /// Runs a query with bound parameters (`?` in the query) that does not return any rows
+    pub fn execute(&mut self, query: &str, params: Vec<Value>) -> Result<(), Error> {
+        let mut statement = self.connection.prepare(query)?;
+        statement.bind_iter(
+            params
+                .into_iter()
+                .enumerate()
+                .map(|(ind, val)| (ind + 1, val)),
         )?;
-        let mut growth = vec![];
-        for mut map in growth_maps.into_iter() {
-            map.insert("date_format".to_owned(), self.date_format.clone());
-            let item: GrowthItem = map.try_into()?;
-            growth.push(item)
-        }
-        Ok(growth)
+        while statement.next()? != State::Done {}
+        Ok(())
     }
 
-    pub fn get_logs_plant(
+    /// Runs a query returning a single count in the column `num`
+    pub fn count_rows(
         &mut self,
-        plant_name: &str,
-    ) -> Result<Vec<LogItem>, Box<dyn std::error::Error>> {
-        let log_query = format!("SELECT * FROM activities WHERE plant='{}'", plant_name);
-        let log_maps = self.read_rows(&log_query, vec!["name", "date", "plant", "note"])?;
-
-        let mut logs = vec![];
-        for mut map in log_maps.into_iter() {
-            map.insert("date_format".to_owned(), self.date_format.clone());
-            let item: LogItem = map.try_into()?;
-            logs.push(item);
-        }
-        Ok(logs)
+        query: &str,
+        params: Vec<Value>,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let count_maps = self.read_rows(query, params, vec!["num"])?;
+        let num = count_maps
+            .first()
+            .and_then(|map| map.get("num"))
+            .ok_or(Error::MissingValue {
+                key: "num".to_owned(),
+            })?;
+        Ok(num.parse::<usize>()?)
     }
 
     pub fn add_to_graveyard(
         &mut self,
         plant: GraveyardPlant,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let fmt_plant = |plant: &GraveyardPlant| {
-            format!(
-                "('{}','{}','{}','{}','{}')",
-                self.sanitize(&plant.name),
-                self.sanitize(&plant.species),
-                plant.planted.format(&self.date_format),
-                plant.died.format(&self.date_format),
-                self.sanitize(&plant.reason)
-            )
-        };
-        let fields = "(name,species,planted,died,reason)";
-        let mut graveyard_query = "INSERT INTO graveyard ".to_owned();
-        graveyard_query += fields;
-        graveyard_query += " VALUES ";
-        graveyard_query += &fmt_plant(&plant);
-        graveyard_query += ";";
-        self.connection.execute(graveyard_query)?;
+        let params = vec![
+            plant.name.into(),
+            plant.species.into(),
+            plant.planted.format(&self.date_format).to_string().into(),
+            plant.died.format(&self.date_format).to_string().into(),
+            plant.reason.into(),
+        ];
+        self.execute(
+            "INSERT INTO graveyard (name,species,planted,died,reason) VALUES (?,?,?,?,?);",
+            params,
+        )?;
+        Ok(())
+    }
+
+    /// Replaces all notes of a plant
+    pub fn write_plant_notes(&mut self, plant_name: &str, notes: Vec<String>) -> Result<(), Error> {
+        self.execute(
+            "DELETE FROM plant_notes WHERE plant=?",
+            vec![plant_name.into()],
+        )?;
+        for (position, note) in notes.into_iter().enumerate() {
+            self.execute(
+                "INSERT INTO plant_notes (plant,position,note) VALUES (?,?,?)",
+                vec![plant_name.into(), (position as i64).into(), note.into()],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Replaces all notes and companions of a species
+    pub fn write_species_lists(&mut self, species: &Species) -> Result<(), Error> {
+        let name = species.name.as_str();
+        self.execute(
+            "DELETE FROM species_notes WHERE species=?",
+            vec![name.into()],
+        )?;
+        self.execute(
+            "DELETE FROM species_companions WHERE species=?",
+            vec![name.into()],
+        )?;
+
+        let kinds = [
+            ("watering", &species.watering_notes),
+            ("fertilizing", &species.fertilizing_notes),
+            ("pruning", &species.pruning_notes),
+            ("additional", &species.additional_notes),
+        ];
+        for (kind, notes) in kinds {
+            for (position, note) in notes.iter().enumerate() {
+                self.execute(
+                    "INSERT INTO species_notes (species,kind,position,note) VALUES (?,?,?,?)",
+                    vec![
+                        name.into(),
+                        kind.into(),
+                        (position as i64).into(),
+                        note.as_str().into(),
+                    ],
+                )?;
+            }
+        }
+
+        for (position, companion) in species.companions.iter().enumerate() {
+            self.execute(
+                "INSERT INTO species_companions (species,position,companion) VALUES (?,?,?)",
+                vec![
+                    name.into(),
+                    (position as i64).into(),
+                    companion.as_str().into(),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Replaces all schedules of a plant or species (`owner` is "plant" or "species")
+    pub fn write_schedules(
+        &mut self,
+        owner: &str,
+        name: &str,
+        schedules: &[ActivitySchedule],
+    ) -> Result<(), Error> {
+        self.execute(
+            &format!("DELETE FROM {owner}_schedules WHERE {owner}=?"),
+            vec![name.into()],
+        )?;
+        for schedule in schedules.iter() {
+            let months: Vec<String> = schedule
+                .months
+                .iter()
+                .map(|month| month.to_string())
+                .collect();
+            self.execute(
+                &format!(
+                    "INSERT INTO {owner}_schedules ({owner},activity,months,interval_days) VALUES (?,?,?,?)"
+                ),
+                vec![
+                    name.into(),
+                    schedule.activity.as_str().into(),
+                    months.join(",").into(),
+                    SQLiteDB::optional_value(schedule.interval_days.map(|days| days as i64)),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the expected climate of a location
+    pub fn write_climate(
+        &mut self,
+        location: &str,
+        climate: &[LocationClimate],
+    ) -> Result<(), Error> {
+        self.execute(
+            "DELETE FROM location_climate WHERE location=?",
+            vec![location.into()],
+        )?;
+        for clim in climate.iter() {
+            let months: Vec<String> = clim.months.iter().map(|month| month.to_string()).collect();
+            self.execute(
+                "INSERT INTO location_climate (location,months,temp_min,temp_max) VALUES (?,?,?,?)",
+                vec![
+                    location.into(),
+                    months.join(",").into(),
+                    (clim.temp_min as f64).into(),
+                    (clim.temp_max as f64).into(),
+                ],
+            )?;
+        }
         Ok(())
     }
 
-    pub fn sanitize<T: ToString>(&self, input: &T) -> String {
-        input.to_string().replace('\'', "''")
+    pub fn optional_value<T: Into<Value>>(value: Option<T>) -> Value {
+        value.map(|val| val.into()).unwrap_or(Value::Null)
     }
 }
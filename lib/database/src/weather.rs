@@ -0,0 +1,81 @@
+use super::file_backend::load_csv::load_csv;
+use chrono::NaiveDate;
+use plants::weather::WeatherRecord;
+use std::{error::Error, path::PathBuf};
+
+pub trait WeatherProvider {
+    /// Weather records from `from` up to and including `to`, sorted by date
+    fn get_weather(
+        &mut self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<WeatherRecord>, Box<dyn Error>>;
+}
+
+/// Weather records from a local csv file, e.g. exported from a weather station
+/// with columns date;rain_mm;temp_min;temp_max
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvWeather {
+    pub weather_file: PathBuf,
+}
+
+impl WeatherProvider for CsvWeather {
+    fn get_weather(
+        &mut self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<WeatherRecord>, Box<dyn Error>> {
+        let mut records: Vec<WeatherRecord> = load_csv(&self.weather_file)?;
+        records.retain(|record| from <= record.date && record.date <= to);
+        records.sort_by_key(|record| record.date);
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod weather_tests {
+    use super::{CsvWeather, WeatherProvider};
+    use crate::file_backend::test_common::FILE_DOES_NOT_EXIST;
+    use chrono::NaiveDate;
+    use plants::weather::WeatherRecord;
+    use std::path::PathBuf;
+
+    const WEATHER_DUMMY: &str = "../../testing/Weather.csv";
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 11, day).unwrap()
+    }
+
+    #[test]
+    fn csv_weather() {
+        let result = CsvWeather {
+            weather_file: PathBuf::from(WEATHER_DUMMY),
+        }
+        .get_weather(date(2), date(3))
+        .unwrap();
+        let expected = vec![
+            WeatherRecord {
+                date: date(2),
+                rain_mm: 0.0,
+                temp_min: -2.5,
+                temp_max: 4.0,
+            },
+            WeatherRecord {
+                date: date(3),
+                rain_mm: 7.2,
+                temp_min: 3.0,
+                temp_max: 9.5,
+            },
+        ];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn csv_weather_missing() {
+        let result = CsvWeather {
+            weather_file: PathBuf::from(FILE_DOES_NOT_EXIST),
+        }
+        .get_weather(date(1), date(3));
+        assert!(result.is_err())
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    PlantDoesNotExist(String),
+    LocationDoesNotExist(String),
+    SpeciesDoesNotExist(String),
+    ParseError(String),
+    BadHealth(i32),
+    DB(Box<dyn std::error::Error>),
+}
+
+impl std::error::Error for Error {}
+
+impl From<Box<dyn std::error::Error>> for Error {
+    fn from(err: Box<dyn std::error::Error>) -> Error {
+        Error::DB(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::PlantDoesNotExist(plant) => write!(frmt, "Plant {plant} does not exist"),
+            Error::LocationDoesNotExist(loc) => write!(frmt, "Location {loc} does not exist"),
+            Error::SpeciesDoesNotExist(name) => write!(frmt, "Species {name} does not exist"),
+            Error::ParseError(msg) => write!(frmt, "Could not parse {msg}"),
+            Error::BadHealth(health) => {
+                write!(frmt, "{health} is not a valid value for plant health")
+            }
+            Error::DB(err) => err.fmt(frmt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::Error;
+
+    #[test]
+    fn display_bad_health() {
+        let result = format!("{}", Error::BadHealth(6));
+        let expected = "6 is not a valid value for plant health";
+        assert_eq!(result, expected)
+    }
+}
@@ -1,9 +1,13 @@
-use crate::errors::Error;
+//! Validation of user input against the database, shared by the bot and the web forms
+pub mod errors;
+
 use chrono::NaiveDate;
 use database::database_manager::DatabaseManager;
+use errors::Error;
 use plants::{
     plant::{PlantLocation, PlantSpecies},
     plant_update::{UpdateField, UpdateValue},
+    schedule::parse_schedules,
 };
 
 pub fn input_plant_name<T: DatabaseManager>(
@@ -111,6 +115,9 @@ pub fn str_to_value<T: DatabaseManager>(
     } else if UpdateField::get_note_fields().contains(field) {
         let notes = input.split(',').map(|nt| nt.trim().to_owned()).collect();
         Ok(UpdateValue::Note(notes, true))
+    } else if UpdateField::get_schedule_fields().contains(field) {
+        let schedules = parse_schedules(&input).map_err(|_| ty_err)?;
+        Ok(UpdateValue::Schedules(schedules))
     } else {
         Err(ty_err)
     }
@@ -120,35 +127,59 @@ pub fn str_to_value<T: DatabaseManager>(
 mod input_handlers_tests {
 
     use super::{
-        input_health, input_notes, input_plant_name, input_plant_names, input_species,
-        str_to_value, PlantSpecies, UpdateField, UpdateValue,
+        input_health, input_location, input_notes, input_plant_name, input_plant_names,
+        input_species, str_to_value, PlantSpecies, UpdateField, UpdateValue,
     };
-    use crate::test_common::{example_species, DummyManager};
     use chrono::NaiveDate;
+    use database::{database_manager::DatabaseManager, file_backend::FileDB};
+    use std::path::PathBuf;
+
+    fn dummy_db() -> FileDB {
+        FileDB {
+            plants_dir: PathBuf::from("../../testing/plants/"),
+            species_dir: PathBuf::from("../../testing/species/"),
+            location_file: PathBuf::from("../../testing/Locations.csv"),
+            logs_dir: PathBuf::from("../../testing/Logs/"),
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn input_plant() {
-        let result = input_plant_name("Plant1".to_owned(), &mut DummyManager {}).unwrap();
-        let expected = "Plant1";
+        let result = input_plant_name("Dummy1".to_owned(), &mut dummy_db()).unwrap();
+        let expected = "Dummy1";
         assert_eq!(result, expected)
     }
 
     #[test]
     fn input_plant_err() {
-        let result = input_plant_name("not a plant".to_owned(), &mut DummyManager {});
+        let result = input_plant_name("not a plant".to_owned(), &mut dummy_db());
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn input_loc() {
+        let result = input_location("test location".to_owned(), &mut dummy_db()).unwrap();
+        let expected = "test location";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn input_loc_err() {
+        let result = input_location("not a location".to_owned(), &mut dummy_db());
         assert!(result.is_err())
     }
 
     #[test]
     fn input_names() {
-        let result = input_plant_names("Plant1,Plant2".to_owned(), &mut DummyManager {}).unwrap();
-        let expected = vec!["Plant1".to_owned(), "Plant2".to_owned()];
+        let result = input_plant_names("Dummy1,Dummy2".to_owned(), &mut dummy_db()).unwrap();
+        let expected = vec!["Dummy1".to_owned(), "Dummy2".to_owned()];
         assert_eq!(result, expected)
     }
 
     #[test]
     fn input_names_fail() {
-        let result = input_plant_names("Plant1, not a plant".to_owned(), &mut DummyManager {});
+        let result = input_plant_names("Dummy1, not a plant".to_owned(), &mut dummy_db());
         assert!(result.is_err())
     }
 
@@ -167,14 +198,14 @@ mod input_handlers_tests {
 
     #[test]
     fn input_sp() {
-        let result = input_species("Species1".to_owned(), &mut DummyManager {}).unwrap();
-        let expected = "Species1".to_owned();
+        let result = input_species("test species".to_owned(), &mut dummy_db()).unwrap();
+        let expected = "test species".to_owned();
         assert_eq!(result, expected)
     }
 
     #[test]
     fn input_species_fail() {
-        let result = input_species("not a species".to_owned(), &mut DummyManager {});
+        let result = input_species("not a species".to_owned(), &mut dummy_db());
         assert!(result.is_err())
     }
 
@@ -190,7 +221,7 @@ mod input_handlers_tests {
         let result = str_to_value(
             "string".to_owned(),
             &UpdateField::Origin,
-            &mut DummyManager {},
+            &mut dummy_db(),
             "%d.%m.%Y",
         )
         .unwrap();
@@ -201,13 +232,14 @@ mod input_handlers_tests {
     #[test]
     fn input_value_species() {
         let result = str_to_value(
-            "Species1".to_owned(),
+            "test species".to_owned(),
             &UpdateField::Species,
-            &mut DummyManager {},
+            &mut dummy_db(),
             "%d.%m.%Y",
         )
         .unwrap();
-        let expected = UpdateValue::Species(PlantSpecies::Species(Box::new(example_species())));
+        let species = dummy_db().get_species("test species").unwrap();
+        let expected = UpdateValue::Species(PlantSpecies::Species(Box::new(species)));
         assert_eq!(result, expected)
     }
 
@@ -216,7 +248,7 @@ mod input_handlers_tests {
         let result = str_to_value(
             "not a species".to_owned(),
             &UpdateField::Species,
-            &mut DummyManager {},
+            &mut dummy_db(),
             "%d.%m.%Y",
         )
         .unwrap();
@@ -229,7 +261,7 @@ mod input_handlers_tests {
         let result = str_to_value(
             "01.01.1970".to_owned(),
             &UpdateField::Obtained,
-            &mut DummyManager {},
+            &mut dummy_db(),
             "%d.%m.%Y",
         )
         .unwrap();
@@ -243,7 +275,7 @@ mod input_handlers_tests {
         let result = str_to_value(
             "not a date".to_owned(),
             &UpdateField::Obtained,
-            &mut DummyManager {},
+            &mut dummy_db(),
             "%d.%m.%Y",
         );
         assert!(result.is_err())
@@ -254,7 +286,7 @@ mod input_handlers_tests {
         let result = str_to_value(
             "true".to_owned(),
             &UpdateField::AutoWater,
-            &mut DummyManager {},
+            &mut dummy_db(),
             "%d.%m.%Y",
         )
         .unwrap();
@@ -267,7 +299,7 @@ mod input_handlers_tests {
         let result = str_to_value(
             "not a bool".to_owned(),
             &UpdateField::AutoWater,
-            &mut DummyManager {},
+            &mut dummy_db(),
             "%d.%m.%Y",
         );
         assert!(result.is_err())
@@ -278,11 +310,24 @@ mod input_handlers_tests {
         let result = str_to_value(
             "note1,note2".to_owned(),
             &UpdateField::Notes,
-            &mut DummyManager {},
+            &mut dummy_db(),
             "%d.%m.%Y",
         )
         .unwrap();
         let expected = UpdateValue::Note(vec!["note1".to_owned(), "note2".to_owned()], true);
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn input_value_schedules() {
+        let result = str_to_value(
+            "Repotting: 365".to_owned(),
+            &UpdateField::Schedules,
+            &mut dummy_db(),
+            "%d.%m.%Y",
+        )
+        .unwrap();
+        let expected = UpdateValue::Schedules(vec!["Repotting: 365".parse().unwrap()]);
+        assert_eq!(result, expected)
+    }
 }
@@ -0,0 +1,127 @@
+use crate::page::CssComponent;
+use html::css::{
+    block::CssBlock,
+    property::{Border, Color, Direction, Font, Padding, Property, Size},
+    selector::TopSelector,
+    value::{Keyword, Unit, Value},
+    CssDocument,
+};
+use log;
+
+pub struct Forms {}
+
+impl CssComponent for Forms {
+    fn render(&self) -> CssDocument {
+        log::info!("Loading Forms CSS");
+        let form = CssBlock {
+            selector: TopSelector::Class("form".to_owned()).into(),
+            decls: vec![
+                (
+                    Color::Background.into(),
+                    Value::Var("bg-color-even".to_owned()),
+                )
+                    .into(),
+                (Border::Radius.into(), (1.0, Unit::Em).into()).into(),
+                (
+                    Padding {
+                        dir: Direction::All,
+                    }
+                    .into(),
+                    (1.0, Unit::Em).into(),
+                )
+                    .into(),
+                (Size::Width.into(), (20.0, Unit::Em).into()).into(),
+            ],
+        };
+
+        let form_header = CssBlock {
+            selector: TopSelector::Class("form_header".to_owned()).into(),
+            decls: vec![
+                (Property::TextAlign, Keyword::Center.into()).into(),
+                (Font::Weight.into(), Keyword::Bold.into()).into(),
+                (Font::Size.into(), (14.0, Unit::Pt).into()).into(),
+            ],
+        };
+
+        let form_submit = CssBlock {
+            selector: TopSelector::Class("form_submit".to_owned()).into(),
+            decls: vec![
+                (
+                    Color::Background.into(),
+                    Value::Var("bg-color-odd".to_owned()),
+                )
+                    .into(),
+                (Border::Radius.into(), (0.5, Unit::Em).into()).into(),
+                (Property::TextAlign, Keyword::Center.into()).into(),
+                (Property::Cursor, Keyword::Pointer.into()).into(),
+            ],
+        };
+
+        CssDocument {
+            decls: vec![form, form_header, form_submit],
+        }
+    }
+}
+
+#[cfg(test)]
+mod forms_css_tests {
+    use super::{CssComponent, Forms};
+    use html::css::{
+        block::CssBlock,
+        property::{Border, Color, Direction, Font, Padding, Property, Size},
+        selector::TopSelector,
+        value::{Keyword, Unit, Value},
+        CssDocument,
+    };
+
+    #[test]
+    fn render_forms() {
+        let result = Forms {}.render();
+        let expected = CssDocument {
+            decls: vec![
+                CssBlock {
+                    selector: TopSelector::Class("form".to_owned()).into(),
+                    decls: vec![
+                        (
+                            Color::Background.into(),
+                            Value::Var("bg-color-even".to_owned()),
+                        )
+                            .into(),
+                        (Border::Radius.into(), (1.0, Unit::Em).into()).into(),
+                        (
+                            Padding {
+                                dir: Direction::All,
+                            }
+                            .into(),
+                            (1.0, Unit::Em).into(),
+                        )
+                            .into(),
+                        (Size::Width.into(), (20.0, Unit::Em).into()).into(),
+                    ],
+                },
+                CssBlock {
+                    selector: TopSelector::Class("form_header".to_owned()).into(),
+                    decls: vec![
+                        (Property::TextAlign, Keyword::Center.into()).into(),
+                        (Font::Weight.into(), Keyword::Bold.into()).into(),
+                        (Font::Size.into(), (14.0, Unit::Pt).into()).into(),
+                    ],
+                },
+                CssBlock {
+                    selector: TopSelector::Class("form_submit".to_owned()).into(),
+                    decls: vec![
+                        (
+                            Color::Background.into(),
+                            Value::Var("bg-color-odd".to_owned()),
+                        )
+                            .into(),
+                        (Border::Radius.into(), (0.5, Unit::Em).into()).into(),
+                        (Property::TextAlign, Keyword::Center.into()).into(),
+                        (Property::Cursor, Keyword::Pointer.into()).into(),
+                    ],
+                },
+            ],
+        };
+        assert_eq!(result, expected)
+    }
+}
@@ -1,5 +1,6 @@
 mod classes;
 mod footer;
+mod forms;
 mod gallery;
 mod hall_of_fame;
 mod header;
@@ -16,6 +17,7 @@ use html::elements::HtmlElement;
 
 use classes::Classes;
 use footer::Footer;
+use forms::Forms;
 use gallery::Gallery;
 use hall_of_fame::HallOfFame;
 use header::Header;
@@ -30,6 +32,8 @@ use upcoming_tasks::UpcomingTasks;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PageCss {
     Activities,
+    Error,
+    Forms,
     Gallery,
     Graveyard,
     Index,
@@ -51,6 +55,23 @@ impl PageComponent for PageCss {
             ]
             .into(),
 
+            PageCss::Error => vec![
+                Classes {}.render().into(),
+                Tags {}.render().into(),
+                Root {}.render().into(),
+                Header {}.render().into(),
+                Footer {}.render().into(),
+            ]
+            .into(),
+            PageCss::Forms => vec![
+                Classes {}.render().into(),
+                Tags {}.render().into(),
+                Root {}.render().into(),
+                Header {}.render().into(),
+                Footer {}.render().into(),
+                Forms {}.render().into(),
+            ]
+            .into(),
             PageCss::Gallery => vec![
                 Classes {}.render().into(),
                 Tags {}.render().into(),
@@ -125,7 +146,7 @@ impl PageComponent for PageCss {
 #[cfg(test)]
 mod pagecss_tests {
     use super::{
-        Classes, CssComponent, Footer, Gallery, HallOfFame, Header, PageComponent, PageCss,
+        Classes, CssComponent, Footer, Forms, Gallery, HallOfFame, Header, PageComponent, PageCss,
         PlantDetails, PlantList, PlantSearch, Root, SpeciesDetails, Tags, UpcomingTasks,
     };
     use crate::test_common::DATE_FORMAT;
@@ -144,6 +165,35 @@ mod pagecss_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn render_error() {
+        let result = PageCss::Error.render(DATE_FORMAT);
+        let expected = vec![
+            Classes {}.render().into(),
+            Tags {}.render().into(),
+            Root {}.render().into(),
+            Header {}.render().into(),
+            Footer {}.render().into(),
+        ]
+        .into();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn render_forms() {
+        let result = PageCss::Forms.render(DATE_FORMAT);
+        let expected = vec![
+            Classes {}.render().into(),
+            Tags {}.render().into(),
+            Root {}.render().into(),
+            Header {}.render().into(),
+            Footer {}.render().into(),
+            Forms {}.render().into(),
+        ]
+        .into();
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn render_gallery() {
         let result = PageCss::Gallery.render(DATE_FORMAT);
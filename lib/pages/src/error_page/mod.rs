@@ -0,0 +1,153 @@
+use super::{css::PageCss, page::Page, shared::html_head::HtmlHead};
+use html::{
+    attribute::Attribute,
+    elements::{Div, HeaderSize, Headline, HtmlElement},
+};
+use std::rc::Rc;
+
+/// Page shown when a request to the web server fails
+#[derive(Debug, PartialEq, Eq)]
+pub struct ErrorPage {
+    pub status_code: u16,
+    pub message: String,
+    /// Set for pages requested from a subdirectory (e.g. plants/), so scripts are loaded from the parent
+    pub relative_up: bool,
+}
+
+impl ErrorPage {
+    pub fn get_reason(&self) -> &str {
+        match self.status_code {
+            400 => "Bad Request",
+            404 => "Not Found",
+            500 => "Internal Server Error",
+            _ => "Error",
+        }
+    }
+}
+
+/// Messages can contain parts of the requested url, so they can't be included as html
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Page for ErrorPage {
+    fn get_title(&self) -> String {
+        format!("{} {}", self.status_code, self.get_reason())
+    }
+
+    fn get_content(&self, _: &str) -> HtmlElement {
+        Div {
+            attributes: vec![Attribute::Id("error_page".to_owned())],
+            content: Rc::new(
+                vec![
+                    Headline {
+                        attributes: vec![],
+                        size: HeaderSize::H1,
+                        content: Rc::new(self.get_title().into()),
+                    }
+                    .into(),
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["error_message".to_owned()])],
+                        content: Rc::new(escape_html(&self.message).into()),
+                    }
+                    .into(),
+                ]
+                .into(),
+            ),
+        }
+        .into()
+    }
+
+    fn get_head(&self, date_format: &str) -> HtmlHead {
+        let prefix = if self.relative_up { "../" } else { "" };
+        HtmlHead {
+            title: self.get_title(),
+            styles: PageCss::Error,
+            scripts: vec![format!("{prefix}js/main.js")],
+            date_format: date_format.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod error_page_tests {
+    use super::{escape_html, ErrorPage, HtmlHead, Page, PageCss};
+    use crate::test_common::DATE_FORMAT;
+    use html::{
+        attribute::Attribute,
+        elements::{Div, HeaderSize, Headline},
+    };
+    use std::rc::Rc;
+
+    fn example_page() -> ErrorPage {
+        ErrorPage {
+            status_code: 404,
+            message: "Could not find plant <b>".to_owned(),
+            relative_up: true,
+        }
+    }
+
+    #[test]
+    fn error_title() {
+        let result = example_page().get_title();
+        let expected = "404 Not Found";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn error_title_unknown() {
+        let page = ErrorPage {
+            status_code: 418,
+            ..example_page()
+        };
+        assert_eq!(page.get_title(), "418 Error")
+    }
+
+    #[test]
+    fn error_content() {
+        let result = example_page().get_content(DATE_FORMAT);
+        let expected = Div {
+            attributes: vec![Attribute::Id("error_page".to_owned())],
+            content: Rc::new(
+                vec![
+                    Headline {
+                        attributes: vec![],
+                        size: HeaderSize::H1,
+                        content: Rc::new("404 Not Found".to_owned().into()),
+                    }
+                    .into(),
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["error_message".to_owned()])],
+                        content: Rc::new("Could not find plant &lt;b&gt;".to_owned().into()),
+                    }
+                    .into(),
+                ]
+                .into(),
+            ),
+        }
+        .into();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn error_head() {
+        let result = example_page().get_head(DATE_FORMAT);
+        let expected = HtmlHead {
+            title: "404 Not Found".to_owned(),
+            styles: PageCss::Error,
+            scripts: vec!["../js/main.js".to_owned()],
+            date_format: DATE_FORMAT.to_owned(),
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn escape() {
+        let result = escape_html("<a href=\"x\">&</a>");
+        let expected = "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;";
+        assert_eq!(result, expected)
+    }
+}
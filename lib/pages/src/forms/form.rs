@@ -0,0 +1,232 @@
+use crate::page::PageComponent;
+use html::{
+    attribute::Attribute,
+    elements::{Div, HtmlElement, Input, Select, SelectOption},
+};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FieldKind {
+    Text,
+    Number,
+    Date,
+    Password,
+    Select(Vec<String>),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FormField {
+    /// Key the value is posted with
+    pub name: String,
+    pub label: String,
+    pub kind: FieldKind,
+}
+
+/// Form that is posted to `action` by `submit_form` in js/forms.js
+/// inputs have the id `{form id}-{field name}`, the server response is shown in `{form id}-status`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Form {
+    pub id: String,
+    pub title: String,
+    pub action: String,
+    pub submit: String,
+    pub fields: Vec<FormField>,
+}
+
+impl FormField {
+    pub fn new(name: &str, label: &str, kind: FieldKind) -> FormField {
+        FormField {
+            name: name.to_owned(),
+            label: label.to_owned(),
+            kind,
+        }
+    }
+
+    fn render_input(&self, form_id: &str) -> HtmlElement {
+        let id = Attribute::Id(format!("{form_id}-{}", self.name));
+        let input_type = match &self.kind {
+            FieldKind::Text => "text",
+            FieldKind::Number => "number",
+            FieldKind::Date => "date",
+            FieldKind::Password => "password",
+            FieldKind::Select(values) => {
+                return Select {
+                    attributes: vec![id],
+                    options: values
+                        .iter()
+                        .map(|value| SelectOption {
+                            value: value.clone(),
+                            content: Rc::new(value.clone().into()),
+                        })
+                        .collect(),
+                }
+                .into()
+            }
+        };
+        Input {
+            attributes: vec![id, Attribute::Type(input_type.to_owned())],
+        }
+        .into()
+    }
+
+    fn render(&self, form_id: &str) -> HtmlElement {
+        Div {
+            attributes: vec![Attribute::Class(vec!["form_field".to_owned()])],
+            content: Rc::new(
+                vec![
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["form_label".to_owned()])],
+                        content: Rc::new(self.label.clone().into()),
+                    }
+                    .into(),
+                    self.render_input(form_id),
+                ]
+                .into(),
+            ),
+        }
+        .into()
+    }
+}
+
+impl PageComponent for Form {
+    fn render(&self, _: &str) -> HtmlElement {
+        let mut contents = vec![Div {
+            attributes: vec![Attribute::Class(vec!["form_header".to_owned()])],
+            content: Rc::new(self.title.clone().into()),
+        }
+        .into()];
+        contents.extend(self.fields.iter().map(|field| field.render(&self.id)));
+        contents.push(
+            Div {
+                attributes: vec![
+                    Attribute::Class(vec!["form_submit".to_owned()]),
+                    Attribute::OnClick(format!("submit_form('{}','{}')", self.id, self.action)),
+                ],
+                content: Rc::new(self.submit.clone().into()),
+            }
+            .into(),
+        );
+        contents.push(
+            Div {
+                attributes: vec![
+                    Attribute::Id(format!("{}-status", self.id)),
+                    Attribute::Class(vec!["form_status".to_owned()]),
+                ],
+                content: Rc::new("".to_owned().into()),
+            }
+            .into(),
+        );
+        Div {
+            attributes: vec![
+                Attribute::Id(self.id.clone()),
+                Attribute::Class(vec!["form".to_owned()]),
+            ],
+            content: Rc::new(contents.into()),
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod form_tests {
+    use super::{FieldKind, Form, FormField, PageComponent};
+    use crate::test_common::DATE_FORMAT;
+    use html::{
+        attribute::Attribute,
+        elements::{Div, Input, Select, SelectOption},
+    };
+    use std::rc::Rc;
+
+    #[test]
+    fn render_text_field() {
+        let result = FormField::new("note", "Note", FieldKind::Text).render("growth");
+        let expected = Div {
+            attributes: vec![Attribute::Class(vec!["form_field".to_owned()])],
+            content: Rc::new(
+                vec![
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["form_label".to_owned()])],
+                        content: Rc::new("Note".to_owned().into()),
+                    }
+                    .into(),
+                    Input {
+                        attributes: vec![
+                            Attribute::Id("growth-note".to_owned()),
+                            Attribute::Type("text".to_owned()),
+                        ],
+                    }
+                    .into(),
+                ]
+                .into(),
+            ),
+        }
+        .into();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn render_select_field() {
+        let field = FormField::new(
+            "plant",
+            "Plant",
+            FieldKind::Select(vec!["Plant1".to_owned()]),
+        );
+        let result = field.render_input("growth");
+        let expected = Select {
+            attributes: vec![Attribute::Id("growth-plant".to_owned())],
+            options: vec![SelectOption {
+                value: "Plant1".to_owned(),
+                content: Rc::new("Plant1".to_owned().into()),
+            }],
+        }
+        .into();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn render_form() {
+        let form = Form {
+            id: "login".to_owned(),
+            title: "Login".to_owned(),
+            action: "/login".to_owned(),
+            submit: "Log in".to_owned(),
+            fields: vec![FormField::new("token", "Token", FieldKind::Password)],
+        };
+        let result = form.render(DATE_FORMAT);
+        let expected = Div {
+            attributes: vec![
+                Attribute::Id("login".to_owned()),
+                Attribute::Class(vec!["form".to_owned()]),
+            ],
+            content: Rc::new(
+                vec![
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["form_header".to_owned()])],
+                        content: Rc::new("Login".to_owned().into()),
+                    }
+                    .into(),
+                    form.fields[0].render("login"),
+                    Div {
+                        attributes: vec![
+                            Attribute::Class(vec!["form_submit".to_owned()]),
+                            Attribute::OnClick("submit_form('login','/login')".to_owned()),
+                        ],
+                        content: Rc::new("Log in".to_owned().into()),
+                    }
+                    .into(),
+                    Div {
+                        attributes: vec![
+                            Attribute::Id("login-status".to_owned()),
+                            Attribute::Class(vec!["form_status".to_owned()]),
+                        ],
+                        content: Rc::new("".to_owned().into()),
+                    }
+                    .into(),
+                ]
+                .into(),
+            ),
+        }
+        .into();
+        assert_eq!(result, expected)
+    }
+}
@@ -0,0 +1,298 @@
+pub mod form;
+
+use super::{
+    css::PageCss,
+    page::{Page, PageComponent},
+    shared::html_head::HtmlHead,
+};
+use form::{FieldKind, Form, FormField};
+use html::{
+    attribute::Attribute,
+    elements::{Div, HtmlElement},
+};
+use plants::{
+    location::Location, plant::Plant, plant_update::UpdateField as PlantField, species::Species,
+    species_update::UpdateField as SpeciesField,
+};
+use std::rc::Rc;
+
+/// Forms to edit plants from the browser, only available when the site is served by serve_html
+#[derive(Debug, PartialEq, Eq)]
+pub struct Forms {
+    pub forms: Vec<Form>,
+}
+
+/// Name a species field is posted with, e.g. `min_temp_opt` for "Min Temp Opt"
+pub fn species_field_name(field: &SpeciesField) -> String {
+    field.to_string().to_lowercase().replace(' ', "_")
+}
+
+fn login_form() -> Form {
+    Form {
+        id: "login".to_owned(),
+        title: "Login".to_owned(),
+        action: "/login".to_owned(),
+        submit: "Log in".to_owned(),
+        fields: vec![FormField::new("token", "Token", FieldKind::Password)],
+    }
+}
+
+fn activity_form() -> Form {
+    Form {
+        id: "activity".to_owned(),
+        title: "Log Activity".to_owned(),
+        action: "/actions/activity".to_owned(),
+        submit: "Save".to_owned(),
+        fields: vec![
+            FormField::new(
+                "activity",
+                "Activity",
+                FieldKind::Select(vec!["Watering".to_owned(), "Fertilizing".to_owned()]),
+            ),
+            FormField::new("plants", "Plants (separate by comma)", FieldKind::Text),
+            FormField::new("date", "Date (empty for today)", FieldKind::Date),
+            FormField::new("note", "Note", FieldKind::Text),
+        ],
+    }
+}
+
+fn growth_form(plants: &[String]) -> Form {
+    Form {
+        id: "growth".to_owned(),
+        title: "Add Growth".to_owned(),
+        action: "/actions/growth".to_owned(),
+        submit: "Save".to_owned(),
+        fields: vec![
+            FormField::new("plant", "Plant", FieldKind::Select(plants.to_vec())),
+            FormField::new("height", "Height (cm)", FieldKind::Number),
+            FormField::new("width", "Width (cm)", FieldKind::Number),
+            FormField::new("health", "Health (0-5)", FieldKind::Number),
+            FormField::new("note", "Note", FieldKind::Text),
+        ],
+    }
+}
+
+fn new_plant_form(species: &[String], locations: &[String]) -> Form {
+    Form {
+        id: "new_plant".to_owned(),
+        title: "New Plant".to_owned(),
+        action: "/actions/new_plant".to_owned(),
+        submit: "Create".to_owned(),
+        fields: vec![
+            FormField::new("name", "Name", FieldKind::Text),
+            FormField::new("species", "Species", FieldKind::Select(species.to_vec())),
+            FormField::new(
+                "location",
+                "Location",
+                FieldKind::Select(locations.to_vec()),
+            ),
+            FormField::new("origin", "Origin", FieldKind::Text),
+            FormField::new("obtained", "Obtained", FieldKind::Date),
+            FormField::new(
+                "auto_water",
+                "Auto Watered",
+                FieldKind::Select(vec!["false".to_owned(), "true".to_owned()]),
+            ),
+            FormField::new("height", "Height (cm)", FieldKind::Number),
+            FormField::new("width", "Width (cm)", FieldKind::Number),
+            FormField::new("health", "Health (0-5)", FieldKind::Number),
+            FormField::new("notes", "Notes (separate by comma)", FieldKind::Text),
+        ],
+    }
+}
+
+fn update_plant_form(plants: &[String]) -> Form {
+    Form {
+        id: "update_plant".to_owned(),
+        title: "Update Plant".to_owned(),
+        action: "/actions/update_plant".to_owned(),
+        submit: "Update".to_owned(),
+        fields: vec![
+            FormField::new("plant", "Plant", FieldKind::Select(plants.to_vec())),
+            FormField::new(
+                "field",
+                "Field",
+                FieldKind::Select(PlantField::fields_strs()),
+            ),
+            FormField::new("value", "Value", FieldKind::Text),
+        ],
+    }
+}
+
+fn new_species_form() -> Form {
+    let mut fields = vec![FormField::new("name", "Name", FieldKind::Text)];
+    fields.extend(SpeciesField::all_fields().iter().map(|field| {
+        FormField::new(
+            &species_field_name(field),
+            &field.to_string(),
+            FieldKind::Text,
+        )
+    }));
+    Form {
+        id: "new_species".to_owned(),
+        title: "New Species".to_owned(),
+        action: "/actions/new_species".to_owned(),
+        submit: "Create".to_owned(),
+        fields,
+    }
+}
+
+fn update_species_form(species: &[String]) -> Form {
+    Form {
+        id: "update_species".to_owned(),
+        title: "Update Species".to_owned(),
+        action: "/actions/update_species".to_owned(),
+        submit: "Update".to_owned(),
+        fields: vec![
+            FormField::new("species", "Species", FieldKind::Select(species.to_vec())),
+            FormField::new(
+                "field",
+                "Field",
+                FieldKind::Select(SpeciesField::fields_strs()),
+            ),
+            FormField::new("value", "Value", FieldKind::Text),
+        ],
+    }
+}
+
+fn graveyard_form(plants: &[String]) -> Form {
+    Form {
+        id: "graveyard".to_owned(),
+        title: "Move to Graveyard".to_owned(),
+        action: "/actions/graveyard".to_owned(),
+        submit: "Move".to_owned(),
+        fields: vec![
+            FormField::new("plant", "Plant", FieldKind::Select(plants.to_vec())),
+            FormField::new("died", "Died", FieldKind::Date),
+            FormField::new("reason", "Reason", FieldKind::Text),
+        ],
+    }
+}
+
+impl Page for Forms {
+    fn get_title(&self) -> String {
+        "Edit".to_owned()
+    }
+
+    fn get_content(&self, date_format: &str) -> HtmlElement {
+        Div {
+            attributes: vec![
+                Attribute::Id("forms".to_owned()),
+                Attribute::Class(vec!["flex_container".to_owned()]),
+            ],
+            content: Rc::new(
+                self.forms
+                    .iter()
+                    .map(|form| form.render(date_format))
+                    .collect::<Vec<HtmlElement>>()
+                    .into(),
+            ),
+        }
+        .into()
+    }
+
+    fn get_head(&self, date_format: &str) -> HtmlHead {
+        HtmlHead {
+            title: self.get_title(),
+            styles: PageCss::Forms,
+            scripts: vec!["js/main.js".to_owned(), "js/forms.js".to_owned()],
+            date_format: date_format.to_owned(),
+        }
+    }
+}
+
+impl From<(&[Plant], &[Species], &[Location])> for Forms {
+    fn from((plants, species, locations): (&[Plant], &[Species], &[Location])) -> Forms {
+        log::info!("Loading Forms");
+        let plant_names: Vec<String> = plants.iter().map(|plant| plant.info.name.clone()).collect();
+        let species_names: Vec<String> = species.iter().map(|sp| sp.name.clone()).collect();
+        let location_names: Vec<String> = locations.iter().map(|loc| loc.name.clone()).collect();
+        Forms {
+            forms: vec![
+                login_form(),
+                activity_form(),
+                growth_form(&plant_names),
+                new_plant_form(&species_names, &location_names),
+                update_plant_form(&plant_names),
+                new_species_form(),
+                update_species_form(&species_names),
+                graveyard_form(&plant_names),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod forms_tests {
+    use super::{
+        form::{FieldKind, FormField},
+        species_field_name, Forms, HtmlHead, Page, PageCss, SpeciesField,
+    };
+    use crate::test_common::{example_plant1, example_plant2, example_species, DATE_FORMAT};
+    use plants::location::Location;
+
+    fn example_forms() -> Forms {
+        let location = Location {
+            name: "test location".to_owned(),
+            outside: false,
+            climate: vec![],
+        };
+        Forms::from((
+            vec![example_plant1(), example_plant2()].as_slice(),
+            vec![example_species()].as_slice(),
+            vec![location].as_slice(),
+        ))
+    }
+
+    #[test]
+    fn forms_title() {
+        assert_eq!(example_forms().get_title(), "Edit")
+    }
+
+    #[test]
+    fn forms_head() {
+        let result = example_forms().get_head(DATE_FORMAT);
+        let expected = HtmlHead {
+            title: "Edit".to_owned(),
+            styles: PageCss::Forms,
+            scripts: vec!["js/main.js".to_owned(), "js/forms.js".to_owned()],
+            date_format: DATE_FORMAT.to_owned(),
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn forms_plant_select() {
+        let forms = example_forms();
+        let growth = forms.forms.iter().find(|form| form.id == "growth").unwrap();
+        let expected = FormField::new(
+            "plant",
+            "Plant",
+            FieldKind::Select(vec!["Plant1".to_owned(), "Plant2".to_owned()]),
+        );
+        assert_eq!(growth.fields[0], expected)
+    }
+
+    #[test]
+    fn forms_species_fields() {
+        let forms = example_forms();
+        let new_species = forms
+            .forms
+            .iter()
+            .find(|form| form.id == "new_species")
+            .unwrap();
+        let result: Vec<&str> = new_species
+            .fields
+            .iter()
+            .take(3)
+            .map(|field| field.name.as_str())
+            .collect();
+        assert_eq!(result, vec!["name", "scientific_name", "genus"])
+    }
+
+    #[test]
+    fn field_name() {
+        let result = species_field_name(&SpeciesField::TempMinOpt);
+        assert_eq!(result, "min_temp_opt")
+    }
+}
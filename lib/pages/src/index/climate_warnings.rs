@@ -0,0 +1,187 @@
+use crate::{page::PageComponent, shared::plant_link::PlantLink};
+use html::{
+    attribute::Attribute,
+    elements::{Div, HeaderSize, Headline, HtmlElement},
+};
+use plants::{climate::get_climate_alerts, plant::Plant};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ClimateWarning {
+    plant: PlantLink,
+    location: String,
+    description: String,
+    risk: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ClimateWarnings {
+    warnings: Vec<ClimateWarning>,
+}
+
+impl PageComponent for ClimateWarnings {
+    fn render(&self, date_format: &str) -> HtmlElement {
+        if self.warnings.is_empty() {
+            return Div {
+                attributes: vec![],
+                content: Rc::new("".to_owned().into()),
+            }
+            .into();
+        }
+        let header = Headline {
+            attributes: vec![],
+            size: HeaderSize::H1,
+            content: Rc::new("Climate Warnings".to_owned().into()),
+        }
+        .into();
+
+        let mut warning_items = vec![];
+        for warning in self.warnings.iter() {
+            warning_items.push(
+                Div {
+                    attributes: vec![Attribute::Class(vec![
+                        "climate_item".to_owned(),
+                        format!("climate_{}", warning.risk),
+                    ])],
+                    content: Rc::new(
+                        vec![
+                            warning.plant.render(date_format),
+                            HtmlElement::Br,
+                            format!("{}: {}", warning.location, warning.description).into(),
+                        ]
+                        .into(),
+                    ),
+                }
+                .into(),
+            );
+        }
+
+        vec![
+            header,
+            Div {
+                attributes: vec![
+                    Attribute::Id("climate_container".to_owned()),
+                    Attribute::Class(vec![
+                        "flex_container".to_owned(),
+                        "alternating_children".to_owned(),
+                    ]),
+                ],
+                content: Rc::new(warning_items.into()),
+            }
+            .into(),
+        ]
+        .into()
+    }
+}
+
+impl From<&[Plant]> for ClimateWarnings {
+    fn from(plants: &[Plant]) -> ClimateWarnings {
+        log::info!("Getting climate warnings");
+        let mut warnings = vec![];
+        for alert in get_climate_alerts(plants) {
+            let Some(plant) = plants.iter().find(|plant| plant.info.name == alert.plant) else {
+                continue;
+            };
+            warnings.push(ClimateWarning {
+                plant: (plant, "plants").into(),
+                location: alert.location.clone(),
+                description: alert.get_description(),
+                risk: alert.risk.to_string(),
+            })
+        }
+        ClimateWarnings { warnings }
+    }
+}
+
+#[cfg(test)]
+mod climate_warnings_tests {
+    use super::{ClimateWarning, ClimateWarnings, PageComponent};
+    use crate::test_common::{example_plant1, example_plant2, example_plantlink1, DATE_FORMAT};
+    use html::{
+        attribute::Attribute,
+        elements::{Div, HeaderSize, Headline, HtmlElement},
+    };
+    use plants::{climate::parse_climate, location::Location, plant::PlantLocation};
+    use std::rc::Rc;
+
+    fn example_warnings() -> ClimateWarnings {
+        ClimateWarnings {
+            warnings: vec![ClimateWarning {
+                plant: example_plantlink1(),
+                location: "Balcony".to_owned(),
+                description: "-5°C to 10°C in Jan is intolerable".to_owned(),
+                risk: "intolerable".to_owned(),
+            }],
+        }
+    }
+
+    #[test]
+    fn render_warnings() {
+        let result = example_warnings().render(DATE_FORMAT);
+        let expected = vec![
+            Headline {
+                attributes: vec![],
+                size: HeaderSize::H1,
+                content: Rc::new("Climate Warnings".to_owned().into()),
+            }
+            .into(),
+            Div {
+                attributes: vec![
+                    Attribute::Id("climate_container".to_owned()),
+                    Attribute::Class(vec![
+                        "flex_container".to_owned(),
+                        "alternating_children".to_owned(),
+                    ]),
+                ],
+                content: Rc::new(
+                    vec![Div {
+                        attributes: vec![Attribute::Class(vec![
+                            "climate_item".to_owned(),
+                            "climate_intolerable".to_owned(),
+                        ])],
+                        content: Rc::new(
+                            vec![
+                                example_plantlink1().render(DATE_FORMAT),
+                                HtmlElement::Br,
+                                "Balcony: -5°C to 10°C in Jan is intolerable"
+                                    .to_owned()
+                                    .into(),
+                            ]
+                            .into(),
+                        ),
+                    }
+                    .into()]
+                    .into(),
+                ),
+            }
+            .into(),
+        ]
+        .into();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn render_empty() {
+        let result = ClimateWarnings { warnings: vec![] }.render(DATE_FORMAT);
+        let expected = Div {
+            attributes: vec![],
+            content: Rc::new("".to_owned().into()),
+        }
+        .into();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn warnings_into() {
+        let mut plant = example_plant1();
+        plant.info.location = PlantLocation::Location(Box::new(Location {
+            name: "Balcony".to_owned(),
+            outside: true,
+            climate: parse_climate("-5..10 @ Jan").unwrap(),
+        }));
+        let result = ClimateWarnings::from(vec![plant, example_plant2()].as_slice());
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].plant, example_plantlink1());
+        assert_eq!(result.warnings[0].location, "Balcony")
+    }
+}
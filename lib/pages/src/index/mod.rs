@@ -1,11 +1,15 @@
 pub mod autowatered;
+pub mod climate_warnings;
 pub mod hall_of_fame;
 pub mod upcoming_tasks;
 
 use super::{
     css::PageCss,
     errors::Error,
-    index::{autowatered::AutoWatered, hall_of_fame::HallOfFame, upcoming_tasks::UpcomingTasks},
+    index::{
+        autowatered::AutoWatered, climate_warnings::ClimateWarnings, hall_of_fame::HallOfFame,
+        upcoming_tasks::UpcomingTasks,
+    },
     page::{Page, PageComponent},
     shared::html_head::HtmlHead,
 };
@@ -15,6 +19,7 @@ use plants::plant::Plant;
 #[derive(Debug, PartialEq)]
 pub struct Index {
     pub next_activities: UpcomingTasks,
+    pub climate_warnings: ClimateWarnings,
     pub autowatered: AutoWatered,
     pub hall_of_fame: HallOfFame,
 }
@@ -27,6 +32,7 @@ impl Page for Index {
     fn get_content(&self, date_format: &str) -> HtmlElement {
         vec![
             self.next_activities.render(date_format),
+            self.climate_warnings.render(date_format),
             self.autowatered.render(date_format),
             self.hall_of_fame.render(date_format),
         ]
@@ -51,6 +57,7 @@ impl TryFrom<&[Plant]> for Index {
         let hall_of_fame = HallOfFame::try_from(plants)?;
         Ok(Index {
             next_activities: UpcomingTasks::from(plants),
+            climate_warnings: ClimateWarnings::from(plants),
             autowatered: AutoWatered::from(plants),
             hall_of_fame,
         })
@@ -60,7 +67,8 @@ impl TryFrom<&[Plant]> for Index {
 #[cfg(test)]
 mod index_tests {
     use super::{
-        AutoWatered, HallOfFame, HtmlHead, Index, Page, PageComponent, PageCss, UpcomingTasks,
+        AutoWatered, ClimateWarnings, HallOfFame, HtmlHead, Index, Page, PageComponent, PageCss,
+        UpcomingTasks,
     };
     use crate::test_common::{example_plant1, example_plant2, example_plant3, DATE_FORMAT};
     use plants::plant::Plant;
@@ -68,6 +76,7 @@ mod index_tests {
     fn example_index() -> Index {
         Index {
             next_activities: UpcomingTasks::from(example_plants().as_slice()),
+            climate_warnings: ClimateWarnings::from(example_plants().as_slice()),
             autowatered: AutoWatered::from(example_plants().as_slice()),
             hall_of_fame: HallOfFame::try_from(example_plants().as_slice()).unwrap(),
         }
@@ -89,6 +98,7 @@ mod index_tests {
         let result = example_index().get_content(DATE_FORMAT);
         let expected = vec![
             UpcomingTasks::from(example_plants().as_slice()).render(DATE_FORMAT),
+            ClimateWarnings::from(example_plants().as_slice()).render(DATE_FORMAT),
             AutoWatered::from(example_plants().as_slice()).render(DATE_FORMAT),
             HallOfFame::try_from(example_plants().as_slice())
                 .unwrap()
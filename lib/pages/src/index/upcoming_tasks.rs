@@ -4,7 +4,10 @@ use html::{
     attribute::Attribute,
     elements::{Div, HeaderSize, Headline, HtmlElement, Table, Td, Tr},
 };
-use plants::plant::Plant;
+use plants::{
+    plant::Plant,
+    schedule::{activity_key, activity_title},
+};
 use std::rc::Rc;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -15,14 +18,15 @@ pub struct UpcomingTasks {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TaskBlock {
     date: NaiveDate,
+    activities: Vec<String>,
     items_inside: Vec<TaskItem>,
     items_outside: Vec<TaskItem>,
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TaskItem {
     plant: PlantLink,
-    watering: bool,
-    fertilizing: bool,
+    /// For each activity of the task block, whether it is due
+    due: Vec<bool>,
 }
 
 impl PageComponent for TaskItem {
@@ -34,19 +38,15 @@ impl PageComponent for TaskItem {
                 "❌".to_owned()
             }
         };
+        let mut cols = vec![Td {
+            content: Rc::new(self.plant.render(date_format)),
+        }];
+        cols.extend(self.due.iter().map(|due| Td {
+            content: Rc::new(get_sym(*due).into()),
+        }));
         Tr {
             attributes: vec![],
-            cols: vec![
-                Td {
-                    content: Rc::new(self.plant.render(date_format)),
-                },
-                Td {
-                    content: Rc::new(get_sym(self.watering).into()),
-                },
-                Td {
-                    content: Rc::new(get_sym(self.fertilizing).into()),
-                },
-            ],
+            cols,
         }
         .into()
     }
@@ -63,19 +63,22 @@ impl PageComponent for TaskBlock {
                     .into(),
             ),
         };
+        let mut header_cols = vec![Td {
+            content: Rc::new("Plant".to_owned().into()),
+        }];
+        header_cols.extend(self.activities.iter().map(|activity| {
+            let header = match activity_key(activity).as_str() {
+                "watering" => "🌊".to_owned(),
+                "fertilizing" => "💩".to_owned(),
+                _ => activity.clone(),
+            };
+            Td {
+                content: Rc::new(header.into()),
+            }
+        }));
         let header_row = Tr {
             attributes: vec![Attribute::Class(vec!["header_row".to_owned()])],
-            cols: vec![
-                Td {
-                    content: Rc::new("Plant".to_owned().into()),
-                },
-                Td {
-                    content: Rc::new("🌊".to_owned().into()),
-                },
-                Td {
-                    content: Rc::new("💩".to_owned().into()),
-                },
-            ],
+            cols: header_cols,
         };
 
         let mut rows_inside = vec![header_row.clone().into()];
@@ -170,24 +173,37 @@ impl From<&[Plant]> for UpcomingTasks {
     fn from(plants: &[Plant]) -> UpcomingTasks {
         struct PlantWDates<'a> {
             plant: &'a Plant,
-            watering: Option<NaiveDate>,
-            fertilizing: Option<NaiveDate>,
+            dates: Vec<(String, NaiveDate)>,
             outside: bool,
         }
 
+        impl PlantWDates<'_> {
+            fn is_due(&self, activity: &str, date: NaiveDate) -> bool {
+                self.dates
+                    .iter()
+                    .any(|(act, next)| activity_key(act) == activity_key(activity) && *next == date)
+            }
+        }
+
         let plants_with_dates: Vec<PlantWDates> = plants
             .iter()
             .map(|plant| PlantWDates {
                 plant,
-                watering: plant.get_next_watering(),
-                fertilizing: plant.get_next_fertilizing(),
+                dates: plant
+                    .get_scheduled_activities()
+                    .into_iter()
+                    .filter_map(|activity| {
+                        let next = plant.get_next_activity_date(&activity)?;
+                        Some((activity, next))
+                    })
+                    .collect(),
                 outside: plant.is_outside(),
             })
             .collect();
 
         let max_date = plants_with_dates
             .iter()
-            .map(|pl| pl.watering.max(pl.fertilizing).unwrap_or_default())
+            .flat_map(|pl| pl.dates.iter().map(|(_, next)| *next))
             .max()
             .unwrap_or(Local::now().date_naive());
 
@@ -200,13 +216,26 @@ impl From<&[Plant]> for UpcomingTasks {
 
             let next_plants: Vec<&PlantWDates> = plants_with_dates
                 .iter()
-                .filter(|pl| pl.watering == Some(next_date) || pl.fertilizing == Some(next_date))
+                .filter(|pl| pl.dates.iter().any(|(_, next)| *next == next_date))
                 .collect();
             if next_plants.is_empty() {
                 last_date = next_date;
                 continue;
             }
 
+            // watering and fertilizing always have a column, other activities only when due
+            let mut other_activities: Vec<String> = next_plants
+                .iter()
+                .flat_map(|pl| pl.dates.iter())
+                .filter(|(_, next)| *next == next_date)
+                .map(|(activity, _)| activity_title(activity))
+                .filter(|activity| !["Watering", "Fertilizing"].contains(&activity.as_str()))
+                .collect();
+            other_activities.sort();
+            other_activities.dedup();
+            let mut activities = vec!["Watering".to_owned(), "Fertilizing".to_owned()];
+            activities.extend(other_activities);
+
             let plants_inside: Vec<&PlantWDates> = next_plants
                 .iter()
                 .filter(|pl| !pl.outside)
@@ -220,8 +249,10 @@ impl From<&[Plant]> for UpcomingTasks {
 
             let to_item = |pl: &PlantWDates| TaskItem {
                 plant: PlantLink::from((pl.plant, "plants")),
-                watering: pl.watering == Some(next_date),
-                fertilizing: pl.fertilizing == Some(next_date),
+                due: activities
+                    .iter()
+                    .map(|activity| pl.is_due(activity, next_date))
+                    .collect(),
             };
 
             let mut next_items_inside: Vec<TaskItem> =
@@ -229,8 +260,13 @@ impl From<&[Plant]> for UpcomingTasks {
             let mut next_items_outside: Vec<TaskItem> =
                 plants_outside.into_iter().map(to_item).collect();
 
+            // watering first, then fertilizing, then the number of other activities
             let sort_key = |pl: &TaskItem| {
-                (if pl.watering { 4 } else { 0 }) + (if pl.fertilizing { 3 } else { 0 })
+                (
+                    pl.due[0],
+                    pl.due[1],
+                    pl.due.iter().filter(|due| **due).count(),
+                )
             };
             let cmp = |pl1: &TaskItem, pl2: &TaskItem| sort_key(pl2).cmp(&sort_key(pl1));
 
@@ -239,6 +275,7 @@ impl From<&[Plant]> for UpcomingTasks {
 
             tasks.push(TaskBlock {
                 date: next_date,
+                activities,
                 items_inside: next_items_inside,
                 items_outside: next_items_outside,
             });
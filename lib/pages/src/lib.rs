@@ -1,6 +1,8 @@
 pub mod activities;
 pub mod css;
+pub mod error_page;
 pub mod errors;
+pub mod forms;
 pub mod gallery;
 pub mod graveyard;
 pub mod index;
@@ -43,6 +45,7 @@ pub mod test_common {
             date: sample_date1(),
             plant,
             note: Some(note),
+            user: None,
         }
     }
 
@@ -52,6 +55,7 @@ pub mod test_common {
             date: sample_date2(),
             plant,
             note: Some("a different note".to_owned()),
+            user: None,
         }
     }
 
@@ -142,6 +146,7 @@ pub mod test_common {
             pruning_notes: vec![],
             companions: vec![],
             additional_notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -156,6 +161,7 @@ pub mod test_common {
                 obtained: sample_date1(),
                 auto_water: false,
                 notes: vec![],
+                schedules: vec![],
             },
             growth: vec![example_growth4(), example_growth1()],
             activities: vec![
@@ -178,6 +184,7 @@ pub mod test_common {
                 obtained: sample_date2(),
                 auto_water: true,
                 notes: vec![],
+                schedules: vec![],
             },
             growth: vec![example_growth5(), example_growth2()],
             activities: vec![example_activity1(name, "a second note".to_owned())],
@@ -196,6 +203,7 @@ pub mod test_common {
                 obtained: sample_date3(),
                 auto_water: false,
                 notes: vec![],
+                schedules: vec![],
             },
             growth: vec![example_growth6(), example_growth3()],
             activities: vec![
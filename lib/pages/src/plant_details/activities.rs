@@ -390,6 +390,7 @@ mod details_activities_tests {
                     date: sample_date1(),
                     plant: "Plant1".to_owned(),
                     note: Some("a note".to_owned()),
+                    user: None,
                 }]
                 .as_slice(),
                 false,
@@ -400,6 +401,7 @@ mod details_activities_tests {
                     date: sample_date2(),
                     plant: "Plant1".to_owned(),
                     note: Some("a different note".to_owned()),
+                    user: None,
                 }]
                 .as_slice(),
                 false,
@@ -442,6 +444,7 @@ mod details_activities_tests {
                     date: sample_date1(),
                     plant: "Plant2".to_owned(),
                     note: Some("a second note".to_owned()),
+                    user: None,
                 }]
                 .as_slice(),
                 false,
@@ -4,18 +4,28 @@ use html::{
     attribute::Attribute,
     elements::{Div, HtmlElement, A},
 };
-use plants::{named::Named, plant::Plant};
+use plants::{
+    named::Named,
+    plant::Plant,
+    prediction::IntervalPrediction,
+    schedule::{activity_key, activity_title},
+};
 use std::rc::Rc;
 
+/// Next and last date of a scheduled activity of a plant
+#[derive(Debug, PartialEq)]
+pub struct ActivityStatus {
+    activity: String,
+    next: Option<NaiveDate>,
+    last: Option<NaiveDate>,
+    frequency: Option<f32>,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Status {
     health: i32,
-    next_watering: Option<NaiveDate>,
-    next_fertilizing: Option<NaiveDate>,
-    last_watering: Option<NaiveDate>,
-    last_fertilizing: Option<NaiveDate>,
-    watering_frequency: Option<f32>,
-    fertilizing_frequency: Option<f32>,
+    activities: Vec<ActivityStatus>,
+    watering_prediction: Option<IntervalPrediction>,
     current_height: f32,
     current_width: f32,
     growth_speed: f32,
@@ -41,56 +51,55 @@ impl PageComponent for Status {
             Some(val) => render_item(title, val),
         };
 
-        let status_items = vec![
-            Div {
-                attributes: vec![Attribute::Class(vec!["status_item".to_owned()])],
-                content: Rc::new(
-                    vec![
-                        "Health".to_owned().into(),
-                        HtmlElement::Br,
-                        Div {
-                            attributes: vec![Attribute::Class(vec![
-                                "health".to_owned(),
-                                "health".to_owned() + &self.health.to_string(),
-                            ])],
-                            content: Rc::new(self.health.to_string().into()),
-                        }
-                        .into(),
-                    ]
+        let mut status_items = vec![Div {
+            attributes: vec![Attribute::Class(vec!["status_item".to_owned()])],
+            content: Rc::new(
+                vec![
+                    "Health".to_owned().into(),
+                    HtmlElement::Br,
+                    Div {
+                        attributes: vec![Attribute::Class(vec![
+                            "health".to_owned(),
+                            "health".to_owned() + &self.health.to_string(),
+                        ])],
+                        content: Rc::new(self.health.to_string().into()),
+                    }
                     .into(),
-                ),
-            }
-            .into(),
-            render_option(
-                "Next Watering".to_owned(),
-                self.next_watering
-                    .map(|wat| wat.format(date_format).to_string()),
-            ),
-            render_option(
-                "Next Fertilizing".to_owned(),
-                self.next_fertilizing
-                    .map(|ft| ft.format(date_format).to_string()),
-            ),
-            render_option(
-                "Last Watering".to_owned(),
-                self.last_watering
-                    .map(|wt| wt.format(date_format).to_string()),
-            ),
-            render_option(
-                "Last Fertilizing".to_owned(),
-                self.last_fertilizing
-                    .map(|ft| ft.format(date_format).to_string()),
-            ),
-            render_option(
-                "Watering Frequency".to_owned(),
-                self.watering_frequency
-                    .map(|frq| format!("{:.2} days/watering", frq)),
-            ),
-            render_option(
-                "Fertilizing Frequency".to_owned(),
-                self.fertilizing_frequency
-                    .map(|frq| format!("{:.2} days/fertilizing", frq)),
+                ]
+                .into(),
             ),
+        }
+        .into()];
+        for activity in self.activities.iter() {
+            status_items.push(render_option(
+                format!("Next {}", activity.activity),
+                activity
+                    .next
+                    .map(|next| next.format(date_format).to_string()),
+            ));
+        }
+        for activity in self.activities.iter() {
+            status_items.push(render_option(
+                format!("Last {}", activity.activity),
+                activity
+                    .last
+                    .map(|last| last.format(date_format).to_string()),
+            ));
+        }
+        for activity in self.activities.iter() {
+            status_items.push(render_option(
+                format!("{} Frequency", activity.activity),
+                activity
+                    .frequency
+                    .map(|frq| format!("{:.2} days/{}", frq, activity_key(&activity.activity))),
+            ));
+        }
+        status_items.push(render_option(
+            "Predicted Watering Interval".to_owned(),
+            self.watering_prediction
+                .map(|prediction| prediction.to_string()),
+        ));
+        status_items.extend(vec![
             render_item(
                 "Current Height".to_owned(),
                 self.current_height.to_string() + "cm",
@@ -132,7 +141,7 @@ impl PageComponent for Status {
             render_item("Origin".to_owned(), self.origin.clone()),
             render_item("Age".to_owned(), self.age.to_string() + " days"),
             render_item("Notes".to_owned(), self.notes.clone()),
-        ];
+        ]);
 
         Div {
             attributes: vec![
@@ -153,12 +162,16 @@ impl TryFrom<&Plant> for Status {
     fn try_from(plant: &Plant) -> Result<Status, Self::Error> {
         log::info!("loading plant status for {}", plant.info.name);
         let health = plant.get_health()?;
-        let next_watering = plant.get_next_watering();
-        let next_fertilizing = plant.get_next_fertilizing();
-        let last_watering = plant.get_last_watering().map(|log| log.date);
-        let last_fertilizing = plant.get_last_fertilizing().map(|log| log.date);
-        let watering_frequency = plant.get_watering_frequency();
-        let fertilizing_frequency = plant.get_fertilizing_frequency();
+        let activities = plant
+            .get_scheduled_activities()
+            .iter()
+            .map(|activity| ActivityStatus {
+                activity: activity_title(activity),
+                next: plant.get_next_activity_date(activity),
+                last: plant.get_last_activity(activity).map(|log| log.date),
+                frequency: plant.get_activity_frequency(activity),
+            })
+            .collect();
         //if we can get height, width and speed can never fail
         let current_height = plant.get_height()?;
         let current_width = plant.get_width().unwrap();
@@ -167,12 +180,8 @@ impl TryFrom<&Plant> for Status {
 
         Ok(Status {
             health,
-            next_watering,
-            next_fertilizing,
-            last_watering,
-            last_fertilizing,
-            watering_frequency,
-            fertilizing_frequency,
+            activities,
+            watering_prediction: plant.get_watering_prediction(),
             current_height,
             current_width,
             growth_speed,
@@ -187,7 +196,7 @@ impl TryFrom<&Plant> for Status {
 
 #[cfg(test)]
 mod status_tests {
-    use super::{PageComponent, Status};
+    use super::{ActivityStatus, PageComponent, Status};
     use crate::test_common::{
         example_plant3, sample_date1, sample_date2, sample_date3, DATE_FORMAT,
     };
@@ -201,12 +210,21 @@ mod status_tests {
     fn example_status() -> Status {
         Status {
             health: 3,
-            next_watering: Some(Local::now().date_naive()),
-            next_fertilizing: Some(Local::now().date_naive()),
-            last_watering: Some(sample_date1()),
-            last_fertilizing: Some(sample_date2()),
-            watering_frequency: Some(0.0),
-            fertilizing_frequency: Some(0.0),
+            activities: vec![
+                ActivityStatus {
+                    activity: "Watering".to_owned(),
+                    next: Some(Local::now().date_naive()),
+                    last: Some(sample_date1()),
+                    frequency: Some(0.0),
+                },
+                ActivityStatus {
+                    activity: "Fertilizing".to_owned(),
+                    next: Some(Local::now().date_naive()),
+                    last: Some(sample_date2()),
+                    frequency: Some(0.0),
+                },
+            ],
+            watering_prediction: example_plant3().get_watering_prediction(),
             current_height: 34.2,
             current_width: 83.4,
             growth_speed: 54.15,
@@ -330,6 +348,18 @@ mod status_tests {
                         ),
                     }
                     .into(),
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["status_item".to_owned()])],
+                        content: Rc::new(
+                            vec![
+                                "Predicted Watering Interval".to_owned().into(),
+                                HtmlElement::Br,
+                                "1.2 days (low confidence)".to_owned().into(),
+                            ]
+                            .into(),
+                        ),
+                    }
+                    .into(),
                     Div {
                         attributes: vec![Attribute::Class(vec!["status_item".to_owned()])],
                         content: Rc::new(
@@ -6,7 +6,7 @@ use html::{
 };
 use plants::{
     named::Named,
-    plant::{Plant, PlantImage},
+    plant::{Plant, PlantImage, THUMBNAIL_DIR},
 };
 use std::rc::Rc;
 
@@ -15,6 +15,7 @@ pub struct PlantImg {
     img_base: String,
     img_url: String,
     date: NaiveDate,
+    caption: Option<String>,
     num_images: i32,
     num_self: i32,
 }
@@ -28,12 +29,21 @@ pub struct PlantGallery {
 
 impl PageComponent for PlantImg {
     fn render(&self, date_format: &str) -> HtmlElement {
-        let caption = vec![
-            Div {
-                attributes: vec![Attribute::Class(vec!["img_date".to_owned()])],
-                content: Rc::new(self.date.format(date_format).to_string().into()),
-            }
-            .into(),
+        let mut caption = vec![Div {
+            attributes: vec![Attribute::Class(vec!["img_date".to_owned()])],
+            content: Rc::new(self.date.format(date_format).to_string().into()),
+        }
+        .into()];
+        if let Some(img_caption) = &self.caption {
+            caption.push(
+                Div {
+                    attributes: vec![Attribute::Class(vec!["img_caption".to_owned()])],
+                    content: Rc::new(img_caption.clone().into()),
+                }
+                .into(),
+            );
+        }
+        caption.push(
             Div {
                 attributes: vec![Attribute::Class(vec!["img_nr".to_owned()])],
                 content: {
@@ -43,9 +53,9 @@ impl PageComponent for PlantImg {
                 },
             }
             .into(),
-        ];
-        let mut img_path = self.img_base.to_owned();
-        img_path.push_str(&self.img_url);
+        );
+        // the full image is only loaded by the image viewer
+        let img_path = format!("{}{THUMBNAIL_DIR}/{}", self.img_base, self.img_url);
         let visible = if self.num_self == 1 {
             "display:block;".to_owned()
         } else {
@@ -133,8 +143,10 @@ impl From<(&Plant, &str)> for PlantGallery {
             num_images: plant.images.len() as i32,
             num_self: i,
         };
+        // the cover image is shown first, then the newest images
         let mut images_sorted = plant.images.clone();
-        images_sorted.sort_by(|img1, img2| img2.created.cmp(&img1.created));
+        images_sorted
+            .sort_by(|img1, img2| (img2.cover, img2.created).cmp(&(img1.cover, img1.created)));
         PlantGallery {
             plant_name: plant.info.name.clone(),
             plant_url: plant.get_url(img_base),
@@ -159,6 +171,7 @@ impl From<ImageInfo> for PlantImg {
             img_url: info.image.file_name,
             img_base: info.base_dir.clone(),
             date: info.image.created,
+            caption: info.image.caption,
             num_images: info.num_images,
             num_self: info.num_self,
         }
@@ -169,7 +182,7 @@ impl From<ImageInfo> for PlantImg {
 mod plant_gallery_tests {
 
     use super::{ImageInfo, PageComponent, PlantGallery, PlantImage, PlantImg};
-    use crate::test_common::{example_plant1, sample_date1, DATE_FORMAT};
+    use crate::test_common::{example_plant1, sample_date1, sample_date2, DATE_FORMAT};
     use html::{
         attribute::Attribute,
         elements::{Div, Figure, HeaderSize, Headline, HtmlElement, Img, A},
@@ -189,6 +202,8 @@ mod plant_gallery_tests {
             created: sample_date1(),
             file_name: "img.jpg".to_owned(),
             file_path: PathBuf::from("./"),
+            caption: None,
+            cover: false,
         }
     }
 
@@ -205,6 +220,7 @@ mod plant_gallery_tests {
             img_base: "plants/Plant1/".to_owned(),
             img_url: "img.jpg".to_owned(),
             date: sample_date1(),
+            caption: None,
             num_images: 2,
             num_self,
         }
@@ -223,7 +239,7 @@ mod plant_gallery_tests {
             ],
             content: Rc::new(
                 Img {
-                    attributes: vec![Attribute::Src("plants/Plant1/img.jpg".to_owned())],
+                    attributes: vec![Attribute::Src("plants/Plant1/thumbs/img.jpg".to_owned())],
                 }
                 .into(),
             ),
@@ -332,4 +348,65 @@ mod plant_gallery_tests {
         let expected = example_img(1);
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn render_image_caption() {
+        let img = PlantImg {
+            caption: Some("new leaves".to_owned()),
+            ..example_img(1)
+        };
+        let result = img.render(DATE_FORMAT);
+        let expected = Figure {
+            attributes: vec![
+                Attribute::Class(vec!["plant_image".to_owned()]),
+                Attribute::Style("display:block;".to_owned()),
+            ],
+            content: Rc::new(
+                Img {
+                    attributes: vec![Attribute::Src("plants/Plant1/thumbs/img.jpg".to_owned())],
+                }
+                .into(),
+            ),
+            caption: Rc::new(
+                vec![
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["img_date".to_owned()])],
+                        content: Rc::new(sample_date1().format(DATE_FORMAT).to_string().into()),
+                    }
+                    .into(),
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["img_caption".to_owned()])],
+                        content: Rc::new("new leaves".to_owned().into()),
+                    }
+                    .into(),
+                    Div {
+                        attributes: vec![Attribute::Class(vec!["img_nr".to_owned()])],
+                        content: Rc::new("1/2".to_owned().into()),
+                    }
+                    .into(),
+                ]
+                .into(),
+            ),
+        }
+        .into();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn gallery_cover_first() {
+        let mut plant = example_plant1();
+        let newer = PlantImage {
+            created: sample_date2(),
+            file_name: "newer.jpg".to_owned(),
+            ..example_image()
+        };
+        let cover = PlantImage {
+            cover: true,
+            ..example_image()
+        };
+        plant.images = vec![newer, cover];
+        let result = PlantGallery::from((&plant, "plants"));
+        assert_eq!(result.plant_images[0].img_url, "img.jpg");
+        assert_eq!(result.plant_images[1].img_url, "newer.jpg")
+    }
 }
@@ -27,6 +27,7 @@ pub struct SpeciesInfo {
     watering_days: Option<SpeciesInfoItem>,
     fertilizing_notes: SpeciesInfoItem,
     fertilizing_days: Option<SpeciesInfoItem>,
+    schedules: Option<SpeciesInfoItem>,
     pruning_notes: SpeciesInfoItem,
     companions: SpeciesInfoItem,
     notes: SpeciesInfoItem,
@@ -60,6 +61,9 @@ impl PageComponent for SpeciesInfo {
             None => (),
             Some(days) => rows.push(days.render(date_format)),
         }
+        if let Some(schedules) = &self.schedules {
+            rows.push(schedules.render(date_format));
+        }
         rows.extend(vec![
             self.pruning_notes.render(date_format),
             self.companions.render(date_format),
@@ -147,6 +151,16 @@ impl From<(&Species, &[Plant])> for SpeciesInfo {
             fertilizing_days: species
                 .avg_fertilizing_days
                 .map(|days| ("Average Fertilizing Days", days.to_string().as_str()).into()),
+            schedules: if species.schedules.is_empty() {
+                None
+            } else {
+                let schedule_strs: Vec<String> = species
+                    .schedules
+                    .iter()
+                    .map(|schedule| format!("{} days", schedule))
+                    .collect();
+                Some(("Schedules", schedule_strs.join(", ").as_str()).into())
+            },
             pruning_notes: ("Pruning Notes", species.pruning_notes.join(", ").as_str()).into(),
             companions: ("Companions", species.companions.join(", ").as_str()).into(),
             notes: ("Notes", species.additional_notes.join(", ").as_str()).into(),
@@ -200,6 +214,7 @@ mod species_info_tests {
             watering_days: Some(("Average Watering Days", "1").into()),
             fertilizing_notes: ("Fertilizing Notes", "").into(),
             fertilizing_days: Some(("Average Fertilizing Days", "1").into()),
+            schedules: None,
             pruning_notes: ("Pruning Notes", "").into(),
             companions: ("Companions", "").into(),
             notes: ("Notes", "").into(),
@@ -0,0 +1,334 @@
+use super::{
+    errors::Error,
+    plant::{Plant, PlantLocation, PlantSpecies},
+    schedule::{format_months, parse_months},
+    species::Species,
+    weather::WeatherRecord,
+};
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Expected temperatures at a location, for some months or the whole year
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LocationClimate {
+    pub temp_min: f32,
+    pub temp_max: f32,
+    /// Months (1-12) the temperatures are expected in, the whole year if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub months: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ClimateRisk {
+    /// Within the temperatures the species tolerates, but outside its optimal range
+    Suboptimal,
+    /// Outside the temperatures the species tolerates
+    Intolerable,
+}
+
+/// A plant whose location gets too hot or cold for its species
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClimateAlert {
+    pub plant: String,
+    pub location: String,
+    pub climate: LocationClimate,
+    pub risk: ClimateRisk,
+    pub species_min: f32,
+    pub species_max: f32,
+}
+
+impl LocationClimate {
+    pub fn get_risk(&self, species: &Species) -> Option<ClimateRisk> {
+        if self.temp_min < species.temp_min || self.temp_max > species.temp_max {
+            Some(ClimateRisk::Intolerable)
+        } else if self.temp_min < species.opt_temp_min || self.temp_max > species.opt_temp_max {
+            Some(ClimateRisk::Suboptimal)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parses a comma separated list of climates, e.g. "-5..10 @ Nov-Feb, 5..30 @ Mar-Oct"
+pub fn parse_climate(input: &str) -> Result<Vec<LocationClimate>, Error> {
+    input
+        .split(',')
+        .filter(|climate| !climate.trim().is_empty())
+        .map(|climate| climate.parse::<LocationClimate>())
+        .collect()
+}
+
+pub fn format_climate(climate: &[LocationClimate]) -> String {
+    let climate_strs: Vec<String> = climate.iter().map(|clim| clim.to_string()).collect();
+    climate_strs.join(", ")
+}
+
+/// Lowest and highest temperature recorded in each month
+pub fn measured_climate(weather: &[WeatherRecord]) -> Vec<LocationClimate> {
+    let mut climate: Vec<LocationClimate> = vec![];
+    for record in weather.iter() {
+        let month = record.date.month();
+        match climate.iter_mut().find(|clim| clim.months == [month]) {
+            None => climate.push(LocationClimate {
+                temp_min: record.temp_min,
+                temp_max: record.temp_max,
+                months: vec![month],
+            }),
+            Some(clim) => {
+                clim.temp_min = clim.temp_min.min(record.temp_min);
+                clim.temp_max = clim.temp_max.max(record.temp_max);
+            }
+        }
+    }
+    climate.sort_by_key(|clim| clim.months.clone());
+    climate
+}
+
+/// Alerts for all plants where the climate of their location is outside the range of their species
+/// sorted with intolerable climates first
+pub fn get_climate_alerts(plants: &[Plant]) -> Vec<ClimateAlert> {
+    let mut alerts = vec![];
+    for plant in plants.iter() {
+        let (species, location) = match (&plant.info.species, &plant.info.location) {
+            (PlantSpecies::Species(species), PlantLocation::Location(location)) => {
+                (species, location)
+            }
+            _ => continue,
+        };
+        for climate in location.climate.iter() {
+            let Some(risk) = climate.get_risk(species) else {
+                continue;
+            };
+            let (species_min, species_max) = match risk {
+                ClimateRisk::Intolerable => (species.temp_min, species.temp_max),
+                ClimateRisk::Suboptimal => (species.opt_temp_min, species.opt_temp_max),
+            };
+            alerts.push(ClimateAlert {
+                plant: plant.info.name.clone(),
+                location: location.name.clone(),
+                climate: climate.clone(),
+                risk,
+                species_min,
+                species_max,
+            })
+        }
+    }
+    alerts.sort_by(|alert1, alert2| {
+        alert2
+            .risk
+            .cmp(&alert1.risk)
+            .then(alert1.plant.cmp(&alert2.plant))
+    });
+    alerts
+}
+
+impl FromStr for LocationClimate {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<LocationClimate, Error> {
+        let err = || Error::ClimateError(s.trim().to_owned());
+        let (temps, months) = match s.split_once('@') {
+            None => (s, vec![]),
+            Some((temps, months)) => (temps, parse_months(months).ok_or_else(err)?),
+        };
+        let (min, max) = temps.split_once("..").ok_or_else(err)?;
+        let temp_min = min.trim().parse::<f32>().map_err(|_| err())?;
+        let temp_max = max.trim().parse::<f32>().map_err(|_| err())?;
+        if temp_min > temp_max {
+            return Err(err());
+        }
+        Ok(LocationClimate {
+            temp_min,
+            temp_max,
+            months,
+        })
+    }
+}
+
+impl fmt::Display for LocationClimate {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(frmt, "{}..{}", self.temp_min, self.temp_max)?;
+        if !self.months.is_empty() {
+            write!(frmt, " @ {}", format_months(&self.months))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ClimateRisk {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClimateRisk::Suboptimal => frmt.write_str("suboptimal"),
+            ClimateRisk::Intolerable => frmt.write_str("intolerable"),
+        }
+    }
+}
+
+impl ClimateAlert {
+    /// Description of the climate without plant and location, e.g. "-5°C to 10°C in Jan is intolerable, ..."
+    pub fn get_description(&self) -> String {
+        let months = if self.climate.months.is_empty() {
+            "".to_owned()
+        } else {
+            format!(" in {}", format_months(&self.climate.months))
+        };
+        format!(
+            "{}°C to {}°C{} is {}, species range is {}°C to {}°C",
+            self.climate.temp_min,
+            self.climate.temp_max,
+            months,
+            self.risk,
+            self.species_min,
+            self.species_max
+        )
+    }
+}
+
+impl fmt::Display for ClimateAlert {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            frmt,
+            "{} ({}): {}",
+            self.plant,
+            self.location,
+            self.get_description()
+        )
+    }
+}
+
+#[cfg(test)]
+mod climate_tests {
+    use super::{
+        get_climate_alerts, measured_climate, parse_climate, ClimateAlert, ClimateRisk,
+        LocationClimate,
+    };
+    use crate::{
+        location::Location,
+        plant::PlantLocation,
+        test_common::{example_date1, example_date2, example_plant, example_species},
+        weather::WeatherRecord,
+    };
+    use chrono::NaiveDate;
+
+    fn winter() -> LocationClimate {
+        LocationClimate {
+            temp_min: -5.0,
+            temp_max: 10.0,
+            months: vec![1, 2, 11, 12],
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let result = parse_climate("-5..10 @ Nov-Feb, 12.5..20").unwrap();
+        let expected = vec![
+            winter(),
+            LocationClimate {
+                temp_min: 12.5,
+                temp_max: 20.0,
+                months: vec![],
+            },
+        ];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_fail() {
+        assert!(parse_climate("10..-5").is_err());
+        assert!(parse_climate("10").is_err());
+        assert!(parse_climate("0..10 @ Winter").is_err())
+    }
+
+    #[test]
+    fn display() {
+        let result = winter().to_string();
+        let expected = "-5..10 @ Jan Feb Nov Dec";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn risk() {
+        let species = example_species();
+        assert_eq!(winter().get_risk(&species), Some(ClimateRisk::Intolerable));
+        let mild = LocationClimate {
+            temp_min: 5.0,
+            temp_max: 20.0,
+            months: vec![],
+        };
+        assert_eq!(mild.get_risk(&species), Some(ClimateRisk::Suboptimal));
+        let optimal = LocationClimate {
+            temp_min: 12.0,
+            temp_max: 20.0,
+            months: vec![],
+        };
+        assert_eq!(optimal.get_risk(&species), None)
+    }
+
+    #[test]
+    fn measured() {
+        let weather = vec![
+            WeatherRecord {
+                date: example_date1(),
+                rain_mm: 0.0,
+                temp_min: -2.0,
+                temp_max: 3.0,
+            },
+            WeatherRecord {
+                date: example_date2(),
+                rain_mm: 0.0,
+                temp_min: 1.0,
+                temp_max: 6.0,
+            },
+            WeatherRecord {
+                date: NaiveDate::from_ymd_opt(1970, 2, 1).unwrap(),
+                rain_mm: 0.0,
+                temp_min: 0.0,
+                temp_max: 8.0,
+            },
+        ];
+        let result = measured_climate(&weather);
+        let expected = vec![
+            LocationClimate {
+                temp_min: -2.0,
+                temp_max: 6.0,
+                months: vec![1],
+            },
+            LocationClimate {
+                temp_min: 0.0,
+                temp_max: 8.0,
+                months: vec![2],
+            },
+        ];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn alerts() {
+        let mut plant = example_plant();
+        plant.info.location = PlantLocation::Location(Box::new(Location {
+            name: "Balcony".to_owned(),
+            outside: true,
+            climate: vec![winter()],
+        }));
+        let result = get_climate_alerts(&[plant]);
+        let expected = vec![ClimateAlert {
+            plant: "A Plant".to_owned(),
+            location: "Balcony".to_owned(),
+            climate: winter(),
+            risk: ClimateRisk::Intolerable,
+            species_min: 0.0,
+            species_max: 30.0,
+        }];
+        assert_eq!(result, expected);
+        assert_eq!(
+            result[0].to_string(),
+            "A Plant (Balcony): -5°C to 10°C in Jan Feb Nov Dec is intolerable, species range is 0°C to 30°C"
+        )
+    }
+
+    #[test]
+    fn alerts_no_climate() {
+        let result = get_climate_alerts(&[example_plant()]);
+        assert!(result.is_empty())
+    }
+}
@@ -12,6 +12,8 @@ pub enum Error {
     FieldError(String),
     WrongType(String),
     SpeciesNotFound(String),
+    ScheduleError(String),
+    ClimateError(String),
     BadHealth(i32),
     KeyNotFound { key: String, task: String },
     DateParsing { msg: String },
@@ -30,6 +32,13 @@ impl fmt::Display for Error {
             Error::FieldError(field) => write!(frmt, "Cannot update {field}"),
             Error::WrongType(field) => write!(frmt, "Wrong type for {field}"),
             Error::SpeciesNotFound(name) => write!(frmt, "Could not find species {name}"),
+            Error::ScheduleError(input) => write!(
+                frmt,
+                "Could not parse schedule {input}, expected activity: days"
+            ),
+            Error::ClimateError(input) => {
+                write!(frmt, "Could not parse climate {input}, expected min..max")
+            }
             Error::BadHealth(num) => write!(frmt, "{num} is not a valid value for health"),
             Error::KeyNotFound { key, task } => write!(frmt, "Could not find {key} for {task}"),
             Error::DateParsing { msg } => {
@@ -1,3 +1,4 @@
+pub mod climate;
 pub mod errors;
 pub mod graveyard;
 pub mod growth_item;
@@ -6,9 +7,12 @@ pub mod log_item;
 pub mod named;
 pub mod plant;
 pub mod plant_update;
+pub mod prediction;
+pub mod schedule;
 pub mod serialize;
 pub mod species;
 pub mod species_update;
+pub mod weather;
 
 #[cfg(test)]
 pub mod test_common {
@@ -43,6 +47,7 @@ pub mod test_common {
             pruning_notes: vec![],
             companions: vec![],
             additional_notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -58,6 +63,7 @@ pub mod test_common {
         Location {
             name: "Inside".to_owned(),
             outside: false,
+            climate: vec![],
         }
     }
 
@@ -70,6 +76,7 @@ pub mod test_common {
             obtained: example_date1(),
             auto_water: false,
             notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -101,6 +108,7 @@ pub mod test_common {
             date: example_date1(),
             plant: "A Plant".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -110,6 +118,7 @@ pub mod test_common {
             date: example_date2(),
             plant: "A Plant".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -118,6 +127,8 @@ pub mod test_common {
             created: example_date1(),
             file_name: "01011970.jpg".to_owned(),
             file_path: PathBuf::from("./"),
+            caption: None,
+            cover: false,
         }
     }
 
@@ -126,6 +137,8 @@ pub mod test_common {
             created: example_date2(),
             file_name: "02011970.jpg".to_owned(),
             file_path: PathBuf::from("/"),
+            caption: None,
+            cover: false,
         }
     }
 
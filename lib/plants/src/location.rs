@@ -1,11 +1,18 @@
-use super::errors::Error;
+use super::{
+    climate::{parse_climate, LocationClimate},
+    errors::Error,
+    serialize::climate_serializer,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Location {
     pub name: String,
     pub outside: bool,
+    /// Expected temperatures, per season or measured
+    #[serde(default, with = "climate_serializer")]
+    pub climate: Vec<LocationClimate>,
 }
 
 impl TryFrom<HashMap<String, String>> for Location {
@@ -20,7 +27,11 @@ impl TryFrom<HashMap<String, String>> for Location {
         };
         Ok(Location {
             name: lookup_fun("name")?,
-            outside: lookup_fun("outside")? == "true",
+            outside: matches!(lookup_fun("outside")?.as_str(), "1" | "true"),
+            climate: match map.get("climate") {
+                None => vec![],
+                Some(climate) => parse_climate(climate)?,
+            },
         })
     }
 }
@@ -10,6 +10,9 @@ pub struct LogItem {
     pub date: NaiveDate,
     pub plant: String,
     pub note: Option<String>,
+    /// User that entered the log, None for logs entered before users were recorded
+    #[serde(default)]
+    pub user: Option<String>,
 }
 
 impl PartialOrd for LogItem {
@@ -42,6 +45,7 @@ impl TryFrom<HashMap<String, String>> for LogItem {
             date: NaiveDate::parse_from_str(&lookup_fun("date")?, &date_format)?,
             plant: lookup_fun("plant")?,
             note: map.get("note").cloned(),
+            user: map.get("user").filter(|user| !user.is_empty()).cloned(),
         })
     }
 }
@@ -1,4 +1,14 @@
-use super::{errors::Error, growth_item::GrowthItem, log_item::LogItem, named::Named};
+use super::{
+    errors::Error,
+    growth_item::GrowthItem,
+    log_item::LogItem,
+    named::Named,
+    prediction::{get_intervals, predict_interval, IntervalPrediction, PlantConditions},
+    schedule::{
+        activity_key, activity_title, interval_on, merge_schedules, next_due_date,
+        next_due_date_with, schedule_on, ActivitySchedule,
+    },
+};
 use chrono::{Local, NaiveDate, TimeDelta};
 use std::cmp::max;
 
@@ -7,7 +17,7 @@ pub mod plant_info;
 pub mod plant_location;
 pub mod plant_species;
 
-pub use plant_image::PlantImage;
+pub use plant_image::{ImageMeta, PlantImage, THUMBNAIL_DIR};
 pub use plant_info::PlantInfo;
 pub use plant_location::PlantLocation;
 pub use plant_species::PlantSpecies;
@@ -32,7 +42,7 @@ impl Plant {
         log::info!("Getting activitiyes {activity_name} for {}", self.info.name);
         let mut activities = vec![];
         for activity in self.activities.iter() {
-            if activity.activity.to_lowercase().trim() == activity_name.to_lowercase().trim() {
+            if activity_key(&activity.activity) == activity_key(activity_name) {
                 activities.push(activity.clone())
             }
         }
@@ -42,10 +52,25 @@ impl Plant {
         activities
     }
 
+    /// Returns the most recent log of an activity
+    pub fn get_last_activity(&self, activity_name: &str) -> Option<LogItem> {
+        log::info!(
+            "Getting last {activity_name} activity for {}",
+            self.info.name
+        );
+        let mut activities = self.get_activities(activity_name);
+        activities.sort_by_key(|log| log.date);
+        activities.last().cloned()
+    }
+
     fn get_watering_activities(&self) -> Vec<LogItem> {
         self.get_activities("watering")
     }
 
+    fn get_fertilizing_activities(&self) -> Vec<LogItem> {
+        self.get_activities("fertilizing")
+    }
+
     pub fn get_last_watering(&self) -> Option<LogItem> {
         log::info!("Getting last watering activity for {}", self.info.name);
         let mut watering_activities = self.get_watering_activities();
@@ -60,10 +85,6 @@ impl Plant {
         watering_activities.last().cloned()
     }
 
-    fn get_fertilizing_activities(&self) -> Vec<LogItem> {
-        self.get_activities("fertilizing")
-    }
-
     pub fn get_age_days(&self) -> i64 {
         log::info!("Getting age for {}", self.info.name);
         let today = Local::now().date_naive();
@@ -71,36 +92,110 @@ impl Plant {
         time_diff.num_days()
     }
 
-    fn get_next_activity_date(&self, activity_name: &str) -> Option<NaiveDate> {
+    /// Schedules of the species, with the schedules of the plant replacing them
+    pub fn get_schedules(&self) -> Vec<ActivitySchedule> {
+        let species_schedules = match &self.info.species {
+            PlantSpecies::Species(species) => species.get_schedules(),
+            PlantSpecies::Other(_) => vec![],
+        };
+        merge_schedules(species_schedules, &self.info.schedules)
+    }
+
+    /// Watering and fertilizing followed by all other scheduled activities
+    pub fn get_scheduled_activities(&self) -> Vec<String> {
+        let mut activities = vec!["Watering".to_owned(), "Fertilizing".to_owned()];
+        for schedule in self.get_schedules() {
+            if !activities.iter().any(|activity| schedule.is_for(activity)) {
+                activities.push(activity_title(&schedule.activity));
+            }
+        }
+        activities
+    }
+
+    pub fn get_activity_delta(&self, activity_name: &str) -> Option<TimeDelta> {
+        interval_on(
+            &self.get_schedules(),
+            activity_name,
+            Local::now().date_naive(),
+        )
+    }
+
+    pub fn get_next_activity_date(&self, activity_name: &str) -> Option<NaiveDate> {
         log::info!(
             "Getting next activity {activity_name} for {}",
             self.info.name
         );
+        if self.info.auto_water && activity_key(activity_name) == "watering" {
+            return None;
+        }
         let self_activities = self.get_activities(activity_name);
         let m_last_activity = self_activities.iter().max();
         let last_growth = self.get_last_growth().map(|gr| gr.health).unwrap_or(1);
         if last_growth == 0 {
             return None;
         }
-        match (m_last_activity, &self.info.species) {
-            (None, _) => Some(Local::now().date_naive()),
-            (_, PlantSpecies::Other(_)) => None,
-            (Some(last_activity), PlantSpecies::Species(species)) => {
-                let activity_delta = species.get_activity_delta(activity_name)?;
-                Some(max(
-                    last_activity.date + activity_delta,
-                    Local::now().date_naive(),
-                ))
-            }
+        let schedules = self.get_schedules();
+        let today = Local::now().date_naive();
+        let is_scheduled = schedules
+            .iter()
+            .any(|schedule| schedule.is_for(activity_name));
+        match m_last_activity {
+            None if !is_scheduled => Some(today),
+            _ if activity_key(activity_name) == "watering" => self.get_next_predicted_watering(
+                &schedules,
+                m_last_activity.map(|last_activity| last_activity.date),
+                today,
+            ),
+            // the interval applying on the due date is used, so nothing is due in seasons without the activity
+            _ => next_due_date(
+                &schedules,
+                activity_name,
+                m_last_activity.map(|last_activity| last_activity.date),
+                today,
+            ),
         }
     }
 
+    /// Watering schedule scaled by the predicted interval, so seasonal differences are kept
+    /// the prediction is used all year if there is no schedule
+    fn get_next_predicted_watering(
+        &self,
+        schedules: &[ActivitySchedule],
+        last: Option<NaiveDate>,
+        today: NaiveDate,
+    ) -> Option<NaiveDate> {
+        let Some(prediction) = self.get_watering_prediction() else {
+            return next_due_date(schedules, "watering", last, today);
+        };
+        let base_days = interval_on(schedules, "watering", today)
+            .map(|interval| interval.num_days() as f32)
+            .filter(|days| *days > 0.0);
+        let interval_fn = |date| {
+            let days = match schedule_on(schedules, "watering", date) {
+                None => Some(prediction.interval_days),
+                Some(schedule) => schedule.get_interval().map(|interval| match base_days {
+                    Some(base) => interval.num_days() as f32 * prediction.interval_days / base,
+                    None => prediction.interval_days,
+                }),
+            };
+            days.map(|days| TimeDelta::days(max(days.round() as i64, 1)))
+        };
+        next_due_date_with(interval_fn, last, today)
+    }
+
+    /// Scheduled activities due on or before `date`
+    pub fn get_due_activities(&self, date: NaiveDate) -> Vec<String> {
+        self.get_scheduled_activities()
+            .into_iter()
+            .filter(|activity| {
+                self.get_next_activity_date(activity)
+                    .is_some_and(|next| next <= date)
+            })
+            .collect()
+    }
+
     pub fn get_next_watering(&self) -> Option<NaiveDate> {
-        if self.info.auto_water {
-            None
-        } else {
-            self.get_next_activity_date("watering")
-        }
+        self.get_next_activity_date("watering")
     }
 
     pub fn get_next_fertilizing(&self) -> Option<NaiveDate> {
@@ -139,7 +234,7 @@ impl Plant {
         }
     }
 
-    fn get_activity_frequency(&self, activity_name: &str) -> Option<f32> {
+    pub fn get_activity_frequency(&self, activity_name: &str) -> Option<f32> {
         log::info!(
             "Getting frequency of activity {} for {}",
             activity_name,
@@ -155,6 +250,38 @@ impl Plant {
         }
     }
 
+    /// Conditions the default interval of the species is adjusted to
+    pub fn get_conditions(&self) -> PlantConditions {
+        PlantConditions {
+            outside: self.is_outside(),
+            size_cm: self
+                .get_last_growth()
+                .ok()
+                .map(|growth| (growth.height_cm + growth.width_cm) / 2.0),
+        }
+    }
+
+    /// Interval of an activity learned from the logs, starting from the scheduled interval
+    pub fn predict_interval(&self, activity_name: &str) -> Option<IntervalPrediction> {
+        log::info!(
+            "Predicting interval of {activity_name} for {}",
+            self.info.name
+        );
+        let default_days = self
+            .get_activity_delta(activity_name)
+            .map(|delta| delta.num_days() as f32);
+        let dates: Vec<NaiveDate> = self
+            .get_activities(activity_name)
+            .iter()
+            .map(|log| log.date)
+            .collect();
+        predict_interval(default_days, self.get_conditions(), &get_intervals(&dates))
+    }
+
+    pub fn get_watering_prediction(&self) -> Option<IntervalPrediction> {
+        self.predict_interval("watering")
+    }
+
     pub fn get_fertilizing_frequency(&self) -> Option<f32> {
         self.get_activity_frequency("fertilizing")
     }
@@ -216,10 +343,19 @@ impl Plant {
         }
     }
 
+    /// The image marked as cover, or the first image if there is none
+    pub fn get_cover_image(&self) -> Option<&PlantImage> {
+        self.images
+            .iter()
+            .find(|image| image.cover)
+            .or(self.images.first())
+    }
+
+    /// Url of the thumbnail of the cover image
     pub fn get_preview_image_url(&self, base: &str) -> Option<String> {
-        let image = self.images.first().cloned()?;
+        let image = self.get_cover_image()?;
         let name = self.get_name().replace(' ', "");
-        let image_url = base.to_owned() + &name + "/" + &image.file_name;
+        let image_url = format!("{base}{name}/{THUMBNAIL_DIR}/{}", image.file_name);
         Some(image_url)
     }
 }
@@ -267,12 +403,15 @@ pub fn sort_age(plants: &[Plant]) -> Result<Vec<(f32, &Plant)>, Error> {
 #[cfg(test)]
 mod plant_tests {
     use super::{sort_age, sort_height, sort_speed, sort_width, PlantSpecies};
+    use crate::log_item::LogItem;
+    use crate::prediction::Confidence;
     use crate::species::Species;
     use crate::test_common::{
         empty_plant, example_activity1, example_activity2, example_date1, example_date2,
         example_growth2, example_plant, example_plant2, example_species,
     };
-    use chrono::Local;
+    use chrono::{Datelike, Local, TimeDelta};
+    use std::cmp::max;
 
     #[test]
     fn into_species() {
@@ -380,6 +519,94 @@ mod plant_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn next_scheduled_activity() {
+        let mut plant = empty_plant();
+        plant.activities.push(LogItem {
+            activity: "Repotting".to_owned(),
+            ..example_activity1()
+        });
+        assert_eq!(plant.get_next_activity_date("repotting"), None);
+        plant.info.schedules = vec!["Repotting: 365".parse().unwrap()];
+        let result = plant.get_next_activity_date("repotting");
+        let expected = Some(max(
+            example_date1() + TimeDelta::days(365),
+            Local::now().date_naive(),
+        ));
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn next_activity_dormant() {
+        let mut plant = example_plant();
+        let today = Local::now().date_naive();
+        let this_month = today.format("%b").to_string();
+        plant.info.schedules = vec![format!("Fertilizing: never @ {this_month}")
+            .parse()
+            .unwrap()];
+        let result = plant.get_next_fertilizing().unwrap();
+        assert!(result > today);
+        assert_ne!(result.month(), today.month())
+    }
+
+    #[test]
+    fn scheduled_activities() {
+        let mut plant = example_plant();
+        plant.info.schedules = vec![
+            "misting: 3".parse().unwrap(),
+            "watering: 2".parse().unwrap(),
+        ];
+        let result = plant.get_scheduled_activities();
+        let expected = vec!["Watering", "Fertilizing", "Misting"];
+        assert_eq!(result, expected);
+        assert_eq!(
+            plant.get_activity_delta("Watering"),
+            Some(TimeDelta::days(2))
+        )
+    }
+
+    #[test]
+    fn due_activities() {
+        let mut plant = example_plant();
+        plant.info.auto_water = true;
+        let result = plant.get_due_activities(Local::now().date_naive());
+        let expected = vec!["Fertilizing"];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn watering_prediction() {
+        let mut plant = empty_plant();
+        plant.info.schedules = vec!["Watering: 10".parse().unwrap()];
+        let today = Local::now().date_naive();
+        for days_ago in [12, 8, 4] {
+            plant.activities.push(LogItem {
+                date: today - TimeDelta::days(days_ago),
+                ..example_activity1()
+            });
+        }
+        let result = plant.get_watering_prediction().unwrap();
+        assert_eq!(result.interval_days, 7.6);
+        assert_eq!(result.confidence, Confidence::Medium);
+        assert_eq!(plant.get_next_watering(), Some(today + TimeDelta::days(4)))
+    }
+
+    #[test]
+    fn watering_prediction_unscheduled() {
+        let mut plant = empty_plant();
+        let today = Local::now().date_naive();
+        for days_ago in [6, 3] {
+            plant.activities.push(LogItem {
+                date: today - TimeDelta::days(days_ago),
+                ..example_activity1()
+            });
+        }
+        let result = plant.get_watering_prediction().unwrap();
+        assert_eq!(result.interval_days, 3.0);
+        assert_eq!(result.confidence, Confidence::Low);
+        assert_eq!(plant.get_next_watering(), Some(today))
+    }
+
     #[test]
     fn next_fertilizing_some() {
         let result = example_plant().get_next_fertilizing();
@@ -508,7 +735,16 @@ mod plant_tests {
     #[test]
     fn preview_url_some() {
         let result = example_plant().get_preview_image_url("img/");
-        let expected = Some("img/APlant/01011970.jpg".to_owned());
+        let expected = Some("img/APlant/thumbs/01011970.jpg".to_owned());
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn preview_url_cover() {
+        let mut plant = example_plant();
+        plant.images[1].cover = true;
+        let result = plant.get_preview_image_url("img/");
+        let expected = Some("img/APlant/thumbs/02011970.jpg".to_owned());
         assert_eq!(result, expected)
     }
 
@@ -1,9 +1,254 @@
 use chrono::NaiveDate;
-use std::path::PathBuf;
+use exif::{DateTime, In, Reader, Tag, Value};
+use image::{codecs::jpeg::JpegDecoder, ImageDecoder};
+use serde::{Deserialize, Serialize};
+use std::{io::Cursor, path::PathBuf};
+
+/// Date format of image names
+const IMAGE_DATE_FORMAT: &str = "%d%m%Y";
+/// Directory next to the rendered images of a plant containing their thumbnails
+pub const THUMBNAIL_DIR: &str = "thumbs";
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct PlantImage {
     pub created: NaiveDate,
     pub file_name: String,
     pub file_path: PathBuf,
+    pub caption: Option<String>,
+    /// Image shown for the plant in lists and first in the gallery
+    pub cover: bool,
+}
+
+/// Caption and cover flag of an image, stored in the database
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ImageMeta {
+    pub plant: String,
+    pub file_name: String,
+    pub caption: Option<String>,
+    pub cover: bool,
+}
+
+impl PlantImage {
+    /// Metadata to store for this image, None if there is nothing to store
+    pub fn get_meta(&self, plant_name: &str) -> Option<ImageMeta> {
+        if self.caption.is_none() && !self.cover {
+            return None;
+        }
+        Some(ImageMeta {
+            plant: plant_name.to_owned(),
+            file_name: self.file_name.clone(),
+            caption: self.caption.clone(),
+            cover: self.cover,
+        })
+    }
+}
+
+/// Sets caption and cover of the images of a plant from the stored metadata
+pub fn apply_image_meta(images: &mut [PlantImage], plant_name: &str, metas: &[ImageMeta]) {
+    for image in images.iter_mut() {
+        if let Some(meta) = metas
+            .iter()
+            .find(|meta| meta.plant == plant_name && meta.file_name == image.file_name)
+        {
+            image.caption = meta.caption.clone();
+            image.cover = meta.cover;
+        }
+    }
+}
+
+/// Name of the `num`th image taken on date (starting at 1)
+/// the first image has no suffix, so names of older images stay valid
+pub fn image_file_name(date: NaiveDate, num: usize) -> String {
+    let date_str = date.format(IMAGE_DATE_FORMAT).to_string();
+    if num <= 1 {
+        format!("{date_str}.jpg")
+    } else {
+        format!("{date_str}_{num}.jpg")
+    }
+}
+
+/// Date in the name of an image, names are `%d%m%Y.jpg` with an optional `_n` suffix
+pub fn image_name_date(file_name: &str) -> Option<NaiveDate> {
+    let stem = file_name.strip_suffix(".jpg")?;
+    let date_str = match stem.split_once('_') {
+        Some((date_str, num)) if num.parse::<usize>().is_ok() => date_str,
+        Some(_) => return None,
+        None => stem,
+    };
+    NaiveDate::parse_from_str(date_str, IMAGE_DATE_FORMAT).ok()
+}
+
+/// Capture date of a jpeg, from the exif DateTimeOriginal tag or the DateTime tag if that is missing
+pub fn exif_date(jpeg: &[u8]) -> Option<NaiveDate> {
+    let exif_data = JpegDecoder::new(Cursor::new(jpeg))
+        .ok()?
+        .exif_metadata()
+        .ok()??;
+    let exif = Reader::new().read_raw(exif_data).ok()?;
+    [Tag::DateTimeOriginal, Tag::DateTime]
+        .into_iter()
+        .find_map(|tag| match &exif.get_field(tag, In::PRIMARY)?.value {
+            Value::Ascii(values) => {
+                let date_time = DateTime::from_ascii(values.first()?).ok()?;
+                NaiveDate::from_ymd_opt(
+                    date_time.year.into(),
+                    date_time.month.into(),
+                    date_time.day.into(),
+                )
+            }
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod plant_image_tests {
+    use super::{
+        apply_image_meta, exif_date, image_file_name, image_name_date, ImageMeta, PlantImage,
+    };
+    use crate::test_common::{example_date1, example_image1, example_image2};
+    use image::{codecs::jpeg::JpegEncoder, ExtendedColorType, ImageEncoder};
+
+    fn example_meta() -> ImageMeta {
+        ImageMeta {
+            plant: "A Plant".to_owned(),
+            file_name: "02011970.jpg".to_owned(),
+            caption: Some("new leaves".to_owned()),
+            cover: true,
+        }
+    }
+
+    /// Single pixel jpeg with exif data, with a date time tag and an exif directory if `original` is set
+    fn example_jpeg(date: &str, original: bool, little_endian: bool) -> Vec<u8> {
+        let u16_bytes = |val: u16| {
+            if little_endian {
+                val.to_le_bytes()
+            } else {
+                val.to_be_bytes()
+            }
+        };
+        let u32_bytes = |val: u32| {
+            if little_endian {
+                val.to_le_bytes()
+            } else {
+                val.to_be_bytes()
+            }
+        };
+        let mut tiff = if little_endian {
+            b"II\x2a\0".to_vec()
+        } else {
+            b"MM\0\x2a".to_vec()
+        };
+        tiff.extend(u32_bytes(8));
+        // directories with a single entry take 18 bytes, the date is stored after them
+        let (tag, date_offset) = if original {
+            tiff.extend(u16_bytes(1));
+            tiff.extend(u16_bytes(0x8769));
+            tiff.extend(u16_bytes(4));
+            tiff.extend(u32_bytes(1));
+            tiff.extend(u32_bytes(26));
+            tiff.extend(u32_bytes(0));
+            (0x9003, 44)
+        } else {
+            (0x0132, 26)
+        };
+        tiff.extend(u16_bytes(1));
+        tiff.extend(u16_bytes(tag));
+        tiff.extend(u16_bytes(2));
+        tiff.extend(u32_bytes(20));
+        tiff.extend(u32_bytes(date_offset));
+        tiff.extend(u32_bytes(0));
+        tiff.extend(date.as_bytes());
+        tiff.push(0);
+
+        let mut jpeg = vec![];
+        let mut encoder = JpegEncoder::new(&mut jpeg);
+        encoder.set_exif_metadata(tiff).unwrap();
+        encoder
+            .write_image(&[0, 0, 0], 1, 1, ExtendedColorType::Rgb8)
+            .unwrap();
+        jpeg
+    }
+
+    #[test]
+    fn file_name_first() {
+        let result = image_file_name(example_date1(), 1);
+        assert_eq!(result, "01011970.jpg")
+    }
+
+    #[test]
+    fn file_name_suffix() {
+        let result = image_file_name(example_date1(), 3);
+        assert_eq!(result, "01011970_3.jpg")
+    }
+
+    #[test]
+    fn name_date() {
+        let result = image_name_date("01011970.jpg");
+        assert_eq!(result, Some(example_date1()))
+    }
+
+    #[test]
+    fn name_date_suffix() {
+        let result = image_name_date("01011970_2.jpg");
+        assert_eq!(result, Some(example_date1()))
+    }
+
+    #[test]
+    fn name_date_fail() {
+        assert_eq!(image_name_date("IMG_1234.jpg"), None);
+        assert_eq!(image_name_date("01011970.png"), None)
+    }
+
+    #[test]
+    fn exif_original() {
+        let result = exif_date(&example_jpeg("1970:01:01 12:00:00", true, false));
+        assert_eq!(result, Some(example_date1()))
+    }
+
+    #[test]
+    fn exif_date_time() {
+        let result = exif_date(&example_jpeg("1970:01:01 12:00:00", false, true));
+        assert_eq!(result, Some(example_date1()))
+    }
+
+    #[test]
+    fn exif_none() {
+        let mut jpeg = vec![];
+        JpegEncoder::new(&mut jpeg)
+            .write_image(&[0, 0, 0], 1, 1, ExtendedColorType::Rgb8)
+            .unwrap();
+        assert_eq!(exif_date(&jpeg), None);
+        assert_eq!(exif_date(b"not a jpeg"), None)
+    }
+
+    #[test]
+    fn meta_none() {
+        assert_eq!(example_image1().get_meta("A Plant"), None)
+    }
+
+    #[test]
+    fn meta_some() {
+        let image = PlantImage {
+            caption: Some("new leaves".to_owned()),
+            cover: true,
+            ..example_image2()
+        };
+        assert_eq!(image.get_meta("A Plant"), Some(example_meta()))
+    }
+
+    #[test]
+    fn apply_meta() {
+        let mut images = vec![example_image1(), example_image2()];
+        apply_image_meta(&mut images, "A Plant", &[example_meta()]);
+        assert_eq!(images[0], example_image1());
+        assert_eq!(images[1].caption, Some("new leaves".to_owned()));
+        assert!(images[1].cover)
+    }
+
+    #[test]
+    fn apply_meta_other_plant() {
+        let mut images = vec![example_image2()];
+        apply_image_meta(&mut images, "Another Plant", &[example_meta()]);
+        assert_eq!(images[0], example_image2())
+    }
 }
@@ -1,13 +1,14 @@
 use super::{PlantLocation, PlantSpecies};
 use crate::{
     errors::Error,
+    schedule::ActivitySchedule,
     serialize::{date_serializer, location_serializer, species_serializer},
 };
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Serialize, Clone, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Clone, Deserialize, Debug, PartialEq)]
 pub struct PlantInfo {
     pub name: String,
     #[serde(with = "species_serializer")]
@@ -19,12 +20,15 @@ pub struct PlantInfo {
     pub obtained: NaiveDate,
     pub auto_water: bool,
     pub notes: Vec<String>,
+    /// Schedules of this plant, replacing the ones of its species for the same activity
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<ActivitySchedule>,
 }
 
 impl TryFrom<HashMap<String, String>> for PlantInfo {
     type Error = Error;
     ///Converts from a HashMap generated by the sqlite backend
-    ///requires all keys to be present
+    ///requires all keys except "notes" to be present
     ///including a key "date_format" to parse dates
     fn try_from(map: HashMap<String, String>) -> Result<PlantInfo, Error> {
         let date_format = map.get("date_format").ok_or(Error::KeyNotFound {
@@ -40,8 +44,17 @@ impl TryFrom<HashMap<String, String>> for PlantInfo {
 
         let species_name = read_fun("species")?;
         let location_name = read_fun("location")?;
-        let auto_water = read_fun("auto_water")? == "1";
-        let notes = read_fun("notes")?;
+        let auto_water = matches!(read_fun("auto_water")?.as_str(), "1" | "true");
+        let notes = map
+            .get("notes")
+            .map(|notes| {
+                notes
+                    .split(", ")
+                    .filter(|note| !note.is_empty())
+                    .map(|note| note.to_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
         Ok(PlantInfo {
             name: read_fun("name")?,
             species: PlantSpecies::Other(species_name),
@@ -49,7 +62,8 @@ impl TryFrom<HashMap<String, String>> for PlantInfo {
             origin: read_fun("origin")?,
             obtained: NaiveDate::parse_from_str(&read_fun("obtained")?, &date_format)?,
             auto_water,
-            notes: notes.split(", ").map(|s| s.to_owned()).collect(),
+            notes,
+            schedules: vec![],
         })
     }
 }
@@ -1,7 +1,7 @@
 use crate::location::Location;
 use std::fmt;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum PlantLocation {
     Location(Box<Location>),
     Other(String),
@@ -2,6 +2,7 @@ use super::{
     errors::Error,
     plant::PlantSpecies,
     plant::{Plant, PlantLocation},
+    schedule::ActivitySchedule,
 };
 use chrono::NaiveDate;
 use std::{fmt, str::FromStr};
@@ -14,6 +15,7 @@ pub enum UpdateValue {
     Date(NaiveDate),
     Bool(bool),
     Note(Vec<String>, bool),
+    Schedules(Vec<ActivitySchedule>),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -24,6 +26,7 @@ pub enum UpdateField {
     Obtained,
     AutoWater,
     Notes,
+    Schedules,
 }
 
 impl UpdateField {
@@ -34,6 +37,7 @@ impl UpdateField {
         all_fields.extend(UpdateField::get_date_fields());
         all_fields.extend(UpdateField::get_note_fields());
         all_fields.extend(UpdateField::get_bool_fields());
+        all_fields.extend(UpdateField::get_schedule_fields());
         all_fields.iter().map(|field| field.to_string()).collect()
     }
 
@@ -58,6 +62,10 @@ impl UpdateField {
     pub fn get_note_fields() -> Vec<UpdateField> {
         vec![UpdateField::Notes]
     }
+
+    pub fn get_schedule_fields() -> Vec<UpdateField> {
+        vec![UpdateField::Schedules]
+    }
 }
 
 impl fmt::Display for UpdateField {
@@ -69,6 +77,7 @@ impl fmt::Display for UpdateField {
             UpdateField::Obtained => frmt.write_str("Obtained"),
             UpdateField::AutoWater => frmt.write_str("Auto Watered"),
             UpdateField::Notes => frmt.write_str("Notes"),
+            UpdateField::Schedules => frmt.write_str("Schedules"),
         }
     }
 }
@@ -84,6 +93,7 @@ impl FromStr for UpdateField {
             "obtained" => Ok(UpdateField::Obtained),
             "autowatered" => Ok(UpdateField::AutoWater),
             "notes" => Ok(UpdateField::Notes),
+            "schedules" => Ok(UpdateField::Schedules),
             _ => Err(Error::FieldError(s.to_owned())),
         }
     }
@@ -147,6 +157,14 @@ pub fn update_plant(
                 Err(field_err)
             }
         }
+        UpdateValue::Schedules(schedules) => {
+            if let UpdateField::Schedules = field {
+                plant.info.schedules = schedules;
+                Ok(())
+            } else {
+                Err(field_err)
+            }
+        }
     }
 }
 
@@ -169,6 +187,7 @@ mod plant_udpate_tests {
             "Obtained".to_owned(),
             "Notes".to_owned(),
             "Auto Watered".to_owned(),
+            "Schedules".to_owned(),
         ];
         assert_eq!(result, expected)
     }
@@ -257,6 +276,13 @@ mod plant_udpate_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn schedules_field() {
+        let result = UpdateField::from_str("schedules").unwrap();
+        let expected = UpdateField::Schedules;
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn str_fail() {
         let result = UpdateField::from_str("other");
@@ -415,4 +441,19 @@ mod plant_udpate_tests {
         );
         assert!(result.is_err())
     }
+
+    #[test]
+    fn update_schedules() {
+        let mut result = example_plant();
+        let mut expected = example_plant();
+        let schedules = vec!["Repotting: 365".parse().unwrap()];
+        update_plant(
+            &mut result,
+            UpdateField::Schedules,
+            UpdateValue::Schedules(schedules.clone()),
+        )
+        .unwrap();
+        expected.info.schedules = schedules;
+        assert_eq!(result, expected)
+    }
 }
@@ -0,0 +1,216 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Weight of the species default, counted as this many logged intervals
+const PRIOR_WEIGHT: f32 = 3.0;
+/// Only the most recent intervals are used, so changes in care are picked up
+const MAX_INTERVALS: usize = 10;
+/// Outside plants dry out faster from sun and wind
+const OUTSIDE_FACTOR: f32 = 0.8;
+/// Plant size (average of height and width) the species default is assumed to be for
+const REFERENCE_SIZE_CM: f32 = 30.0;
+/// Bounds of the size factor, so very small or large plants are not scheduled absurdly
+const MIN_SIZE_FACTOR: f32 = 0.75;
+const MAX_SIZE_FACTOR: f32 = 1.25;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// Interval between two activities predicted from the species default and the logged history
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct IntervalPrediction {
+    pub interval_days: f32,
+    pub confidence: Confidence,
+    /// Number of logged intervals the prediction is based on
+    pub num_intervals: usize,
+}
+
+/// Conditions of a plant adjusting the species default
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlantConditions {
+    pub outside: bool,
+    /// Average of height and width of the last growth entry
+    pub size_cm: Option<f32>,
+}
+
+impl PlantConditions {
+    /// Larger plants usually have larger pots which hold water longer
+    pub fn get_factor(&self) -> f32 {
+        let size_factor = self
+            .size_cm
+            .filter(|size| *size > 0.0)
+            .map(|size| {
+                (size / REFERENCE_SIZE_CM)
+                    .powf(0.25)
+                    .clamp(MIN_SIZE_FACTOR, MAX_SIZE_FACTOR)
+            })
+            .unwrap_or(1.0);
+        let location_factor = if self.outside { OUTSIDE_FACTOR } else { 1.0 };
+        size_factor * location_factor
+    }
+}
+
+/// Days between consecutive dates, ignoring multiple logs on the same day
+pub fn get_intervals(dates: &[NaiveDate]) -> Vec<f32> {
+    let mut dates = dates.to_vec();
+    dates.sort();
+    dates.dedup();
+    let intervals: Vec<f32> = dates
+        .windows(2)
+        .map(|window| (window[1] - window[0]).num_days() as f32)
+        .collect();
+    let skip = intervals.len().saturating_sub(MAX_INTERVALS);
+    intervals.into_iter().skip(skip).collect()
+}
+
+fn get_confidence(intervals: &[f32], mean: f32) -> Confidence {
+    if intervals.len() < 2 || mean <= 0.0 {
+        return Confidence::Low;
+    }
+    let variance = intervals
+        .iter()
+        .map(|interval| (interval - mean).powi(2))
+        .sum::<f32>()
+        / intervals.len() as f32;
+    let variation = variance.sqrt() / mean;
+    match intervals.len() {
+        n if n >= 5 && variation <= 0.35 => Confidence::High,
+        _ if variation <= 0.6 => Confidence::Medium,
+        _ => Confidence::Low,
+    }
+}
+
+/// Blends the species default (adjusted to the plant's conditions) with the logged intervals
+/// the more intervals are logged, the less the default counts
+pub fn predict_interval(
+    default_days: Option<f32>,
+    conditions: PlantConditions,
+    intervals: &[f32],
+) -> Option<IntervalPrediction> {
+    let prior = default_days.map(|days| days * conditions.get_factor());
+    let num_intervals = intervals.len();
+    let observed = if num_intervals == 0 {
+        None
+    } else {
+        Some(intervals.iter().sum::<f32>() / num_intervals as f32)
+    };
+    let interval_days = match (prior, observed) {
+        (None, None) => return None,
+        (Some(prior), None) => prior,
+        (None, Some(observed)) => observed,
+        (Some(prior), Some(observed)) => {
+            (prior * PRIOR_WEIGHT + observed * num_intervals as f32)
+                / (PRIOR_WEIGHT + num_intervals as f32)
+        }
+    };
+    let confidence = observed
+        .map(|observed| get_confidence(intervals, observed))
+        .unwrap_or(Confidence::Low);
+    Some(IntervalPrediction {
+        interval_days: interval_days.max(1.0),
+        confidence,
+        num_intervals,
+    })
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Confidence::Low => frmt.write_str("low"),
+            Confidence::Medium => frmt.write_str("medium"),
+            Confidence::High => frmt.write_str("high"),
+        }
+    }
+}
+
+impl fmt::Display for IntervalPrediction {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            frmt,
+            "{:.1} days ({} confidence)",
+            self.interval_days, self.confidence
+        )
+    }
+}
+
+#[cfg(test)]
+mod prediction_tests {
+    use super::{get_intervals, predict_interval, Confidence, IntervalPrediction, PlantConditions};
+    use chrono::NaiveDate;
+
+    fn indoors() -> PlantConditions {
+        PlantConditions {
+            outside: false,
+            size_cm: None,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    #[test]
+    fn intervals() {
+        let result = get_intervals(&[date(10), date(1), date(4), date(4)]);
+        let expected = vec![3.0, 6.0];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn predict_default_only() {
+        let result = predict_interval(Some(7.0), indoors(), &[]).unwrap();
+        let expected = IntervalPrediction {
+            interval_days: 7.0,
+            confidence: Confidence::Low,
+            num_intervals: 0,
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn predict_blended() {
+        let result = predict_interval(Some(7.0), indoors(), &[3.0, 3.0, 3.0]).unwrap();
+        assert_eq!(result.interval_days, 5.0);
+        assert_eq!(result.confidence, Confidence::Medium)
+    }
+
+    #[test]
+    fn predict_high_confidence() {
+        let intervals = [4.0, 5.0, 4.0, 4.0, 5.0, 4.0];
+        let result = predict_interval(None, indoors(), &intervals).unwrap();
+        assert_eq!(result.confidence, Confidence::High)
+    }
+
+    #[test]
+    fn predict_none() {
+        assert_eq!(predict_interval(None, indoors(), &[]), None)
+    }
+
+    #[test]
+    fn conditions_factor() {
+        let conditions = PlantConditions {
+            outside: true,
+            size_cm: Some(30.0),
+        };
+        assert_eq!(conditions.get_factor(), 0.8);
+        let conditions = PlantConditions {
+            outside: false,
+            size_cm: Some(1000.0),
+        };
+        assert_eq!(conditions.get_factor(), 1.25)
+    }
+
+    #[test]
+    fn display() {
+        let result = predict_interval(Some(7.0), indoors(), &[])
+            .unwrap()
+            .to_string();
+        let expected = "7.0 days (low confidence)";
+        assert_eq!(result, expected)
+    }
+}
@@ -0,0 +1,379 @@
+use super::errors::Error;
+use chrono::{Datelike, NaiveDate, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// How far ahead next due dates are searched, a bit more than two years
+const MAX_SEARCH_DAYS: i64 = 2 * 366;
+
+/// A recurring care activity, due `interval_days` days after it was last logged
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ActivitySchedule {
+    pub activity: String,
+    /// `None` if the activity should not be done at all, e.g. fertilizing during dormancy
+    pub interval_days: Option<i32>,
+    /// Months (1-12) the schedule applies in, the whole year if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub months: Vec<u32>,
+}
+
+impl ActivitySchedule {
+    pub fn get_interval(&self) -> Option<TimeDelta> {
+        self.interval_days.map(|days| TimeDelta::days(days as i64))
+    }
+
+    /// Activities are compared ignoring case and surrounding whitespace, as logs are free text
+    pub fn is_for(&self, activity_name: &str) -> bool {
+        activity_key(&self.activity) == activity_key(activity_name)
+    }
+
+    pub fn applies_on(&self, date: NaiveDate) -> bool {
+        self.months.is_empty() || self.months.contains(&date.month())
+    }
+}
+
+pub fn activity_key(activity_name: &str) -> String {
+    activity_name.trim().to_lowercase()
+}
+
+/// Capitalizes an activity name for display, e.g. "repotting" becomes "Repotting"
+pub fn activity_title(activity_name: &str) -> String {
+    let key = activity_key(activity_name);
+    let mut chars = key.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+    }
+}
+
+/// Adds `overrides` to `schedules`, so the overrides apply whenever they cover a date
+/// schedules for the whole year replace all schedules of the same activity,
+/// schedules for some months take over these months from the schedules of the same activity
+pub fn merge_schedules(
+    schedules: Vec<ActivitySchedule>,
+    overrides: &[ActivitySchedule],
+) -> Vec<ActivitySchedule> {
+    let mut merged: Vec<ActivitySchedule> = schedules
+        .into_iter()
+        .filter_map(|mut schedule| {
+            let activity_overrides: Vec<&ActivitySchedule> = overrides
+                .iter()
+                .filter(|over| over.is_for(&schedule.activity))
+                .collect();
+            if activity_overrides.iter().any(|over| over.months.is_empty()) {
+                return None;
+            }
+            // schedules for the whole year already lose to schedules for some months
+            if schedule.months.is_empty() {
+                return Some(schedule);
+            }
+            schedule.months.retain(|month| {
+                !activity_overrides
+                    .iter()
+                    .any(|over| over.months.contains(month))
+            });
+            (!schedule.months.is_empty()).then_some(schedule)
+        })
+        .collect();
+    merged.extend(overrides.iter().cloned());
+    merged
+}
+
+/// The schedule of an activity applying on `date`
+/// schedules for some months take precedence over schedules for the whole year
+pub fn schedule_on<'a>(
+    schedules: &'a [ActivitySchedule],
+    activity_name: &str,
+    date: NaiveDate,
+) -> Option<&'a ActivitySchedule> {
+    schedules
+        .iter()
+        .filter(|schedule| schedule.is_for(activity_name) && schedule.applies_on(date))
+        .min_by_key(|schedule| schedule.months.is_empty())
+}
+
+pub fn interval_on(
+    schedules: &[ActivitySchedule],
+    activity_name: &str,
+    date: NaiveDate,
+) -> Option<TimeDelta> {
+    schedule_on(schedules, activity_name, date)?.get_interval()
+}
+
+/// The first date from `today` on, where the interval applying on that date has passed since `last`
+/// `None` if there is no such date, e.g. when there is no schedule for the activity
+pub fn next_due_date(
+    schedules: &[ActivitySchedule],
+    activity_name: &str,
+    last: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Option<NaiveDate> {
+    next_due_date_with(
+        |date| interval_on(schedules, activity_name, date),
+        last,
+        today,
+    )
+}
+
+/// Same as `next_due_date`, with the interval applying on a date given by `interval_fn`
+pub fn next_due_date_with<F>(
+    interval_fn: F,
+    last: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Option<NaiveDate>
+where
+    F: Fn(NaiveDate) -> Option<TimeDelta>,
+{
+    (0..MAX_SEARCH_DAYS)
+        .map(|offset| today + TimeDelta::days(offset))
+        .find(|date| match interval_fn(*date) {
+            None => false,
+            Some(interval) => last.is_none_or(|last| *date - last >= interval),
+        })
+}
+
+/// Parses a comma separated list of schedules, e.g. "Repotting: 365, Fertilizing: never @ Nov-Feb"
+pub fn parse_schedules(input: &str) -> Result<Vec<ActivitySchedule>, Error> {
+    input
+        .split(',')
+        .filter(|schedule| !schedule.trim().is_empty())
+        .map(|schedule| schedule.parse::<ActivitySchedule>())
+        .collect()
+}
+
+fn parse_month(input: &str) -> Option<u32> {
+    let input = input.trim();
+    if let Ok(month) = input.parse::<u32>() {
+        return (1..=12).contains(&month).then_some(month);
+    }
+    let prefix: String = input.chars().take(3).collect();
+    MONTH_NAMES
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(&prefix))
+        .map(|ind| ind as u32 + 1)
+}
+
+/// Parses space separated months or ranges of months, e.g. "Nov-Feb" or "6 7 8"
+pub fn parse_months(input: &str) -> Option<Vec<u32>> {
+    let mut months = vec![];
+    for part in input.split_whitespace() {
+        match part.split_once('-') {
+            None => months.push(parse_month(part)?),
+            Some((from, to)) => {
+                let (from, to) = (parse_month(from)?, parse_month(to)?);
+                let mut month = from;
+                months.push(month);
+                while month != to {
+                    month = month % 12 + 1;
+                    months.push(month);
+                }
+            }
+        }
+    }
+    months.sort();
+    months.dedup();
+    Some(months)
+}
+
+/// Space separated month names, e.g. "Jan Feb"
+pub fn format_months(months: &[u32]) -> String {
+    let names: Vec<&str> = months
+        .iter()
+        .filter_map(|month| MONTH_NAMES.get((*month as usize).wrapping_sub(1)).copied())
+        .collect();
+    names.join(" ")
+}
+
+impl FromStr for ActivitySchedule {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<ActivitySchedule, Error> {
+        let err = || Error::ScheduleError(s.trim().to_owned());
+        let (schedule, months) = match s.split_once('@') {
+            None => (s, vec![]),
+            Some((schedule, months)) => (schedule, parse_months(months).ok_or_else(err)?),
+        };
+        let (activity, days) = schedule.split_once(':').ok_or_else(err)?;
+        let interval_days = match days.trim().to_lowercase().as_str() {
+            "never" => None,
+            days => Some(days.parse::<i32>().map_err(|_| err())?),
+        };
+        if activity.trim().is_empty() || interval_days.is_some_and(|days| days <= 0) {
+            return Err(err());
+        }
+        Ok(ActivitySchedule {
+            activity: activity.trim().to_owned(),
+            interval_days,
+            months,
+        })
+    }
+}
+
+impl fmt::Display for ActivitySchedule {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.interval_days {
+            None => write!(frmt, "{}: never", self.activity)?,
+            Some(days) => write!(frmt, "{}: {}", self.activity, days)?,
+        }
+        if !self.months.is_empty() {
+            write!(frmt, " @ {}", format_months(&self.months))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod schedule_tests {
+    use super::{
+        activity_title, interval_on, merge_schedules, next_due_date, parse_schedules,
+        ActivitySchedule,
+    };
+    use chrono::{Datelike, NaiveDate, TimeDelta};
+
+    fn schedule(activity: &str, interval_days: i32) -> ActivitySchedule {
+        ActivitySchedule {
+            activity: activity.to_owned(),
+            interval_days: Some(interval_days),
+            months: vec![],
+        }
+    }
+
+    fn seasonal_schedules() -> Vec<ActivitySchedule> {
+        parse_schedules(
+            "Fertilizing: 14, Fertilizing: never @ Nov-Feb, Watering: 7, Watering: 21 @ Dec",
+        )
+        .unwrap()
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn schedule_is_for() {
+        assert!(schedule("Repotting", 365).is_for(" repotting"));
+        assert!(!schedule("Repotting", 365).is_for("pruning"))
+    }
+
+    #[test]
+    fn title() {
+        let result = activity_title(" pest treatment");
+        let expected = "Pest treatment";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn merge() {
+        let result = merge_schedules(
+            vec![schedule("Watering", 7), schedule("Misting", 3)],
+            &[schedule("watering", 4)],
+        );
+        let expected = vec![schedule("Misting", 3), schedule("watering", 4)];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn merge_replaces_seasonal() {
+        let merged = merge_schedules(seasonal_schedules(), &[schedule("Watering", 3)]);
+        let result = interval_on(&merged, "Watering", date(12, 1));
+        assert_eq!(result, Some(TimeDelta::days(3)))
+    }
+
+    #[test]
+    fn merge_seasonal_months() {
+        let merged = merge_schedules(
+            parse_schedules("Watering: 7, Watering: 21 @ Nov-Jan").unwrap(),
+            &parse_schedules("Watering: 10 @ Dec").unwrap(),
+        );
+        let result: Vec<_> = [date(10, 1), date(11, 1), date(12, 1), date(1, 1)]
+            .into_iter()
+            .map(|date| interval_on(&merged, "Watering", date))
+            .collect();
+        let expected: Vec<_> = [7, 21, 10, 21]
+            .into_iter()
+            .map(|days| Some(TimeDelta::days(days)))
+            .collect();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse() {
+        let result = parse_schedules("Repotting: 365, Misting:3,").unwrap();
+        let expected = vec![schedule("Repotting", 365), schedule("Misting", 3)];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_empty() {
+        let result = parse_schedules(" ").unwrap();
+        assert_eq!(result, vec![])
+    }
+
+    #[test]
+    fn parse_fail() {
+        assert!(parse_schedules("Repotting").is_err());
+        assert!(parse_schedules("Repotting: 0").is_err());
+        assert!(parse_schedules("Repotting: often").is_err())
+    }
+
+    #[test]
+    fn display() {
+        let result = schedule("Repotting", 365).to_string();
+        let expected = "Repotting: 365";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_seasonal() {
+        let result = "Fertilizing: never @ Nov-Feb"
+            .parse::<ActivitySchedule>()
+            .unwrap();
+        let expected = ActivitySchedule {
+            activity: "Fertilizing".to_owned(),
+            interval_days: None,
+            months: vec![1, 2, 11, 12],
+        };
+        assert_eq!(result, expected);
+        assert!("Fertilizing: 14 @ Smarch"
+            .parse::<ActivitySchedule>()
+            .is_err())
+    }
+
+    #[test]
+    fn display_seasonal() {
+        let result = "Watering: 21 @ 12 jan"
+            .parse::<ActivitySchedule>()
+            .unwrap()
+            .to_string();
+        let expected = "Watering: 21 @ Jan Dec";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn seasonal_interval() {
+        let schedules = seasonal_schedules();
+        assert_eq!(
+            interval_on(&schedules, "watering", date(12, 1)),
+            Some(TimeDelta::days(21))
+        );
+        assert_eq!(
+            interval_on(&schedules, "watering", date(11, 1)),
+            Some(TimeDelta::days(7))
+        );
+        assert_eq!(interval_on(&schedules, "fertilizing", date(1, 1)), None)
+    }
+
+    #[test]
+    fn next_due_seasonal() {
+        let schedules = seasonal_schedules();
+        let result = next_due_date(&schedules, "Watering", Some(date(11, 28)), date(11, 30));
+        assert_eq!(result, Some(date(12, 19)));
+        let result = next_due_date(&schedules, "Fertilizing", Some(date(10, 1)), date(12, 10));
+        assert_eq!(result, Some(date(3, 1).with_year(2025).unwrap()));
+        let result = next_due_date(&schedules, "Repotting", None, date(12, 10));
+        assert_eq!(result, None)
+    }
+}
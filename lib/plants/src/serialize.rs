@@ -37,6 +37,25 @@ pub mod species_serializer {
     }
 }
 
+pub mod climate_serializer {
+    use crate::climate::{format_climate, parse_climate, LocationClimate};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        climate: &[LocationClimate],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_climate(climate))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<LocationClimate>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        parse_climate(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 pub mod location_serializer {
     use crate::{named::Named, plant::PlantLocation};
     use serde::{Deserialize, Deserializer, Serializer};
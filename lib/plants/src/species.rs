@@ -2,8 +2,9 @@ use super::{
     errors::Error,
     named::Named,
     plant::{Plant, PlantSpecies},
+    schedule::{interval_on, merge_schedules, ActivitySchedule},
 };
-use chrono::TimeDelta;
+use chrono::{Local, NaiveDate, TimeDelta};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt, str::FromStr};
 
@@ -35,20 +36,42 @@ pub struct Species {
     pub pruning_notes: Vec<String>,
     pub companions: Vec<String>,
     pub additional_notes: Vec<String>,
+    /// Recurring activities besides watering and fertilizing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<ActivitySchedule>,
 }
 
 impl Species {
     pub fn get_activity_delta(&self, activity_name: &str) -> Option<TimeDelta> {
+        self.get_activity_delta_on(activity_name, Local::now().date_naive())
+    }
+
+    /// Time between two activities for the season of `date`
+    pub fn get_activity_delta_on(&self, activity_name: &str, date: NaiveDate) -> Option<TimeDelta> {
         log::info!(
             "Getting time delta between {} for {}",
             activity_name,
             self.name
         );
-        match activity_name.to_lowercase().trim() {
-            "watering" => self.avg_watering_days.map(|x| TimeDelta::days(x as i64)),
-            "fertilizing" => self.avg_fertilizing_days.map(|x| TimeDelta::days(x as i64)),
-            _ => None,
-        }
+        interval_on(&self.get_schedules(), activity_name, date)
+    }
+
+    /// Watering and fertilizing intervals for the whole year together with all other schedules of the species
+    pub fn get_schedules(&self) -> Vec<ActivitySchedule> {
+        let defaults = [
+            ("Watering", self.avg_watering_days),
+            ("Fertilizing", self.avg_fertilizing_days),
+        ]
+        .into_iter()
+        .filter_map(|(activity, days)| {
+            days.map(|days| ActivitySchedule {
+                activity: activity.to_owned(),
+                interval_days: Some(days),
+                months: vec![],
+            })
+        })
+        .collect();
+        merge_schedules(defaults, &self.schedules)
     }
 
     pub fn get_url(&self, base: &str) -> String {
@@ -90,6 +113,7 @@ impl std::hash::Hash for Species {
         self.pruning_notes.hash(state);
         self.companions.hash(state);
         self.additional_notes.hash(state);
+        self.schedules.hash(state);
     }
 }
 
@@ -127,33 +151,60 @@ impl TryFrom<HashMap<String, String>> for Species {
             .transpose()?;
         let watering_notes = map
             .get("watering_notes")
-            .map(|s| s.split(", ").map(|x| x.to_owned()).collect())
+            .map(|s| {
+                s.split(", ")
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.to_owned())
+                    .collect()
+            })
             .unwrap_or(vec![]);
 
         let avg_watering_days = map
             .get("avg_watering_days")
+            .filter(|d| !d.is_empty())
             .map(|d| d.parse::<i32>())
             .transpose()?;
         let avg_fertilizing_days = map
-            .get("avg_watering_days")
+            .get("avg_fertilizing_days")
+            .filter(|d| !d.is_empty())
             .map(|d| d.parse::<i32>())
             .transpose()?;
 
         let fertilizing_notes = map
             .get("fertilizing_notes")
-            .map(|s| s.split(", ").map(|x| x.to_owned()).collect())
+            .map(|s| {
+                s.split(", ")
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.to_owned())
+                    .collect()
+            })
             .unwrap_or(vec![]);
         let pruning_notes = map
             .get("pruning_notes")
-            .map(|s| s.split(", ").map(|x| x.to_owned()).collect())
+            .map(|s| {
+                s.split(", ")
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.to_owned())
+                    .collect()
+            })
             .unwrap_or(vec![]);
         let companions = map
             .get("companions")
-            .map(|s| s.split(", ").map(|x| x.to_owned()).collect())
+            .map(|s| {
+                s.split(", ")
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.to_owned())
+                    .collect()
+            })
             .unwrap_or(vec![]);
         let additional_notes = map
             .get("additional_notes")
-            .map(|s| s.split(", ").map(|x| x.to_owned()).collect())
+            .map(|s| {
+                s.split(", ")
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.to_owned())
+                    .collect()
+            })
             .unwrap_or(vec![]);
 
         Ok(Species {
@@ -176,6 +227,7 @@ impl TryFrom<HashMap<String, String>> for Species {
             pruning_notes,
             companions,
             additional_notes,
+            schedules: vec![],
         })
     }
 }
@@ -204,10 +256,13 @@ impl fmt::Display for SunlightRequirement {
 
 #[cfg(test)]
 mod species_tests {
-    use super::SunlightRequirement;
-    use crate::test_common::{empty_plant, example_plant, example_plant2, example_species};
-    use chrono::TimeDelta;
-    use std::str::FromStr;
+    use super::{Species, SunlightRequirement};
+    use crate::{
+        schedule::parse_schedules,
+        test_common::{empty_plant, example_plant, example_plant2, example_species},
+    };
+    use chrono::{NaiveDate, TimeDelta};
+    use std::{collections::HashMap, str::FromStr};
 
     #[test]
     fn sunlight_direct() {
@@ -272,6 +327,41 @@ mod species_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn scheduled_delta() {
+        let mut species = example_species();
+        species.schedules = vec![
+            "Repotting: 365".parse().unwrap(),
+            "watering: 3".parse().unwrap(),
+        ];
+        assert_eq!(
+            species.get_activity_delta("repotting"),
+            Some(TimeDelta::days(365))
+        );
+        assert_eq!(
+            species.get_activity_delta("Watering"),
+            Some(TimeDelta::days(3))
+        )
+    }
+
+    #[test]
+    fn seasonal_delta() {
+        let mut species = example_species();
+        species.schedules =
+            parse_schedules("Fertilizing: never @ Nov-Feb, Watering: 21 @ Dec").unwrap();
+        let winter = NaiveDate::from_ymd_opt(2024, 12, 24).unwrap();
+        let summer = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        assert_eq!(species.get_activity_delta_on("fertilizing", winter), None);
+        assert_eq!(
+            species.get_activity_delta_on("fertilizing", summer),
+            Some(TimeDelta::days(14))
+        );
+        assert_eq!(
+            species.get_activity_delta_on("watering", winter),
+            Some(TimeDelta::days(21))
+        )
+    }
+
     #[test]
     fn species_plants() {
         let result = example_species()
@@ -286,4 +376,30 @@ mod species_tests {
         let expected = "species/Testspecies.html";
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn species_from_map() {
+        let map = HashMap::from([
+            ("name".to_owned(), "Test species".to_owned()),
+            ("scientific_name".to_owned(), "Scientific Name".to_owned()),
+            ("genus".to_owned(), "Genus".to_owned()),
+            ("family".to_owned(), "Family".to_owned()),
+            ("sunlight".to_owned(), "Direct".to_owned()),
+            ("temp_min".to_owned(), "0.0".to_owned()),
+            ("temp_max".to_owned(), "30.0".to_owned()),
+            ("temp_min_opt".to_owned(), "10.0".to_owned()),
+            ("temp_max_opt".to_owned(), "25.0".to_owned()),
+            ("planting_distance".to_owned(), "".to_owned()),
+            ("ph_min".to_owned(), "4.5".to_owned()),
+            ("ph_max".to_owned(), "8.5".to_owned()),
+            ("avg_watering_days".to_owned(), "7".to_owned()),
+            ("avg_fertilizing_days".to_owned(), "".to_owned()),
+            ("watering_notes".to_owned(), "".to_owned()),
+        ]);
+        let result = Species::try_from(map).unwrap();
+        let mut expected = example_species();
+        expected.planting_distance = None;
+        expected.avg_fertilizing_days = None;
+        assert_eq!(result, expected)
+    }
 }
@@ -1,5 +1,6 @@
 use super::{
     errors::Error,
+    schedule::{parse_schedules, ActivitySchedule},
     species::{Species, SunlightRequirement},
 };
 use std::{fmt, str::FromStr};
@@ -12,6 +13,7 @@ pub enum UpdateValue {
     MFl(Option<f32>),
     Note(Vec<String>, bool),
     MInt(Option<i32>),
+    Schedules(Vec<ActivitySchedule>),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -34,17 +36,26 @@ pub enum UpdateField {
     PruningNotes,
     Companions,
     AdditionalNotes,
+    Schedules,
 }
 
 impl UpdateField {
-    pub fn fields_strs() -> Vec<String> {
+    pub fn all_fields() -> Vec<UpdateField> {
         let mut all_fields = UpdateField::get_str_fields();
         all_fields.extend(UpdateField::get_sun_fields());
         all_fields.extend(UpdateField::get_fl_fields());
         all_fields.extend(UpdateField::get_mfl_fields());
         all_fields.extend(UpdateField::get_note_fields());
         all_fields.extend(UpdateField::get_mint_fields());
-        all_fields.iter().map(|field| field.to_string()).collect()
+        all_fields.extend(UpdateField::get_schedule_fields());
+        all_fields
+    }
+
+    pub fn fields_strs() -> Vec<String> {
+        UpdateField::all_fields()
+            .iter()
+            .map(|field| field.to_string())
+            .collect()
     }
 
     pub fn get_str_fields() -> Vec<UpdateField> {
@@ -85,6 +96,9 @@ impl UpdateField {
             UpdateField::AvgFertilizingDays,
         ]
     }
+    fn get_schedule_fields() -> Vec<UpdateField> {
+        vec![UpdateField::Schedules]
+    }
 }
 
 impl TryFrom<(String, &UpdateField)> for UpdateValue {
@@ -118,6 +132,9 @@ impl TryFrom<(String, &UpdateField)> for UpdateValue {
                 UpdateValue::MInt(Some(i))
             };
             Ok(value)
+        } else if UpdateField::get_schedule_fields().contains(field) {
+            let schedules = parse_schedules(&s).map_err(|_| ty_err)?;
+            Ok(UpdateValue::Schedules(schedules))
         } else {
             Err(ty_err)
         }
@@ -145,6 +162,7 @@ impl fmt::Display for UpdateField {
             UpdateField::PruningNotes => frmt.write_str("Pruning Notes"),
             UpdateField::Companions => frmt.write_str("Companions"),
             UpdateField::AdditionalNotes => frmt.write_str("Additional Notes"),
+            UpdateField::Schedules => frmt.write_str("Schedules"),
         }
     }
 }
@@ -172,6 +190,7 @@ impl FromStr for UpdateField {
             "pruningnotes" => Ok(UpdateField::PruningNotes),
             "companions" => Ok(UpdateField::Companions),
             "additionalnotes" => Ok(UpdateField::AdditionalNotes),
+            "schedules" => Ok(UpdateField::Schedules),
             _ => Err(Error::FieldError(s.to_owned())),
         }
     }
@@ -301,6 +320,14 @@ pub fn update_species(
             }
             _ => Err(field_err),
         },
+        UpdateValue::Schedules(schedules) => {
+            if let UpdateField::Schedules = field {
+                species.schedules = schedules;
+                Ok(())
+            } else {
+                Err(field_err)
+            }
+        }
     }
 }
 
@@ -332,10 +359,19 @@ mod update_species_tests {
             "Additional Notes".to_owned(),
             "Average Watering Days".to_owned(),
             "Average Fertilizing Days".to_owned(),
+            "Schedules".to_owned(),
         ];
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn all_fields() {
+        let result = UpdateField::all_fields();
+        assert_eq!(result.len(), UpdateField::fields_strs().len());
+        assert_eq!(result[0], UpdateField::ScientificName);
+        assert_eq!(result[18], UpdateField::Schedules)
+    }
+
     #[test]
     fn str_fields() {
         let result = UpdateField::get_str_fields();
@@ -1092,4 +1128,15 @@ mod update_species_tests {
         );
         assert!(result.is_err())
     }
+
+    #[test]
+    fn update_schedules() {
+        let mut result = example_species();
+        let value =
+            UpdateValue::try_from(("Repotting: 365".to_owned(), &UpdateField::Schedules)).unwrap();
+        update_species(&mut result, UpdateField::Schedules, value).unwrap();
+        let mut expected = example_species();
+        expected.schedules = vec!["Repotting: 365".parse().unwrap()];
+        assert_eq!(result, expected)
+    }
 }
@@ -0,0 +1,205 @@
+use super::{
+    log_item::LogItem,
+    plant::{Plant, PlantSpecies},
+    schedule::activity_key,
+    serialize::date_serializer,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Rain of at least this many mm counts as watering outside plants
+pub const RAIN_THRESHOLD_MM: f32 = 5.0;
+pub const RAIN_NOTE: &str = "rain";
+
+/// Recorded or forecast weather of one day
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WeatherRecord {
+    #[serde(with = "date_serializer")]
+    pub date: NaiveDate,
+    pub rain_mm: f32,
+    pub temp_min: f32,
+    pub temp_max: f32,
+}
+
+/// An outside plant exposed to temperatures below the minimum of its species
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrostWarning {
+    pub plant: String,
+    pub date: NaiveDate,
+    pub temp: f32,
+    pub species_min: f32,
+}
+
+impl WeatherRecord {
+    pub fn is_rain(&self) -> bool {
+        self.rain_mm >= RAIN_THRESHOLD_MM
+    }
+}
+
+/// Watering logs for outside plants on days with rain up to `today`
+/// days where a plant was already watered are skipped
+pub fn get_rain_logs(
+    plants: &[Plant],
+    weather: &[WeatherRecord],
+    today: NaiveDate,
+) -> Vec<LogItem> {
+    let mut logs = vec![];
+    for plant in plants.iter().filter(|plant| plant.is_outside()) {
+        for record in weather
+            .iter()
+            .filter(|record| record.date <= today && record.is_rain())
+        {
+            let already_watered = plant
+                .activities
+                .iter()
+                .any(|log| log.date == record.date && activity_key(&log.activity) == "watering");
+            if !already_watered {
+                logs.push(LogItem {
+                    activity: "Watering".to_owned(),
+                    date: record.date,
+                    plant: plant.info.name.clone(),
+                    note: Some(RAIN_NOTE.to_owned()),
+                    user: None,
+                })
+            }
+        }
+    }
+    logs
+}
+
+/// Outside plants where the minimum temperature from `today` on drops below the minimum of their species
+pub fn get_frost_warnings(
+    plants: &[Plant],
+    weather: &[WeatherRecord],
+    today: NaiveDate,
+) -> Vec<FrostWarning> {
+    let mut warnings = vec![];
+    for plant in plants.iter().filter(|plant| plant.is_outside()) {
+        let species_min = match &plant.info.species {
+            PlantSpecies::Species(species) => species.temp_min,
+            PlantSpecies::Other(_) => continue,
+        };
+        for record in weather
+            .iter()
+            .filter(|record| record.date >= today && record.temp_min < species_min)
+        {
+            warnings.push(FrostWarning {
+                plant: plant.info.name.clone(),
+                date: record.date,
+                temp: record.temp_min,
+                species_min,
+            })
+        }
+    }
+    warnings
+}
+
+impl fmt::Display for FrostWarning {
+    fn fmt(&self, frmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            frmt,
+            "{}: {}°C on {}, species minimum is {}°C",
+            self.plant,
+            self.temp,
+            self.date.format("%d.%m.%Y"),
+            self.species_min
+        )
+    }
+}
+
+#[cfg(test)]
+mod weather_tests {
+    use super::{get_frost_warnings, get_rain_logs, FrostWarning, WeatherRecord, RAIN_NOTE};
+    use crate::{
+        location::Location,
+        log_item::LogItem,
+        plant::{Plant, PlantLocation},
+        test_common::{example_activity1, example_date1, example_date2, example_plant},
+    };
+
+    fn outside_plant() -> Plant {
+        let mut plant = example_plant();
+        plant.info.location = PlantLocation::Location(Box::new(Location {
+            name: "Garden".to_owned(),
+            outside: true,
+            climate: vec![],
+        }));
+        plant
+    }
+
+    fn example_weather() -> Vec<WeatherRecord> {
+        vec![
+            WeatherRecord {
+                date: example_date1(),
+                rain_mm: 12.0,
+                temp_min: 5.0,
+                temp_max: 10.0,
+            },
+            WeatherRecord {
+                date: example_date2(),
+                rain_mm: 8.5,
+                temp_min: -3.5,
+                temp_max: 2.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn rain_logs() {
+        let result = get_rain_logs(&[outside_plant()], &example_weather(), example_date2());
+        let expected = vec![LogItem {
+            date: example_date2(),
+            note: Some(RAIN_NOTE.to_owned()),
+            ..example_activity1()
+        }];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn rain_logs_inside() {
+        let result = get_rain_logs(&[example_plant()], &example_weather(), example_date2());
+        assert!(result.is_empty())
+    }
+
+    #[test]
+    fn rain_logs_light_rain() {
+        let mut weather = example_weather();
+        weather[1].rain_mm = 1.0;
+        let result = get_rain_logs(&[outside_plant()], &weather, example_date2());
+        assert!(result.is_empty())
+    }
+
+    #[test]
+    fn frost_warnings() {
+        let result = get_frost_warnings(&[outside_plant()], &example_weather(), example_date1());
+        let expected = vec![FrostWarning {
+            plant: "A Plant".to_owned(),
+            date: example_date2(),
+            temp: -3.5,
+            species_min: 0.0,
+        }];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn frost_warnings_past() {
+        let mut weather = example_weather();
+        weather[0].temp_min = -10.0;
+        let result = get_frost_warnings(&[outside_plant()], &weather[..1], example_date2());
+        assert!(result.is_empty())
+    }
+
+    #[test]
+    fn display_warning() {
+        let result = FrostWarning {
+            plant: "A Plant".to_owned(),
+            date: example_date2(),
+            temp: -3.5,
+            species_min: 0.0,
+        }
+        .to_string();
+        let expected = "A Plant: -3.5°C on 02.01.1970, species minimum is 0°C";
+        assert_eq!(result, expected)
+    }
+}
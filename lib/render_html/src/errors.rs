@@ -4,6 +4,8 @@ use std::fmt;
 pub enum Error {
     PageError(pages::errors::Error),
     IOError(std::io::Error),
+    ImageError(image::ImageError),
+    BadManifest(String),
     Other(Box<dyn std::error::Error>),
 }
 
@@ -19,6 +21,12 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<image::ImageError> for Error {
+    fn from(img_err: image::ImageError) -> Error {
+        Error::ImageError(img_err)
+    }
+}
+
 impl From<Box<dyn std::error::Error>> for Error {
     fn from(err: Box<dyn std::error::Error>) -> Error {
         Error::Other(err)
@@ -30,6 +38,8 @@ impl fmt::Display for Error {
         match self {
             Error::PageError(page_err) => page_err.fmt(frmt),
             Error::IOError(io_err) => io_err.fmt(frmt),
+            Error::ImageError(img_err) => img_err.fmt(frmt),
+            Error::BadManifest(line) => write!(frmt, "Could not parse render manifest line {line}"),
             Error::Other(err) => err.fmt(frmt),
         }
     }
@@ -40,6 +50,7 @@ impl std::error::Error for Error {}
 #[cfg(test)]
 mod error_tests {
     use super::Error;
+    use image::ImageError;
     use pages::errors::Error as PageError;
     use std::io::Error as IOError;
 
@@ -64,6 +75,16 @@ mod error_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn img_err_into() {
+        let result = format!(
+            "{}",
+            <ImageError as Into<Error>>::into(ImageError::IoError(example_io_err()))
+        );
+        let expected = format!("{}", example_io_err());
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn dyn_err_into() {
         let result = format!(
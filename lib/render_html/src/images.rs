@@ -0,0 +1,255 @@
+use super::errors::Error;
+use image::{
+    codecs::jpeg::JpegEncoder, imageops::FilterType, DynamicImage, ImageDecoder, ImageReader,
+};
+use plants::plant::THUMBNAIL_DIR;
+use std::{
+    ffi::OsStr,
+    fs::{create_dir_all, read_dir, remove_dir_all, remove_file, File},
+    io::BufWriter,
+    path::Path,
+};
+
+/// Longest side of images shown in the image viewer, in pixels
+pub const WEB_SIZE: u32 = 1600;
+/// Longest side of thumbnails shown on pages, in pixels
+pub const THUMBNAIL_SIZE: u32 = 400;
+const JPEG_QUALITY: u8 = 80;
+
+/// Writes a web version and a thumbnail of every image in `plants_dir` (one directory per plant)
+/// to `out_dir`, images are only processed again when the original changed
+/// and images in `out_dir` without an original in `plants_dir` are removed
+/// Returns the number of processed images, images that can not be loaded are skipped
+pub fn process_images(plants_dir: &Path, out_dir: &Path) -> Result<usize, Error> {
+    remove_stale(plants_dir, out_dir)?;
+    let mut num_processed = 0;
+    for plant_entry in read_dir(plants_dir)? {
+        let plant_path = plant_entry?.path();
+        let Some(plant_dir) = plant_path.file_name().filter(|_| plant_path.is_dir()) else {
+            continue;
+        };
+        let plant_out = out_dir.join(plant_dir);
+        let thumbnail_out = plant_out.join(THUMBNAIL_DIR);
+        for image_entry in read_dir(&plant_path)? {
+            let image_path = image_entry?.path();
+            if image_path.extension() != Some(OsStr::new("jpg")) {
+                continue;
+            }
+            let Some(file_name) = image_path.file_name() else {
+                continue;
+            };
+            let web_path = plant_out.join(file_name);
+            let thumbnail_path = thumbnail_out.join(file_name);
+            if is_up_to_date(&image_path, &web_path) && is_up_to_date(&image_path, &thumbnail_path)
+            {
+                continue;
+            }
+
+            log::info!("Processing image {image_path:?}");
+            create_dir_all(&thumbnail_out)?;
+            // a broken image only leaves its own pages without it
+            let image = match load_image(&image_path) {
+                Ok(image) => image,
+                Err(err) => {
+                    log::error!("Could not load image {image_path:?}, skipping it: {err}");
+                    continue;
+                }
+            };
+            write_resized(&image, WEB_SIZE, &web_path)?;
+            write_resized(&image, THUMBNAIL_SIZE, &thumbnail_path)?;
+            num_processed += 1;
+        }
+    }
+    Ok(num_processed)
+}
+
+/// Removes the images of deleted originals and the directories of plants that no longer have one
+/// (e.g. plants that were renamed or died)
+fn remove_stale(plants_dir: &Path, out_dir: &Path) -> Result<(), Error> {
+    if !out_dir.is_dir() {
+        return Ok(());
+    }
+    for out_entry in read_dir(out_dir)? {
+        let plant_out = out_entry?.path();
+        let Some(plant_dir) = plant_out.file_name() else {
+            continue;
+        };
+        let plant_path = plants_dir.join(plant_dir);
+        if !plant_path.is_dir() {
+            log::info!("Removing images {plant_out:?}");
+            if plant_out.is_dir() {
+                remove_dir_all(&plant_out)?;
+            } else {
+                remove_file(&plant_out)?;
+            }
+            continue;
+        }
+        for dir in [plant_out.clone(), plant_out.join(THUMBNAIL_DIR)] {
+            if !dir.is_dir() {
+                continue;
+            }
+            for image_entry in read_dir(&dir)? {
+                let image_out = image_entry?.path();
+                let Some(file_name) = image_out.file_name() else {
+                    continue;
+                };
+                if image_out.is_file() && !plant_path.join(file_name).is_file() {
+                    log::info!("Removing image {image_out:?}");
+                    remove_file(&image_out)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks if `out` was written after `source` was last changed
+fn is_up_to_date(source: &Path, out: &Path) -> bool {
+    let modified = |path: &Path| path.metadata().and_then(|meta| meta.modified()).ok();
+    match (modified(source), modified(out)) {
+        (Some(source_time), Some(out_time)) => out_time >= source_time,
+        _ => false,
+    }
+}
+
+/// Loads an image rotated according to its exif orientation
+/// the orientation is lost when re-encoding, so phone photos would end up sideways otherwise
+fn load_image(path: &Path) -> Result<DynamicImage, Error> {
+    let mut decoder = ImageReader::open(path)?
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Writes `image` as jpeg, scaled down so its longest side is at most `max_size`
+fn write_resized(image: &DynamicImage, max_size: u32, out: &Path) -> Result<(), Error> {
+    let resized = if image.width() > max_size || image.height() > max_size {
+        image.resize(max_size, max_size, FilterType::Triangle)
+    } else {
+        image.clone()
+    };
+    let out_file = BufWriter::new(File::create(out)?);
+    JpegEncoder::new_with_quality(out_file, JPEG_QUALITY).encode_image(&resized.to_rgb8())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod images_tests {
+    use super::{process_images, THUMBNAIL_SIZE, WEB_SIZE};
+    use image::{image_dimensions, Rgb, RgbImage};
+    use std::{
+        fs::{create_dir_all, remove_dir_all, remove_file, write},
+        path::PathBuf,
+    };
+
+    const IMAGES_BASE: &str = "../../testing/process_images_test";
+
+    fn write_example_image(path: &PathBuf, width: u32, height: u32) {
+        RgbImage::from_pixel(width, height, Rgb([46, 139, 87]))
+            .save(path)
+            .unwrap();
+    }
+
+    #[test]
+    fn process_resize() {
+        let base = PathBuf::from(IMAGES_BASE).join("resize");
+        let plant_dir = base.join("Plants").join("Plant1");
+        create_dir_all(&plant_dir).unwrap();
+        write_example_image(&plant_dir.join("01011970.jpg"), 2000, 1000);
+        write(plant_dir.join("Plant1.json"), "{}").unwrap();
+        let out = base.join("img");
+        let result = process_images(&base.join("Plants"), &out).unwrap();
+        let web = image_dimensions(out.join("Plant1").join("01011970.jpg")).unwrap();
+        let thumbnail =
+            image_dimensions(out.join("Plant1").join("thumbs").join("01011970.jpg")).unwrap();
+        let json_exists = out.join("Plant1").join("Plant1.json").exists();
+        remove_dir_all(base).unwrap();
+        assert_eq!(result, 1);
+        assert_eq!(web, (WEB_SIZE, WEB_SIZE / 2));
+        assert_eq!(thumbnail, (THUMBNAIL_SIZE, THUMBNAIL_SIZE / 2));
+        assert!(!json_exists)
+    }
+
+    #[test]
+    fn process_small() {
+        let base = PathBuf::from(IMAGES_BASE).join("small");
+        let plant_dir = base.join("Plants").join("Plant1");
+        create_dir_all(&plant_dir).unwrap();
+        write_example_image(&plant_dir.join("01011970.jpg"), 20, 10);
+        let out = base.join("img");
+        process_images(&base.join("Plants"), &out).unwrap();
+        let thumbnail =
+            image_dimensions(out.join("Plant1").join("thumbs").join("01011970.jpg")).unwrap();
+        remove_dir_all(base).unwrap();
+        assert_eq!(thumbnail, (20, 10))
+    }
+
+    #[test]
+    fn process_skip_unchanged() {
+        let base = PathBuf::from(IMAGES_BASE).join("skip");
+        let plant_dir = base.join("Plants").join("Plant1");
+        create_dir_all(&plant_dir).unwrap();
+        write_example_image(&plant_dir.join("01011970.jpg"), 20, 10);
+        let out = base.join("img");
+        let first = process_images(&base.join("Plants"), &out).unwrap();
+        let second = process_images(&base.join("Plants"), &out).unwrap();
+        remove_dir_all(base).unwrap();
+        assert_eq!(first, 1);
+        assert_eq!(second, 0)
+    }
+
+    #[test]
+    fn process_skip_broken() {
+        let base = PathBuf::from(IMAGES_BASE).join("broken");
+        let plant_dir = base.join("Plants").join("Plant1");
+        create_dir_all(&plant_dir).unwrap();
+        write(plant_dir.join("01011970.jpg"), "not an image").unwrap();
+        write_example_image(&plant_dir.join("02011970.jpg"), 20, 10);
+        let out = base.join("img");
+        let result = process_images(&base.join("Plants"), &out).unwrap();
+        let broken_exists = out.join("Plant1").join("01011970.jpg").exists();
+        let image_exists = out.join("Plant1").join("02011970.jpg").exists();
+        remove_dir_all(base).unwrap();
+        assert_eq!(result, 1);
+        assert!(!broken_exists);
+        assert!(image_exists)
+    }
+
+    #[test]
+    fn process_remove_stale() {
+        let base = PathBuf::from(IMAGES_BASE).join("stale");
+        let plant_dir = base.join("Plants").join("Plant1");
+        let dead_dir = base.join("Plants").join("Plant2");
+        create_dir_all(&plant_dir).unwrap();
+        create_dir_all(&dead_dir).unwrap();
+        write_example_image(&plant_dir.join("01011970.jpg"), 20, 10);
+        write_example_image(&plant_dir.join("02011970.jpg"), 20, 10);
+        write_example_image(&dead_dir.join("01011970.jpg"), 20, 10);
+        let out = base.join("img");
+        process_images(&base.join("Plants"), &out).unwrap();
+        remove_file(plant_dir.join("02011970.jpg")).unwrap();
+        remove_dir_all(&dead_dir).unwrap();
+        let result = process_images(&base.join("Plants"), &out).unwrap();
+        let kept_exists = out
+            .join("Plant1")
+            .join("thumbs")
+            .join("01011970.jpg")
+            .exists();
+        let removed_exists = out.join("Plant1").join("02011970.jpg").exists();
+        let removed_thumb_exists = out
+            .join("Plant1")
+            .join("thumbs")
+            .join("02011970.jpg")
+            .exists();
+        let dead_exists = out.join("Plant2").exists();
+        remove_dir_all(base).unwrap();
+        assert_eq!(result, 0);
+        assert!(kept_exists);
+        assert!(!removed_exists);
+        assert!(!removed_thumb_exists);
+        assert!(!dead_exists)
+    }
+}
@@ -0,0 +1,176 @@
+use super::errors::Error;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{read_to_string, File},
+    io::Write,
+    path::Path,
+    str::FromStr,
+};
+
+/// File in the output directory the manifest is stored in
+pub const MANIFEST_FILE: &str = ".render_manifest";
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Hashes of the inputs of every written page, by path relative to the output directory
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub pages: BTreeMap<String, u64>,
+}
+
+/// Pages written, left unchanged and deleted by an incremental render
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    pub written: usize,
+    pub unchanged: usize,
+    pub deleted: usize,
+}
+
+/// Hash of the debug representation of page inputs
+/// FNV-1a is used since the std hasher may change between releases, rendering the whole site again
+pub fn hash_inputs<T: fmt::Debug + ?Sized>(inputs: &T) -> u64 {
+    format!("{inputs:?}")
+        .bytes()
+        .fold(FNV_OFFSET, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+}
+
+impl Manifest {
+    /// Loads the manifest in `out_dir`, a missing manifest is empty so all pages are rendered
+    pub fn load(out_dir: &Path) -> Result<Manifest, Error> {
+        let path = out_dir.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(Manifest::default());
+        }
+        read_to_string(path)?.parse()
+    }
+
+    pub fn save(&self, out_dir: &Path) -> Result<(), Error> {
+        let mut out_file = File::create(out_dir.join(MANIFEST_FILE))?;
+        out_file.write_all(self.to_string().as_bytes())?;
+        Ok(())
+    }
+
+    /// Checks if `page` was last rendered with inputs hashing to `hash`
+    pub fn is_current(&self, page: &str, hash: u64) -> bool {
+        self.pages.get(page) == Some(&hash)
+    }
+
+    /// Pages in this manifest that are not contained in `other`
+    pub fn removed_pages<'a>(&'a self, other: &'a Manifest) -> impl Iterator<Item = &'a String> {
+        self.pages
+            .keys()
+            .filter(|page| !other.pages.contains_key(*page))
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Manifest, Self::Err> {
+        let mut pages = BTreeMap::new();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (hash, page) = line
+                .split_once(' ')
+                .ok_or(Error::BadManifest(line.to_owned()))?;
+            let hash =
+                u64::from_str_radix(hash, 16).map_err(|_| Error::BadManifest(line.to_owned()))?;
+            pages.insert(page.to_owned(), hash);
+        }
+        Ok(Manifest { pages })
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (page, hash) in self.pages.iter() {
+            writeln!(f, "{hash:016x} {page}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod incremental_tests {
+    use super::{hash_inputs, Manifest};
+    use std::{
+        collections::BTreeMap,
+        fs::{create_dir_all, remove_dir_all},
+        path::PathBuf,
+    };
+
+    const MANIFEST_DIR: &str = "../../testing/render_manifest_test";
+
+    fn example_manifest() -> Manifest {
+        Manifest {
+            pages: BTreeMap::from([
+                ("index.html".to_owned(), 1),
+                ("plants/APlant.html".to_owned(), 0xabcdef),
+            ]),
+        }
+    }
+
+    #[test]
+    fn hash_stable() {
+        assert_eq!(hash_inputs(&1), 0xaf63ac4c86019afc);
+        assert_eq!(hash_inputs(&("a", 1)), hash_inputs(&("a", 1)))
+    }
+
+    #[test]
+    fn hash_changed() {
+        assert_ne!(hash_inputs(&("a", 1)), hash_inputs(&("a", 2)))
+    }
+
+    #[test]
+    fn manifest_display() {
+        let result = example_manifest().to_string();
+        let expected = "0000000000000001 index.html\n0000000000abcdef plants/APlant.html\n";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn manifest_parse() {
+        let result: Manifest = example_manifest().to_string().parse().unwrap();
+        assert_eq!(result, example_manifest())
+    }
+
+    #[test]
+    fn manifest_parse_fail() {
+        assert!("index.html".parse::<Manifest>().is_err());
+        assert!("xyz index.html".parse::<Manifest>().is_err())
+    }
+
+    #[test]
+    fn manifest_current() {
+        let manifest = example_manifest();
+        assert!(manifest.is_current("index.html", 1));
+        assert!(!manifest.is_current("index.html", 2));
+        assert!(!manifest.is_current("gallery.html", 1))
+    }
+
+    #[test]
+    fn manifest_removed() {
+        let mut new = example_manifest();
+        new.pages.remove("plants/APlant.html");
+        let result: Vec<_> = example_manifest().removed_pages(&new).cloned().collect();
+        assert_eq!(result, vec!["plants/APlant.html".to_owned()])
+    }
+
+    #[test]
+    fn manifest_save_load() {
+        let dir = PathBuf::from(MANIFEST_DIR);
+        create_dir_all(&dir).unwrap();
+        example_manifest().save(&dir).unwrap();
+        let result = Manifest::load(&dir).unwrap();
+        remove_dir_all(&dir).unwrap();
+        assert_eq!(result, example_manifest())
+    }
+
+    #[test]
+    fn manifest_load_missing() {
+        let result = Manifest::load(&PathBuf::from(MANIFEST_DIR).join("missing")).unwrap();
+        assert_eq!(result, Manifest::default())
+    }
+}
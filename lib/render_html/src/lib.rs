@@ -1,4 +1,6 @@
 pub mod errors;
+pub mod images;
+pub mod incremental;
 pub mod renderer;
 pub mod write_html;
 
@@ -12,7 +14,7 @@ pub mod test_common {
         growth_item::GrowthItem,
         location::Location,
         log_item::LogItem,
-        plant::{Plant, PlantImage, PlantInfo, PlantLocation, PlantSpecies},
+        plant::{ImageMeta, Plant, PlantImage, PlantInfo, PlantLocation, PlantSpecies},
         species::{Species, SunlightRequirement},
     };
     use std::{error::Error, fmt, path::PathBuf};
@@ -50,12 +52,14 @@ pub mod test_common {
             pruning_notes: vec![],
             companions: vec![],
             additional_notes: vec![],
+            schedules: vec![],
         }
     }
     pub fn example_location() -> Location {
         Location {
             name: "Inside".to_owned(),
             outside: false,
+            climate: vec![],
         }
     }
 
@@ -76,6 +80,7 @@ pub mod test_common {
             obtained: example_date1(),
             auto_water: false,
             notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -107,6 +112,7 @@ pub mod test_common {
             date: example_date1(),
             plant: "A Plant".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -116,6 +122,7 @@ pub mod test_common {
             date: example_date2(),
             plant: "A Plant".to_owned(),
             note: None,
+            user: None,
         }
     }
     pub fn example_image1() -> PlantImage {
@@ -123,6 +130,8 @@ pub mod test_common {
             created: example_date1(),
             file_name: "01011970.jpg".to_owned(),
             file_path: PathBuf::from("./"),
+            caption: None,
+            cover: false,
         }
     }
 
@@ -131,6 +140,8 @@ pub mod test_common {
             created: example_date2(),
             file_name: "02011970.jpg".to_owned(),
             file_path: PathBuf::from("/"),
+            caption: None,
+            cover: false,
         }
     }
 
@@ -222,9 +233,13 @@ pub mod test_common {
             panic!("not implemented")
         }
 
-        fn get_locations(&mut self) -> Result<Vec<Location>, Box<dyn Error>> {
+        fn write_graveyard(&mut self, _: Vec<GraveyardPlant>) -> Result<(), Box<dyn Error>> {
             panic!("not implemented")
         }
+
+        fn get_locations(&mut self) -> Result<Vec<Location>, Box<dyn Error>> {
+            Ok(vec![])
+        }
         fn write_location(&mut self, _: Location) -> Result<(), Box<dyn Error>> {
             panic!("not implemented")
         }
@@ -255,6 +270,42 @@ pub mod test_common {
         fn species_exists(&mut self, species_name: &str) -> Result<bool, Box<dyn Error>> {
             Ok(self.get_species(species_name).is_ok())
         }
+
+        fn rename_plant(&mut self, _: &str, _: &str) -> Result<(), Box<dyn Error>> {
+            panic!("not implemented")
+        }
+
+        fn delete_species(&mut self, _: &str) -> Result<(), Box<dyn Error>> {
+            panic!("not implemented")
+        }
+
+        fn delete_location(&mut self, _: &str) -> Result<(), Box<dyn Error>> {
+            panic!("not implemented")
+        }
+
+        fn delete_log(&mut self, _: LogItem) -> Result<(), Box<dyn Error>> {
+            panic!("not implemented")
+        }
+
+        fn delete_growth(&mut self, _: GrowthItem) -> Result<(), Box<dyn Error>> {
+            panic!("not implemented")
+        }
+
+        fn write_image_meta(&mut self, _: ImageMeta) -> Result<(), Box<dyn Error>> {
+            panic!("not implemented")
+        }
+
+        fn begin_transaction(&mut self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn commit_transaction(&mut self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn rollback_transaction(&mut self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
     }
 
     pub fn example_renderer() -> Renderer<DummyManager> {
@@ -312,12 +363,6 @@ pub mod test_common {
         DummyManager {}.kill_plant(example_graveyard()).unwrap();
     }
 
-    #[test]
-    #[should_panic]
-    fn locations() {
-        DummyManager {}.get_locations().unwrap();
-    }
-
     #[test]
     #[should_panic]
     fn location() {
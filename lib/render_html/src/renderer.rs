@@ -1,12 +1,23 @@
-use super::errors::Error;
+use super::{
+    errors::Error,
+    incremental::{hash_inputs, Manifest, RenderStats},
+    write_html::write_html,
+};
+use chrono::Local;
 use database::database_manager::DatabaseManager;
 use html::render::Render;
 use log;
 use pages::{
-    activities::Activities, gallery::Gallery, graveyard::Graveyard, index::Index, page::Page,
-    plant_details::PlantDetails, plant_overview::PlantOverview, species_details::SpeciesDetails,
+    activities::Activities, error_page::ErrorPage, forms::Forms, gallery::Gallery,
+    graveyard::Graveyard, index::Index, page::Page, plant_details::PlantDetails,
+    plant_overview::PlantOverview, species_details::SpeciesDetails,
     species_overview::SpeciesOverview,
 };
+use plants::{graveyard::GraveyardPlant, plant::Plant, species::Species};
+use std::{
+    fs::{create_dir_all, remove_file},
+    path::Path,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct NamedPage {
@@ -26,6 +37,25 @@ pub struct PagesHtml {
     pub species_htmls: Vec<NamedPage>,
 }
 
+/// A page of the site, with the data it is rendered from
+enum SitePage<'a> {
+    Index,
+    PlantOverview,
+    SpeciesOverview,
+    Gallery,
+    Activities,
+    Graveyard,
+    Plant(&'a Plant),
+    Species(&'a Species),
+}
+
+/// Data shared between all pages during rendering
+pub struct RenderData {
+    pub plants: Vec<Plant>,
+    pub species: Vec<Species>,
+    pub graveyard: Vec<GraveyardPlant>,
+}
+
 pub struct Renderer<T: DatabaseManager> {
     pub database_manager: T,
     pub date_format: String,
@@ -39,68 +69,127 @@ impl<T: DatabaseManager> Renderer<T> {
         }
     }
 
+    /// Loads everything needed to render all pages
+    pub fn load_data(&mut self) -> Result<RenderData, Error> {
+        log::info!("Loading render data");
+        let plants = self.database_manager.get_all_plants()?;
+        let species = self.database_manager.get_all_species()?;
+        let graveyard = self.database_manager.get_graveyard()?;
+        Ok(RenderData {
+            plants,
+            species,
+            graveyard,
+        })
+    }
+
     pub fn render_index(&mut self) -> Result<String, Error> {
-        log::info!("Building index");
         let plants = self.database_manager.get_all_plants()?;
+        self.index_html(&plants)
+    }
 
-        let index = Index::try_from(plants.as_slice())?;
+    fn index_html(&self, plants: &[Plant]) -> Result<String, Error> {
+        log::info!("Building index");
+        let index = Index::try_from(plants)?;
         Ok(index
             .render(&self.date_format, false, plants.len() as i32)
             .render())
     }
 
     pub fn render_plant_overview(&mut self) -> Result<String, Error> {
-        log::info!("Building plant overview");
         let plants = self.database_manager.get_all_plants()?;
-        let plant_overview = PlantOverview::from(plants.as_slice());
-        Ok(plant_overview
+        Ok(self.plant_overview_html(&plants))
+    }
+
+    fn plant_overview_html(&self, plants: &[Plant]) -> String {
+        log::info!("Building plant overview");
+        let plant_overview = PlantOverview::from(plants);
+        plant_overview
             .render(&self.date_format, false, plants.len() as i32)
-            .render())
+            .render()
     }
 
     pub fn render_species_overview(&mut self) -> Result<String, Error> {
-        log::info!("Building Species Overview");
         let species = self.database_manager.get_all_species()?;
         let plants = self.database_manager.get_all_plants()?;
-        let species_overview = SpeciesOverview::from((species.as_slice(), plants.as_slice()));
-        Ok(species_overview
+        Ok(self.species_overview_html(&species, &plants))
+    }
+
+    fn species_overview_html(&self, species: &[Species], plants: &[Plant]) -> String {
+        log::info!("Building Species Overview");
+        let species_overview = SpeciesOverview::from((species, plants));
+        species_overview
             .render(&self.date_format, false, plants.len() as i32)
-            .render())
+            .render()
     }
 
     pub fn render_gallery(&mut self) -> Result<String, Error> {
-        log::info!("Building Gallery");
         let plants = self.database_manager.get_all_plants()?;
-        let gallery = Gallery::from(plants.as_slice());
-        Ok(gallery
+        Ok(self.gallery_html(&plants))
+    }
+
+    fn gallery_html(&self, plants: &[Plant]) -> String {
+        log::info!("Building Gallery");
+        let gallery = Gallery::from(plants);
+        gallery
             .render(&self.date_format, false, plants.len() as i32)
-            .render())
+            .render()
     }
 
     pub fn render_activities(&mut self) -> Result<String, Error> {
-        log::info!("Building Activities");
         let plants = self.database_manager.get_all_plants()?;
-        let activities = Activities::from(plants.as_slice());
-        Ok(activities
+        Ok(self.activities_html(&plants))
+    }
+
+    fn activities_html(&self, plants: &[Plant]) -> String {
+        log::info!("Building Activities");
+        let activities = Activities::from(plants);
+        activities
             .render(&self.date_format, false, plants.len() as i32)
-            .render())
+            .render()
     }
 
     pub fn render_graveyard(&mut self) -> Result<String, Error> {
-        log::info!("Building Graveyard");
         let graveyard_plants = self.database_manager.get_graveyard()?;
         let num_plants = self.database_manager.get_num_plants()?;
-        let graveyard = Graveyard::from(graveyard_plants.as_slice());
-        Ok(graveyard
+        Ok(self.graveyard_html(&graveyard_plants, num_plants))
+    }
+
+    fn graveyard_html(&self, graveyard_plants: &[GraveyardPlant], num_plants: i32) -> String {
+        log::info!("Building Graveyard");
+        let graveyard = Graveyard::from(graveyard_plants);
+        graveyard
             .render(&self.date_format, false, num_plants)
+            .render()
+    }
+
+    /// Renders an error page, the footer shows no plants if they can't be counted
+    pub fn render_error(&mut self, page: &ErrorPage) -> String {
+        let num_plants = self.database_manager.get_num_plants().unwrap_or(0);
+        page.render(&self.date_format, page.relative_up, num_plants)
+            .render()
+    }
+
+    /// Forms post to serve_html, so they are not part of the static site
+    pub fn render_forms(&mut self) -> Result<String, Error> {
+        log::info!("Building Forms");
+        let plants = self.database_manager.get_all_plants()?;
+        let species = self.database_manager.get_all_species()?;
+        let locations = self.database_manager.get_locations()?;
+        let forms = Forms::from((plants.as_slice(), species.as_slice(), locations.as_slice()));
+        Ok(forms
+            .render(&self.date_format, false, plants.len() as i32)
             .render())
     }
 
     pub fn render_plant_details(&mut self, plant: String) -> Result<NamedPage, Error> {
-        log::info!("Rendering Details Page for plant {}", plant);
         let plant = self.database_manager.get_plant(&plant)?;
         let num_plants = self.database_manager.get_num_plants()?;
-        let plant_details = PlantDetails::try_from(&plant)?;
+        self.plant_details_html(&plant, num_plants)
+    }
+
+    fn plant_details_html(&self, plant: &Plant, num_plants: i32) -> Result<NamedPage, Error> {
+        log::info!("Rendering Details Page for plant {}", plant.info.name);
+        let plant_details = PlantDetails::try_from(plant)?;
         let page_html = plant_details
             .render(&self.date_format, true, num_plants)
             .render();
@@ -112,70 +201,65 @@ impl<T: DatabaseManager> Renderer<T> {
     }
 
     pub fn render_all_plants(&mut self) -> Result<Vec<NamedPage>, Error> {
-        log::info!("Rendering Plant Details");
         let plants = self.database_manager.get_all_plants()?;
+        self.all_plants_html(&plants)
+    }
+
+    fn all_plants_html(&self, plants: &[Plant]) -> Result<Vec<NamedPage>, Error> {
+        log::info!("Rendering Plant Details");
         let num_plants = plants.len() as i32;
         let mut plant_htmls = vec![];
         for plant in plants.iter() {
-            log::info!("Rendering Details Page for plant {}", plant.info.name);
-            let plant_details = PlantDetails::try_from(plant)?;
-            let page_html = plant_details
-                .render(&self.date_format, true, num_plants)
-                .render();
-            let page_name = plant.get_url("");
-            plant_htmls.push(NamedPage {
-                page_name,
-                page_html,
-            })
+            plant_htmls.push(self.plant_details_html(plant, num_plants)?);
         }
         Ok(plant_htmls)
     }
 
     pub fn render_species_details(&mut self, species: String) -> Result<NamedPage, Error> {
-        log::info!("Rendering Details Page for spieces {}", species);
         let species = self.database_manager.get_species(&species)?;
         let all_plants = self.database_manager.get_all_plants()?;
-        let species_details = SpeciesDetails::from((&species, all_plants.as_slice()));
+        Ok(self.species_details_html(&species, &all_plants))
+    }
+
+    fn species_details_html(&self, species: &Species, all_plants: &[Plant]) -> NamedPage {
+        log::info!("Rendering Details Page for spieces {}", species.name);
+        let species_details = SpeciesDetails::from((species, all_plants));
         let species_html = species_details
             .render(&self.date_format, true, all_plants.len() as i32)
             .render();
-        Ok(NamedPage {
+        NamedPage {
             page_name: species.get_url(""),
             page_html: species_html,
-        })
+        }
     }
 
     pub fn render_all_species(&mut self) -> Result<Vec<NamedPage>, Error> {
-        log::info!("Rendering Species Details");
-        let mut species_htmls = vec![];
         let all_species = self.database_manager.get_all_species()?;
         let all_plants = self.database_manager.get_all_plants()?;
+        Ok(self.all_species_html(&all_species, &all_plants))
+    }
 
-        for species in all_species.iter() {
-            log::info!("Rendering Details Page for spieces {}", species.name);
-            let species_details = SpeciesDetails::from((species, all_plants.as_slice()));
-            let species_html = species_details
-                .render(&self.date_format, true, all_plants.len() as i32)
-                .render();
-            species_htmls.push(NamedPage {
-                page_name: species.get_url(""),
-                page_html: species_html,
-            })
-        }
-
-        Ok(species_htmls)
+    fn all_species_html(&self, all_species: &[Species], all_plants: &[Plant]) -> Vec<NamedPage> {
+        log::info!("Rendering Species Details");
+        all_species
+            .iter()
+            .map(|species| self.species_details_html(species, all_plants))
+            .collect()
     }
 
+    /// Renders all pages, loading the data from the database only once
     pub fn render_all(&mut self) -> Result<PagesHtml, Error> {
         log::info!("Rendering all pages");
-        let index_html = self.render_index()?;
-        let plants_overview_html = self.render_plant_overview()?;
-        let species_overview_html = self.render_species_overview()?;
-        let gallery_html = self.render_gallery()?;
-        let activities_html = self.render_activities()?;
-        let graveyard_html = self.render_graveyard()?;
-        let plant_htmls = self.render_all_plants()?;
-        let species_htmls = self.render_all_species()?;
+        let data = self.load_data()?;
+        let num_plants = data.plants.len() as i32;
+        let index_html = self.index_html(&data.plants)?;
+        let plants_overview_html = self.plant_overview_html(&data.plants);
+        let species_overview_html = self.species_overview_html(&data.species, &data.plants);
+        let gallery_html = self.gallery_html(&data.plants);
+        let activities_html = self.activities_html(&data.plants);
+        let graveyard_html = self.graveyard_html(&data.graveyard, num_plants);
+        let plant_htmls = self.all_plants_html(&data.plants)?;
+        let species_htmls = self.all_species_html(&data.species, &data.plants);
         log::info!("Rendered all pages");
 
         Ok(PagesHtml {
@@ -189,21 +273,159 @@ impl<T: DatabaseManager> Renderer<T> {
             species_htmls,
         })
     }
+
+    fn site_page_html(&self, page: &SitePage, data: &RenderData) -> Result<String, Error> {
+        let num_plants = data.plants.len() as i32;
+        match page {
+            SitePage::Index => self.index_html(&data.plants),
+            SitePage::PlantOverview => Ok(self.plant_overview_html(&data.plants)),
+            SitePage::SpeciesOverview => {
+                Ok(self.species_overview_html(&data.species, &data.plants))
+            }
+            SitePage::Gallery => Ok(self.gallery_html(&data.plants)),
+            SitePage::Activities => Ok(self.activities_html(&data.plants)),
+            SitePage::Graveyard => Ok(self.graveyard_html(&data.graveyard, num_plants)),
+            SitePage::Plant(plant) => Ok(self.plant_details_html(plant, num_plants)?.page_html),
+            SitePage::Species(species) => {
+                Ok(self.species_details_html(species, &data.plants).page_html)
+            }
+        }
+    }
+
+    /// Renders and writes only the pages whose inputs changed since the last render to `out_dir`
+    /// and deletes pages of removed plants and species
+    /// `build_key` is hashed with every page, changing it renders all pages again
+    pub fn render_incremental(
+        &mut self,
+        out_dir: &Path,
+        plants_dir: &str,
+        species_dir: &str,
+        build_key: &str,
+    ) -> Result<RenderStats, Error> {
+        log::info!("Rendering changed pages");
+        let data = self.load_data()?;
+        // all pages show the number of plants, and tasks are relative to today
+        let common = (
+            build_key,
+            &self.date_format,
+            Local::now().date_naive(),
+            data.plants.len(),
+        );
+
+        let mut pages = vec![
+            (
+                "index.html".to_owned(),
+                hash_inputs(&(common, &data.plants)),
+                SitePage::Index,
+            ),
+            (
+                "plant_overview.html".to_owned(),
+                hash_inputs(&(common, &data.plants)),
+                SitePage::PlantOverview,
+            ),
+            (
+                "species_overview.html".to_owned(),
+                hash_inputs(&(common, &data.species, &data.plants)),
+                SitePage::SpeciesOverview,
+            ),
+            (
+                "gallery.html".to_owned(),
+                hash_inputs(&(common, &data.plants)),
+                SitePage::Gallery,
+            ),
+            (
+                "activities.html".to_owned(),
+                hash_inputs(&(common, &data.plants)),
+                SitePage::Activities,
+            ),
+            (
+                "graveyard.html".to_owned(),
+                hash_inputs(&(common, &data.graveyard)),
+                SitePage::Graveyard,
+            ),
+        ];
+        for plant in data.plants.iter() {
+            pages.push((
+                plant.get_url(plants_dir),
+                hash_inputs(&(common, plant)),
+                SitePage::Plant(plant),
+            ));
+        }
+        for species in data.species.iter() {
+            pages.push((
+                species.get_url(species_dir),
+                hash_inputs(&(common, species, species.get_plants(&data.plants))),
+                SitePage::Species(species),
+            ));
+        }
+
+        let old_manifest = Manifest::load(out_dir)?;
+        let mut new_manifest = Manifest::default();
+        let mut stats = RenderStats::default();
+        for (page_name, hash, page) in pages.iter() {
+            let path = out_dir.join(page_name);
+            if old_manifest.is_current(page_name, *hash) && path.exists() {
+                stats.unchanged += 1;
+            } else {
+                log::info!("Writing {page_name}");
+                if let Some(parent) = path.parent() {
+                    create_dir_all(parent)?;
+                }
+                write_html(self.site_page_html(page, &data)?, &path)?;
+                stats.written += 1;
+            }
+            new_manifest.pages.insert(page_name.clone(), *hash);
+        }
+
+        for page_name in old_manifest.removed_pages(&new_manifest) {
+            log::info!("Removing {page_name}");
+            let path = out_dir.join(page_name);
+            if path.exists() {
+                remove_file(path)?;
+            }
+            stats.deleted += 1;
+        }
+        new_manifest.save(out_dir)?;
+        log::info!(
+            "Wrote {} pages, {} unchanged, {} removed",
+            stats.written,
+            stats.unchanged,
+            stats.deleted
+        );
+        Ok(stats)
+    }
 }
 
 #[cfg(test)]
 mod renderer_tests {
     use super::{NamedPage, PagesHtml};
+    use crate::incremental::{Manifest, RenderStats};
     use crate::test_common::{
         example_graveyard, example_plant, example_plant2, example_renderer, example_species,
         DATE_FORMAT,
     };
     use html::render::Render;
     use pages::{
-        activities::Activities, gallery::Gallery, graveyard::Graveyard, index::Index, page::Page,
-        plant_details::PlantDetails, plant_overview::PlantOverview,
-        species_details::SpeciesDetails, species_overview::SpeciesOverview,
+        activities::Activities, error_page::ErrorPage, forms::Forms, gallery::Gallery,
+        graveyard::Graveyard, index::Index, page::Page, plant_details::PlantDetails,
+        plant_overview::PlantOverview, species_details::SpeciesDetails,
+        species_overview::SpeciesOverview,
     };
+    use plants::location::Location;
+    use std::{
+        fs::{remove_dir_all, write},
+        path::PathBuf,
+    };
+
+    const INCREMENTAL_OUT: &str = "../../testing/incremental_out";
+
+    #[test]
+    fn load_data() {
+        let result = example_renderer().load_data().unwrap();
+        assert_eq!(result.plants, vec![example_plant(), example_plant2()]);
+        assert_eq!(result.species, vec![example_species()]);
+        assert_eq!(result.graveyard, vec![example_graveyard()])
+    }
 
     #[test]
     fn index() {
@@ -300,6 +522,31 @@ mod renderer_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn error() {
+        let page = ErrorPage {
+            status_code: 404,
+            message: "Could not find page".to_owned(),
+            relative_up: false,
+        };
+        let result = example_renderer().render_error(&page);
+        let expected = page.render(DATE_FORMAT, false, 2).render();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn forms() {
+        let result = example_renderer().render_forms().unwrap();
+        let expected = Forms::from((
+            vec![example_plant(), example_plant2()].as_slice(),
+            vec![example_species()].as_slice(),
+            Vec::<Location>::new().as_slice(),
+        ))
+        .render(DATE_FORMAT, false, 2)
+        .render();
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn all() {
         let result = example_renderer().render_all().unwrap();
@@ -315,4 +562,68 @@ mod renderer_tests {
         };
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn incremental_unchanged() {
+        let out_dir = PathBuf::from(INCREMENTAL_OUT).join("unchanged");
+        let first = example_renderer()
+            .render_incremental(&out_dir, "plants", "species", "build")
+            .unwrap();
+        let second = example_renderer()
+            .render_incremental(&out_dir, "plants", "species", "build")
+            .unwrap();
+        let index_exists = out_dir.join("index.html").exists();
+        let plant_exists = out_dir.join(example_plant().get_url("plants")).exists();
+        remove_dir_all(&out_dir).unwrap();
+        assert!(index_exists);
+        assert!(plant_exists);
+        assert_eq!(
+            first,
+            RenderStats {
+                written: 9,
+                unchanged: 0,
+                deleted: 0
+            }
+        );
+        assert_eq!(
+            second,
+            RenderStats {
+                written: 0,
+                unchanged: 9,
+                deleted: 0
+            }
+        )
+    }
+
+    #[test]
+    fn incremental_build_key() {
+        let out_dir = PathBuf::from(INCREMENTAL_OUT).join("build_key");
+        example_renderer()
+            .render_incremental(&out_dir, "plants", "species", "build")
+            .unwrap();
+        let result = example_renderer()
+            .render_incremental(&out_dir, "plants", "species", "new build")
+            .unwrap();
+        remove_dir_all(&out_dir).unwrap();
+        assert_eq!(result.written, 9)
+    }
+
+    #[test]
+    fn incremental_remove_stale() {
+        let out_dir = PathBuf::from(INCREMENTAL_OUT).join("stale");
+        example_renderer()
+            .render_incremental(&out_dir, "plants", "species", "build")
+            .unwrap();
+        let mut manifest = Manifest::load(&out_dir).unwrap();
+        manifest.pages.insert("plants/Dead.html".to_owned(), 0);
+        manifest.save(&out_dir).unwrap();
+        write(out_dir.join("plants/Dead.html"), "").unwrap();
+        let result = example_renderer()
+            .render_incremental(&out_dir, "plants", "species", "build")
+            .unwrap();
+        let stale_exists = out_dir.join("plants/Dead.html").exists();
+        remove_dir_all(&out_dir).unwrap();
+        assert_eq!(result.deleted, 1);
+        assert!(!stale_exists)
+    }
 }
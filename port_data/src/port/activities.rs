@@ -29,6 +29,7 @@ impl From<LogCSV> for Vec<LogItem> {
                 activity: log_csv.activity.clone(),
                 plant: item.clone(),
                 note: log_csv.note.clone(),
+                user: None,
             };
             items.push(new_log);
         }
@@ -91,6 +92,7 @@ mod activities_tests {
             activity: "Watering".to_owned(),
             plant: "Plant1".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -100,6 +102,7 @@ mod activities_tests {
             activity: "Watering".to_owned(),
             plant: "Plant2".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -118,6 +121,7 @@ mod activities_tests {
             activity: "Fertilizing".to_owned(),
             plant: "Plant2".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -127,6 +131,7 @@ mod activities_tests {
             activity: "Fertilizing".to_owned(),
             plant: "Plant3".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -144,12 +149,14 @@ mod activities_tests {
                 date: example_date1(),
                 plant: "Plant1".to_owned(),
                 note: None,
+                user: None,
             },
             LogItem {
                 activity: "Watering".to_owned(),
                 date: example_date1(),
                 plant: "Plant2".to_owned(),
                 note: None,
+                user: None,
             },
         ];
         assert_eq!(result, expected)
@@ -77,6 +77,8 @@ impl Port<Vec<PlantImage>> for Vec<OldImage> {
                     created: image.created,
                     file_name: new_file_name,
                     file_path: new_path_base,
+                    caption: None,
+                    cover: false,
                 };
                 new_images.push(new_image)
             }
@@ -124,6 +126,8 @@ mod images_tests {
             file_path: PathBuf::from(BASE_DIR)
                 .join(PLANTS_DIR_OUT)
                 .join("Plant1".to_owned()),
+            caption: None,
+            cover: false,
         }
     }
     fn example_image2() -> PlantImage {
@@ -133,6 +137,8 @@ mod images_tests {
             file_path: PathBuf::from(BASE_DIR)
                 .join(PLANTS_DIR_OUT)
                 .join("Plant2".to_owned()),
+            caption: None,
+            cover: false,
         }
     }
     fn example_image3() -> PlantImage {
@@ -142,6 +148,8 @@ mod images_tests {
             file_path: PathBuf::from(BASE_DIR)
                 .join(PLANTS_DIR_OUT)
                 .join("Plant1".to_owned()),
+            caption: None,
+            cover: false,
         }
     }
     fn example_image4() -> PlantImage {
@@ -151,6 +159,8 @@ mod images_tests {
             file_path: PathBuf::from(BASE_DIR)
                 .join(PLANTS_DIR_OUT)
                 .join("Plant3".to_owned()),
+            caption: None,
+            cover: false,
         }
     }
 
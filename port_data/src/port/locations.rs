@@ -29,6 +29,7 @@ impl Port<Vec<Location>> for Vec<PlantJSON> {
             let mut new_location = Location {
                 name: plant.current_location.clone(),
                 outside: false,
+                climate: vec![],
             };
 
             if interactive.to_owned() {
@@ -77,12 +78,14 @@ mod locations_test {
         Location {
             name: "Location1".to_owned(),
             outside: false,
+            climate: vec![],
         }
     }
     fn example_location2() -> Location {
         Location {
             name: "Location2".to_owned(),
             outside: false,
+            climate: vec![],
         }
     }
 
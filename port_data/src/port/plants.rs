@@ -80,6 +80,7 @@ impl Port<Vec<PlantInfo>> for Vec<PlantJSON> {
                 obtained,
                 auto_water,
                 notes: old_plant.plant_notes,
+                schedules: vec![],
             };
             new_plants.push(new_plant);
         }
@@ -112,6 +113,7 @@ mod plants_tests {
             obtained: example_date1(),
             auto_water: false,
             notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -124,6 +126,7 @@ mod plants_tests {
             obtained: example_date1(),
             auto_water: false,
             notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -136,6 +139,7 @@ mod plants_tests {
             obtained: example_date2(),
             auto_water: true,
             notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -204,8 +208,10 @@ mod plants_tests {
         assert!(file2.exists());
         assert!(file3.exists());
 
-        let result = HashSet::from_iter(load_dir(&plant_dir).unwrap().iter().cloned());
-        let expected = HashSet::from([example_info3(), example_info1(), example_info2()]);
+        // plants are compared by name, as they can not be hashed
+        let mut result: Vec<PlantInfo> = load_dir(&plant_dir).unwrap();
+        result.sort_by(|info1, info2| info1.name.cmp(&info2.name));
+        let expected = vec![example_info1(), example_info2(), example_info3()];
         assert_eq!(result, expected);
 
         std::fs::remove_file(file1.clone()).unwrap();
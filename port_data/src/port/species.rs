@@ -105,6 +105,7 @@ impl TryInto<Species> for SpeciesJSON {
             pruning_notes: self.pruning_notes,
             companions: self.companions,
             additional_notes: self.additional_notes,
+            schedules: vec![],
         })
     }
 }
@@ -218,6 +219,7 @@ mod species_test {
             pruning_notes: vec![],
             companions: vec![],
             additional_notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -265,6 +267,7 @@ mod species_test {
             pruning_notes: vec![],
             companions: vec![],
             additional_notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -1,24 +1,49 @@
 use database::sqlite_backend::SQLiteDB;
 use log::Level;
 use logger::{file_logger::FileLogger, init::init_logger};
-use render_html::{renderer::Renderer, write_html::write_all};
-use std::path::PathBuf;
+use render_html::{images::process_images, renderer::Renderer, write_html::write_all};
+use std::path::{Path, PathBuf};
+
+/// Only renders pages whose data changed since the last render
+const INCREMENTAL_FLAG: &str = "--incremental";
 
 static LOGGER: FileLogger = FileLogger {
     level: Level::Info,
     file_path: "build.log",
 };
 
+/// Changes whenever the renderer is rebuilt, so pages are rendered again when the code changed
+fn build_key() -> String {
+    std::env::current_exe()
+        .and_then(|exe| exe.metadata())
+        .and_then(|meta| meta.modified())
+        .map(|modified| format!("{modified:?}"))
+        .unwrap_or_default()
+}
+
 fn main() -> Result<(), String> {
     init_logger(&LOGGER)?;
 
     let db_man = SQLiteDB::new(PathBuf::from("plants.db")).map_err(|err| err.to_string())?;
 
+    log::info!("Processing images");
+    let num_images = process_images(&db_man.plants_dir, &PathBuf::from("html_out").join("img"))
+        .map_err(|err| err.to_string())?;
+    log::info!("Processed {num_images} new or changed images");
+
     let mut renderer = Renderer {
         database_manager: db_man,
         date_format: "%d.%m.%Y".to_owned(),
     };
 
+    if std::env::args().any(|arg| arg == INCREMENTAL_FLAG) {
+        renderer
+            .render_incremental(Path::new("html_out"), "plants", "species", &build_key())
+            .map_err(|err| err.to_string())?;
+        log::info!("Successfully rendered changed pages");
+        return Ok(());
+    }
+
     log::info!("Rendering Pages");
     let pages = renderer.render_all().map_err(|err| err.to_string())?;
     log::info!("Wrote page htmls");
@@ -0,0 +1,488 @@
+//! JSON API under /api/v1, reads are public, writes need the same token as the forms
+use super::{
+    errors::Error,
+    forms::{handle_form, parse_date, FormData},
+};
+use chrono::{Local, NaiveDate, TimeDelta};
+use database::database_manager::DatabaseManager;
+use plants::{
+    growth_item::GrowthItem, log_item::LogItem, named::Named, plant::Plant, plant::PlantInfo,
+    serialize::date_serializer,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// Query parameters read from list requests
+pub const QUERY_PARAMS: [&str; 7] = [
+    "plant", "location", "species", "from", "to", "offset", "limit",
+];
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+/// Upcoming tasks are listed until this many days from today when no `to` is given
+const DEFAULT_TASK_DAYS: i64 = 7;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ApiQuery {
+    pub plant: Option<String>,
+    pub location: Option<String>,
+    pub species: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ApiPage<T: Serialize> {
+    /// Number of items matching the filters, before pagination
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub items: Vec<T>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PlantDetails {
+    #[serde(flatten)]
+    pub info: PlantInfo,
+    pub growth: Vec<GrowthItem>,
+    pub activities: Vec<LogItem>,
+    pub next_activities: Vec<Task>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct Task {
+    pub plant: String,
+    pub activity: String,
+    #[serde(with = "date_serializer")]
+    pub date: NaiveDate,
+    pub outside: bool,
+}
+
+impl Default for ApiQuery {
+    fn default() -> ApiQuery {
+        ApiQuery {
+            plant: None,
+            location: None,
+            species: None,
+            from: None,
+            to: None,
+            offset: 0,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+}
+
+fn parse_count(input: &str, name: &str) -> Result<usize, Error> {
+    input
+        .parse::<usize>()
+        .map_err(|_| Error::BadRequest(format!("Could not parse {name} {input}")))
+}
+
+impl TryFrom<&FormData> for ApiQuery {
+    type Error = Error;
+    fn try_from(params: &FormData) -> Result<ApiQuery, Error> {
+        let param = |name: &str| {
+            params
+                .get(name)
+                .map(|value| value.trim().to_owned())
+                .filter(|value| !value.is_empty())
+        };
+        let mut query = ApiQuery {
+            plant: param("plant"),
+            location: param("location"),
+            species: param("species"),
+            from: param("from")
+                .map(|from| parse_date(&from, "from"))
+                .transpose()?,
+            to: param("to").map(|to| parse_date(&to, "to")).transpose()?,
+            ..Default::default()
+        };
+        if let Some(offset) = param("offset") {
+            query.offset = parse_count(&offset, "offset")?;
+        }
+        if let Some(limit) = param("limit") {
+            query.limit = parse_count(&limit, "limit")?;
+        }
+        if query.limit == 0 || query.limit > MAX_LIMIT {
+            return Err(Error::BadRequest(format!(
+                "limit has to be between 1 and {MAX_LIMIT}"
+            )));
+        }
+        Ok(query)
+    }
+}
+
+impl ApiQuery {
+    fn in_range(&self, date: &NaiveDate) -> bool {
+        self.from.is_none_or(|from| from <= *date) && self.to.is_none_or(|to| *date <= to)
+    }
+
+    fn matches_plant(&self, info: &PlantInfo) -> bool {
+        self.plant.as_ref().is_none_or(|name| *name == info.name)
+            && self
+                .location
+                .as_ref()
+                .is_none_or(|location| *location == info.location.get_name())
+            && self
+                .species
+                .as_ref()
+                .is_none_or(|species| *species == info.species.get_name())
+    }
+
+    /// Names of the plants matching the plant filters, None if there are no plant filters
+    fn plant_names<T: DatabaseManager>(
+        &self,
+        db_man: &mut T,
+    ) -> Result<Option<HashSet<String>>, Error> {
+        if self.plant.is_none() && self.location.is_none() && self.species.is_none() {
+            return Ok(None);
+        }
+        let plants = db_man.get_all_plants()?;
+        Ok(Some(
+            plants
+                .into_iter()
+                .filter(|plant| self.matches_plant(&plant.info))
+                .map(|plant| plant.info.name)
+                .collect(),
+        ))
+    }
+
+    fn paginate<T: Serialize>(&self, items: Vec<T>) -> Result<Value, Error> {
+        let page = ApiPage {
+            total: items.len(),
+            offset: self.offset,
+            limit: self.limit,
+            items: items
+                .into_iter()
+                .skip(self.offset)
+                .take(self.limit)
+                .collect(),
+        };
+        Ok(serde_json::to_value(page)?)
+    }
+}
+
+fn plant_tasks(plant: &Plant) -> Vec<Task> {
+    plant
+        .get_scheduled_activities()
+        .into_iter()
+        .filter_map(|activity| {
+            plant.get_next_activity_date(&activity).map(|date| Task {
+                plant: plant.info.name.clone(),
+                activity,
+                date,
+                outside: plant.is_outside(),
+            })
+        })
+        .collect()
+}
+
+/// Filtered by location, species and obtained date
+pub fn plants<T: DatabaseManager>(query: &ApiQuery, db_man: &mut T) -> Result<Value, Error> {
+    let plants: Vec<PlantInfo> = db_man
+        .get_all_plants()?
+        .into_iter()
+        .map(|plant| plant.info)
+        .filter(|info| query.matches_plant(info) && query.in_range(&info.obtained))
+        .collect();
+    query.paginate(plants)
+}
+
+pub fn plant<T: DatabaseManager>(name: &str, db_man: &mut T) -> Result<Value, Error> {
+    if !db_man.plant_exists(name)? {
+        return Err(Error::NotFound(format!("/api/v1/plants/{name}")));
+    }
+    let plant = db_man.get_plant(name)?;
+    let details = PlantDetails {
+        next_activities: plant_tasks(&plant),
+        info: plant.info,
+        growth: plant.growth,
+        activities: plant.activities,
+    };
+    Ok(serde_json::to_value(details)?)
+}
+
+pub fn species<T: DatabaseManager>(query: &ApiQuery, db_man: &mut T) -> Result<Value, Error> {
+    query.paginate(db_man.get_all_species()?)
+}
+
+pub fn locations<T: DatabaseManager>(query: &ApiQuery, db_man: &mut T) -> Result<Value, Error> {
+    query.paginate(db_man.get_locations()?)
+}
+
+pub fn activities<T: DatabaseManager>(query: &ApiQuery, db_man: &mut T) -> Result<Value, Error> {
+    let names = query.plant_names(db_man)?;
+    let logs: Vec<LogItem> = db_man
+        .get_logs()?
+        .into_iter()
+        .filter(|log| {
+            names
+                .as_ref()
+                .is_none_or(|names| names.contains(&log.plant))
+        })
+        .filter(|log| query.in_range(&log.date))
+        .collect();
+    query.paginate(logs)
+}
+
+pub fn growth<T: DatabaseManager>(query: &ApiQuery, db_man: &mut T) -> Result<Value, Error> {
+    let names = query.plant_names(db_man)?;
+    let growth: Vec<GrowthItem> = db_man
+        .get_growth()?
+        .into_iter()
+        .filter(|growth| {
+            names
+                .as_ref()
+                .is_none_or(|names| names.contains(&growth.plant))
+        })
+        .filter(|growth| query.in_range(&growth.date))
+        .collect();
+    query.paginate(growth)
+}
+
+/// Filtered by plant, species and date of death, dead plants have no location
+pub fn graveyard<T: DatabaseManager>(query: &ApiQuery, db_man: &mut T) -> Result<Value, Error> {
+    let graveyard: Vec<_> = db_man
+        .get_graveyard()?
+        .into_iter()
+        .filter(|plant| query.plant.as_ref().is_none_or(|name| *name == plant.name))
+        .filter(|plant| {
+            query
+                .species
+                .as_ref()
+                .is_none_or(|species| *species == plant.species)
+        })
+        .filter(|plant| query.in_range(&plant.died))
+        .collect();
+    query.paginate(graveyard)
+}
+
+/// Tasks due until `to`, or the next week, including overdue tasks unless `from` is given
+pub fn upcoming_tasks<T: DatabaseManager>(
+    query: &ApiQuery,
+    db_man: &mut T,
+) -> Result<Value, Error> {
+    let to = query
+        .to
+        .unwrap_or(Local::now().date_naive() + TimeDelta::days(DEFAULT_TASK_DAYS));
+    let mut tasks: Vec<Task> = db_man
+        .get_all_plants()?
+        .iter()
+        .filter(|plant| query.matches_plant(&plant.info))
+        .flat_map(plant_tasks)
+        .filter(|task| query.from.is_none_or(|from| from <= task.date) && task.date <= to)
+        .collect();
+    tasks.sort_by(|task1, task2| {
+        task1
+            .date
+            .cmp(&task2.date)
+            .then(task1.plant.cmp(&task2.plant))
+    });
+    query.paginate(tasks)
+}
+
+/// Runs a form handler with a json body, `path_field` is the plant or species named in the url
+pub fn write<T: DatabaseManager>(
+    action: &str,
+    body: Value,
+    path_field: Option<(&str, String)>,
+    db_man: &mut T,
+    date_format: &str,
+) -> Result<Value, Error> {
+    let Value::Object(fields) = body else {
+        return Err(Error::BadRequest("Expected a json object".to_owned()));
+    };
+    let mut data = FormData::new();
+    for (key, value) in fields {
+        let value = match value {
+            Value::String(st) => st,
+            // plant lists can be given as arrays
+            Value::Array(values) => values
+                .iter()
+                .map(|value| {
+                    value
+                        .as_str()
+                        .map(|st| st.to_owned())
+                        .unwrap_or(value.to_string())
+                })
+                .collect::<Vec<String>>()
+                .join(","),
+            Value::Null => "".to_owned(),
+            other => other.to_string(),
+        };
+        data.insert(key, value);
+    }
+    if let Some((key, name)) = path_field {
+        data.insert(key.to_owned(), name);
+    }
+    let message = handle_form(action, &data, db_man, date_format)?;
+    Ok(json!({ "message": message }))
+}
+
+#[cfg(test)]
+mod api_tests {
+    use super::{
+        activities, graveyard, plant, plants, upcoming_tasks, write, ApiQuery, FormData,
+        DEFAULT_LIMIT,
+    };
+    use chrono::NaiveDate;
+    use database::file_backend::FileDB;
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+
+    fn dummy_db() -> FileDB {
+        FileDB {
+            plants_dir: PathBuf::from("../testing/plants/"),
+            species_dir: PathBuf::from("../testing/species/"),
+            location_file: PathBuf::from("../testing/Locations.csv"),
+            logs_dir: PathBuf::from("../testing/Logs/"),
+            ..Default::default()
+        }
+    }
+
+    fn params(values: &[(&str, &str)]) -> FormData {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn item_names(page: &Value, key: &str) -> Vec<String> {
+        page["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item[key].as_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn query_defaults() {
+        let result = ApiQuery::try_from(&FormData::new()).unwrap();
+        assert_eq!(result, ApiQuery::default());
+        assert_eq!(result.limit, DEFAULT_LIMIT)
+    }
+
+    #[test]
+    fn query_all() {
+        let result = ApiQuery::try_from(&params(&[
+            ("plant", "Dummy1"),
+            ("location", "test location"),
+            ("species", "test species"),
+            ("from", "2024-01-01"),
+            ("to", "2024-02-01"),
+            ("offset", "10"),
+            ("limit", "5"),
+        ]))
+        .unwrap();
+        let expected = ApiQuery {
+            plant: Some("Dummy1".to_owned()),
+            location: Some("test location".to_owned()),
+            species: Some("test species".to_owned()),
+            from: NaiveDate::from_ymd_opt(2024, 1, 1),
+            to: NaiveDate::from_ymd_opt(2024, 2, 1),
+            offset: 10,
+            limit: 5,
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn query_bad_limit() {
+        let result = ApiQuery::try_from(&params(&[("limit", "0")]));
+        assert_eq!(result.unwrap_err().status_code(), 400)
+    }
+
+    #[test]
+    fn query_bad_date() {
+        let result = ApiQuery::try_from(&params(&[("from", "01.01.2024")]));
+        assert_eq!(result.unwrap_err().status_code(), 400)
+    }
+
+    #[test]
+    fn plants_by_location() {
+        let query = ApiQuery::try_from(&params(&[("location", "test location")])).unwrap();
+        let result = plants(&query, &mut dummy_db()).unwrap();
+        assert_eq!(result["total"], 1);
+        assert_eq!(item_names(&result, "name"), vec!["Dummy1"])
+    }
+
+    #[test]
+    fn plants_paginated() {
+        let query = ApiQuery::try_from(&params(&[("offset", "1"), ("limit", "1")])).unwrap();
+        let result = plants(&query, &mut dummy_db()).unwrap();
+        assert_eq!(result["total"], 2);
+        assert_eq!(result["items"].as_array().unwrap().len(), 1)
+    }
+
+    #[test]
+    fn plant_details() {
+        let result = plant("Dummy1", &mut dummy_db()).unwrap();
+        assert_eq!(result["name"], "Dummy1");
+        assert_eq!(result["species"], "test species");
+        assert!(result["growth"].is_array())
+    }
+
+    #[test]
+    fn plant_not_found() {
+        let result = plant("not a plant", &mut dummy_db());
+        assert_eq!(result.unwrap_err().status_code(), 404)
+    }
+
+    #[test]
+    fn activities_by_date() {
+        let query = ApiQuery::try_from(&params(&[("from", "1970-01-02")])).unwrap();
+        let result = activities(&query, &mut dummy_db()).unwrap();
+        assert_eq!(result["total"], 0)
+    }
+
+    #[test]
+    fn graveyard_by_species() {
+        let query = ApiQuery::try_from(&params(&[("species", "test species")])).unwrap();
+        let result = graveyard(&query, &mut dummy_db()).unwrap();
+        assert_eq!(item_names(&result, "name"), vec!["Dummy1"])
+    }
+
+    #[test]
+    fn tasks_sorted() {
+        let query = ApiQuery::try_from(&params(&[("to", "2100-01-01")])).unwrap();
+        let result = upcoming_tasks(&query, &mut dummy_db()).unwrap();
+        let dates: Vec<NaiveDate> = result["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|task| {
+                NaiveDate::parse_from_str(task["date"].as_str().unwrap(), "%d.%m.%Y").unwrap()
+            })
+            .collect();
+        let mut sorted = dates.clone();
+        sorted.sort();
+        assert_eq!(dates, sorted)
+    }
+
+    #[test]
+    fn write_not_object() {
+        let result = write("activity", json!([]), None, &mut dummy_db(), "%d.%m.%Y");
+        assert_eq!(result.unwrap_err().status_code(), 400)
+    }
+
+    #[test]
+    fn write_path_field() {
+        let body = json!({"field": "origin", "value": "shop"});
+        let path_field = Some(("plant", "not a plant".to_owned()));
+        let result = write(
+            "update_plant",
+            body,
+            path_field,
+            &mut dummy_db(),
+            "%d.%m.%Y",
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Plant not a plant does not exist"
+        )
+    }
+}
@@ -0,0 +1,104 @@
+use super::errors::Error;
+use rouille::Request;
+
+/// Cookie set by /login
+pub const TOKEN_COOKIE: &str = "plants_token";
+
+/// Compares without returning early so the token can't be guessed from response times
+fn tokens_equal(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Checks the login form token against the configured one
+pub fn check_token(given: &str, auth_token: &Option<String>) -> Result<(), Error> {
+    match auth_token {
+        None => Err(Error::Unauthorized(
+            "editing is disabled, set auth_token in the server config".to_owned(),
+        )),
+        Some(token) if tokens_equal(given, token) => Ok(()),
+        Some(_) => Err(Error::Unauthorized("wrong token".to_owned())),
+    }
+}
+
+/// Requests are authorized by the login cookie or an `Authorization: Bearer <token>` header
+pub fn check_request(request: &Request, auth_token: &Option<String>) -> Result<(), Error> {
+    let cookie = rouille::input::cookies(request)
+        .find(|(name, _)| *name == TOKEN_COOKIE)
+        .map(|(_, value)| value);
+    let bearer = request
+        .header("Authorization")
+        .and_then(|header| header.strip_prefix("Bearer "));
+    match cookie.or(bearer) {
+        Some(given) => check_token(given, auth_token),
+        None => Err(Error::Unauthorized("please log in first".to_owned())),
+    }
+}
+
+pub fn login_cookie(token: &str) -> String {
+    format!("{TOKEN_COOKIE}={token}; Path=/; HttpOnly; SameSite=Strict")
+}
+
+#[cfg(test)]
+mod auth_tests {
+    use super::{check_request, check_token, login_cookie};
+    use rouille::Request;
+
+    fn token() -> Option<String> {
+        Some("secret".to_owned())
+    }
+
+    fn request(header: (&str, &str)) -> Request {
+        Request::fake_http(
+            "POST",
+            "/actions/activity",
+            vec![(header.0.to_owned(), header.1.to_owned())],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn token_ok() {
+        assert!(check_token("secret", &token()).is_ok())
+    }
+
+    #[test]
+    fn token_wrong() {
+        assert!(check_token("secrets", &token()).is_err())
+    }
+
+    #[test]
+    fn token_not_configured() {
+        assert!(check_token("", &None).is_err())
+    }
+
+    #[test]
+    fn request_cookie() {
+        let request = request(("Cookie", "other=1; plants_token=secret"));
+        assert!(check_request(&request, &token()).is_ok())
+    }
+
+    #[test]
+    fn request_bearer() {
+        let request = request(("Authorization", "Bearer secret"));
+        assert!(check_request(&request, &token()).is_ok())
+    }
+
+    #[test]
+    fn request_no_token() {
+        let request = request(("Accept", "text/plain"));
+        assert!(check_request(&request, &token()).is_err())
+    }
+
+    #[test]
+    fn cookie() {
+        assert_eq!(
+            login_cookie("secret"),
+            "plants_token=secret; Path=/; HttpOnly; SameSite=Strict"
+        )
+    }
+}
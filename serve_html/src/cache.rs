@@ -0,0 +1,138 @@
+use chrono::{Local, NaiveDate};
+use std::{collections::HashMap, fs::read_dir, path::Path, time::SystemTime};
+
+/// Everything a page depends on besides its url
+/// pages change when the database is written to, when images are added or removed,
+/// or when the day changes (e.g. next watering dates)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheVersion {
+    pub db_modified: SystemTime,
+    pub images_modified: Option<SystemTime>,
+    pub date: NaiveDate,
+}
+
+impl CacheVersion {
+    /// Returns None if the database file can't be read, pages are not cached then
+    pub fn current(db_file: &Path, plants_dir: &Path) -> Option<CacheVersion> {
+        let db_modified = db_file.metadata().and_then(|meta| meta.modified()).ok()?;
+        Some(CacheVersion {
+            db_modified,
+            images_modified: images_modified(plants_dir),
+            date: Local::now().date_naive(),
+        })
+    }
+}
+
+/// Latest change of the plants directory or the image directory of a plant
+/// adding or removing an image changes the modification time of its directory
+fn images_modified(plants_dir: &Path) -> Option<SystemTime> {
+    let modified = |dir: &Path| dir.metadata().and_then(|meta| meta.modified()).ok();
+    let image_dirs = read_dir(plants_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir());
+    image_dirs
+        .filter_map(|dir| modified(&dir))
+        .chain(modified(plants_dir))
+        .max()
+}
+
+/// Rendered pages by url, all pages are dropped once the version changes
+#[derive(Debug, Default)]
+pub struct PageCache {
+    pub version: Option<CacheVersion>,
+    pub pages: HashMap<String, String>,
+}
+
+impl PageCache {
+    pub fn get(&self, url: &str, version: &CacheVersion) -> Option<String> {
+        if self.version.as_ref() != Some(version) {
+            return None;
+        }
+        self.pages.get(url).cloned()
+    }
+
+    pub fn insert(&mut self, url: String, version: CacheVersion, html: String) {
+        if self.version.as_ref() != Some(&version) {
+            self.pages.clear();
+            self.version = Some(version);
+        }
+        self.pages.insert(url, html);
+    }
+}
+
+#[cfg(test)]
+mod cache_tests {
+    use super::{CacheVersion, PageCache};
+    use chrono::NaiveDate;
+    use std::{
+        fs::{create_dir_all, remove_dir_all, write},
+        path::PathBuf,
+        thread::sleep,
+        time::{Duration, SystemTime},
+    };
+
+    const PLANTS_DIR: &str = "../testing/cache_plants";
+
+    fn example_version(secs: u64) -> CacheVersion {
+        CacheVersion {
+            db_modified: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            images_modified: None,
+            date: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+        }
+    }
+
+    #[test]
+    fn cache_hit() {
+        let mut cache = PageCache::default();
+        cache.insert("/".to_owned(), example_version(1), "index".to_owned());
+        let result = cache.get("/", &example_version(1));
+        assert_eq!(result, Some("index".to_owned()))
+    }
+
+    #[test]
+    fn cache_miss() {
+        let mut cache = PageCache::default();
+        cache.insert("/".to_owned(), example_version(1), "index".to_owned());
+        assert_eq!(cache.get("/gallery.html", &example_version(1)), None)
+    }
+
+    #[test]
+    fn cache_outdated() {
+        let mut cache = PageCache::default();
+        cache.insert("/".to_owned(), example_version(1), "index".to_owned());
+        assert_eq!(cache.get("/", &example_version(2)), None);
+        cache.insert(
+            "/gallery.html".to_owned(),
+            example_version(2),
+            "gallery".to_owned(),
+        );
+        assert_eq!(cache.pages.len(), 1)
+    }
+
+    #[test]
+    fn version_missing_db() {
+        let result =
+            CacheVersion::current(&PathBuf::from("missing.db"), &PathBuf::from(PLANTS_DIR));
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn version_new_image() {
+        let plants_dir = PathBuf::from(PLANTS_DIR);
+        let image_dir = plants_dir.join("Plant1");
+        create_dir_all(&image_dir).unwrap();
+        let db_file = plants_dir.join("plants.db");
+        write(&db_file, "").unwrap();
+        let before = CacheVersion::current(&db_file, &plants_dir).unwrap();
+        sleep(Duration::from_millis(20));
+        write(image_dir.join("01052024.jpg"), "").unwrap();
+        let after = CacheVersion::current(&db_file, &plants_dir).unwrap();
+        remove_dir_all(&plants_dir).unwrap();
+
+        assert_eq!(before.db_modified, after.db_modified);
+        assert_ne!(before, after)
+    }
+}
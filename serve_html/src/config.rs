@@ -0,0 +1,182 @@
+use super::errors::Error;
+use database::file_backend::load_json::load_json;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage: serve_html [--config <file>] [--bind <address>] [--db <file>] [--html-dir <dir>] [--plants-dir <dir>] [--date-format <format>]
+    --config       json file with server settings (default: serve_conf.json if it exists)
+    --bind         address to listen on (default: 0.0.0.0:8080)
+    --db           sqlite database file (default: plants.db)
+    --html-dir     directory with rendered images and scripts (default: html_out)
+    --plants-dir   directory with the original plant images (default: data/Plants)
+    --date-format  format of dates shown on pages (default: %d.%m.%Y)
+arguments override the settings in the config file
+editing from /forms.html needs an auth_token in the config file";
+
+/// Config file loaded when no `--config` is given
+pub const DEFAULT_CONFIG_FILE: &str = "serve_conf.json";
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub bind_address: String,
+    pub db_file: PathBuf,
+    pub html_dir: PathBuf,
+    pub plants_dir: PathBuf,
+    pub date_format: String,
+    /// Token needed to post forms, editing is disabled without one
+    pub auth_token: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> ServerConfig {
+        ServerConfig {
+            bind_address: "0.0.0.0:8080".to_owned(),
+            db_file: PathBuf::from("plants.db"),
+            html_dir: PathBuf::from("html_out"),
+            plants_dir: PathBuf::from("data").join("Plants"),
+            date_format: "%d.%m.%Y".to_owned(),
+            auth_token: None,
+        }
+    }
+}
+
+fn load_config(path: &PathBuf) -> Result<ServerConfig, Error> {
+    load_json(path).map_err(|err| Error::Config(Box::new(err)))
+}
+
+impl TryFrom<Vec<String>> for ServerConfig {
+    type Error = Error;
+    fn try_from(args: Vec<String>) -> Result<ServerConfig, Error> {
+        let mut args = args.into_iter().peekable();
+        let mut config = if args.peek().map(|arg| arg.as_str()) == Some("--config") {
+            args.next();
+            let path = args
+                .next()
+                .ok_or(Error::Args("Missing value for --config".to_owned()))?;
+            load_config(&PathBuf::from(path))?
+        } else if PathBuf::from(DEFAULT_CONFIG_FILE).exists() {
+            load_config(&PathBuf::from(DEFAULT_CONFIG_FILE))?
+        } else {
+            ServerConfig::default()
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| Error::Args(format!("Missing value for {name}")))
+            };
+            match arg.as_str() {
+                "--bind" => config.bind_address = value("--bind")?,
+                "--db" => config.db_file = PathBuf::from(value("--db")?),
+                "--html-dir" => config.html_dir = PathBuf::from(value("--html-dir")?),
+                "--plants-dir" => config.plants_dir = PathBuf::from(value("--plants-dir")?),
+                "--date-format" => config.date_format = value("--date-format")?,
+                "--config" => {
+                    return Err(Error::Args(
+                        "--config has to be the first argument".to_owned(),
+                    ))
+                }
+                _ => return Err(Error::Args(format!("Unknown argument {arg}"))),
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::ServerConfig;
+    use std::{
+        fs::{create_dir_all, remove_dir_all, write},
+        path::PathBuf,
+    };
+
+    const CONFIG_DIR: &str = "../testing/serve_config_test";
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let result = ServerConfig::try_from(to_args(&[])).unwrap();
+        assert_eq!(result, ServerConfig::default())
+    }
+
+    #[test]
+    fn parse_all() {
+        let result = ServerConfig::try_from(to_args(&[
+            "--bind",
+            "127.0.0.1:3000",
+            "--db",
+            "test.db",
+            "--html-dir",
+            "out",
+            "--plants-dir",
+            "images",
+            "--date-format",
+            "%Y-%m-%d",
+        ]))
+        .unwrap();
+        let expected = ServerConfig {
+            bind_address: "127.0.0.1:3000".to_owned(),
+            db_file: PathBuf::from("test.db"),
+            html_dir: PathBuf::from("out"),
+            plants_dir: PathBuf::from("images"),
+            date_format: "%Y-%m-%d".to_owned(),
+            auth_token: None,
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_config_file() {
+        let dir = PathBuf::from(CONFIG_DIR);
+        create_dir_all(&dir).unwrap();
+        let path = dir.join("parse_config_file.json");
+        write(
+            &path,
+            r#"{"bind_address":"127.0.0.1:80","db_file":"other.db","auth_token":"secret"}"#,
+        )
+        .unwrap();
+        let result = ServerConfig::try_from(to_args(&[
+            "--config",
+            path.to_str().unwrap(),
+            "--db",
+            "test.db",
+        ]));
+        remove_dir_all(&dir).unwrap();
+        let expected = ServerConfig {
+            bind_address: "127.0.0.1:80".to_owned(),
+            db_file: PathBuf::from("test.db"),
+            auth_token: Some("secret".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(result.unwrap(), expected)
+    }
+
+    #[test]
+    fn parse_missing_config_file() {
+        let result = ServerConfig::try_from(to_args(&["--config", "missing.json"]));
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn parse_config_not_first() {
+        let result = ServerConfig::try_from(to_args(&["--db", "test.db", "--config", "c.json"]));
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn parse_missing_value() {
+        let result = ServerConfig::try_from(to_args(&["--bind"]));
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn parse_unknown() {
+        let result = ServerConfig::try_from(to_args(&["--port", "80"]));
+        assert!(result.is_err())
+    }
+}
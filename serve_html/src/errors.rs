@@ -0,0 +1,115 @@
+use super::config::USAGE;
+use input_handlers::errors::Error as InputError;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Args(String),
+    Config(Box<dyn std::error::Error>),
+    DB(Box<dyn std::error::Error>),
+    Render(render_html::errors::Error),
+    Json(serde_json::Error),
+    Logger(String),
+    NotFound(String),
+    Unauthorized(String),
+    BadRequest(String),
+}
+
+impl Error {
+    /// Http status code of the response for this error
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Error::BadRequest(_) => 400,
+            Error::Unauthorized(_) => 401,
+            Error::NotFound(_) => 404,
+            _ => 500,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Args(msg) => write!(f, "Bad arguments: {msg}\n{USAGE}"),
+            Error::Config(err) => write!(f, "Could not load config: {err}"),
+            Error::DB(err) => write!(f, "{err}"),
+            Error::Render(err) => write!(f, "{err}"),
+            Error::Json(err) => write!(f, "Could not serialize response: {err}"),
+            Error::Logger(msg) => write!(f, "Could not start logger: {msg}"),
+            Error::NotFound(url) => write!(f, "Could not find page {url}"),
+            Error::Unauthorized(msg) => write!(f, "Not authorized: {msg}"),
+            Error::BadRequest(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Box<dyn std::error::Error>> for Error {
+    fn from(err: Box<dyn std::error::Error>) -> Error {
+        Error::DB(err)
+    }
+}
+
+impl From<render_html::errors::Error> for Error {
+    fn from(err: render_html::errors::Error) -> Error {
+        Error::Render(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(err: InputError) -> Error {
+        match err {
+            InputError::DB(err) => Error::DB(err),
+            _ => Error::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<plants::errors::Error> for Error {
+    fn from(err: plants::errors::Error) -> Error {
+        Error::BadRequest(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::{Error, InputError, USAGE};
+
+    #[test]
+    fn display_args() {
+        let result = format!("{}", Error::Args("Unknown argument --port".to_owned()));
+        let expected = format!("Bad arguments: Unknown argument --port\n{USAGE}");
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn status_not_found() {
+        assert_eq!(
+            Error::NotFound("/plants/x.html".to_owned()).status_code(),
+            404
+        )
+    }
+
+    #[test]
+    fn status_internal() {
+        assert_eq!(Error::Logger("".to_owned()).status_code(), 500)
+    }
+
+    #[test]
+    fn status_bad_input() {
+        let err: Error = InputError::BadHealth(7).into();
+        assert_eq!(err.status_code(), 400)
+    }
+
+    #[test]
+    fn status_unauthorized() {
+        assert_eq!(Error::Unauthorized("".to_owned()).status_code(), 401)
+    }
+}
@@ -0,0 +1,373 @@
+use super::errors::Error;
+use chrono::{Local, NaiveDate};
+use database::database_manager::DatabaseManager;
+use input_handlers::{
+    input_health, input_location, input_notes, input_plant_name, input_plant_names, input_species,
+    str_to_value,
+};
+use pages::forms::species_field_name;
+use plants::{
+    graveyard::GraveyardPlant,
+    growth_item::GrowthItem,
+    log_item::LogItem,
+    named::Named,
+    plant::{PlantInfo, PlantLocation, PlantSpecies},
+    plant_update::{update_plant, UpdateField as PlantField},
+    species::{Species, SunlightRequirement},
+    species_update::{update_species, UpdateField as SpeciesField, UpdateValue as SpeciesValue},
+};
+use std::collections::HashMap;
+
+/// Values posted by js/forms.js, keyed by field name
+pub type FormData = HashMap<String, String>;
+
+/// Format of `<input type="date">` values
+const INPUT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+fn optional_field(data: &FormData, name: &str) -> Option<String> {
+    data.get(name)
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+fn required_field(data: &FormData, name: &str) -> Result<String, Error> {
+    optional_field(data, name).ok_or_else(|| Error::BadRequest(format!("Input {name} is missing")))
+}
+
+/// Dates are posted and queried as `YYYY-MM-DD`, the format of date inputs
+pub fn parse_date(input: &str, name: &str) -> Result<NaiveDate, Error> {
+    NaiveDate::parse_from_str(input, INPUT_DATE_FORMAT)
+        .map_err(|_| Error::BadRequest(format!("Could not parse {name} date {input}")))
+}
+
+fn parse_float(input: &str, name: &str) -> Result<f32, Error> {
+    input
+        .parse::<f32>()
+        .map_err(|_| Error::BadRequest(format!("Could not parse {name} {input}")))
+}
+
+fn log_activity<T: DatabaseManager>(data: &FormData, db_man: &mut T) -> Result<String, Error> {
+    let activity = required_field(data, "activity")?;
+    let plants = input_plant_names(required_field(data, "plants")?, db_man)?;
+    let date = match optional_field(data, "date") {
+        Some(date) => parse_date(&date, "activity")?,
+        None => Local::now().date_naive(),
+    };
+    let note = optional_field(data, "note");
+    let logs = plants
+        .iter()
+        .map(|plant| LogItem {
+            activity: activity.clone(),
+            date,
+            plant: plant.clone(),
+            note: note.clone(),
+            user: None,
+        })
+        .collect();
+    db_man.write_logs(logs)?;
+    Ok(format!(
+        "Successfully logged {activity} for {}",
+        plants.join(",")
+    ))
+}
+
+fn add_growth<T: DatabaseManager>(data: &FormData, db_man: &mut T) -> Result<String, Error> {
+    let plant = input_plant_name(required_field(data, "plant")?, db_man)?;
+    let height_cm = parse_float(&required_field(data, "height")?, "height")?;
+    let width_cm = parse_float(&required_field(data, "width")?, "width")?;
+    let health = input_health(required_field(data, "health")?)?;
+    let growth = GrowthItem {
+        plant: plant.clone(),
+        date: Local::now().date_naive(),
+        height_cm,
+        width_cm,
+        note: optional_field(data, "note"),
+        health,
+    };
+    db_man.write_growth(growth)?;
+    Ok(format!(
+        "Successfully added growth {height_cm}x{width_cm}, {health} for {plant}"
+    ))
+}
+
+fn new_plant<T: DatabaseManager>(data: &FormData, db_man: &mut T) -> Result<String, Error> {
+    let name = required_field(data, "name")?;
+    if db_man.plant_exists(&name)? {
+        return Err(Error::BadRequest(format!("Plant {name} already exists")));
+    }
+    let species_name = input_species(required_field(data, "species")?, db_man)?;
+    let species = PlantSpecies::Species(Box::new(db_man.get_species(&species_name)?));
+    let location_name = input_location(required_field(data, "location")?, db_man)?;
+    let location = PlantLocation::Location(Box::new(db_man.get_location(&location_name)?));
+    let obtained = parse_date(&required_field(data, "obtained")?, "obtained")?;
+    let growth = GrowthItem {
+        plant: name.clone(),
+        date: Local::now().date_naive(),
+        height_cm: parse_float(&required_field(data, "height")?, "height")?,
+        width_cm: parse_float(&required_field(data, "width")?, "width")?,
+        note: Some("Created during plant creation".to_owned()),
+        health: input_health(required_field(data, "health")?)?,
+    };
+    let plant = PlantInfo {
+        name: name.clone(),
+        species,
+        location,
+        origin: optional_field(data, "origin").unwrap_or_default(),
+        obtained,
+        auto_water: optional_field(data, "auto_water").as_deref() == Some("true"),
+        notes: optional_field(data, "notes")
+            .map(input_notes)
+            .unwrap_or_default(),
+        schedules: vec![],
+    };
+    db_man.write_plant(plant)?;
+    db_man.write_growth(growth)?;
+    Ok(format!("Successfully saved plant {name}"))
+}
+
+fn edit_plant<T: DatabaseManager>(
+    data: &FormData,
+    db_man: &mut T,
+    date_format: &str,
+) -> Result<String, Error> {
+    let plant_name = input_plant_name(required_field(data, "plant")?, db_man)?;
+    let field = required_field(data, "field")?.parse::<PlantField>()?;
+    let value = str_to_value(required_field(data, "value")?, &field, db_man, date_format)?;
+    let mut plant = db_man.get_plant(&plant_name)?;
+    update_plant(&mut plant, field, value)?;
+    db_man.write_plant(plant.info)?;
+    Ok(format!("Successfully updated plant {plant_name}"))
+}
+
+/// Species fields that have no sensible empty value
+fn species_field_required(field: &SpeciesField) -> bool {
+    matches!(
+        field,
+        SpeciesField::ScientificName
+            | SpeciesField::Genus
+            | SpeciesField::Family
+            | SpeciesField::Sunlight
+            | SpeciesField::TempMin
+            | SpeciesField::TempMax
+            | SpeciesField::TempMinOpt
+            | SpeciesField::TempMaxOpt
+            | SpeciesField::PhMin
+            | SpeciesField::PhMax
+    )
+}
+
+fn new_species<T: DatabaseManager>(data: &FormData, db_man: &mut T) -> Result<String, Error> {
+    let name = required_field(data, "name")?;
+    if db_man.species_exists(&name)? {
+        return Err(Error::BadRequest(format!("Species {name} already exists")));
+    }
+    let mut species = Species {
+        name: name.clone(),
+        scientific_name: "".to_owned(),
+        genus: "".to_owned(),
+        family: "".to_owned(),
+        sunlight: SunlightRequirement::Direct,
+        temp_min: 0.0,
+        temp_max: 0.0,
+        opt_temp_min: 0.0,
+        opt_temp_max: 0.0,
+        planting_distance: None,
+        ph_min: 0.0,
+        ph_max: 0.0,
+        watering_notes: vec![],
+        avg_watering_days: None,
+        fertilizing_notes: vec![],
+        avg_fertilizing_days: None,
+        pruning_notes: vec![],
+        companions: vec![],
+        additional_notes: vec![],
+        schedules: vec![],
+    };
+    for field in SpeciesField::all_fields() {
+        let field_name = species_field_name(&field);
+        match optional_field(data, &field_name) {
+            Some(input) => {
+                let value = SpeciesValue::try_from((input, &field))?;
+                update_species(&mut species, field, value)?;
+            }
+            None if species_field_required(&field) => {
+                return Err(Error::BadRequest(format!("Input {field_name} is missing")))
+            }
+            None => (),
+        }
+    }
+    db_man.write_species(species)?;
+    Ok(format!("Successfully saved species {name}"))
+}
+
+fn edit_species<T: DatabaseManager>(data: &FormData, db_man: &mut T) -> Result<String, Error> {
+    let species_name = input_species(required_field(data, "species")?, db_man)?;
+    let field = required_field(data, "field")?.parse::<SpeciesField>()?;
+    let value = SpeciesValue::try_from((required_field(data, "value")?, &field))?;
+    let mut species = db_man.get_species(&species_name)?;
+    update_species(&mut species, field, value)?;
+    db_man.write_species(species)?;
+    Ok(format!("Successfully updated species {species_name}"))
+}
+
+fn move_to_graveyard<T: DatabaseManager>(data: &FormData, db_man: &mut T) -> Result<String, Error> {
+    let name = input_plant_name(required_field(data, "plant")?, db_man)?;
+    let died = parse_date(&required_field(data, "died")?, "died")?;
+    let reason = required_field(data, "reason")?;
+    let plant = db_man.get_plant(&name)?;
+    db_man.kill_plant(GraveyardPlant {
+        name: name.clone(),
+        species: plant.info.species.get_name(),
+        planted: plant.info.obtained,
+        died,
+        reason,
+    })?;
+    Ok(format!("Successfully moved plant {name} to graveyard"))
+}
+
+/// Runs the form posted to `/actions/{action}` and returns the message shown below the form
+pub fn handle_form<T: DatabaseManager>(
+    action: &str,
+    data: &FormData,
+    db_man: &mut T,
+    date_format: &str,
+) -> Result<String, Error> {
+    // forms can write several items, so they are written in one transaction
+    db_man.transaction(|db_man| match action {
+        "activity" => log_activity(data, db_man),
+        "growth" => add_growth(data, db_man),
+        "new_plant" => new_plant(data, db_man),
+        "update_plant" => edit_plant(data, db_man, date_format),
+        "new_species" => new_species(data, db_man),
+        "update_species" => edit_species(data, db_man),
+        "graveyard" => move_to_graveyard(data, db_man),
+        _ => Err(Error::NotFound(format!("/actions/{action}"))),
+    })
+}
+
+#[cfg(test)]
+mod forms_tests {
+    use super::{handle_form, FormData};
+    use database::{database_manager::DatabaseManager, file_backend::FileDB};
+    use std::{
+        fs::{copy, create_dir_all, remove_dir_all},
+        path::PathBuf,
+    };
+
+    const TEST_DIR: &str = "../testing/serve_forms_test";
+
+    fn dummy_db() -> FileDB {
+        FileDB {
+            plants_dir: PathBuf::from("../testing/plants/"),
+            species_dir: PathBuf::from("../testing/species/"),
+            location_file: PathBuf::from("../testing/Locations.csv"),
+            logs_dir: PathBuf::from("../testing/Logs/"),
+            ..Default::default()
+        }
+    }
+
+    /// Copies the logs so tests can write to them
+    fn copy_logs_db(test_name: &str) -> FileDB {
+        let logs_dir = PathBuf::from(TEST_DIR).join(test_name);
+        create_dir_all(&logs_dir).unwrap();
+        for file in ["Activities.csv", "Growth.csv"] {
+            copy(
+                PathBuf::from("../testing/Logs").join(file),
+                logs_dir.join(file),
+            )
+            .unwrap();
+        }
+        FileDB {
+            logs_dir,
+            ..dummy_db()
+        }
+    }
+
+    /// Fresh database on the copied logs, so nothing is read from the writer's cache
+    fn copy_logs_db_reader(test_name: &str) -> FileDB {
+        FileDB {
+            logs_dir: PathBuf::from(TEST_DIR).join(test_name),
+            ..dummy_db()
+        }
+    }
+
+    fn form_data(values: &[(&str, &str)]) -> FormData {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn activity() {
+        let mut db = copy_logs_db("activity");
+        let data = form_data(&[
+            ("activity", "Watering"),
+            ("plants", "Dummy1,Dummy2"),
+            ("date", "2024-05-01"),
+            ("note", ""),
+        ]);
+        let result = handle_form("activity", &data, &mut db, "%d.%m.%Y").unwrap();
+        let logs_before = dummy_db().get_logs().unwrap();
+        let logs_after = copy_logs_db_reader("activity").get_logs().unwrap();
+        remove_dir_all(TEST_DIR).unwrap();
+        assert_eq!(result, "Successfully logged Watering for Dummy1,Dummy2");
+        assert_eq!(logs_after.len(), logs_before.len() + 2)
+    }
+
+    #[test]
+    fn activity_unknown_plant() {
+        let data = form_data(&[("activity", "Watering"), ("plants", "Dummy1,not a plant")]);
+        let result = handle_form("activity", &data, &mut dummy_db(), "%d.%m.%Y");
+        assert_eq!(result.unwrap_err().status_code(), 400)
+    }
+
+    #[test]
+    fn growth_bad_health() {
+        let data = form_data(&[
+            ("plant", "Dummy1"),
+            ("height", "10"),
+            ("width", "10"),
+            ("health", "7"),
+        ]);
+        let result = handle_form("growth", &data, &mut dummy_db(), "%d.%m.%Y");
+        assert_eq!(result.unwrap_err().status_code(), 400)
+    }
+
+    #[test]
+    fn growth_missing_input() {
+        let data = form_data(&[("plant", "Dummy1"), ("height", "")]);
+        let result = handle_form("growth", &data, &mut dummy_db(), "%d.%m.%Y");
+        assert_eq!(result.unwrap_err().to_string(), "Input height is missing")
+    }
+
+    #[test]
+    fn new_plant_exists() {
+        let data = form_data(&[("name", "Dummy1")]);
+        let result = handle_form("new_plant", &data, &mut dummy_db(), "%d.%m.%Y");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Plant Dummy1 already exists"
+        )
+    }
+
+    #[test]
+    fn update_plant_bad_field() {
+        let data = form_data(&[("plant", "Dummy1"), ("field", "color"), ("value", "red")]);
+        let result = handle_form("update_plant", &data, &mut dummy_db(), "%d.%m.%Y");
+        assert_eq!(result.unwrap_err().status_code(), 400)
+    }
+
+    #[test]
+    fn new_species_missing_field() {
+        let data = form_data(&[("name", "new species"), ("scientific_name", "Species nova")]);
+        let result = handle_form("new_species", &data, &mut dummy_db(), "%d.%m.%Y");
+        assert_eq!(result.unwrap_err().to_string(), "Input genus is missing")
+    }
+
+    #[test]
+    fn unknown_action() {
+        let result = handle_form("water", &FormData::new(), &mut dummy_db(), "%d.%m.%Y");
+        assert_eq!(result.unwrap_err().status_code(), 404)
+    }
+}
@@ -1,94 +1,323 @@
 #[macro_use]
 extern crate rouille;
 
-use database::{database_manager::DatabaseManager, sqlite_backend::SQLiteDB};
+mod api;
+mod auth;
+mod cache;
+mod config;
+mod errors;
+mod forms;
+
+use api::{ApiQuery, QUERY_PARAMS};
+use auth::{check_request, check_token, login_cookie};
+use cache::{CacheVersion, PageCache};
+use config::ServerConfig;
+use database::{
+    database_manager::DatabaseManager,
+    sqlite_backend::{errors::Error as SQLiteError, SQLiteDB},
+};
+use errors::Error;
+use forms::{handle_form, FormData};
+use logger::{init::init_logger, std_out_logger::StdOutLogger};
+use pages::error_page::ErrorPage;
 use render_html::renderer::Renderer;
-use std::{fs::File, path::PathBuf, sync::Mutex};
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let db = SQLiteDB::new(PathBuf::from("plants.db"))?;
-    let renderer = Renderer::new(db, "%d.%m.%Y");
-    let render_mutex = Mutex::new(renderer);
-    rouille::start_server("0.0.0.0:8080", move |request| {
-        router!(request, (GET) ["/"] =>{
-                let content = render_mutex.lock()
-                    .expect("Could not lock database")
-                    .render_index().expect("Could not render index");
-                rouille::Response::html(content)
+use rouille::{Request, Response};
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+static LOGGER: StdOutLogger = StdOutLogger {
+    level: log::Level::Info,
+};
+
+struct Server {
+    config: ServerConfig,
+    renderer: Mutex<Renderer<SQLiteDB>>,
+    cache: Mutex<PageCache>,
+}
+
+/// Plants or species that don't exist are a 404, any other database error a 500
+fn not_found_or_db(err: Box<dyn std::error::Error>, url: &str) -> Error {
+    match err.downcast_ref::<SQLiteError>() {
+        Some(SQLiteError::PlantNotFound { .. }) | Some(SQLiteError::SpeciesNotFound { .. }) => {
+            Error::NotFound(url.to_owned())
+        }
+        _ => Error::DB(err),
+    }
+}
+
+impl Server {
+    // a panic while rendering doesn't leave the renderer or cache in an invalid state
+    fn lock_renderer(&self) -> MutexGuard<'_, Renderer<SQLiteDB>> {
+        self.renderer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, PageCache> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn handle(&self, request: &Request) -> Response {
+        let start = Instant::now();
+        let response = if request.url().starts_with("/api/") {
+            self.route_api(request)
+                .unwrap_or_else(|err| self.api_error_response(request, err))
+        } else if request.method() == "POST" {
+            self.route_post(request)
+                .unwrap_or_else(|err| self.post_error_response(request, err))
+        } else {
+            self.route(request)
+                .unwrap_or_else(|err| self.error_response(request, err))
+        };
+        log::info!(
+            "{} {} - {} ({:?})",
+            request.method(),
+            request.url(),
+            response.status_code,
+            start.elapsed()
+        );
+        response
+    }
+
+    fn route(&self, request: &Request) -> Result<Response, Error> {
+        let url = request.url();
+        if url.contains("//") {
+            return Ok(Response::redirect_303(url.replace("//", "/")));
+        }
+        if url.starts_with("/img/") || url.starts_with("/js/") {
+            // checks the path stays inside html_dir and sets the content type
+            let response = rouille::match_assets(request, &self.config.html_dir);
+            return if response.is_success() {
+                Ok(response)
+            } else {
+                Err(Error::NotFound(url))
+            };
+        }
+
+        let html = router!(request,
+            (GET) ["/"] => {
+                self.cached_page(&url, |renderer| Ok(renderer.render_index()?))
             },
             (GET) ["/index.html"] => {
-
-                let content = render_mutex.lock()
-                    .expect("Could not lock database").
-                    render_index().expect("Could not render index");
-                rouille::Response::html(content)
+                self.cached_page(&url, |renderer| Ok(renderer.render_index()?))
             },
             (GET) ["/plant_overview.html"] => {
-                let content = render_mutex.lock()
-                    .expect("Could not lock database")
-                    .render_plant_overview().expect("Could not render plant overview");
-                rouille::Response::html(content)
+                self.cached_page(&url, |renderer| Ok(renderer.render_plant_overview()?))
             },
             (GET) ["/species_overview.html"] => {
-                let content = render_mutex.lock()
-                    .expect("Could not lock database")
-                    .render_species_overview().expect("Could not render species overview");
-                rouille::Response::html(content)
-            },
-            (GET) ["/gallery.html"] =>{
-                let content = render_mutex.lock()
-                    .expect("Could not lock database")
-                    .render_gallery().expect("Could not render galley");
-                rouille::Response::html(content)
-            },
-            (GET) ["/activities.html"] =>{
-                let content = render_mutex.lock()
-                    .expect("Could not lock database")
-                    .render_activities().expect("Could not render galley");
-                rouille::Response::html(content)
-            },
-            (GET) ["/graveyard.html"] =>{
-                let content = render_mutex.lock()
-                    .expect("Could not lock database")
-                    .render_graveyard().expect("Could not render graveyard");
-                rouille::Response::html(content)
-            },
-            (GET) ["/plants/{name}", name:String] => {
-                let name = name.replace(".html","");
-                let mut renderer = render_mutex.lock().expect("Could not lock database");
-                let plant_name = renderer.database_manager.find_plant_name(name).expect("Could not find plant");
-                let content =renderer
-                    .render_plant_details(plant_name.clone())
-                    .expect(&format!("Could not render details for plant {plant_name}"));
-                rouille::Response::html(content.page_html)
-            },
-            (GET) ["/species/{name}", name:String] => {
-                let name = name.replace(".html","");
-                let mut renderer = render_mutex.lock().expect("Could not lock database");
-                let species_name = renderer.database_manager.find_species_name(name).expect("Could not find species");
-                let content =renderer
-                    .render_species_details(species_name.clone())
-                    .expect(&format!("Could not render details for species {species_name}"));
-                rouille::Response::html(content.page_html)
-            },
-            (GET) ["/img/{plant}/{img}", plant:String,img:String] => {
-                let ext = img.split(".").last().unwrap_or("jpg");
-                let file = File::open(format!("html_out/img/{plant}/{img}")).expect("Could not find image");
-                rouille::Response::from_file(format!("image/{ext}"),file)
-            },
-            (GET) ["/js/{file}", file:String] => {
-                let file = File::open(format!("html_out/js/{file}")).expect("Could not find image");
-                rouille::Response::from_file(format!("text/javascript"),file)
-            },
-            _ => {
-                if request.url().contains("//"){
-                    return rouille::Response::redirect_303(request.url().replace("//","/"));
-                }
-                println!("tried to serve {}",request.url());
-                let mut response =rouille::Response::text(format!("Could not find page {}",request.url()));
-                response.status_code=404;
-                response
-            }
-        )
-    })
+                self.cached_page(&url, |renderer| Ok(renderer.render_species_overview()?))
+            },
+            (GET) ["/gallery.html"] => {
+                self.cached_page(&url, |renderer| Ok(renderer.render_gallery()?))
+            },
+            (GET) ["/activities.html"] => {
+                self.cached_page(&url, |renderer| Ok(renderer.render_activities()?))
+            },
+            (GET) ["/graveyard.html"] => {
+                self.cached_page(&url, |renderer| Ok(renderer.render_graveyard()?))
+            },
+            (GET) ["/forms.html"] => {
+                self.cached_page(&url, |renderer| Ok(renderer.render_forms()?))
+            },
+            (GET) ["/plants/{name}", name: String] => {
+                self.cached_page(&url, |renderer| {
+                    let plant_name = renderer
+                        .database_manager
+                        .find_plant_name(name.replace(".html", ""))
+                        .map_err(|err| not_found_or_db(err, &url))?;
+                    Ok(renderer.render_plant_details(plant_name)?.page_html)
+                })
+            },
+            (GET) ["/species/{name}", name: String] => {
+                self.cached_page(&url, |renderer| {
+                    let species_name = renderer
+                        .database_manager
+                        .find_species_name(name.replace(".html", ""))
+                        .map_err(|err| not_found_or_db(err, &url))?;
+                    Ok(renderer.render_species_details(species_name)?.page_html)
+                })
+            },
+            _ => Err(Error::NotFound(url.clone()))
+        )?;
+        Ok(Response::html(html))
+    }
+
+    /// Forms are answered with a plain text message that js/forms.js shows below the form
+    fn route_post(&self, request: &Request) -> Result<Response, Error> {
+        let url = request.url();
+        let data: FormData = rouille::input::post::raw_urlencoded_post_input(request)
+            .map_err(|err| Error::BadRequest(format!("Could not read form: {err}")))?
+            .into_iter()
+            .collect();
+        if url == "/login" {
+            let token = data.get("token").cloned().unwrap_or_default();
+            check_token(&token, &self.config.auth_token)?;
+            return Ok(Response::text("Logged in")
+                .with_additional_header("Set-Cookie", login_cookie(&token)));
+        }
+        let action = url
+            .strip_prefix("/actions/")
+            .ok_or_else(|| Error::NotFound(url.clone()))?;
+        check_request(request, &self.config.auth_token)?;
+        // cached pages are rendered again once the write changes the database file
+        let message = handle_form(
+            action,
+            &data,
+            &mut self.lock_renderer().database_manager,
+            &self.config.date_format,
+        )?;
+        log::info!("{message}");
+        Ok(Response::text(message))
+    }
+
+    fn route_api(&self, request: &Request) -> Result<Response, Error> {
+        let params: FormData = QUERY_PARAMS
+            .iter()
+            .filter_map(|key| request.get_param(key).map(|value| (key.to_string(), value)))
+            .collect();
+        let query = ApiQuery::try_from(&params)?;
+        let json = router!(request,
+            (GET) ["/api/v1/plants"] => {
+                api::plants(&query, &mut self.lock_renderer().database_manager)
+            },
+            (GET) ["/api/v1/plants/{name}", name: String] => {
+                api::plant(&name, &mut self.lock_renderer().database_manager)
+            },
+            (GET) ["/api/v1/species"] => {
+                api::species(&query, &mut self.lock_renderer().database_manager)
+            },
+            (GET) ["/api/v1/locations"] => {
+                api::locations(&query, &mut self.lock_renderer().database_manager)
+            },
+            (GET) ["/api/v1/activities"] => {
+                api::activities(&query, &mut self.lock_renderer().database_manager)
+            },
+            (GET) ["/api/v1/growth"] => {
+                api::growth(&query, &mut self.lock_renderer().database_manager)
+            },
+            (GET) ["/api/v1/graveyard"] => {
+                api::graveyard(&query, &mut self.lock_renderer().database_manager)
+            },
+            (GET) ["/api/v1/tasks/upcoming"] => {
+                api::upcoming_tasks(&query, &mut self.lock_renderer().database_manager)
+            },
+            (POST) ["/api/v1/activities"] => { self.api_write(request, "activity", None) },
+            (POST) ["/api/v1/growth"] => { self.api_write(request, "growth", None) },
+            (POST) ["/api/v1/plants"] => { self.api_write(request, "new_plant", None) },
+            (PATCH) ["/api/v1/plants/{name}", name: String] => {
+                self.api_write(request, "update_plant", Some(("plant", name)))
+            },
+            (POST) ["/api/v1/species"] => { self.api_write(request, "new_species", None) },
+            (PATCH) ["/api/v1/species/{name}", name: String] => {
+                self.api_write(request, "update_species", Some(("species", name)))
+            },
+            (POST) ["/api/v1/graveyard"] => { self.api_write(request, "graveyard", None) },
+            _ => Err(Error::NotFound(request.url()))
+        )?;
+        Ok(Response::json(&json))
+    }
+
+    fn api_write(
+        &self,
+        request: &Request,
+        action: &str,
+        path_field: Option<(&str, String)>,
+    ) -> Result<serde_json::Value, Error> {
+        check_request(request, &self.config.auth_token)?;
+        let body: serde_json::Value = rouille::input::json_input(request)
+            .map_err(|err| Error::BadRequest(format!("Could not read json body: {err}")))?;
+        let json = api::write(
+            action,
+            body,
+            path_field,
+            &mut self.lock_renderer().database_manager,
+            &self.config.date_format,
+        )?;
+        log::info!("{}", json["message"]);
+        Ok(json)
+    }
+
+    /// Returns the cached page for `url` if the database, images and date did not change since it was rendered
+    fn cached_page<F>(&self, url: &str, render: F) -> Result<String, Error>
+    where
+        F: FnOnce(&mut Renderer<SQLiteDB>) -> Result<String, Error>,
+    {
+        let version = CacheVersion::current(&self.config.db_file, &self.config.plants_dir);
+        if let Some(html) = version
+            .as_ref()
+            .and_then(|version| self.lock_cache().get(url, version))
+        {
+            return Ok(html);
+        }
+        let html = render(&mut self.lock_renderer())?;
+        if let Some(version) = version {
+            self.lock_cache()
+                .insert(url.to_owned(), version, html.clone());
+        }
+        Ok(html)
+    }
+
+    fn error_response(&self, request: &Request, err: Error) -> Response {
+        let status_code = err.status_code();
+        let message = if status_code == 404 {
+            err.to_string()
+        } else {
+            log::error!("Could not serve {}: {err}", request.url());
+            "Something went wrong while loading this page".to_owned()
+        };
+        let url = request.url();
+        let page = ErrorPage {
+            status_code,
+            message,
+            relative_up: url.starts_with("/plants/") || url.starts_with("/species/"),
+        };
+        let html = self.lock_renderer().render_error(&page);
+        Response::html(html).with_status_code(status_code)
+    }
+
+    fn api_error_response(&self, request: &Request, err: Error) -> Response {
+        let status_code = err.status_code();
+        let message = if status_code == 500 {
+            log::error!("Could not handle {}: {err}", request.url());
+            "Something went wrong".to_owned()
+        } else {
+            err.to_string()
+        };
+        Response::json(&serde_json::json!({ "error": message })).with_status_code(status_code)
+    }
+
+    fn post_error_response(&self, request: &Request, err: Error) -> Response {
+        let status_code = err.status_code();
+        let message = if status_code == 500 {
+            log::error!("Could not handle {}: {err}", request.url());
+            "Something went wrong while saving".to_owned()
+        } else {
+            err.to_string()
+        };
+        Response::text(message).with_status_code(status_code)
+    }
+}
+
+fn run() -> Result<(), Error> {
+    init_logger(&LOGGER).map_err(Error::Logger)?;
+    let config = ServerConfig::try_from(std::env::args().skip(1).collect::<Vec<String>>())?;
+    let mut db = SQLiteDB::new(config.db_file.clone()).map_err(|err| Error::DB(Box::new(err)))?;
+    db.plants_dir = config.plants_dir.clone();
+    let renderer = Renderer::new(db, &config.date_format);
+
+    let address = config.bind_address.clone();
+    let server = Server {
+        config,
+        renderer: Mutex::new(renderer),
+        cache: Mutex::new(PageCache::default()),
+    };
+    log::info!("Serving on {address}");
+    rouille::start_server(address, move |request| server.handle(request))
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,123 @@
+use super::errors::Error;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage: sync_databases <file-to-sqlite|sqlite-to-file> [--dry-run] [--force] [--data-dir <dir>] [--db <file>]
+    --dry-run   only print the differences between source and target
+    --force     overwrite conflicting plants and remove logs and growth only in the target
+    --data-dir  directory of the file database (default: data)
+    --db        sqlite database file (default: plants.db)";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    FileToSQLite,
+    SQLiteToFile,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub direction: Direction,
+    pub dry_run: bool,
+    pub force: bool,
+    pub data_dir: PathBuf,
+    pub db_file: PathBuf,
+}
+
+impl TryFrom<Vec<String>> for Args {
+    type Error = Error;
+    fn try_from(args: Vec<String>) -> Result<Args, Error> {
+        let mut direction = None;
+        let mut dry_run = false;
+        let mut force = false;
+        let mut data_dir = PathBuf::from("data");
+        let mut db_file = PathBuf::from("plants.db");
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .map(PathBuf::from)
+                    .ok_or_else(|| Error::Args(format!("Missing value for {name}")))
+            };
+            match arg.as_str() {
+                "file-to-sqlite" => direction = Some(Direction::FileToSQLite),
+                "sqlite-to-file" => direction = Some(Direction::SQLiteToFile),
+                "--dry-run" => dry_run = true,
+                "--force" => force = true,
+                "--data-dir" => data_dir = value("--data-dir")?,
+                "--db" => db_file = value("--db")?,
+                _ => return Err(Error::Args(format!("Unknown argument {arg}"))),
+            }
+        }
+
+        Ok(Args {
+            direction: direction.ok_or(Error::Args("Missing direction".to_owned()))?,
+            dry_run,
+            force,
+            data_dir,
+            db_file,
+        })
+    }
+}
+
+#[cfg(test)]
+mod args_tests {
+    use super::{Args, Direction};
+    use std::path::PathBuf;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let result = Args::try_from(to_args(&["file-to-sqlite"])).unwrap();
+        let expected = Args {
+            direction: Direction::FileToSQLite,
+            dry_run: false,
+            force: false,
+            data_dir: PathBuf::from("data"),
+            db_file: PathBuf::from("plants.db"),
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_all() {
+        let result = Args::try_from(to_args(&[
+            "--dry-run",
+            "sqlite-to-file",
+            "--db",
+            "other.db",
+            "--data-dir",
+            "other_data",
+            "--force",
+        ]))
+        .unwrap();
+        let expected = Args {
+            direction: Direction::SQLiteToFile,
+            dry_run: true,
+            force: true,
+            data_dir: PathBuf::from("other_data"),
+            db_file: PathBuf::from("other.db"),
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_missing_direction() {
+        let result = Args::try_from(to_args(&["--dry-run"]));
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn parse_missing_value() {
+        let result = Args::try_from(to_args(&["file-to-sqlite", "--db"]));
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn parse_unknown() {
+        let result = Args::try_from(to_args(&["file-to-sqlite", "--verbose"]));
+        assert!(result.is_err())
+    }
+}
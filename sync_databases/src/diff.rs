@@ -0,0 +1,524 @@
+use database::database_manager::DatabaseManager;
+use plants::{
+    graveyard::GraveyardPlant,
+    growth_item::GrowthItem,
+    location::Location,
+    log_item::LogItem,
+    named::Named,
+    plant::{PlantInfo, PlantLocation, PlantSpecies},
+    species::Species,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+/// An item that can be compared between source and target
+pub trait SyncItem: Clone + PartialEq {
+    /// Identifies the same item in source and target
+    fn key(&self) -> String;
+    /// The plant this item belongs to
+    fn plant_name(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl SyncItem for Location {
+    fn key(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl SyncItem for Species {
+    fn key(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl SyncItem for PlantInfo {
+    fn key(&self) -> String {
+        self.name.clone()
+    }
+    fn plant_name(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+}
+
+impl SyncItem for LogItem {
+    fn key(&self) -> String {
+        format!("{} of {} on {}", self.activity, self.plant, self.date)
+    }
+    fn plant_name(&self) -> Option<&str> {
+        Some(&self.plant)
+    }
+}
+
+impl SyncItem for GrowthItem {
+    fn key(&self) -> String {
+        format!("{} on {}", self.plant, self.date)
+    }
+    fn plant_name(&self) -> Option<&str> {
+        Some(&self.plant)
+    }
+}
+
+impl SyncItem for GraveyardPlant {
+    fn key(&self) -> String {
+        format!("{} ({} - {})", self.name, self.planted, self.died)
+    }
+}
+
+/// Differences of one kind of item between source and target
+/// `changed` contains the source version of changed items
+#[derive(Debug, PartialEq)]
+pub struct Diff<T> {
+    pub added: Vec<T>,
+    pub changed: Vec<T>,
+    pub removed: Vec<T>,
+}
+
+impl<T: SyncItem> Diff<T> {
+    pub fn new(source: Vec<T>, target: Vec<T>) -> Diff<T> {
+        let mut target_items: HashMap<String, T> =
+            target.into_iter().map(|item| (item.key(), item)).collect();
+        let mut seen = HashSet::new();
+        let mut added = vec![];
+        let mut changed = vec![];
+        for item in source.into_iter() {
+            let key = item.key();
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            match target_items.remove(&key) {
+                None => added.push(item),
+                Some(target_item) if target_item != item => changed.push(item),
+                Some(_) => (),
+            }
+        }
+        let mut removed: Vec<T> = target_items.into_values().collect();
+        removed.sort_by_key(|item| item.key());
+        Diff {
+            added,
+            changed,
+            removed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// Removes all items belonging to one of `plants`
+    pub fn without_plants(self, plants: &HashSet<String>) -> Diff<T> {
+        let keep = |item: &T| {
+            item.plant_name()
+                .map(|name| !plants.contains(name))
+                .unwrap_or(true)
+        };
+        Diff {
+            added: self.added.into_iter().filter(keep).collect(),
+            changed: self.changed.into_iter().filter(keep).collect(),
+            removed: self.removed.into_iter().filter(keep).collect(),
+        }
+    }
+}
+
+impl<T: SyncItem> fmt::Display for Diff<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} added, {} changed, {} removed",
+            self.added.len(),
+            self.changed.len(),
+            self.removed.len()
+        )?;
+        for item in self.added.iter() {
+            writeln!(f, "  + {}", item.key())?;
+        }
+        for item in self.changed.iter() {
+            writeln!(f, "  ~ {}", item.key())?;
+        }
+        for item in self.removed.iter() {
+            writeln!(f, "  - {}", item.key())?;
+        }
+        Ok(())
+    }
+}
+
+/// A plant that was changed in the source while the target has data the source does not have
+#[derive(Debug, PartialEq)]
+pub struct Conflict {
+    pub plant: String,
+    pub source_changes: Vec<String>,
+    pub target_changes: Vec<String>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "  ! {}", self.plant)?;
+        for change in self.source_changes.iter() {
+            writeln!(f, "      source: {change}")?;
+        }
+        for change in self.target_changes.iter() {
+            writeln!(f, "      target: {change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// All differences between a source and target database
+#[derive(Debug, PartialEq)]
+pub struct SyncDiff {
+    pub locations: Diff<Location>,
+    pub species: Diff<Species>,
+    pub plants: Diff<PlantInfo>,
+    pub logs: Diff<LogItem>,
+    pub growth: Diff<GrowthItem>,
+    pub graveyard: Diff<GraveyardPlant>,
+    pub conflicts: Vec<Conflict>,
+    /// number of source logs and growth items of plants that are not in the source
+    pub orphaned: usize,
+}
+
+/// Species and locations are compared by name only, as they are compared separately
+fn plant_info(plant: PlantInfo) -> PlantInfo {
+    PlantInfo {
+        species: PlantSpecies::Other(plant.species.get_name()),
+        location: PlantLocation::Other(plant.location.get_name()),
+        ..plant
+    }
+}
+
+fn plant_infos<T: DatabaseManager>(db: &mut T) -> Result<Vec<PlantInfo>, Box<dyn Error>> {
+    Ok(db
+        .get_all_plants()?
+        .into_iter()
+        .map(|plant| plant_info(plant.info))
+        .collect())
+}
+
+impl SyncDiff {
+    pub fn load<S: DatabaseManager, T: DatabaseManager>(
+        source: &mut S,
+        target: &mut T,
+    ) -> Result<SyncDiff, Box<dyn Error>> {
+        let source_plants = plant_infos(source)?;
+        let plant_names: HashSet<String> = source_plants
+            .iter()
+            .map(|plant| plant.name.clone())
+            .collect();
+        // logs and growth of dead plants can not be written to the target
+        let source_logs = source.get_logs()?;
+        let num_logs = source_logs.len();
+        let source_logs: Vec<LogItem> = source_logs
+            .into_iter()
+            .filter(|log| plant_names.contains(&log.plant))
+            .collect();
+        let source_growth = source.get_growth()?;
+        let num_growth = source_growth.len();
+        let source_growth: Vec<GrowthItem> = source_growth
+            .into_iter()
+            .filter(|item| plant_names.contains(&item.plant))
+            .collect();
+        let orphaned = num_logs - source_logs.len() + num_growth - source_growth.len();
+
+        let mut diff = SyncDiff::new(
+            Diff::new(source.get_locations()?, target.get_locations()?),
+            Diff::new(source.get_all_species()?, target.get_all_species()?),
+            Diff::new(source_plants, plant_infos(target)?),
+            Diff::new(source_logs, target.get_logs()?),
+            Diff::new(source_growth, target.get_growth()?),
+            Diff::new(source.get_graveyard()?, target.get_graveyard()?),
+        );
+        diff.orphaned = orphaned;
+        Ok(diff)
+    }
+
+    pub fn new(
+        locations: Diff<Location>,
+        species: Diff<Species>,
+        plants: Diff<PlantInfo>,
+        logs: Diff<LogItem>,
+        growth: Diff<GrowthItem>,
+        graveyard: Diff<GraveyardPlant>,
+    ) -> SyncDiff {
+        let conflicts = find_conflicts(&plants, &logs, &growth);
+        SyncDiff {
+            locations,
+            species,
+            plants,
+            logs,
+            growth,
+            graveyard,
+            conflicts,
+            orphaned: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+            && self.species.is_empty()
+            && self.plants.is_empty()
+            && self.logs.is_empty()
+            && self.growth.is_empty()
+            && self.graveyard.is_empty()
+    }
+
+    /// Number of logs and growth items only in the target
+    /// without a common base, these might be new in the target or deleted in the source
+    pub fn num_target_only(&self) -> usize {
+        self.logs.removed.len() + self.growth.removed.len()
+    }
+
+    /// Keeps logs and growth items only in the target, instead of removing them
+    pub fn keeping_target_only(self) -> SyncDiff {
+        SyncDiff {
+            logs: Diff {
+                removed: vec![],
+                ..self.logs
+            },
+            growth: Diff {
+                removed: vec![],
+                ..self.growth
+            },
+            ..self
+        }
+    }
+
+    /// Removes all changes of conflicting plants
+    pub fn without_conflicts(self) -> SyncDiff {
+        let conflicting: HashSet<String> = self
+            .conflicts
+            .iter()
+            .map(|conflict| conflict.plant.clone())
+            .collect();
+        SyncDiff {
+            plants: self.plants.without_plants(&conflicting),
+            logs: self.logs.without_plants(&conflicting),
+            growth: self.growth.without_plants(&conflicting),
+            conflicts: vec![],
+            ..self
+        }
+    }
+}
+
+/// Collects the keys of `items` per plant
+fn add_changes<T: SyncItem>(
+    changes: &mut BTreeMap<String, Vec<String>>,
+    prefix: &str,
+    items: &[T],
+) {
+    for item in items.iter() {
+        if let Some(plant) = item.plant_name() {
+            changes
+                .entry(plant.to_owned())
+                .or_default()
+                .push(format!("{prefix} {}", item.key()));
+        }
+    }
+}
+
+/// A plant is conflicting if the source changed it
+/// while the target has logs or growth that are not in the source
+fn find_conflicts(
+    plants: &Diff<PlantInfo>,
+    logs: &Diff<LogItem>,
+    growth: &Diff<GrowthItem>,
+) -> Vec<Conflict> {
+    let mut source_changes = BTreeMap::new();
+    add_changes(&mut source_changes, "changed plant", &plants.changed);
+    add_changes(&mut source_changes, "added log", &logs.added);
+    add_changes(&mut source_changes, "changed log", &logs.changed);
+    add_changes(&mut source_changes, "added growth", &growth.added);
+    add_changes(&mut source_changes, "changed growth", &growth.changed);
+    let mut target_changes = BTreeMap::new();
+    add_changes(&mut target_changes, "log", &logs.removed);
+    add_changes(&mut target_changes, "growth", &growth.removed);
+
+    // changes of removed plants are removed with the plant
+    let removed: HashSet<&str> = plants
+        .removed
+        .iter()
+        .map(|plant| plant.name.as_str())
+        .collect();
+    let mut conflicts = vec![];
+    for (plant, source_changes) in source_changes.into_iter() {
+        if removed.contains(plant.as_str()) {
+            continue;
+        }
+        if let Some(target_changes) = target_changes.remove(&plant) {
+            conflicts.push(Conflict {
+                plant,
+                source_changes,
+                target_changes,
+            });
+        }
+    }
+    conflicts
+}
+
+impl fmt::Display for SyncDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Locations: {}", self.locations)?;
+        write!(f, "Species: {}", self.species)?;
+        write!(f, "Plants: {}", self.plants)?;
+        write!(f, "Logs: {}", self.logs)?;
+        write!(f, "Growth: {}", self.growth)?;
+        write!(f, "Graveyard: {}", self.graveyard)?;
+        if self.orphaned > 0 {
+            writeln!(
+                f,
+                "Ignoring {} logs and growth items of plants not in the source",
+                self.orphaned
+            )?;
+        }
+        if !self.conflicts.is_empty() {
+            writeln!(f, "Conflicts: {}", self.conflicts.len())?;
+            for conflict in self.conflicts.iter() {
+                write!(f, "{conflict}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod diff_tests {
+    use super::{Conflict, Diff, SyncDiff};
+    use chrono::NaiveDate;
+    use plants::{
+        location::Location,
+        log_item::LogItem,
+        plant::{PlantInfo, PlantLocation, PlantSpecies},
+    };
+
+    fn location(name: &str, outside: bool) -> Location {
+        Location {
+            name: name.to_owned(),
+            outside,
+            climate: vec![],
+        }
+    }
+
+    fn plant(name: &str, origin: &str) -> PlantInfo {
+        PlantInfo {
+            name: name.to_owned(),
+            species: PlantSpecies::Other("species".to_owned()),
+            location: PlantLocation::Other("location".to_owned()),
+            origin: origin.to_owned(),
+            obtained: NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+            auto_water: false,
+            notes: vec![],
+            schedules: vec![],
+        }
+    }
+
+    fn log(plant: &str, day: u32) -> LogItem {
+        LogItem {
+            activity: "Watering".to_owned(),
+            date: NaiveDate::from_ymd_opt(1970, 1, day).unwrap(),
+            plant: plant.to_owned(),
+            note: None,
+            user: None,
+        }
+    }
+
+    fn empty<T>() -> Diff<T> {
+        Diff {
+            added: vec![],
+            changed: vec![],
+            removed: vec![],
+        }
+    }
+
+    #[test]
+    fn diff_locations() {
+        let source = vec![location("Inside", false), location("Balcony", true)];
+        let target = vec![location("Inside", true), location("Garden", true)];
+        let result = Diff::new(source, target);
+        let expected = Diff {
+            added: vec![location("Balcony", true)],
+            changed: vec![location("Inside", false)],
+            removed: vec![location("Garden", true)],
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn diff_display() {
+        let result = format!(
+            "{}",
+            Diff::new(
+                vec![location("Balcony", true)],
+                vec![location("Garden", true)]
+            )
+        );
+        let expected = "1 added, 0 changed, 1 removed\n  + Balcony\n  - Garden\n";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn no_conflict_without_target_changes() {
+        let diff = SyncDiff::new(
+            empty(),
+            empty(),
+            Diff::new(vec![plant("Plant1", "new")], vec![plant("Plant1", "old")]),
+            Diff::new(
+                vec![log("Plant1", 1), log("Plant1", 2)],
+                vec![log("Plant1", 1)],
+            ),
+            empty(),
+            empty(),
+        );
+        assert!(diff.conflicts.is_empty());
+        assert_eq!(diff.logs.added, vec![log("Plant1", 2)])
+    }
+
+    #[test]
+    fn conflict_both_changed() {
+        let diff = SyncDiff::new(
+            empty(),
+            empty(),
+            Diff::new(
+                vec![plant("Plant1", "new"), plant("Plant2", "new")],
+                vec![plant("Plant1", "old"), plant("Plant2", "new")],
+            ),
+            Diff::new(
+                vec![log("Plant1", 1), log("Plant2", 1)],
+                vec![log("Plant1", 2), log("Plant2", 1)],
+            ),
+            empty(),
+            empty(),
+        );
+        let expected = vec![Conflict {
+            plant: "Plant1".to_owned(),
+            source_changes: vec![
+                "changed plant Plant1".to_owned(),
+                "added log Watering of Plant1 on 1970-01-01".to_owned(),
+            ],
+            target_changes: vec!["log Watering of Plant1 on 1970-01-02".to_owned()],
+        }];
+        assert_eq!(diff.conflicts, expected);
+
+        let without = diff.without_conflicts();
+        assert!(without.plants.is_empty());
+        assert!(without.logs.is_empty())
+    }
+
+    #[test]
+    fn keep_target_only() {
+        let diff = SyncDiff::new(
+            empty(),
+            empty(),
+            empty(),
+            Diff::new(vec![log("Plant1", 1)], vec![log("Plant1", 2)]),
+            empty(),
+            empty(),
+        );
+        assert_eq!(diff.num_target_only(), 1);
+        let result = diff.keeping_target_only();
+        assert_eq!(result.logs.added, vec![log("Plant1", 1)]);
+        assert!(result.logs.removed.is_empty())
+    }
+}
@@ -0,0 +1,37 @@
+use super::args::USAGE;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Args(String),
+    DB(Box<dyn std::error::Error>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Args(msg) => write!(f, "Bad arguments: {msg}\n{USAGE}"),
+            Error::DB(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Box<dyn std::error::Error>> for Error {
+    fn from(err: Box<dyn std::error::Error>) -> Error {
+        Error::DB(err)
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::{Error, USAGE};
+
+    #[test]
+    fn display_args() {
+        let result = format!("{}", Error::Args("missing direction".to_owned()));
+        let expected = format!("Bad arguments: missing direction\n{USAGE}");
+        assert_eq!(result, expected)
+    }
+}
@@ -1,62 +1,79 @@
-use database::{database_manager::DatabaseManager, file_backend::FileDB, sqlite_backend::SQLiteDB};
-use std::path::PathBuf;
-
-type SourceDB = FileDB;
-type TargetDB = SQLiteDB;
+mod args;
+mod diff;
+mod errors;
+mod sync;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut source = SourceDB::default();
-    let mut target = TargetDB::new(PathBuf::from("plants.db")).map_err(|err| err.to_string())?;
-
-    // sync plants
-    let all_plants = source.get_all_plants()?;
-    println!("Loaded {} plants from source", all_plants.len());
-    target.write_plants(all_plants.into_iter().map(|pl| pl.info).collect())?;
-    println!("Write plants to target");
-    let target_num = target.get_num_plants()?;
-    println!("Target now has {target_num} plants");
+use args::{Args, Direction};
+use database::{database_manager::DatabaseManager, file_backend::FileDB, sqlite_backend::SQLiteDB};
+use diff::SyncDiff;
+use errors::Error;
+use std::path::Path;
 
-    // sync species
-    let all_species = source.get_all_species()?;
-    println!("Loaded {} species from source", all_species.len());
-    for species in all_species.into_iter() {
-        target.write_species(species)?;
+fn file_db(data_dir: &Path) -> FileDB {
+    FileDB {
+        plants_dir: data_dir.join("Plants"),
+        species_dir: data_dir.join("Species"),
+        location_file: data_dir.join("Locations.csv"),
+        logs_dir: data_dir.join("Logs"),
+        ..Default::default()
     }
-    println!("Wrote species to taget");
-    let target_num = target.get_all_species()?.len();
-    println!("Target now has {target_num} plants");
+}
 
-    let graveyard = source.get_graveyard()?;
-    println!("Loaded {} graveyard plants from source", graveyard.len());
-    for graveyard_plant in graveyard.into_iter() {
-        target.add_to_graveyard(graveyard_plant)?;
+fn run_sync<S: DatabaseManager, T: DatabaseManager>(
+    source: &mut S,
+    target: &mut T,
+    args: &Args,
+) -> Result<(), Error> {
+    let diff = SyncDiff::load(source, target)?;
+    print!("{diff}");
+    if diff.is_empty() {
+        println!("Source and target are already in sync");
+        return Ok(());
     }
-    println!("Wrote graveyard plants to target");
-    let target_num = target.get_graveyard()?.len();
-    println!("Target now has {target_num} graveyard plants");
-
-    let locations = source.get_locations()?;
-    println!("Loaded {} locations from source", locations.len());
-    for loc in locations.into_iter() {
-        target.write_location(loc)?;
+    if args.dry_run {
+        println!("Dry run, nothing was written");
+        return Ok(());
     }
-    println!("Wrote locations to target");
-    let target_num = target.get_locations()?.len();
-    println!("Target now has {target_num} locations");
 
-    let logs = source.get_logs()?;
-    println!("Loaded {} logs from source", logs.len());
-    target.write_logs(logs)?;
-    println!("Wrote logs to target");
-    let target_num = target.get_locations()?.len();
-    println!("Target now has {target_num} locations");
+    let diff = if args.force {
+        diff
+    } else {
+        if !diff.conflicts.is_empty() {
+            println!(
+                "Skipping {} conflicting plants, use --force to overwrite them",
+                diff.conflicts.len()
+            );
+        }
+        if diff.num_target_only() > 0 {
+            println!(
+                "Keeping {} logs and growth items only in the target, use --force to remove them",
+                diff.num_target_only()
+            );
+        }
+        diff.without_conflicts().keeping_target_only()
+    };
+    // either all changes are written or none
+    target.transaction(|db| sync::apply(diff, db))?;
+    println!("Sync finished");
+    Ok(())
+}
 
-    let growth = source.get_growth()?;
-    println!("Loaded {} growth items from source", growth.len());
-    target.write_growths(growth)?;
-    println!("Wrote growth to target");
-    let target_num = target.get_growth()?.len();
-    println!("Target now has {target_num} growth items");
+fn run() -> Result<(), Error> {
+    let args = Args::try_from(std::env::args().skip(1).collect::<Vec<String>>())?;
+    let mut file_db = file_db(&args.data_dir);
+    let mut sqlite_db =
+        SQLiteDB::new(args.db_file.clone()).map_err(|err| Error::DB(Box::new(err)))?;
+    sqlite_db.plants_dir = args.data_dir.join("Plants");
 
-    Ok(())
+    match args.direction {
+        Direction::FileToSQLite => run_sync(&mut file_db, &mut sqlite_db, &args),
+        Direction::SQLiteToFile => run_sync(&mut sqlite_db, &mut file_db, &args),
+    }
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,75 @@
+use super::diff::{SyncDiff, SyncItem};
+use database::database_manager::DatabaseManager;
+use std::error::Error;
+
+/// Writes all differences to `target`, so it matches the source
+pub fn apply<T: DatabaseManager>(diff: SyncDiff, target: &mut T) -> Result<(), Box<dyn Error>> {
+    let SyncDiff {
+        locations,
+        species,
+        plants,
+        logs,
+        growth,
+        mut graveyard,
+        ..
+    } = diff;
+
+    // locations and species first, so plants can reference them
+    for location in locations.added.into_iter().chain(locations.changed) {
+        target.write_location(location)?;
+    }
+    for species in species.added.into_iter().chain(species.changed) {
+        target.write_species(species)?;
+    }
+    target.write_plants(plants.added.into_iter().chain(plants.changed).collect())?;
+    println!("Wrote locations, species and plants");
+
+    // changed items are deleted first, so they are not duplicated
+    for log in logs.changed.iter().chain(logs.removed.iter()) {
+        target.delete_log(log.clone())?;
+    }
+    let new_logs = logs.added.into_iter().chain(logs.changed).collect();
+    target.write_logs(new_logs)?;
+    for item in growth.changed.iter().chain(growth.removed.iter()) {
+        target.delete_growth(item.clone())?;
+    }
+    let new_growth = growth.added.into_iter().chain(growth.changed).collect();
+    target.write_growths(new_growth)?;
+    println!("Wrote logs and growth");
+
+    // removed plants are moved to the graveyard using their source graveyard entry
+    for plant in plants.removed.into_iter() {
+        match graveyard
+            .added
+            .iter()
+            .position(|dead| dead.name == plant.name)
+        {
+            Some(ind) => target.kill_plant(graveyard.added.remove(ind))?,
+            None => println!(
+                "Not removing plant {}, it is not in the source graveyard",
+                plant.name
+            ),
+        }
+    }
+    target.write_graveyard(graveyard.added)?;
+    for dead in graveyard.changed.iter().chain(graveyard.removed.iter()) {
+        println!(
+            "Not syncing changed or removed graveyard entry {}",
+            dead.key()
+        );
+    }
+    println!("Wrote graveyard");
+
+    // species and locations can only be removed once no plants reference them
+    for species in species.removed.into_iter() {
+        if let Err(err) = target.delete_species(&species.name) {
+            println!("Could not remove species {}: {err}", species.name);
+        }
+    }
+    for location in locations.removed.into_iter() {
+        if let Err(err) = target.delete_location(&location.name) {
+            println!("Could not remove location {}: {err}", location.name);
+        }
+    }
+    Ok(())
+}
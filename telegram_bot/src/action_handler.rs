@@ -1,48 +1,93 @@
 use super::{
-    bot_actions::{Action, BotAction},
+    bot_actions::{Action, BotAction, Choice, Media},
     commands::{Command, CommandRes},
+    digest::Digest,
     errors::{CommandError, Error},
 };
-use bot_api::{bot::Bot, handlers::Handler, message::Message, photo_size::Photo};
+use bot_api::{
+    bot::Bot, callback_query::CallbackQuery, formatting::ParseMode, handlers::Handler,
+    keyboard::InlineKeyboardMarkup, message::Message, photo_size::Photo, user::User,
+};
 use bytes::Bytes;
-use chrono::Local;
-use database::{database_manager::DatabaseManager, file_backend::FileDB};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeDelta};
+use database::{
+    database_manager::DatabaseManager,
+    file_backend::{export::export, FileDB},
+    weather::{CsvWeather, WeatherProvider},
+};
+use plants::{
+    climate::{get_climate_alerts, measured_climate},
+    plant::{
+        plant_image::{exif_date, image_file_name},
+        ImageMeta, Plant, PlantLocation,
+    },
+    weather::{get_frost_warnings, get_rain_logs, WeatherRecord},
+};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fs::{create_dir_all, File},
     io::{Read, Write},
     path::PathBuf,
-    process, str,
+    process,
+    str::{self, FromStr},
 };
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ImmediateAction {
     Push,
-    Abort,
     CheckLogs,
     GetWaterToday,
     GetFertilizeToday,
     GetGrowthToday,
+    GetTasksToday,
+    CheckWeather,
+    CheckClimate,
     GetAllPlants,
     GetAllSpecies,
 }
 
+/// Days of weather data before and after today that are checked
+const WEATHER_DAYS: i64 = 7;
+/// Days of weather data the climate of outside locations is measured from
+const CLIMATE_DAYS: i64 = 365;
+/// Number of buttons in each row of choice keyboards
+const KEYBOARD_ROW_LEN: usize = 3;
+/// Minutes without input after which running actions are aborted
+pub const DEFAULT_ACTION_TIMEOUT: i64 = 30;
+
+/// Action running in a chat
+#[derive(Debug, PartialEq, Eq)]
+pub struct Conversation {
+    pub action: BotAction,
+    pub last_input: NaiveDateTime,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct ActionHandler<T: DatabaseManager> {
-    pub current_action: BotAction,
+    /// Running actions by chat id
+    pub conversations: HashMap<i64, Conversation>,
+    /// Photos to send with the next reply, by chat id
+    pub media: HashMap<i64, Media>,
+    pub action_timeout: TimeDelta,
     pub white_list: Vec<i64>,
     pub plants_dir: PathBuf,
     pub log_path: PathBuf,
+    pub weather_file: Option<PathBuf>,
+    pub digest: Digest,
     pub db_man: T,
 }
 
 impl Default for ActionHandler<FileDB> {
     fn default() -> ActionHandler<FileDB> {
         ActionHandler {
-            current_action: BotAction::Idle,
+            conversations: HashMap::new(),
+            media: HashMap::new(),
+            action_timeout: TimeDelta::minutes(DEFAULT_ACTION_TIMEOUT),
             white_list: vec![],
             plants_dir: PathBuf::from("data/Plants"),
             log_path: PathBuf::from("./build.log"),
+            weather_file: None,
+            digest: Digest::default(),
             db_man: FileDB::default(),
         }
     }
@@ -51,34 +96,165 @@ impl Default for ActionHandler<FileDB> {
 impl<T: DatabaseManager> ActionHandler<T> {
     pub fn new(white_list: Vec<i64>, db_man: T) -> Self {
         ActionHandler {
-            current_action: BotAction::Idle,
+            conversations: HashMap::new(),
+            media: HashMap::new(),
+            action_timeout: TimeDelta::minutes(DEFAULT_ACTION_TIMEOUT),
             white_list,
             plants_dir: PathBuf::from("data/Plants"),
             log_path: PathBuf::from("./build.log"),
+            weather_file: None,
+            digest: Digest::default(),
             db_man,
         }
     }
-    fn check_action(&mut self) -> Result<Option<String>, Error> {
-        if self.current_action.is_done() {
-            let ret_msg = self.current_action.write_result(&mut self.db_man)?;
-            self.current_action = BotAction::Idle;
+    fn check_action(&mut self, chat: i64) -> Result<Option<String>, Error> {
+        let conversation = self
+            .conversations
+            .get(&chat)
+            .ok_or(Error::NoActionRunning)?;
+        if conversation.action.is_done() {
+            // actions write several items, so they are written in one transaction
+            let action = &conversation.action;
+            let ret_msg = self
+                .db_man
+                .transaction(|db_man| action.write_result(db_man))?;
+            match action.get_media(&mut self.db_man) {
+                Ok(Some(media)) => {
+                    self.media.insert(chat, media);
+                }
+                Ok(None) => (),
+                Err(err) => log::error!("Could not get media for chat {chat}: {err}"),
+            }
+            self.conversations.remove(&chat);
             Ok(Some(ret_msg))
         } else {
             Ok(None)
         }
     }
 
-    pub fn save_image(&self, img: Bytes, msg: &Message) -> Result<PathBuf, Error> {
-        let plant_name = msg
-            .caption
-            .clone()
-            .ok_or(Error::MissingInput("Plant Name".to_owned()))?;
-        let img_name = Local::now().date_naive().format("%d%m%Y.jpg").to_string();
-        let plant_path = self.plants_dir.join(plant_name.replace(' ', ""));
+    fn get_next_prompt(&self, chat: i64) -> Result<String, Error> {
+        let conversation = self
+            .conversations
+            .get(&chat)
+            .ok_or(Error::NoActionRunning)?;
+        conversation.action.get_next_prompt()
+    }
+
+    /// Values the next input of the action in chat can be chosen from
+    pub fn get_options(&mut self, chat: i64) -> Result<Vec<String>, Error> {
+        let Some(choice) = self
+            .conversations
+            .get(&chat)
+            .and_then(|conversation| conversation.action.get_choice())
+        else {
+            return Ok(vec![]);
+        };
+        let mut options: Vec<String> = match choice {
+            Choice::Plant => self
+                .db_man
+                .get_all_plants()?
+                .into_iter()
+                .map(|plant| plant.info.name)
+                .collect(),
+            Choice::Location => self
+                .db_man
+                .get_locations()?
+                .into_iter()
+                .map(|location| location.name)
+                .collect(),
+            Choice::Species => self
+                .db_man
+                .get_all_species()?
+                .into_iter()
+                .map(|species| species.name)
+                .collect(),
+            Choice::Health => return Ok((0..=5).map(|health| health.to_string()).collect()),
+        };
+        options.sort();
+        Ok(options)
+    }
+
+    fn get_keyboard(&mut self, chat: i64) -> Option<InlineKeyboardMarkup> {
+        match self.get_options(chat) {
+            Ok(options) => {
+                let keyboard = InlineKeyboardMarkup::from_options(&options, KEYBOARD_ROW_LEN);
+                (!keyboard.is_empty()).then_some(keyboard)
+            }
+            Err(err) => {
+                log::error!("Could not get options for chat {chat}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Sends text to chat, with buttons if the running action expects a choice
+    /// if the finished action has a photo, it is sent instead, falling back to the text if that fails
+    async fn send_reply(&mut self, b: &Bot, chat: i64, text: String) {
+        if let Some(media) = self.media.remove(&chat) {
+            match b
+                .send_photo(
+                    chat.to_string(),
+                    media.photo,
+                    Some(media.caption),
+                    Some(ParseMode::Html),
+                )
+                .await
+            {
+                Ok(()) => return,
+                Err(err) => log::error!("Could not send photo to chat {chat}: {err}"),
+            }
+        }
+        let _ = match self.get_keyboard(chat) {
+            None => b.send_message(chat.to_string(), text).await,
+            Some(keyboard) => b.send_keyboard(chat.to_string(), text, keyboard).await,
+        };
+    }
+
+    /// Saves an image sent with caption `Plant Name[: caption][ #cover]`
+    /// images are named by the date they were taken (or the date they were sent), with a suffix if there already is one
+    /// photos compressed by telegram have no exif data, so they always use the date they were sent
+    pub fn save_image(
+        &mut self,
+        img: Bytes,
+        caption: Option<String>,
+        sent: NaiveDate,
+    ) -> Result<PathBuf, Error> {
+        let image_caption: ImageCaption = caption
+            .ok_or(Error::MissingInput("Plant Name".to_owned()))?
+            .parse()?;
+        if !self
+            .db_man
+            .plant_exists(&image_caption.plant_name)
+            .map_err(Error::DBError)?
+        {
+            return Err(Error::PlantDoesNotExist(image_caption.plant_name));
+        }
+        let plant_path = self
+            .plants_dir
+            .join(image_caption.plant_name.replace(' ', ""));
         if !plant_path.exists() {
             create_dir_all(plant_path.clone()).map_err(|err| Error::Other(Box::new(err)))?;
         }
-        let out_path = plant_path.join(img_name);
+        let date = exif_date(&img).unwrap_or(sent);
+        let mut num = 1;
+        while plant_path.join(image_file_name(date, num)).exists() {
+            num += 1;
+        }
+        let img_name = image_file_name(date, num);
+
+        // the metadata is written first, so a failed write does not leave an image behind
+        if image_caption.caption.is_some() || image_caption.cover {
+            self.db_man
+                .write_image_meta(ImageMeta {
+                    plant: image_caption.plant_name,
+                    file_name: img_name.clone(),
+                    caption: image_caption.caption,
+                    cover: image_caption.cover,
+                })
+                .map_err(Error::DBError)?;
+        }
+
+        let out_path = plant_path.join(&img_name);
         let mut out_file =
             File::create(out_path.clone()).map_err(|err| Error::Other(Box::new(err)))?;
         out_file
@@ -90,17 +266,23 @@ impl<T: DatabaseManager> ActionHandler<T> {
         Ok(out_path)
     }
 
-    pub fn handle_input(&mut self, input: String) -> Result<String, Error> {
-        if let Some(ret_msg) = self.check_action()? {
+    pub fn handle_input(&mut self, chat: i64, input: String) -> Result<String, Error> {
+        if let Some(ret_msg) = self.check_action(chat)? {
             Ok(ret_msg)
         } else {
-            self.current_action
+            let conversation = self
+                .conversations
+                .get_mut(&chat)
+                .ok_or(Error::NoActionRunning)?;
+            conversation.last_input = Local::now().naive_local();
+            conversation
+                .action
                 .handle_input(input.to_owned(), &mut self.db_man)?;
 
-            if let Some(ret_msg) = self.check_action()? {
+            if let Some(ret_msg) = self.check_action(chat)? {
                 Ok(ret_msg)
             } else {
-                let ret_msg = self.current_action.get_next_prompt()?;
+                let ret_msg = self.get_next_prompt(chat)?;
                 Ok(ret_msg)
             }
         }
@@ -109,6 +291,8 @@ impl<T: DatabaseManager> ActionHandler<T> {
     pub fn handle_immediate(&mut self, action: &ImmediateAction) -> Result<String, Error> {
         match action {
             ImmediateAction::Push => {
+                // the database is exported first, so all changes show up as readable diffs
+                export(&mut self.db_man, &mut FileDB::default()).map_err(Error::DBError)?;
                 let output_add = process::Command::new("git")
                     .arg("add")
                     .arg("-A")
@@ -205,11 +389,6 @@ impl<T: DatabaseManager> ActionHandler<T> {
                     Ok(lines.join("\n"))
                 }
             }
-            ImmediateAction::Abort => {
-                let action = self.current_action.to_string();
-                self.current_action = BotAction::Idle;
-                Ok(format!("Aborted action {action}"))
-            }
             ImmediateAction::GetWaterToday => {
                 let plants = self.db_man.get_all_plants()?;
                 let today = Local::now().date_naive();
@@ -267,6 +446,33 @@ impl<T: DatabaseManager> ActionHandler<T> {
                     names.join("\n ")
                 ))
             }
+            ImmediateAction::GetTasksToday => {
+                let mut plants = self.db_man.get_all_plants()?;
+                plants.sort_by(|plant1, plant2| plant1.info.name.cmp(&plant2.info.name));
+                let today = Local::now().date_naive();
+                let mut tasks = vec![];
+                for plant in plants.into_iter() {
+                    let activities = plant.get_due_activities(today);
+                    if !activities.is_empty() {
+                        tasks.push(format!("{}: {}", plant.info.name, activities.join(", ")));
+                    }
+                }
+
+                Ok(format!("Tasks for today:\n {}", tasks.join("\n ")))
+            }
+            ImmediateAction::CheckWeather => {
+                let weather_file = self.weather_file.clone().ok_or(Error::NoWeatherData)?;
+                self.check_weather(&mut CsvWeather { weather_file }, Local::now().date_naive())
+            }
+            ImmediateAction::CheckClimate => {
+                let today = Local::now().date_naive();
+                let weather = match self.weather_file.clone() {
+                    None => vec![],
+                    Some(weather_file) => CsvWeather { weather_file }
+                        .get_weather(today - TimeDelta::days(CLIMATE_DAYS), today)?,
+                };
+                self.check_climate(&weather)
+            }
             ImmediateAction::GetAllPlants => {
                 let plants = self.db_man.get_all_plants()?;
                 let mut plants_formatted = vec![];
@@ -288,26 +494,186 @@ impl<T: DatabaseManager> ActionHandler<T> {
         }
     }
 
-    pub fn new_action(&mut self, new_action: &BotAction) -> Result<String, Error> {
-        if self.current_action == BotAction::Idle {
-            self.current_action = new_action.clone();
-            if let Some(ret_msg) = self.check_action()? {
-                Ok(ret_msg)
-            } else {
-                let ret_msg = self.current_action.get_next_prompt()?;
-                Ok(ret_msg)
+    /// Logs rain as watering for outside plants and warns about upcoming frost
+    pub fn check_weather<W: WeatherProvider>(
+        &mut self,
+        weather: &mut W,
+        today: NaiveDate,
+    ) -> Result<String, Error> {
+        let records = weather.get_weather(
+            today - TimeDelta::days(WEATHER_DAYS),
+            today + TimeDelta::days(WEATHER_DAYS),
+        )?;
+        let plants = self.db_man.get_all_plants()?;
+        self.weather_report(&plants, &records, today)
+    }
+
+    fn weather_report(
+        &mut self,
+        plants: &[Plant],
+        records: &[WeatherRecord],
+        today: NaiveDate,
+    ) -> Result<String, Error> {
+        let rain_logs = get_rain_logs(plants, records, today);
+        let warnings = get_frost_warnings(plants, records, today);
+
+        let mut lines = vec![];
+        if rain_logs.is_empty() {
+            lines.push("No rain to log".to_owned());
+        } else {
+            let rain_strs: Vec<String> = rain_logs
+                .iter()
+                .map(|log| format!("{} ({})", log.plant, log.date.format("%d.%m.%Y")))
+                .collect();
+            lines.push(format!("Logged rain for: {}", rain_strs.join(", ")));
+            self.db_man.write_logs(rain_logs)?;
+        }
+        if !warnings.is_empty() {
+            let warning_strs: Vec<String> = warnings.iter().map(|warn| warn.to_string()).collect();
+            lines.push(format!("Frost warnings:\n {}", warning_strs.join("\n ")));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Lists plants whose location is too cold or hot for their species
+    /// outside locations without an expected climate use the climate measured in `weather`
+    pub fn check_climate(&mut self, weather: &[WeatherRecord]) -> Result<String, Error> {
+        let measured = measured_climate(weather);
+        let mut plants = self.db_man.get_all_plants()?;
+        for plant in plants.iter_mut() {
+            if let PlantLocation::Location(location) = &mut plant.info.location {
+                if location.outside && location.climate.is_empty() {
+                    location.climate = measured.clone();
+                }
+            }
+        }
+        let alerts = get_climate_alerts(&plants);
+        if alerts.is_empty() {
+            return Ok("No plants are at risk at their location".to_owned());
+        }
+        let alert_strs: Vec<String> = alerts.iter().map(|alert| alert.to_string()).collect();
+        Ok(format!(
+            "Plants at risk at their location:\n {}",
+            alert_strs.join("\n ")
+        ))
+    }
+
+    /// Activities due today, sent as daily digest
+    /// if weather data is configured, the weather is checked as well
+    pub fn get_digest(&mut self, today: NaiveDate) -> Result<String, Error> {
+        let mut sections = vec![];
+        for action in [
+            ImmediateAction::GetWaterToday,
+            ImmediateAction::GetFertilizeToday,
+            ImmediateAction::GetGrowthToday,
+        ] {
+            sections.push(self.handle_immediate(&action)?);
+        }
+        if let Some(weather_file) = self.weather_file.clone() {
+            // a failing weather check should not hold back the due activities
+            match self.check_weather(&mut CsvWeather { weather_file }, today) {
+                Ok(report) => sections.push(report),
+                Err(err) => log::error!("Could not check weather: {err}"),
+            }
+        }
+        Ok(format!(
+            "Daily digest\n\n{}\n\nUse /{} to stop receiving the digest",
+            sections.join("\n\n"),
+            Command::DigestOff
+        ))
+    }
+
+    pub fn set_digest(&mut self, user: Option<i64>, enabled: bool) -> Result<String, Error> {
+        let user = user.ok_or(Error::MissingInput("User".to_owned()))?;
+        self.digest.set_enabled(user, enabled)?;
+        if enabled {
+            Ok("You will receive the daily digest".to_owned())
+        } else {
+            Ok("You will no longer receive the daily digest".to_owned())
+        }
+    }
+
+    async fn send_digest(&mut self, b: &Bot) -> Result<(), Error> {
+        let now = Local::now().naive_local();
+        if !self.digest.is_due(now) {
+            return Ok(());
+        }
+        // marked as sent first, so a failing digest is not retried on every poll
+        self.digest.mark_sent(now)?;
+        let digest = self.get_digest(now.date())?;
+        for user in self.digest.get_recipients(&self.white_list) {
+            if let Err(err) = b.send_message(user.to_string(), digest.clone()).await {
+                log::error!("Could not send digest to {user}: {err}");
             }
+        }
+        Ok(())
+    }
+
+    /// Starts `new_action` in `chat`, logs written by the action are recorded for `user`
+    pub fn new_action(
+        &mut self,
+        chat: i64,
+        user: Option<&str>,
+        new_action: &BotAction,
+    ) -> Result<String, Error> {
+        if let Some(conversation) = self.conversations.get(&chat) {
+            return Err(Error::ActionAlreadyRunning(conversation.action.to_string()));
+        }
+        let mut action = new_action.clone();
+        if let Some(user) = user {
+            action.set_user(user);
+        }
+        self.conversations.insert(
+            chat,
+            Conversation {
+                action,
+                last_input: Local::now().naive_local(),
+            },
+        );
+        if let Some(ret_msg) = self.check_action(chat)? {
+            Ok(ret_msg)
         } else {
-            Err(Error::ActionAlreadyRunning(self.current_action.to_string()))
+            let ret_msg = self.get_next_prompt(chat)?;
+            Ok(ret_msg)
         }
     }
 
-    fn process_command(&mut self, cmd: Command) -> String {
+    pub fn abort(&mut self, chat: i64) -> String {
+        let action = match self.conversations.remove(&chat) {
+            None => BotAction::Idle,
+            Some(conversation) => conversation.action,
+        };
+        format!("Aborted action {action}")
+    }
+
+    /// Aborts all actions without input for longer than the timeout
+    /// returns the chats and their aborted actions
+    pub fn abort_stale(&mut self, now: NaiveDateTime) -> Vec<(i64, BotAction)> {
+        let stale: Vec<i64> = self
+            .conversations
+            .iter()
+            .filter(|(_, conversation)| now - conversation.last_input > self.action_timeout)
+            .map(|(chat, _)| *chat)
+            .collect();
+        stale
+            .into_iter()
+            .filter_map(|chat| {
+                self.conversations
+                    .remove(&chat)
+                    .map(|conversation| (chat, conversation.action))
+            })
+            .collect()
+    }
+
+    fn process_command(&mut self, cmd: Command, chat: i64, user: Option<&User>) -> String {
+        let user_name = user.map(get_user_name);
         let action_res = match cmd.get_res() {
             CommandRes::Message(msg) => Ok(msg),
-            CommandRes::NewAction(action) => self.new_action(&action),
-            CommandRes::NewInput(inp) => self.handle_input(inp),
+            CommandRes::NewAction(action) => self.new_action(chat, user_name.as_deref(), &action),
+            CommandRes::NewInput(inp) => self.handle_input(chat, inp),
             CommandRes::ImmediateAction(act) => self.handle_immediate(&act),
+            CommandRes::Abort => Ok(self.abort(chat)),
+            CommandRes::SetDigest(enabled) => self.set_digest(user.map(|user| user.id), enabled),
         };
         match action_res {
             Ok(res_msg) => res_msg,
@@ -331,7 +697,10 @@ impl<T: DatabaseManager> ActionHandler<T> {
     async fn get_image(&mut self, b: &mut Bot, photo: Photo, msg: &Message) -> Result<(), Error> {
         let img_biggest = photo.get_biggest()?;
         let res = b.download_image(img_biggest.file_id.clone()).await?;
-        match self.save_image(res, msg) {
+        let sent = DateTime::from_timestamp(msg.date, 0)
+            .map(|date| date.with_timezone(&Local).date_naive())
+            .unwrap_or(Local::now().date_naive());
+        match self.save_image(res, msg.caption.clone(), sent) {
             Ok(out_path) => {
                 b.send_message(
                     msg.chat.id.to_string(),
@@ -348,6 +717,45 @@ impl<T: DatabaseManager> ActionHandler<T> {
     }
 }
 
+/// Caption of an image sent to the bot
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImageCaption {
+    pub plant_name: String,
+    pub caption: Option<String>,
+    pub cover: bool,
+}
+
+impl FromStr for ImageCaption {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<ImageCaption, Self::Err> {
+        let cover = s.split_whitespace().any(|word| word == "#cover");
+        let text = s
+            .split_whitespace()
+            .filter(|word| *word != "#cover")
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (plant_name, caption) = match text.split_once(':') {
+            Some((plant_name, caption)) => (plant_name.trim(), Some(caption.trim())),
+            None => (text.as_str(), None),
+        };
+        if plant_name.is_empty() {
+            return Err(Error::MissingInput("Plant Name".to_owned()));
+        }
+        Ok(ImageCaption {
+            plant_name: plant_name.to_owned(),
+            caption: caption
+                .filter(|caption| !caption.is_empty())
+                .map(|caption| caption.to_owned()),
+            cover,
+        })
+    }
+}
+
+/// Name logs are recorded with, the username if the user has one
+fn get_user_name(user: &User) -> String {
+    user.username.clone().unwrap_or(user.first_name.clone())
+}
+
 impl<T: DatabaseManager> Handler<Command> for ActionHandler<T> {
     async fn handle_msg(&mut self, b: &mut Bot, msg: Message) {
         if !(self.authorize(b, &msg).await) {
@@ -357,24 +765,43 @@ impl<T: DatabaseManager> Handler<Command> for ActionHandler<T> {
         if text.is_empty() {
             return;
         };
-        match self.handle_input(text) {
-            Ok(ret_msg) => {
-                let _ = b.send_message(msg.chat.id.to_string(), ret_msg).await;
-            }
-            Err(err) => {
-                let _ = b
-                    .send_message(msg.chat.id.to_string(), format!("{err}"))
-                    .await;
-            }
-        }
+        let ret_msg = match self.handle_input(msg.chat.id, text) {
+            Ok(ret_msg) => ret_msg,
+            Err(err) => format!("{err}"),
+        };
+        self.send_reply(b, msg.chat.id, ret_msg).await;
     }
 
     async fn handle_cmd(&mut self, b: &mut Bot, cmd: Command, msg: Message) {
         if !(self.authorize(b, &msg).await) {
             return;
         };
-        let ret_msg = self.process_command(cmd);
-        let _ = b.send_message(msg.chat.id.to_string(), ret_msg).await;
+        let ret_msg = self.process_command(cmd, msg.chat.id, msg.from.as_ref());
+        self.send_reply(b, msg.chat.id, ret_msg).await;
+    }
+
+    async fn handle_callback(&mut self, b: &mut Bot, query: CallbackQuery) {
+        let _ = b.answer_callback_query(query.id, None).await;
+        if !self.white_list.contains(&query.from.id) {
+            return;
+        }
+        let (Some(msg), Some(data)) = (query.message, query.data) else {
+            return;
+        };
+        // the buttons are removed, so every choice is only entered once
+        let _ = b
+            .edit_message_text(
+                msg.chat.id.to_string(),
+                msg.id,
+                format!("{} {data}", msg.text.unwrap_or_default()),
+                None,
+            )
+            .await;
+        let ret_msg = match self.handle_input(msg.chat.id, data) {
+            Ok(ret_msg) => ret_msg,
+            Err(err) => format!("{err}"),
+        };
+        self.send_reply(b, msg.chat.id, ret_msg).await;
     }
 
     async fn handle_img(&mut self, b: &mut Bot, photo: Photo, msg: Message) {
@@ -393,66 +820,153 @@ impl<T: DatabaseManager> Handler<Command> for ActionHandler<T> {
             }
         }
     }
+
+    async fn on_poll(&mut self, b: &mut Bot) {
+        for (chat, action) in self.abort_stale(Local::now().naive_local()) {
+            let _ = b
+                .send_message(
+                    chat.to_string(),
+                    format!(
+                        "Aborted action {action} after {} minutes without input",
+                        self.action_timeout.num_minutes()
+                    ),
+                )
+                .await;
+        }
+        if let Err(err) = self.send_digest(b).await {
+            log::error!("Could not send digest: {err}");
+        }
+    }
 }
 
 #[cfg(test)]
 mod action_handler_tests {
-    use super::{ActionHandler, BotAction, Command};
-    use crate::bot_actions::{NewPlant, Rain};
-    use crate::test_common::DummyManager;
-    use std::path::PathBuf;
+    use super::{
+        ActionHandler, BotAction, Command, Conversation, ImageCaption, ImmediateAction,
+        DEFAULT_ACTION_TIMEOUT,
+    };
+    use crate::bot_actions::{NewPlant, Rain, WaterLocation};
+    use crate::{
+        digest::Digest,
+        test_common::{example_location, example_plant, example_plant_info, DummyManager},
+    };
+    use bot_api::user::User;
+    use bytes::Bytes;
+    use chrono::{Local, NaiveDate, TimeDelta};
+    use database::weather::{CsvWeather, WeatherProvider};
+    use plants::{
+        location::Location,
+        plant::{Plant, PlantInfo, PlantLocation},
+    };
+    use std::{
+        collections::HashMap,
+        fs::{read, remove_dir_all},
+        path::PathBuf,
+    };
+
+    const CHAT: i64 = 1;
+    const SAVE_IMAGE_DIR: &str = "../testing/save_image_test";
+    const SAVE_IMAGE_UNKNOWN_DIR: &str = "../testing/save_image_unknown_test";
 
     fn example_handler() -> ActionHandler<DummyManager> {
         ActionHandler {
-            current_action: BotAction::Idle,
+            conversations: HashMap::new(),
+            media: HashMap::new(),
+            action_timeout: TimeDelta::minutes(DEFAULT_ACTION_TIMEOUT),
             white_list: vec![],
             log_path: PathBuf::from("log.txt"),
             plants_dir: PathBuf::from("data/Plants"),
+            weather_file: None,
+            digest: Digest::default(),
             db_man: DummyManager {},
         }
     }
 
+    fn example_conversation(action: BotAction) -> Conversation {
+        Conversation {
+            action,
+            last_input: Local::now().naive_local(),
+        }
+    }
+
+    fn example_user() -> User {
+        User {
+            id: 1,
+            is_bot: false,
+            first_name: "First".to_owned(),
+            last_name: None,
+            username: Some("gardener".to_owned()),
+            language_code: None,
+        }
+    }
+
     #[test]
     fn check_err() {
-        let result = example_handler().check_action();
+        let result = example_handler().check_action(CHAT);
         assert!(result.is_err());
     }
 
     #[test]
     fn check_done() {
         let mut handler = example_handler();
-        handler.current_action = BotAction::Rain(Rain {});
-        let result = handler.check_action().unwrap();
+        handler
+            .conversations
+            .insert(CHAT, example_conversation(BotAction::Rain(Rain::default())));
+        let result = handler.check_action(CHAT).unwrap();
         let expected = Some("Successfully watered plants: ".to_owned());
-        assert_eq!(result, expected)
+        assert_eq!(result, expected);
+        assert!(handler.conversations.is_empty())
     }
 
     #[test]
     fn check_not_done() {
         let mut handler = example_handler();
-        handler.current_action = BotAction::NewPlant(NewPlant::default());
-        let result = handler.check_action().unwrap();
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::NewPlant(NewPlant::default())),
+        );
+        let result = handler.check_action(CHAT).unwrap();
         assert_eq!(result, None)
     }
 
     #[test]
     fn handle_inp() {
         let mut handler = example_handler();
-        handler.current_action = BotAction::NewPlant(NewPlant::default());
-        let result = handler.handle_input("name".to_owned()).unwrap();
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::NewPlant(NewPlant::default())),
+        );
+        let result = handler.handle_input(CHAT, "name".to_owned()).unwrap();
         let expected = "Please enter species";
         assert_eq!(result, expected)
     }
+
     #[test]
     fn handle_inp_err() {
-        let result = example_handler().handle_input("".to_owned());
+        let result = example_handler().handle_input(CHAT, "".to_owned());
         assert!(result.is_err())
     }
 
+    #[test]
+    fn handle_inp_other_chat() {
+        let mut handler = example_handler();
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::NewPlant(NewPlant::default())),
+        );
+        let result = handler.handle_input(2, "name".to_owned());
+        assert!(result.is_err());
+        let result = handler.get_next_prompt(CHAT).unwrap();
+        let expected = "Please enter plant name";
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn new_action() {
         let mut handler = example_handler();
-        let result = handler.new_action(&BotAction::Rain(Rain {})).unwrap();
+        let result = handler
+            .new_action(CHAT, None, &BotAction::Rain(Rain::default()))
+            .unwrap();
         let expected = "Successfully watered plants: ";
         assert_eq!(result, expected)
     }
@@ -460,22 +974,243 @@ mod action_handler_tests {
     #[test]
     fn new_action_fail() {
         let mut handler = example_handler();
-        handler.current_action = BotAction::NewPlant(NewPlant::default());
-        let result = handler.new_action(&BotAction::Rain(Rain {}));
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::NewPlant(NewPlant::default())),
+        );
+        let result = handler.new_action(CHAT, None, &BotAction::Rain(Rain::default()));
         assert!(result.is_err())
     }
 
+    #[test]
+    fn new_action_other_chat() {
+        let mut handler = example_handler();
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::NewPlant(NewPlant::default())),
+        );
+        let result = handler.new_action(2, None, &BotAction::Rain(Rain::default()));
+        assert!(result.is_ok());
+        assert!(handler.conversations.contains_key(&CHAT))
+    }
+
+    #[test]
+    fn abort_stale() {
+        let mut handler = example_handler();
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::NewPlant(NewPlant::default())),
+        );
+        handler.conversations.insert(
+            2,
+            Conversation {
+                action: BotAction::NewPlant(NewPlant::default()),
+                last_input: Local::now().naive_local() - TimeDelta::minutes(60),
+            },
+        );
+        let result = handler.abort_stale(Local::now().naive_local());
+        let expected = vec![(2, BotAction::NewPlant(NewPlant::default()))];
+        assert_eq!(result, expected);
+        assert!(handler.conversations.contains_key(&CHAT))
+    }
+
     #[test]
     fn handle_cmd_action() {
-        let result = example_handler().process_command(Command::Abort);
+        let result = example_handler().process_command(Command::Abort, CHAT, None);
         let expected = "Aborted action Idle";
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn handle_cmd_abort() {
+        let mut handler = example_handler();
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::NewPlant(NewPlant::default())),
+        );
+        let result = handler.process_command(Command::Abort, CHAT, None);
+        let expected = "Aborted action New Plant";
+        assert_eq!(result, expected);
+        assert!(handler.conversations.is_empty())
+    }
+
+    #[test]
+    fn check_weather() {
+        let mut weather = CsvWeather {
+            weather_file: PathBuf::from("../testing/Weather.csv"),
+        };
+        let result = example_handler()
+            .check_weather(&mut weather, NaiveDate::from_ymd_opt(2024, 11, 3).unwrap())
+            .unwrap();
+        let expected = "No rain to log";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn weather_report_rain() {
+        let mut weather = CsvWeather {
+            weather_file: PathBuf::from("../testing/Weather.csv"),
+        };
+        let today = NaiveDate::from_ymd_opt(2024, 11, 3).unwrap();
+        let records = weather
+            .get_weather(today - TimeDelta::days(7), today + TimeDelta::days(7))
+            .unwrap();
+        let plant = Plant {
+            info: PlantInfo {
+                location: PlantLocation::Location(Box::new(Location {
+                    outside: true,
+                    ..example_location()
+                })),
+                ..example_plant_info()
+            },
+            ..example_plant()
+        };
+        let result = example_handler()
+            .weather_report(&[plant], &records, today)
+            .unwrap();
+        let expected = "Logged rain for: A Plant (01.11.2024), A Plant (03.11.2024)";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn get_digest_weather() {
+        let mut handler = example_handler();
+        handler.weather_file = Some(PathBuf::from("../testing/Weather.csv"));
+        let result = handler
+            .get_digest(NaiveDate::from_ymd_opt(2024, 11, 3).unwrap())
+            .unwrap();
+        assert!(result.contains("Plants to get growth for today:\n \n\nNo rain to log\n\nUse"))
+    }
+
+    #[test]
+    fn check_climate() {
+        let result = example_handler().check_climate(&[]).unwrap();
+        let expected = "No plants are at risk at their location";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn get_digest() {
+        let result = example_handler()
+            .get_digest(NaiveDate::from_ymd_opt(2024, 11, 3).unwrap())
+            .unwrap();
+        let expected = "Daily digest\n\nPlants to water today: \n\n\nPlants to fertilize today:\n \n\nPlants to get growth for today:\n \n\nUse /digest_off to stop receiving the digest";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn set_digest() {
+        let mut handler = example_handler();
+        handler.white_list = vec![1, 2];
+        let result = handler.process_command(Command::DigestOff, CHAT, Some(&example_user()));
+        let expected = "You will no longer receive the daily digest";
+        assert_eq!(result, expected);
+        assert_eq!(handler.digest.get_recipients(&handler.white_list), vec![2])
+    }
+
+    #[test]
+    fn set_digest_no_user() {
+        let result = example_handler().set_digest(None, true);
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn check_weather_unconfigured() {
+        let result = example_handler().handle_immediate(&ImmediateAction::CheckWeather);
+        assert!(result.is_err())
+    }
+
     #[test]
     fn handle_cmd_input() {
-        let result = example_handler().process_command(Command::Today);
+        let result = example_handler().process_command(Command::Today, CHAT, None);
         let expected = "Currently there is no active action, please try again";
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn get_options() {
+        let mut handler = example_handler();
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::WaterLocation(WaterLocation::default())),
+        );
+        let result = handler.get_options(CHAT).unwrap();
+        let expected = vec!["Inside".to_owned()];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn get_options_no_choice() {
+        let mut handler = example_handler();
+        assert!(handler.get_options(CHAT).unwrap().is_empty());
+        handler.conversations.insert(
+            CHAT,
+            example_conversation(BotAction::NewPlant(NewPlant::default())),
+        );
+        assert!(handler.get_options(CHAT).unwrap().is_empty())
+    }
+
+    #[test]
+    fn parse_caption() {
+        let result: ImageCaption = "Plant1".parse().unwrap();
+        let expected = ImageCaption {
+            plant_name: "Plant1".to_owned(),
+            caption: None,
+            cover: false,
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_caption_cover() {
+        let result: ImageCaption = "Plant1: new leaves #cover".parse().unwrap();
+        let expected = ImageCaption {
+            plant_name: "Plant1".to_owned(),
+            caption: Some("new leaves".to_owned()),
+            cover: true,
+        };
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_caption_empty() {
+        assert!("#cover".parse::<ImageCaption>().is_err())
+    }
+
+    #[test]
+    fn save_image_unique() {
+        let mut handler = example_handler();
+        handler.plants_dir = PathBuf::from(SAVE_IMAGE_DIR);
+        let sent = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let first = handler
+            .save_image(Bytes::from("first"), Some("Plant1".to_owned()), sent)
+            .unwrap();
+        let second = handler
+            .save_image(
+                Bytes::from("second"),
+                Some("Plant1: #cover".to_owned()),
+                sent,
+            )
+            .unwrap();
+        let content = read(&first).unwrap();
+        remove_dir_all(SAVE_IMAGE_DIR).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(content, b"first");
+        assert!(first.to_string_lossy().ends_with("01052024.jpg"));
+        assert!(second.to_string_lossy().ends_with("01052024_2.jpg"))
+    }
+
+    #[test]
+    fn save_image_unknown_plant() {
+        let mut handler = example_handler();
+        handler.plants_dir = PathBuf::from(SAVE_IMAGE_UNKNOWN_DIR);
+        let sent = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let result = handler.save_image(
+            Bytes::from("image"),
+            Some("Not a plant: #cover".to_owned()),
+            sent,
+        );
+        assert!(result.is_err());
+        assert!(!PathBuf::from(SAVE_IMAGE_UNKNOWN_DIR).exists())
+    }
 }
@@ -1,13 +1,16 @@
-use super::{input_handlers::input_plant_names, Action, BotAction};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use chrono::Local;
 use database::database_manager::DatabaseManager;
+use input_handlers::input_plant_names;
 use plants::log_item::LogItem;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct FertilizePlants {
     fertilized_plants: Option<Vec<String>>,
     done: bool,
+    /// User the logs are recorded for
+    user: Option<String>,
 }
 
 impl FertilizePlants {
@@ -15,6 +18,7 @@ impl FertilizePlants {
         FertilizePlants {
             fertilized_plants: None,
             done: false,
+            user: None,
         }
     }
 }
@@ -53,6 +57,7 @@ impl Action for FertilizePlants {
                 date: Local::now().date_naive(),
                 plant,
                 note: None,
+                user: self.user.clone(),
             });
         }
         db_man.write_logs(activities)?;
@@ -67,6 +72,14 @@ impl Action for FertilizePlants {
             Ok("Please enter plants to fertilize (separate by comma)".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Plant)
+    }
+
+    fn set_user(&mut self, user: &str) {
+        self.user = Some(user.to_owned());
+    }
 }
 
 impl From<FertilizePlants> for BotAction {
@@ -86,6 +99,7 @@ mod fertilize_plants_tests {
         let expected = FertilizePlants {
             fertilized_plants: None,
             done: false,
+            user: None,
         };
         assert_eq!(result, expected)
     }
@@ -99,6 +113,7 @@ mod fertilize_plants_tests {
         let expected = FertilizePlants {
             fertilized_plants: Some(vec!["Plant1".to_owned(), "Plant2".to_owned()]),
             done: true,
+            user: None,
         };
         assert_eq!(action, expected)
     }
@@ -1,4 +1,5 @@
-use super::{input_handlers::input_location, Action, BotAction, DatabaseManager, Error};
+use super::{Action, BotAction, Choice, DatabaseManager, Error};
+use input_handlers::input_location;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct GetLocationPlants {
@@ -55,6 +56,10 @@ impl Action for GetLocationPlants {
             Ok("Please enter location to look up".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Location)
+    }
 }
 
 impl From<GetLocationPlants> for BotAction {
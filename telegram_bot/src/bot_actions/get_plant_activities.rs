@@ -1,4 +1,5 @@
-use super::{input_handlers::input_plant_name, Action, BotAction, DatabaseManager, Error};
+use super::{Action, BotAction, Choice, DatabaseManager, Error};
+use input_handlers::input_plant_name;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct GetPlantActivities {
@@ -65,6 +66,10 @@ impl Action for GetPlantActivities {
             Ok("Please enter plant to look up".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Plant)
+    }
 }
 
 impl From<GetPlantActivities> for BotAction {
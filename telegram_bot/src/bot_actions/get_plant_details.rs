@@ -1,4 +1,6 @@
-use super::{input_handlers::input_plant_name, Action, BotAction, DatabaseManager, Error};
+use super::{Action, BotAction, Choice, DatabaseManager, Error, Media};
+use bot_api::{formatting::escape_html, input_file::InputFile};
+use input_handlers::input_plant_name;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct GetPlantDetails {
@@ -56,6 +58,39 @@ impl Action for GetPlantDetails {
             Ok("Please enter plant to look up".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Plant)
+    }
+
+    fn get_media<T>(&self, db_man: &mut T) -> Result<Option<Media>, Error>
+    where
+        T: DatabaseManager,
+    {
+        let plant_name = self
+            .plant_name
+            .clone()
+            .ok_or(Error::MissingInput("Plant to look up".to_owned()))?;
+        let plant = db_man.get_plant(&plant_name)?;
+        let Some(image) = plant.images.iter().max_by_key(|image| image.created) else {
+            return Ok(None);
+        };
+        let caption = format!(
+            "<b>{}</b> (<i>{}</i>)\nLocation: {}\nOrigin: {}\nObtained: {}\nAutomatically Watered: {}\nNotes: {}\nPhoto from {}",
+            escape_html(&plant.info.name),
+            escape_html(&plant.info.species.to_string()),
+            escape_html(&plant.info.location.to_string()),
+            escape_html(&plant.info.origin),
+            plant.info.obtained,
+            plant.info.auto_water,
+            escape_html(&plant.info.notes.join(", ")),
+            image.created
+        );
+        Ok(Some(Media {
+            photo: InputFile::Path(image.file_path.join(&image.file_name)),
+            caption,
+        }))
+    }
 }
 
 impl From<GetPlantDetails> for BotAction {
@@ -63,3 +98,31 @@ impl From<GetPlantDetails> for BotAction {
         BotAction::GetPlantDetails(gt)
     }
 }
+
+#[cfg(test)]
+mod get_plant_details_tests {
+    use super::{Action, GetPlantDetails};
+    use crate::test_common::DummyManager;
+    use bot_api::input_file::InputFile;
+    use std::path::PathBuf;
+
+    #[test]
+    fn media_latest_image() {
+        let action = GetPlantDetails {
+            plant_name: Some("Plant1".to_owned()),
+            done: true,
+        };
+        let result = action.get_media(&mut DummyManager {}).unwrap().unwrap();
+        assert_eq!(
+            result.photo,
+            InputFile::Path(PathBuf::from("/02011970.jpg"))
+        );
+        assert!(result.caption.starts_with("<b>"))
+    }
+
+    #[test]
+    fn media_no_plant() {
+        let result = GetPlantDetails::default().get_media(&mut DummyManager {});
+        assert!(result.is_err())
+    }
+}
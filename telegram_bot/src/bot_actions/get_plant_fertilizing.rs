@@ -1,4 +1,5 @@
-use super::{input_handlers::input_plant_name, Action, BotAction, DatabaseManager, Error};
+use super::{Action, BotAction, Choice, DatabaseManager, Error};
+use input_handlers::input_plant_name;
 use plants::log_item::LogItem;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -58,6 +59,10 @@ impl Action for GetPlantFertilizing {
             Ok("Please enter plant to look up".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Plant)
+    }
 }
 
 impl From<GetPlantFertilizing> for BotAction {
@@ -1,4 +1,7 @@
-use super::{input_handlers::input_plant_name, Action, BotAction, DatabaseManager, Error};
+use super::{Action, BotAction, Choice, DatabaseManager, Error, Media};
+use crate::growth_chart::draw_growth_chart;
+use bot_api::{formatting::escape_html, input_file::InputFile};
+use input_handlers::input_plant_name;
 
 #[derive(Debug, Clone)]
 pub struct GetPlantGrowth {
@@ -71,6 +74,44 @@ impl Action for GetPlantGrowth {
             Ok("Please enter Plant to look up".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Plant)
+    }
+
+    fn get_media<T>(&self, db_man: &mut T) -> Result<Option<Media>, Error>
+    where
+        T: DatabaseManager,
+    {
+        let plant_name = self
+            .plant_name
+            .clone()
+            .ok_or(Error::MissingInput("Plant to look up".to_owned()))?;
+        let growth = db_man.get_growth_plant(&plant_name)?;
+        let Some(latest) = growth.iter().max() else {
+            return Ok(None);
+        };
+        let max_size = growth
+            .iter()
+            .map(|item| item.height_cm.max(item.width_cm))
+            .fold(1.0, f32::max);
+        let caption = format!(
+            "Growth of <b>{}</b>\nHeight (green) and width (blue) from 0 to {max_size}cm, health (red) from 0 to 5\nLatest ({}): height {}cm, width {}cm, health {}",
+            escape_html(&plant_name),
+            latest.date,
+            latest.height_cm,
+            latest.width_cm,
+            latest.health
+        );
+        let chart = draw_growth_chart(&growth).to_png()?;
+        Ok(Some(Media {
+            photo: InputFile::Bytes {
+                file_name: format!("{}_growth.png", plant_name.replace(' ', "")),
+                data: chart,
+            },
+            caption,
+        }))
+    }
 }
 
 impl From<GetPlantGrowth> for BotAction {
@@ -1,4 +1,5 @@
-use super::{input_handlers::input_plant_name, Action, BotAction, DatabaseManager, Error};
+use super::{Action, BotAction, Choice, DatabaseManager, Error};
+use input_handlers::input_plant_name;
 use plants::log_item::LogItem;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -44,10 +45,15 @@ impl Action for GetPlantWatering {
         for watering in watering.iter() {
             out_strs.push(format!("\t{}", watering.date));
         }
+        let prediction = match db_man.get_plant(&plant_name)?.get_watering_prediction() {
+            None => "No watering interval could be predicted".to_owned(),
+            Some(prediction) => format!("Predicted watering interval: {prediction}"),
+        };
         Ok(format!(
-            "Watering dates for {}:\n{}",
+            "Watering dates for {}:\n{}\n{}",
             plant_name,
-            out_strs.join("\n")
+            out_strs.join("\n"),
+            prediction
         ))
     }
 
@@ -58,6 +64,10 @@ impl Action for GetPlantWatering {
             Ok("Please enter plant to look up".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Plant)
+    }
 }
 
 impl From<GetPlantWatering> for BotAction {
@@ -1,4 +1,5 @@
-use super::{input_handlers::input_species, Action, BotAction, DatabaseManager, Error};
+use super::{Action, BotAction, Choice, DatabaseManager, Error};
+use input_handlers::input_species;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct GetSpeciesDetails {
@@ -86,6 +87,10 @@ impl Action for GetSpeciesDetails {
             Ok("Please enter species to look up".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Species)
+    }
 }
 
 impl From<GetSpeciesDetails> for BotAction {
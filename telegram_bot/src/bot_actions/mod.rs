@@ -6,7 +6,6 @@ mod get_plant_fertilizing;
 mod get_plant_growth;
 mod get_plant_watering;
 mod get_species_details;
-mod input_handlers;
 mod move_to_graveyard;
 mod new_activity;
 mod new_growth;
@@ -19,6 +18,7 @@ mod water_location;
 mod water_plants;
 
 use crate::errors::Error;
+use bot_api::input_file::InputFile;
 use database::database_manager::DatabaseManager;
 use std::fmt;
 
@@ -41,6 +41,22 @@ pub use update_species::UpdateSpecies;
 pub use water_location::WaterLocation;
 pub use water_plants::WaterPlants;
 
+/// Kind of input an action expects next, offered as buttons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    Plant,
+    Location,
+    Species,
+    Health,
+}
+
+/// Photo sent in place of the text result of an action, the caption is formatted as html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Media {
+    pub photo: InputFile,
+    pub caption: String,
+}
+
 #[derive(Clone, Debug)]
 pub enum BotAction {
     Idle,
@@ -117,6 +133,16 @@ pub trait Action {
     fn is_done(&self) -> bool;
     fn write_result<T: DatabaseManager>(&self, db_man: &mut T) -> Result<String, Error>;
     fn get_next_prompt(&self) -> Result<String, Error>;
+    /// Sets the user that started the action, for actions that record it
+    fn set_user(&mut self, _user: &str) {}
+    /// Kind of the next input, if it can be chosen from existing values
+    fn get_choice(&self) -> Option<Choice> {
+        None
+    }
+    /// Photo to send with the result of a finished action
+    fn get_media<T: DatabaseManager>(&self, _db_man: &mut T) -> Result<Option<Media>, Error> {
+        Ok(None)
+    }
 }
 
 impl fmt::Display for BotAction {
@@ -244,6 +270,49 @@ impl Action for BotAction {
             BotAction::GetPlantGrowth(gpg) => gpg.get_next_prompt(),
         }
     }
+
+    fn set_user(&mut self, user: &str) {
+        match self {
+            BotAction::WaterPlants(water) => water.set_user(user),
+            BotAction::WaterLocation(water) => water.set_user(user),
+            BotAction::Rain(rain) => rain.set_user(user),
+            BotAction::FertilizePlants(fert) => fert.set_user(user),
+            BotAction::NewActivity(act) => act.set_user(user),
+            _ => (),
+        }
+    }
+
+    fn get_choice(&self) -> Option<Choice> {
+        match self {
+            BotAction::Idle => None,
+            BotAction::WaterPlants(water) => water.get_choice(),
+            BotAction::WaterLocation(water) => water.get_choice(),
+            BotAction::Rain(rain) => rain.get_choice(),
+            BotAction::FertilizePlants(fert) => fert.get_choice(),
+            BotAction::NewGrowth(growth) => growth.get_choice(),
+            BotAction::NewActivity(act) => act.get_choice(),
+            BotAction::NewPlant(newp) => newp.get_choice(),
+            BotAction::NewSpecies(newsp) => newsp.get_choice(),
+            BotAction::UpdateSpecies(updsp) => updsp.get_choice(),
+            BotAction::UpdatePlant(updpl) => updpl.get_choice(),
+            BotAction::MoveToGraveyard(gr) => gr.get_choice(),
+            BotAction::GetLocationPlants(glp) => glp.get_choice(),
+            BotAction::GetPlantDetails(gpd) => gpd.get_choice(),
+            BotAction::GetSpeciesDetails(gsd) => gsd.get_choice(),
+            BotAction::GetPlantActivities(gpa) => gpa.get_choice(),
+            BotAction::GetPlantWatering(gpw) => gpw.get_choice(),
+            BotAction::GetPlantFertilizing(gpf) => gpf.get_choice(),
+            BotAction::GetPlantGrowth(gpg) => gpg.get_choice(),
+        }
+    }
+
+    fn get_media<T: DatabaseManager>(&self, db_man: &mut T) -> Result<Option<Media>, Error> {
+        match self {
+            BotAction::GetPlantDetails(gpd) => gpd.get_media(db_man),
+            BotAction::GetPlantGrowth(gpg) => gpg.get_media(db_man),
+            _ => Ok(None),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -261,7 +330,7 @@ mod bot_action_tests {
 
     #[test]
     fn action_neq() {
-        assert!(BotAction::Idle != BotAction::Rain(Rain {}))
+        assert!(BotAction::Idle != BotAction::Rain(Rain::default()))
     }
 
     #[test]
@@ -287,7 +356,7 @@ mod bot_action_tests {
 
     #[test]
     fn display_rain() {
-        let result = format!("{}", BotAction::Rain(Rain {}));
+        let result = format!("{}", BotAction::Rain(Rain::default()));
         let expected = "Rain";
         assert_eq!(result, expected)
     }
@@ -378,7 +447,8 @@ mod bot_action_tests {
 
     #[test]
     fn input_rain() {
-        let result = BotAction::Rain(Rain {}).handle_input("".to_owned(), &mut DummyManager {});
+        let result =
+            BotAction::Rain(Rain::default()).handle_input("".to_owned(), &mut DummyManager {});
         assert!(result.is_err())
     }
 
@@ -737,4 +807,16 @@ mod bot_action_tests {
         let expected = MoveToGraveyard::default().get_next_prompt().unwrap();
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn get_choice_newplant() {
+        let result = BotAction::NewPlant(NewPlant::default()).get_choice();
+        let expected = NewPlant::default().get_choice();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn get_choice_idle() {
+        assert_eq!(BotAction::Idle.get_choice(), None)
+    }
 }
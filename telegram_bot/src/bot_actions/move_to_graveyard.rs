@@ -1,7 +1,8 @@
-use super::{input_handlers::input_plant_name, Action, BotAction};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use chrono::NaiveDate;
 use database::database_manager::DatabaseManager;
+use input_handlers::input_plant_name;
 use plants::{graveyard::GraveyardPlant, named::Named};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -103,6 +104,13 @@ impl Action for MoveToGraveyard {
             Step::Done => Err(Error::ActionAlreadyDone("Move To Graveyard".to_owned())),
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        match self.current_step {
+            Step::PlantName => Some(Choice::Plant),
+            _ => None,
+        }
+    }
 }
 
 impl From<MoveToGraveyard> for BotAction {
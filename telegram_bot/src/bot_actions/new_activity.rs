@@ -1,7 +1,8 @@
-use super::{input_handlers::input_plant_names, Action, BotAction};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use chrono::NaiveDate;
 use database::database_manager::DatabaseManager;
+use input_handlers::input_plant_names;
 use plants::log_item::LogItem;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,6 +22,8 @@ pub struct NewActivity {
     activity: Option<String>,
     plants: Option<Vec<String>>,
     note: Option<String>,
+    /// User the logs are recorded for
+    user: Option<String>,
 }
 
 impl NewActivity {
@@ -32,6 +35,7 @@ impl NewActivity {
             activity: None,
             plants: None,
             note: None,
+            user: None,
         }
     }
 }
@@ -102,6 +106,7 @@ impl Action for NewActivity {
                 date,
                 plant,
                 note: self.note.clone(),
+                user: self.user.clone(),
             };
             log_items.push(log)
         }
@@ -123,6 +128,17 @@ impl Action for NewActivity {
             Step::Done => Err(Error::ActionAlreadyDone("Activity".to_owned())),
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        match self.current_step {
+            Step::Plants => Some(Choice::Plant),
+            _ => None,
+        }
+    }
+
+    fn set_user(&mut self, user: &str) {
+        self.user = Some(user.to_owned());
+    }
 }
 
 impl From<NewActivity> for BotAction {
@@ -146,6 +162,7 @@ mod new_activity_tests {
             activity: None,
             plants: None,
             note: None,
+            user: None,
         };
         assert_eq!(result, expected)
     }
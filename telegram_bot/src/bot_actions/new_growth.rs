@@ -1,10 +1,8 @@
-use super::{
-    input_handlers::{input_health, input_plant_name},
-    Action, BotAction,
-};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use chrono::Local;
 use database::database_manager::DatabaseManager;
+use input_handlers::{input_health, input_plant_name};
 use plants::growth_item::GrowthItem;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -140,6 +138,14 @@ impl Action for NewGrowth {
             Step::Done => Err(Error::ActionAlreadyDone("New Growth".to_owned())),
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        match self.current_step {
+            Step::PlantName => Some(Choice::Plant),
+            Step::Health => Some(Choice::Health),
+            _ => None,
+        }
+    }
 }
 
 impl From<NewGrowth> for BotAction {
@@ -150,7 +156,7 @@ impl From<NewGrowth> for BotAction {
 
 #[cfg(test)]
 mod new_growth_tests {
-    use super::{Action, NewGrowth, Step};
+    use super::{Action, Choice, NewGrowth, Step};
     use crate::test_common::DummyManager;
 
     #[test]
@@ -381,4 +387,14 @@ mod new_growth_tests {
         let result = action.get_next_prompt();
         assert!(result.is_err())
     }
+
+    #[test]
+    fn choice() {
+        let mut action = NewGrowth::default();
+        assert_eq!(action.get_choice(), Some(Choice::Plant));
+        action.current_step = Step::Health;
+        assert_eq!(action.get_choice(), Some(Choice::Health));
+        action.current_step = Step::Note;
+        assert_eq!(action.get_choice(), None)
+    }
 }
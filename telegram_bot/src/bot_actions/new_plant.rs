@@ -1,11 +1,9 @@
-use super::{
-    input_handlers::{input_health, input_species},
-    Action, BotAction,
-};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use chrono::Local;
 use chrono::NaiveDate;
 use database::database_manager::DatabaseManager;
+use input_handlers::{input_health, input_species};
 use plants::{
     growth_item::GrowthItem,
     plant::{PlantInfo, PlantLocation, PlantSpecies},
@@ -242,6 +240,7 @@ impl Action for NewPlant {
             origin,
             notes: plant_notes,
             species,
+            schedules: vec![],
         };
 
         db_man.write_plant(plant_json)?;
@@ -268,6 +267,15 @@ impl Action for NewPlant {
             }
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        match self.current_step {
+            Step::SpeciesName => Some(Choice::Species),
+            Step::Health => Some(Choice::Health),
+            Step::Location => Some(Choice::Location),
+            _ => None,
+        }
+    }
 }
 
 impl From<NewPlant> for BotAction {
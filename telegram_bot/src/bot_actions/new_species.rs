@@ -1,6 +1,7 @@
-use super::{input_handlers::input_notes, Action, BotAction};
+use super::{Action, BotAction};
 use crate::errors::Error;
 use database::database_manager::DatabaseManager;
+use input_handlers::input_notes;
 use plants::species::{Species, SunlightRequirement};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -336,6 +337,7 @@ impl Action for NewSpecies {
             pruning_notes,
             companions,
             additional_notes,
+            schedules: vec![],
         };
         db_man.write_species(species)?;
         let ret_msg = format!("Successfully created species {name}");
@@ -5,7 +5,10 @@ use database::database_manager::DatabaseManager;
 use plants::{location::Location, log_item::LogItem, named::Named};
 
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
-pub struct Rain;
+pub struct Rain {
+    /// User the logs are recorded for
+    user: Option<String>,
+}
 
 impl Action for Rain {
     fn handle_input<T: DatabaseManager>(&mut self, _: String, _: &mut T) -> Result<(), Error> {
@@ -32,6 +35,7 @@ impl Action for Rain {
                 date: Local::now().date_naive(),
                 plant: plant.get_name(),
                 note: None,
+                user: self.user.clone(),
             };
             watering_items.push(plant_log);
         }
@@ -51,6 +55,10 @@ impl Action for Rain {
     fn get_next_prompt(&self) -> Result<String, Error> {
         Err(Error::ActionAlreadyDone("Rain".to_owned()))
     }
+
+    fn set_user(&mut self, user: &str) {
+        self.user = Some(user.to_owned());
+    }
 }
 
 impl From<Rain> for BotAction {
@@ -67,7 +75,7 @@ mod rain_tests {
     #[test]
     fn rain_default() {
         let result = Rain::default();
-        let expected = Rain {};
+        let expected = Rain { user: None };
         assert_eq!(result, expected)
     }
 
@@ -100,4 +108,11 @@ mod rain_tests {
         let expected = BotAction::Rain(Rain::default());
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn set_user() {
+        let mut action = Rain::default();
+        action.set_user("gardener");
+        assert_eq!(action.user, Some("gardener".to_owned()))
+    }
 }
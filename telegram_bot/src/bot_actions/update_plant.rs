@@ -1,9 +1,7 @@
-use super::{
-    input_handlers::{input_plant_name, str_to_value},
-    Action, BotAction,
-};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use database::database_manager::DatabaseManager;
+use input_handlers::{input_plant_name, str_to_value};
 use plants::plant_update::{update_plant, UpdateField, UpdateValue};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -116,6 +114,13 @@ impl Action for UpdatePlant {
             Step::Done => Err(Error::ActionAlreadyDone("Update Species".to_owned())),
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        match self.current_step {
+            Step::PlantName => Some(Choice::Plant),
+            _ => None,
+        }
+    }
 }
 
 impl From<UpdatePlant> for BotAction {
@@ -277,7 +282,7 @@ mod update_plant_tests {
         let mut action = UpdatePlant::default();
         action.current_step = Step::UpdateField;
         let result = action.get_next_prompt().unwrap();
-        let expected = "Please enter field to update, possible fields: Origin, Species, Location, Obtained, Notes, Auto Watered";
+        let expected = "Please enter field to update, possible fields: Origin, Species, Location, Obtained, Notes, Auto Watered, Schedules";
         assert_eq!(result, expected)
     }
 
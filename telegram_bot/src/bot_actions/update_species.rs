@@ -1,6 +1,7 @@
-use super::{input_handlers::input_species, Action, BotAction};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use database::database_manager::DatabaseManager;
+use input_handlers::input_species;
 use plants::species_update::{update_species, UpdateField, UpdateValue};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -107,6 +108,13 @@ impl Action for UpdateSpecies {
             Step::Done => Err(Error::ActionAlreadyDone("Update Species".to_owned())),
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        match self.current_step {
+            Step::SpeciesName => Some(Choice::Species),
+            _ => None,
+        }
+    }
 }
 
 impl From<UpdateSpecies> for BotAction {
@@ -268,7 +276,7 @@ mod update_species_tests {
         let mut action = UpdateSpecies::default();
         action.current_step = Step::UpdateField;
         let result = action.get_next_prompt().unwrap();
-        let expected = "Please enter field to update, possible fields: Scientific Name, Genus, Family, Sunlight, Min Temp, Max Temp, Min Temp Opt, Max Temp Opt, pH Min, pH Max, Planting Distance, Watering Notes, Fertilizing Notes, Pruning Notes, Companions, Additional Notes, Average Watering Days, Average Fertilizing Days";
+        let expected = "Please enter field to update, possible fields: Scientific Name, Genus, Family, Sunlight, Min Temp, Max Temp, Min Temp Opt, Max Temp Opt, pH Min, pH Max, Planting Distance, Watering Notes, Fertilizing Notes, Pruning Notes, Companions, Additional Notes, Average Watering Days, Average Fertilizing Days, Schedules";
         assert_eq!(result, expected)
     }
 
@@ -1,4 +1,4 @@
-use super::{Action, BotAction};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use chrono::Local;
 use database::database_manager::DatabaseManager;
@@ -8,6 +8,8 @@ use plants::log_item::LogItem;
 pub struct WaterLocation {
     watered_plants: Option<Vec<String>>,
     done: bool,
+    /// User the logs are recorded for
+    user: Option<String>,
 }
 
 impl WaterLocation {
@@ -15,6 +17,7 @@ impl WaterLocation {
         WaterLocation {
             watered_plants: None,
             done: false,
+            user: None,
         }
     }
 }
@@ -61,6 +64,7 @@ impl Action for WaterLocation {
                 date: Local::now().date_naive(),
                 plant,
                 note: None,
+                user: self.user.clone(),
             });
         }
         db_man.write_logs(activities)?;
@@ -75,6 +79,14 @@ impl Action for WaterLocation {
             Ok("Please enter location to water".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Location)
+    }
+
+    fn set_user(&mut self, user: &str) {
+        self.user = Some(user.to_owned());
+    }
 }
 
 impl From<WaterLocation> for BotAction {
@@ -94,6 +106,7 @@ mod water_location_tests {
         let expected = WaterLocation {
             watered_plants: None,
             done: false,
+            user: None,
         };
         assert_eq!(result, expected)
     }
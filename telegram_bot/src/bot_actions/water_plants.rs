@@ -1,13 +1,16 @@
-use super::{input_handlers::input_plant_names, Action, BotAction};
+use super::{Action, BotAction, Choice};
 use crate::errors::Error;
 use chrono::Local;
 use database::database_manager::DatabaseManager;
+use input_handlers::input_plant_names;
 use plants::log_item::LogItem;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct WaterPlants {
     watered_plants: Option<Vec<String>>,
     done: bool,
+    /// User the logs are recorded for
+    user: Option<String>,
 }
 
 impl WaterPlants {
@@ -15,6 +18,7 @@ impl WaterPlants {
         WaterPlants {
             watered_plants: None,
             done: false,
+            user: None,
         }
     }
 }
@@ -53,6 +57,7 @@ impl Action for WaterPlants {
                 date: Local::now().date_naive(),
                 plant,
                 note: None,
+                user: self.user.clone(),
             });
         }
 
@@ -68,6 +73,14 @@ impl Action for WaterPlants {
             Ok("Please enter watered plants (separate by comma)".to_owned())
         }
     }
+
+    fn get_choice(&self) -> Option<Choice> {
+        (!self.done).then_some(Choice::Plant)
+    }
+
+    fn set_user(&mut self, user: &str) {
+        self.user = Some(user.to_owned());
+    }
 }
 
 impl From<WaterPlants> for BotAction {
@@ -78,7 +91,7 @@ impl From<WaterPlants> for BotAction {
 
 #[cfg(test)]
 mod water_plants_tests {
-    use super::{Action, BotAction, WaterPlants};
+    use super::{Action, BotAction, Choice, WaterPlants};
     use crate::test_common::DummyManager;
 
     #[test]
@@ -87,6 +100,7 @@ mod water_plants_tests {
         let expected = WaterPlants {
             watered_plants: None,
             done: false,
+            user: None,
         };
         assert_eq!(result, expected)
     }
@@ -157,4 +171,19 @@ mod water_plants_tests {
         let expected = BotAction::WaterPlants(WaterPlants::default());
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn set_user() {
+        let mut action = WaterPlants::default();
+        action.set_user("gardener");
+        assert_eq!(action.user, Some("gardener".to_owned()))
+    }
+
+    #[test]
+    fn choice() {
+        let mut action = WaterPlants::default();
+        assert_eq!(action.get_choice(), Some(Choice::Plant));
+        action.done = true;
+        assert_eq!(action.get_choice(), None)
+    }
 }
@@ -33,6 +33,11 @@ pub enum Command {
     TodayWater,
     TodayFertilize,
     TodayGrowth,
+    TodayTasks,
+    Weather,
+    Climate,
+    DigestOn,
+    DigestOff,
 
     AllPlants,
     AllSpecies,
@@ -51,6 +56,8 @@ pub enum CommandRes {
     NewInput(String),
     ImmediateAction(ImmediateAction),
     Message(String),
+    Abort,
+    SetDigest(bool),
 }
 
 impl Command {
@@ -75,6 +82,11 @@ impl Command {
             Command::TodayWater,
             Command::TodayFertilize,
             Command::TodayGrowth,
+            Command::TodayTasks,
+            Command::Weather,
+            Command::Climate,
+            Command::DigestOn,
+            Command::DigestOff,
             Command::AllPlants,
             Command::AllSpecies,
             Command::LocationPlants,
@@ -101,7 +113,7 @@ impl Command {
             Command::Today => {
                 CommandRes::NewInput(Local::now().date_naive().format("%d.%m.%Y").to_string())
             }
-            Command::Abort => CommandRes::ImmediateAction(ImmediateAction::Abort),
+            Command::Abort => CommandRes::Abort,
             Command::Push => CommandRes::ImmediateAction(ImmediateAction::Push),
             Command::CheckLogs => CommandRes::ImmediateAction(ImmediateAction::CheckLogs),
             Command::Water => CommandRes::NewAction(Box::new(WaterPlants::default().into())),
@@ -111,7 +123,7 @@ impl Command {
             Command::Fertilize => {
                 CommandRes::NewAction(Box::new(FertilizePlants::default().into()))
             }
-            Command::Rain => CommandRes::NewAction(Box::new(Rain.into())),
+            Command::Rain => CommandRes::NewAction(Box::new(Rain::default().into())),
             Command::NewGrowth => CommandRes::NewAction(Box::new(NewGrowth::default().into())),
             Command::NewPlant => CommandRes::NewAction(Box::new(NewPlant::default().into())),
             Command::NewSpecies => CommandRes::NewAction(Box::new(NewSpecies::default().into())),
@@ -128,6 +140,11 @@ impl Command {
                 CommandRes::ImmediateAction(ImmediateAction::GetFertilizeToday)
             }
             Command::TodayGrowth => CommandRes::ImmediateAction(ImmediateAction::GetGrowthToday),
+            Command::TodayTasks => CommandRes::ImmediateAction(ImmediateAction::GetTasksToday),
+            Command::Weather => CommandRes::ImmediateAction(ImmediateAction::CheckWeather),
+            Command::Climate => CommandRes::ImmediateAction(ImmediateAction::CheckClimate),
+            Command::DigestOn => CommandRes::SetDigest(true),
+            Command::DigestOff => CommandRes::SetDigest(false),
             Command::AllPlants => CommandRes::ImmediateAction(ImmediateAction::GetAllPlants),
             Command::LocationPlants => {
                 CommandRes::NewAction(Box::new(GetLocationPlants::default().into()))
@@ -177,6 +194,11 @@ impl fmt::Display for Command {
             Command::TodayWater => f.write_str("today_water"),
             Command::TodayFertilize => f.write_str("today_fertilize"),
             Command::TodayGrowth => f.write_str("today_growth"),
+            Command::TodayTasks => f.write_str("today_tasks"),
+            Command::Weather => f.write_str("weather"),
+            Command::Climate => f.write_str("climate"),
+            Command::DigestOn => f.write_str("digest_on"),
+            Command::DigestOff => f.write_str("digest_off"),
             Command::AllPlants => f.write_str("all_plants"),
             Command::LocationPlants => f.write_str("loction_plants"),
             Command::AllSpecies => f.write_str("all_species"),
@@ -213,6 +235,11 @@ impl str::FromStr for Command {
             "today_water" => Ok(Command::TodayWater),
             "today_fertilize" => Ok(Command::TodayFertilize),
             "today_growth" => Ok(Command::TodayGrowth),
+            "today_tasks" => Ok(Command::TodayTasks),
+            "weather" => Ok(Command::Weather),
+            "climate" => Ok(Command::Climate),
+            "digest_on" => Ok(Command::DigestOn),
+            "digest_off" => Ok(Command::DigestOff),
             "all_plants" => Ok(Command::AllPlants),
             "loction_plants" => Ok(Command::LocationPlants),
             "all_species" => Ok(Command::AllSpecies),
@@ -252,6 +279,15 @@ impl BotCommand for Command {
             Command::TodayWater => "Check which plants should get watered today".to_owned(),
             Command::TodayFertilize => "Check which plants should be fertilized today".to_owned(),
             Command::TodayGrowth => "Check which plants need growth updates by today".to_owned(),
+            Command::TodayTasks => "Check all scheduled activities due today".to_owned(),
+            Command::Weather => {
+                "Log rain for outside plants and check for frost from weather data".to_owned()
+            }
+            Command::Climate => {
+                "Check which plants are too cold or hot at their location".to_owned()
+            }
+            Command::DigestOn => "Receive the daily digest of due activities".to_owned(),
+            Command::DigestOff => "Stop receiving the daily digest".to_owned(),
             Command::AllPlants => "Show all plants".to_owned(),
             Command::LocationPlants => "Show plants at location".to_owned(),
             Command::AllSpecies => "Show all species".to_owned(),
@@ -361,6 +397,41 @@ mod command_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn result_today_tasks() {
+        let result = Command::TodayTasks.get_res();
+        let expected = CommandRes::ImmediateAction(ImmediateAction::GetTasksToday);
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn result_weather() {
+        let result = Command::Weather.get_res();
+        let expected = CommandRes::ImmediateAction(ImmediateAction::CheckWeather);
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn result_climate() {
+        let result = Command::Climate.get_res();
+        let expected = CommandRes::ImmediateAction(ImmediateAction::CheckClimate);
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn result_digest_on() {
+        let result = Command::DigestOn.get_res();
+        let expected = CommandRes::SetDigest(true);
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn result_digest_off() {
+        let result = Command::DigestOff.get_res();
+        let expected = CommandRes::SetDigest(false);
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn result_movegraveyard() {
         let result = Command::MoveToGraveyard.get_res();
@@ -373,7 +444,7 @@ mod command_tests {
     #[test]
     fn result_abort() {
         let result = Command::Abort.get_res();
-        let expected = CommandRes::ImmediateAction(ImmediateAction::Abort);
+        let expected = CommandRes::Abort;
         assert_eq!(result, expected)
     }
 
@@ -405,6 +476,41 @@ mod command_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn display_today_tasks() {
+        let result = format!("{}", Command::TodayTasks);
+        let expected = "today_tasks";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn display_weather() {
+        let result = format!("{}", Command::Weather);
+        let expected = "weather";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn display_climate() {
+        let result = format!("{}", Command::Climate);
+        let expected = "climate";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn display_digest_on() {
+        let result = format!("{}", Command::DigestOn);
+        let expected = "digest_on";
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn display_digest_off() {
+        let result = format!("{}", Command::DigestOff);
+        let expected = "digest_off";
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn display_abort() {
         let result = format!("{}", Command::Abort);
@@ -517,6 +623,41 @@ mod command_tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn from_str_today_tasks() {
+        let result = Command::from_str("today_tasks").unwrap();
+        let expected = Command::TodayTasks;
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn from_str_weather() {
+        let result = Command::from_str("weather").unwrap();
+        let expected = Command::Weather;
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn from_str_climate() {
+        let result = Command::from_str("climate").unwrap();
+        let expected = Command::Climate;
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn from_str_digest_on() {
+        let result = Command::from_str("digest_on").unwrap();
+        let expected = Command::DigestOn;
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn from_str_digest_off() {
+        let result = Command::from_str("digest_off").unwrap();
+        let expected = Command::DigestOff;
+        assert_eq!(result, expected)
+    }
+
     #[test]
     fn from_str_abort() {
         let result = Command::from_str("abort").unwrap();
@@ -1,4 +1,5 @@
-use super::errors::Error;
+use super::{action_handler::DEFAULT_ACTION_TIMEOUT, errors::Error};
+use bot_api::{bot_methods::DEFAULT_API_URL, webhook::WebhookConfig};
 use database::file_backend::{errors::Error as DBError, load_json::load_json};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -7,6 +8,29 @@ use std::path::PathBuf;
 pub struct BotConfig {
     pub api_key: String,
     pub white_list: Vec<i64>,
+    /// Csv file with recorded and forecast weather
+    #[serde(default)]
+    pub weather_file: Option<PathBuf>,
+    /// Times of day (hh:mm) the daily digest is sent
+    #[serde(default)]
+    pub digest_times: Vec<String>,
+    /// Minutes without input after which running actions are aborted
+    #[serde(default = "default_action_timeout")]
+    pub action_timeout: i64,
+    /// Url of the bot api, e.g. a local server for testing
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    /// Updates are received with a webhook if set, otherwise they are polled
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+}
+
+fn default_action_timeout() -> i64 {
+    DEFAULT_ACTION_TIMEOUT
+}
+
+fn default_api_url() -> String {
+    DEFAULT_API_URL.to_owned()
 }
 
 pub fn load_config() -> Result<BotConfig, Error> {
@@ -0,0 +1,115 @@
+use crate::{
+    action_handler::ActionHandler,
+    test_common::{
+        example_growth1, example_growth2, example_location, example_plant_info, example_species,
+    },
+};
+use bot_api::{bot::Bot, mock_server::MockServer};
+use database::{database_manager::DatabaseManager, sqlite_backend::SQLiteDB};
+use std::{env::temp_dir, fs::remove_file};
+
+const API_KEY: &str = "test_key";
+const CHAT: i64 = 1;
+const USER: i64 = 2;
+
+/// Fresh database in the temp dir, containing the example plant
+fn example_db(name: &str) -> SQLiteDB {
+    let db_path = temp_dir().join(format!("{name}.db"));
+    if db_path.exists() {
+        remove_file(&db_path).unwrap();
+    }
+    let mut db = SQLiteDB::new(db_path).unwrap();
+    db.write_location(example_location()).unwrap();
+    db.write_species(example_species()).unwrap();
+    db.write_plant(example_plant_info()).unwrap();
+    db
+}
+
+fn example_setup(name: &str) -> (MockServer, Bot, ActionHandler<SQLiteDB>) {
+    let server = MockServer::start(API_KEY).unwrap();
+    let mut bot = Bot::new(API_KEY.to_owned());
+    bot.api_url = server.url.clone();
+    let handler = ActionHandler::new(vec![USER], example_db(name));
+    (server, bot, handler)
+}
+
+fn last_text(server: &MockServer) -> String {
+    server.get_sent().last().unwrap().text.clone()
+}
+
+#[tokio::test]
+async fn water_conversation() {
+    let (server, mut bot, mut handler) = example_setup("conversation_water");
+    server.push_message(CHAT, USER, "/water");
+    bot.poll(&mut handler).await.unwrap();
+    assert_eq!(
+        last_text(&server),
+        "Please enter watered plants (separate by comma)"
+    );
+    let keyboard = server.get_sent().last().unwrap().reply_markup.clone();
+    assert!(keyboard.unwrap().contains("A Plant"));
+
+    server.push_message(CHAT, USER, "A Plant");
+    bot.poll(&mut handler).await.unwrap();
+    assert_eq!(last_text(&server), "Successfully watered plants A Plant");
+    assert_eq!(bot.last_update, 2);
+
+    let logs = handler.db_man.get_logs_plant("A Plant").unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].activity, "Watering");
+    assert_eq!(logs[0].user, Some("tester".to_owned()))
+}
+
+#[tokio::test]
+async fn button_conversation() {
+    let (server, mut bot, mut handler) = example_setup("conversation_button");
+    server.push_message(CHAT, USER, "/plant_details");
+    bot.poll(&mut handler).await.unwrap();
+    let prompt = server.get_sent().last().unwrap().clone();
+    assert!(prompt.reply_markup.is_some());
+
+    server.push_callback(USER, prompt.message_id, "A Plant");
+    bot.poll(&mut handler).await.unwrap();
+    let sent = server.get_sent();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0].text, "Please enter plant to look up A Plant");
+    assert_eq!(sent[0].reply_markup, None);
+    assert!(sent[1].text.starts_with("A Plant"));
+    assert!(handler.conversations.is_empty())
+}
+
+#[tokio::test]
+async fn growth_chart_conversation() {
+    let (server, mut bot, mut handler) = example_setup("conversation_growth");
+    handler
+        .db_man
+        .write_growths(vec![example_growth1(), example_growth2()])
+        .unwrap();
+    server.push_message(CHAT, USER, "/plant_growth");
+    server.push_message(CHAT, USER, "A Plant");
+    bot.poll(&mut handler).await.unwrap();
+    let sent = server.get_sent().last().unwrap().clone();
+    assert!(sent.text.starts_with("Growth of <b>A Plant</b>"));
+    assert_eq!(sent.parse_mode, Some("HTML".to_owned()));
+    assert!(sent.photo.unwrap().starts_with(b"\x89PNG"));
+    assert!(handler.media.is_empty())
+}
+
+#[tokio::test]
+async fn abort_conversation() {
+    let (server, mut bot, mut handler) = example_setup("conversation_abort");
+    server.push_message(CHAT, USER, "/new_growth");
+    server.push_message(CHAT, USER, "/abort");
+    bot.poll(&mut handler).await.unwrap();
+    assert_eq!(last_text(&server), "Aborted action New Growth");
+    assert!(handler.conversations.is_empty())
+}
+
+#[tokio::test]
+async fn unauthorized_user() {
+    let (server, mut bot, mut handler) = example_setup("conversation_unauthorized");
+    server.push_message(CHAT, USER + 1, "/water");
+    bot.poll(&mut handler).await.unwrap();
+    assert!(server.get_sent().is_empty());
+    assert!(handler.conversations.is_empty())
+}
@@ -0,0 +1,261 @@
+use super::errors::Error;
+use chrono::{NaiveDateTime, NaiveTime, TimeDelta};
+use database::file_backend::{load_json::load_json, write_json::write_json};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Persisted state of the daily digest
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct DigestState {
+    #[serde(default, with = "datetime_serializer")]
+    pub last_sent: Option<NaiveDateTime>,
+    /// Users on the white list that do not want to receive the digest
+    #[serde(default)]
+    pub opted_out: Vec<i64>,
+}
+
+/// Daily digest of due activities, sent to all users on the white list
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Digest {
+    pub times: Vec<NaiveTime>,
+    /// File the state is saved in, the state is not saved if this is None
+    pub state_file: Option<PathBuf>,
+    pub state: DigestState,
+}
+
+/// Parses send times given as hh:mm
+pub fn parse_times(times: &[String]) -> Result<Vec<NaiveTime>, Error> {
+    times
+        .iter()
+        .map(|time| {
+            NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .map_err(|_| Error::ParseError(format!("digest time {time}")))
+        })
+        .collect()
+}
+
+impl Digest {
+    /// Loads the state saved in `state_file`
+    /// if no digest was sent yet, the first one is sent at the first send time after `now`
+    pub fn load(
+        times: Vec<NaiveTime>,
+        state_file: PathBuf,
+        now: NaiveDateTime,
+    ) -> Result<Digest, Error> {
+        let mut state: DigestState = if state_file.exists() {
+            load_json(&state_file).map_err(|err| Error::DBError(Box::new(err)))?
+        } else {
+            DigestState::default()
+        };
+        state.last_sent.get_or_insert(now);
+        Ok(Digest {
+            times,
+            state_file: Some(state_file),
+            state,
+        })
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        match &self.state_file {
+            None => Ok(()),
+            Some(state_file) => {
+                write_json(&self.state, state_file).map_err(|err| Error::DBError(Box::new(err)))
+            }
+        }
+    }
+
+    /// Most recent send time at or before now
+    pub fn last_scheduled(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.times
+            .iter()
+            .map(|time| {
+                let scheduled = now.date().and_time(*time);
+                if scheduled <= now {
+                    scheduled
+                } else {
+                    scheduled - TimeDelta::days(1)
+                }
+            })
+            .max()
+    }
+
+    /// A digest is due if a send time has passed since the last one was sent
+    /// after downtime only a single digest is sent
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        match (self.last_scheduled(now), self.state.last_sent) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(scheduled), Some(last_sent)) => last_sent < scheduled,
+        }
+    }
+
+    pub fn mark_sent(&mut self, now: NaiveDateTime) -> Result<(), Error> {
+        self.state.last_sent = Some(now);
+        self.save()
+    }
+
+    pub fn set_enabled(&mut self, user: i64, enabled: bool) -> Result<(), Error> {
+        self.state.opted_out.retain(|id| *id != user);
+        if !enabled {
+            self.state.opted_out.push(user);
+        }
+        self.save()
+    }
+
+    pub fn get_recipients(&self, white_list: &[i64]) -> Vec<i64> {
+        white_list
+            .iter()
+            .filter(|user| !self.state.opted_out.contains(user))
+            .cloned()
+            .collect()
+    }
+}
+
+mod datetime_serializer {
+    use chrono::NaiveDateTime;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%d.%m.%Y %H:%M:%S";
+
+    pub fn serialize<S: Serializer>(
+        datetime: &Option<NaiveDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match datetime {
+            None => serializer.serialize_none(),
+            Some(datetime) => serializer.serialize_str(&datetime.format(FORMAT).to_string()),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDateTime>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(s) => NaiveDateTime::parse_from_str(&s, FORMAT)
+                .map(Some)
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod digest_tests {
+    use super::{parse_times, Digest, DigestState};
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use std::{env::temp_dir, fs::remove_file};
+
+    fn example_time(hour: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn example_datetime(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(1970, 1, day)
+            .unwrap()
+            .and_time(example_time(hour))
+    }
+
+    fn example_digest() -> Digest {
+        Digest {
+            times: vec![example_time(8), example_time(18)],
+            state_file: None,
+            state: DigestState::default(),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let result = parse_times(&["08:00".to_owned(), "18:30".to_owned()]).unwrap();
+        let expected = vec![example_time(8), NaiveTime::from_hms_opt(18, 30, 0).unwrap()];
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn parse_fail() {
+        let result = parse_times(&["8 o'clock".to_owned()]);
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn last_scheduled() {
+        let digest = example_digest();
+        assert_eq!(
+            digest.last_scheduled(example_datetime(2, 12)),
+            Some(example_datetime(2, 8))
+        );
+        assert_eq!(
+            digest.last_scheduled(example_datetime(2, 7)),
+            Some(example_datetime(1, 18))
+        );
+        assert_eq!(
+            Digest::default().last_scheduled(example_datetime(2, 7)),
+            None
+        )
+    }
+
+    #[test]
+    fn due() {
+        let mut digest = example_digest();
+        assert!(digest.is_due(example_datetime(2, 9)));
+        digest.mark_sent(example_datetime(2, 9)).unwrap();
+        assert!(!digest.is_due(example_datetime(2, 17)));
+        assert!(digest.is_due(example_datetime(2, 18)))
+    }
+
+    #[test]
+    fn due_catch_up() {
+        let mut digest = example_digest();
+        digest.mark_sent(example_datetime(1, 8)).unwrap();
+        assert!(digest.is_due(example_datetime(4, 7)));
+        digest.mark_sent(example_datetime(4, 7)).unwrap();
+        assert!(!digest.is_due(example_datetime(4, 7)))
+    }
+
+    #[test]
+    fn recipients() {
+        let mut digest = example_digest();
+        digest.set_enabled(1, false).unwrap();
+        assert_eq!(digest.get_recipients(&[1, 2]), vec![2]);
+        digest.set_enabled(1, true).unwrap();
+        assert_eq!(digest.get_recipients(&[1, 2]), vec![1, 2])
+    }
+
+    #[test]
+    fn save_load() {
+        let state_file = temp_dir().join("digest_state.json");
+        let mut digest = Digest::load(
+            vec![example_time(8)],
+            state_file.clone(),
+            example_datetime(1, 7),
+        )
+        .unwrap();
+        digest.set_enabled(1, false).unwrap();
+        digest.mark_sent(example_datetime(1, 8)).unwrap();
+        let result = Digest::load(
+            vec![example_time(8)],
+            state_file.clone(),
+            example_datetime(2, 7),
+        )
+        .unwrap();
+        remove_file(state_file).unwrap();
+        let expected = DigestState {
+            last_sent: Some(example_datetime(1, 8)),
+            opted_out: vec![1],
+        };
+        assert_eq!(result.state, expected)
+    }
+
+    #[test]
+    fn load_not_due() {
+        let state_file = temp_dir().join("digest_state_new.json");
+        let digest = Digest::load(
+            vec![example_time(8), example_time(18)],
+            state_file.clone(),
+            example_datetime(2, 9),
+        )
+        .unwrap();
+        assert!(!state_file.exists());
+        assert!(!digest.is_due(example_datetime(2, 9)));
+        assert!(digest.is_due(example_datetime(2, 18)))
+    }
+}
@@ -1,4 +1,5 @@
 use bot_api::errors::Error as BotError;
+use input_handlers::errors::Error as InputError;
 use plants::errors::Error as PlantError;
 use std::fmt;
 
@@ -22,6 +23,7 @@ pub enum Error {
     Unauthorized(String),
     Command(CommandError),
     Logger,
+    NoWeatherData,
     Other(Box<dyn std::error::Error>),
 }
 
@@ -83,6 +85,7 @@ impl fmt::Display for Error {
             Error::SpeciesExists(name) => write!(f, "Species {name} already exists"),
             Error::Unauthorized(name) => write!(f, "User {name} is not authorized"),
             Error::Logger => write!(f, "Could not initialize logger"),
+            Error::NoWeatherData => write!(f, "No weather file configured in bot_conf.json"),
             Error::Command(msg) => msg.fmt(f),
             Error::Other(err) => err.fmt(f),
         }
@@ -101,6 +104,19 @@ impl From<PlantError> for Error {
     }
 }
 
+impl From<InputError> for Error {
+    fn from(input_err: InputError) -> Error {
+        match input_err {
+            InputError::PlantDoesNotExist(name) => Error::PlantDoesNotExist(name),
+            InputError::LocationDoesNotExist(name) => Error::LocationDoesNotExist(name),
+            InputError::SpeciesDoesNotExist(name) => Error::SpeciesDoesNotExist(name),
+            InputError::ParseError(msg) => Error::ParseError(msg),
+            InputError::BadHealth(health) => Error::BadHealth(health),
+            InputError::DB(err) => Error::DBError(err),
+        }
+    }
+}
+
 impl From<BotError> for Error {
     fn from(bot_err: BotError) -> Error {
         Error::BotError(bot_err)
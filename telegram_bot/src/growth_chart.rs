@@ -0,0 +1,218 @@
+use super::errors::Error;
+use image::{codecs::png::PngEncoder, ExtendedColorType, ImageEncoder};
+use plants::growth_item::GrowthItem;
+
+type Color = [u8; 3];
+
+pub const CHART_WIDTH: usize = 600;
+pub const CHART_HEIGHT: usize = 300;
+/// Space around the plot area in pixels
+const MARGIN: usize = 20;
+/// Number of horizontal grid lines
+const GRID_LINES: usize = 5;
+/// Health values range from 0 to this
+const MAX_HEALTH: f32 = 5.0;
+
+const BACKGROUND: Color = [255, 255, 255];
+const GRID: Color = [220, 220, 220];
+const AXIS: Color = [0, 0, 0];
+pub const HEIGHT_COLOR: Color = [46, 139, 87];
+pub const WIDTH_COLOR: Color = [65, 105, 225];
+pub const HEALTH_COLOR: Color = [220, 20, 60];
+
+/// RGB image that charts are drawn on
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize, background: Color) -> Canvas {
+        Canvas {
+            width,
+            height,
+            pixels: background.repeat(width * height),
+        }
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let ind = (y * self.width + x) * 3;
+        Some([self.pixels[ind], self.pixels[ind + 1], self.pixels[ind + 2]])
+    }
+
+    /// Pixels outside the canvas are ignored
+    fn set_pixel(&mut self, x: i64, y: i64, color: Color) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let ind = (y as usize * self.width + x as usize) * 3;
+        self.pixels[ind..ind + 3].copy_from_slice(&color);
+    }
+
+    fn fill_rect(&mut self, center: (i64, i64), radius: i64, color: Color) {
+        for x in center.0 - radius..=center.0 + radius {
+            for y in center.1 - radius..=center.1 + radius {
+                self.set_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Draws a line 2 pixels wide
+    fn draw_line(&mut self, from: (i64, i64), to: (i64, i64), color: Color) {
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let step_x = if x < to.0 { 1 } else { -1 };
+        let step_y = if y < to.1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.set_pixel(x, y, color);
+            self.set_pixel(x + 1, y, color);
+            self.set_pixel(x, y + 1, color);
+            if (x, y) == to {
+                break;
+            }
+            let err2 = 2 * err;
+            if err2 >= dy {
+                err += dy;
+                x += step_x;
+            }
+            if err2 <= dx {
+                err += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Encodes the canvas as 8 bit RGB png
+    pub fn to_png(&self) -> Result<Vec<u8>, Error> {
+        let mut png = vec![];
+        PngEncoder::new(&mut png)
+            .write_image(
+                &self.pixels,
+                self.width as u32,
+                self.height as u32,
+                ExtendedColorType::Rgb8,
+            )
+            .map_err(|err| Error::Other(Box::new(err)))?;
+        Ok(png)
+    }
+}
+
+/// Draws height and width (scaled to the largest value) and health (scaled to 0-5) over time
+pub fn draw_growth_chart(growth: &[GrowthItem]) -> Canvas {
+    let mut canvas = Canvas::new(CHART_WIDTH, CHART_HEIGHT, BACKGROUND);
+    let left = MARGIN as i64;
+    let right = (CHART_WIDTH - MARGIN) as i64;
+    let top = MARGIN as i64;
+    let bottom = (CHART_HEIGHT - MARGIN) as i64;
+
+    for line in 1..=GRID_LINES as i64 {
+        let y = bottom - (bottom - top) * line / GRID_LINES as i64;
+        canvas.draw_line((left, y), (right, y), GRID);
+    }
+    canvas.draw_line((left, top), (left, bottom), AXIS);
+    canvas.draw_line((left, bottom), (right, bottom), AXIS);
+
+    let mut growth = growth.to_vec();
+    growth.sort();
+    let (Some(first), Some(last)) = (growth.first(), growth.last()) else {
+        return canvas;
+    };
+    let num_days = (last.date - first.date).num_days();
+    let max_size = growth
+        .iter()
+        .map(|item| item.height_cm.max(item.width_cm))
+        .fold(1.0, f32::max);
+
+    let get_x = |item: &GrowthItem| {
+        if num_days == 0 {
+            (left + right) / 2
+        } else {
+            left + (right - left) * (item.date - first.date).num_days() / num_days
+        }
+    };
+    let get_y = |val: f32, max: f32| bottom - ((bottom - top) as f32 * val / max).round() as i64;
+
+    let heights = growth.iter().map(|item| item.height_cm).collect();
+    let widths = growth.iter().map(|item| item.width_cm).collect();
+    let healths = growth.iter().map(|item| item.health as f32).collect();
+    let lines: [(Color, Vec<f32>, f32); 3] = [
+        (HEIGHT_COLOR, heights, max_size),
+        (WIDTH_COLOR, widths, max_size),
+        (HEALTH_COLOR, healths, MAX_HEALTH),
+    ];
+    for (color, vals, max) in lines {
+        let points: Vec<(i64, i64)> = growth
+            .iter()
+            .zip(vals)
+            .map(|(item, val)| (get_x(item), get_y(val, max)))
+            .collect();
+        for pair in points.windows(2) {
+            canvas.draw_line(pair[0], pair[1], color);
+        }
+        for point in points {
+            canvas.fill_rect(point, 2, color);
+        }
+    }
+    canvas
+}
+
+#[cfg(test)]
+mod growth_chart_tests {
+    use super::{
+        draw_growth_chart, Canvas, AXIS, BACKGROUND, CHART_HEIGHT, CHART_WIDTH, HEALTH_COLOR,
+        HEIGHT_COLOR, MARGIN, WIDTH_COLOR,
+    };
+    use crate::test_common::{example_growth1, example_growth2};
+    use image::{load_from_memory_with_format, ImageFormat};
+    use plants::growth_item::GrowthItem;
+
+    #[test]
+    fn png_size() {
+        let png = Canvas::new(3, 2, BACKGROUND).to_png().unwrap();
+        let image = load_from_memory_with_format(&png, ImageFormat::Png).unwrap();
+        assert_eq!((image.width(), image.height()), (3, 2))
+    }
+
+    #[test]
+    fn png_data() {
+        let png = Canvas::new(3, 2, [1, 2, 3]).to_png().unwrap();
+        let image = load_from_memory_with_format(&png, ImageFormat::Png).unwrap();
+        assert_eq!(image.into_rgb8().into_raw(), [1, 2, 3].repeat(6))
+    }
+
+    #[test]
+    fn chart_empty() {
+        let canvas = draw_growth_chart(&[]);
+        assert_eq!(canvas.get_pixel(MARGIN, MARGIN), Some(AXIS));
+        assert_eq!(canvas.get_pixel(CHART_WIDTH / 2, 30), Some(BACKGROUND))
+    }
+
+    #[test]
+    fn chart_points() {
+        let growth = GrowthItem {
+            width_cm: 5.0,
+            ..example_growth2()
+        };
+        let canvas = draw_growth_chart(&[growth, example_growth1()]);
+        let right = CHART_WIDTH - MARGIN;
+        let bottom = CHART_HEIGHT - MARGIN;
+        let colors: Vec<_> = (MARGIN..bottom)
+            .filter_map(|y| canvas.get_pixel(right, y))
+            .collect();
+        assert!(colors.contains(&HEIGHT_COLOR));
+        assert!(colors.contains(&WIDTH_COLOR));
+        assert!(colors.contains(&HEALTH_COLOR))
+    }
+
+    #[test]
+    fn pixel_outside() {
+        let canvas = Canvas::new(3, 2, BACKGROUND);
+        assert_eq!(canvas.get_pixel(3, 0), None)
+    }
+}
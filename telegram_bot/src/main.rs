@@ -2,12 +2,18 @@ pub mod action_handler;
 pub mod bot_actions;
 pub mod commands;
 pub mod config;
+#[cfg(test)]
+mod conversation_tests;
+pub mod digest;
 pub mod errors;
+pub mod growth_chart;
 
 use action_handler::ActionHandler;
 use bot_api::bot::Bot;
+use chrono::{Local, TimeDelta};
 use config::load_config;
 use database::sqlite_backend::SQLiteDB;
+use digest::{parse_times, Digest};
 use errors::Error;
 use log::Level;
 use logger::{file_logger::FileLogger, init::init_logger};
@@ -27,12 +33,28 @@ async fn main() -> Result<(), Error> {
     log::info!("Successfully loaded config");
 
     let mut bot = Bot::new(conf.api_key);
+    bot.api_url = conf.api_url;
     let db =
         SQLiteDB::new(PathBuf::from("plants.db")).map_err(|err| Error::DBError(Box::new(err)))?;
     let mut handler = ActionHandler::new(conf.white_list, db);
-
-    log::info!("Running bot");
-    bot.run(&mut handler).await;
+    handler.weather_file = conf.weather_file;
+    handler.action_timeout = TimeDelta::minutes(conf.action_timeout);
+    handler.digest = Digest::load(
+        parse_times(&conf.digest_times)?,
+        PathBuf::from("digest_state.json"),
+        Local::now().naive_local(),
+    )?;
+
+    match conf.webhook {
+        None => {
+            log::info!("Running bot");
+            bot.run(&mut handler).await;
+        }
+        Some(webhook) => {
+            log::info!("Running bot with webhook");
+            bot.run_webhook(&mut handler, &webhook).await?;
+        }
+    }
     Ok(())
 }
 
@@ -45,7 +67,7 @@ pub mod test_common {
         growth_item::GrowthItem,
         location::Location,
         log_item::LogItem,
-        plant::{Plant, PlantImage, PlantInfo, PlantLocation, PlantSpecies},
+        plant::{ImageMeta, Plant, PlantImage, PlantInfo, PlantLocation, PlantSpecies},
         species::{Species, SunlightRequirement},
     };
     use std::{error::Error, fmt, path::PathBuf};
@@ -121,6 +143,10 @@ pub mod test_common {
             Ok(())
         }
 
+        fn write_graveyard(&mut self, _: Vec<GraveyardPlant>) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
         fn get_locations(&mut self) -> Result<Vec<Location>, Box<dyn Error>> {
             Ok(vec![example_location()])
         }
@@ -159,6 +185,42 @@ pub mod test_common {
         fn species_exists(&mut self, name: &str) -> Result<bool, Box<dyn Error>> {
             Ok(vec!["Species1", "Species2", "Species3"].contains(&name))
         }
+
+        fn rename_plant(&mut self, _: &str, _: &str) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn delete_species(&mut self, _: &str) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn delete_location(&mut self, _: &str) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn delete_log(&mut self, _: LogItem) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn delete_growth(&mut self, _: GrowthItem) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn write_image_meta(&mut self, _: ImageMeta) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn begin_transaction(&mut self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn commit_transaction(&mut self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn rollback_transaction(&mut self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
     }
     pub fn example_species() -> Species {
         Species {
@@ -181,6 +243,7 @@ pub mod test_common {
             pruning_notes: vec![],
             companions: vec![],
             additional_notes: vec![],
+            schedules: vec![],
         }
     }
 
@@ -201,6 +264,7 @@ pub mod test_common {
             obtained: example_date1(),
             auto_water: false,
             notes: vec![],
+            schedules: vec![],
         }
     }
     pub fn example_growth1() -> GrowthItem {
@@ -231,6 +295,7 @@ pub mod test_common {
             date: example_date1(),
             plant: "A Plant".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -240,6 +305,7 @@ pub mod test_common {
             date: example_date1(),
             plant: "A Plant".to_owned(),
             note: None,
+            user: None,
         }
     }
 
@@ -248,6 +314,8 @@ pub mod test_common {
             created: example_date1(),
             file_name: "01011970.jpg".to_owned(),
             file_path: PathBuf::from("./"),
+            caption: None,
+            cover: false,
         }
     }
 
@@ -256,6 +324,8 @@ pub mod test_common {
             created: example_date2(),
             file_name: "02011970.jpg".to_owned(),
             file_path: PathBuf::from("/"),
+            caption: None,
+            cover: false,
         }
     }
 
@@ -263,6 +333,7 @@ pub mod test_common {
         Location {
             name: "Inside".to_owned(),
             outside: false,
+            climate: vec![],
         }
     }
 